@@ -5,16 +5,24 @@
 //! of both subsystems and handles inter-thread communication.
 //!
 
-use tokio::sync::mpsc;
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, error, info, warn};
 
+use super::gamepad_profile::{GamepadProfile, ProfileSelection};
+use super::rumble::{RumbleHandle, RumblePattern};
+use crate::idle::IdleTracker;
+
 pub use super::event_collector::{
-    ButtonState, ButtonType, CollectorError, CollectorHandle, CollectorSettings, JoystickType,
-    RawControllerEvent, TriggerType,
+    ButtonState, ButtonType, CollectorError, CollectorHandle, CollectorSettings, ControllerStatus,
+    JoystickType, RawControllerEvent, TriggerType,
 };
 pub use super::event_processor::{
-    ButtonEvent, ButtonEventState, ControllerOutput, JoystickPosition, ProcessorError,
-    ProcessorHandle, ProcessorSettings, TriggerValue,
+    ButtonChord, ButtonEvent, ButtonEventState, ChordEvent, ControllerOutput, DeadzoneMode,
+    JoystickPosition, ProcessorError, ProcessorHandle, ProcessorSettings, ResponseCurve,
+    StickCurve, TriggerValue,
 };
 
 /// Configuration settings for the complete controller subsystem
@@ -27,6 +35,10 @@ pub use super::event_processor::{
 /// - `collection_interval_ms`: Lower values increase responsiveness but consume more CPU
 /// - `button_press_threshold_ms`: Filters accidental button presses; too low may cause false positives
 /// - `joystick_deadzone`: Prevents analog stick drift; too high reduces precision
+/// - `deadzone_mode`: Radial, per-axis or scaled radial deadzone shape, see [`DeadzoneMode`]
+/// - `left_stick_curve`/`right_stick_curve`: Shape stick response after the deadzone
+/// - `chord_window_ms`: Longer windows catch sloppy chords but delay single presses of chord buttons
+/// - `invert_*`: Flip single stick axes for pads that report them the other way round
 ///
 /// # Examples
 ///
@@ -38,6 +50,7 @@ pub use super::event_processor::{
 ///     collection_interval_ms: 100,
 ///     button_press_threshold_ms: 20,
 ///     joystick_deadzone: 0.03,
+///     ..Default::default()
 /// };
 ///
 /// // Relaxed Smart Home control
@@ -45,9 +58,18 @@ pub use super::event_processor::{
 ///     collection_interval_ms: 200,
 ///     button_press_threshold_ms: 50,
 ///     joystick_deadzone: 0.08,
+///     ..Default::default()
 /// };
 /// ```
-#[derive(Clone, Debug)]
+///
+/// # Runtime Changes
+///
+/// Settings can be changed while the subsystem runs via
+/// [`ControllerHandle::update_settings`]. The collector picks up a new trigger
+/// deadzone and gamepad profile on its next poll, the processor a new
+/// interval, threshold, stick deadzone and chords on its next cycle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ControllerSettings {
     /// Processing interval in milliseconds (applied to both collection and processing)
     ///
@@ -67,6 +89,54 @@ pub struct ControllerSettings {
     /// Prevents analog stick drift by ignoring small movements near the center position.
     /// Typical values range from 0.03 (precise) to 0.1 (loose/worn controllers).
     pub joystick_deadzone: f32,
+
+    /// How the deadzone is applied to the two axes of a stick
+    pub deadzone_mode: DeadzoneMode,
+
+    /// Response curve for the left stick, applied after deadzone removal
+    pub left_stick_curve: StickCurve,
+
+    /// Response curve for the right stick, applied after deadzone removal
+    pub right_stick_curve: StickCurve,
+
+    /// Button combinations reported as [`ChordEvent`]s instead of single presses
+    pub chords: Vec<ButtonChord>,
+
+    /// Longest time in milliseconds between the first and last press of a chord
+    ///
+    /// A single press of a button that belongs to a chord is reported up to
+    /// one processing cycle later, since the rest of the chord could follow.
+    pub chord_window_ms: u32,
+
+    /// Buttons that disable all mapping output when held together
+    ///
+    /// See [`crate::mapping::kill_switch`]. An empty combo turns the kill
+    /// switch off.
+    pub kill_combo: Vec<ButtonType>,
+
+    /// Flip the horizontal axis of the left stick
+    ///
+    /// Inversion is applied before the deadzone, so keyboard regions, ELRS
+    /// channels and every other consumer see the corrected direction.
+    pub invert_left_x: bool,
+
+    /// Flip the vertical axis of the left stick
+    pub invert_left_y: bool,
+
+    /// Flip the horizontal axis of the right stick
+    pub invert_right_x: bool,
+
+    /// Flip the vertical axis of the right stick
+    pub invert_right_y: bool,
+
+    /// Button profile used for connected gamepads
+    ///
+    /// Auto-detection picks the profile from the gamepad name, so an Xbox pad
+    /// and a DualShock both report their top face button as `Y`.
+    pub gamepad_profile: ProfileSelection,
+
+    /// User defined button profiles, see [`GamepadProfile`]
+    pub custom_profiles: Vec<GamepadProfile>,
 }
 
 impl Default for ControllerSettings {
@@ -79,6 +149,47 @@ impl Default for ControllerSettings {
             collection_interval_ms: 130,   // Based on human reaction time studies
             button_press_threshold_ms: 30, // Filters most accidental presses
             joystick_deadzone: 0.05,       // 5% deadzone for typical controllers
+            deadzone_mode: DeadzoneMode::default(),
+            left_stick_curve: StickCurve::default(),
+            right_stick_curve: StickCurve::default(),
+            chords: Vec::new(),
+            chord_window_ms: 50,
+            kill_combo: vec![ButtonType::Start, ButtonType::Select],
+            invert_left_x: false,
+            invert_left_y: false,
+            invert_right_x: false,
+            invert_right_y: false,
+            gamepad_profile: ProfileSelection::default(),
+            custom_profiles: Vec::new(),
+        }
+    }
+}
+
+impl From<&ControllerSettings> for CollectorSettings {
+    fn from(settings: &ControllerSettings) -> Self {
+        Self {
+            joystick_deadzone: settings.joystick_deadzone,
+            gamepad_profile: settings.gamepad_profile.clone(),
+            custom_profiles: settings.custom_profiles.clone(),
+        }
+    }
+}
+
+impl From<&ControllerSettings> for ProcessorSettings {
+    fn from(settings: &ControllerSettings) -> Self {
+        Self {
+            processing_interval_ms: settings.collection_interval_ms,
+            button_press_threshold_ms: settings.button_press_threshold_ms,
+            joystick_deadzone: settings.joystick_deadzone,
+            deadzone_mode: settings.deadzone_mode,
+            left_stick_curve: settings.left_stick_curve,
+            right_stick_curve: settings.right_stick_curve,
+            chords: settings.chords.clone(),
+            chord_window_ms: settings.chord_window_ms,
+            invert_left_x: settings.invert_left_x,
+            invert_left_y: settings.invert_left_y,
+            invert_right_x: settings.invert_right_x,
+            invert_right_y: settings.invert_right_y,
         }
     }
 }
@@ -135,7 +246,31 @@ pub enum ControllerError {
 ///
 /// The spawned threads are fire-and-forget; they run until the application terminates.
 /// No explicit cleanup is required as tokio handles task lifecycle automatically.
-pub struct ControllerHandle {}
+///
+/// # Status Reporting
+///
+/// The handle keeps the receiving ends of the collector's watch channels.
+/// Use [`ControllerHandle::battery_receiver`] to observe the primary gamepad's
+/// battery level and [`ControllerHandle::status_receiver`] to observe whether
+/// any gamepad is connected.
+///
+/// # Haptic Feedback
+///
+/// [`ControllerHandle::rumble`] and [`ControllerHandle::rumble_pattern`] drive the
+/// primary gamepad's force feedback motors. Other threads can get a cloneable
+/// [`RumbleHandle`] via [`ControllerHandle::rumble_handle`].
+///
+/// # Live Settings
+///
+/// Both threads watch the current [`ControllerSettings`]. Publish new values with
+/// [`ControllerHandle::update_settings`] or through the sender returned by
+/// [`ControllerHandle::settings_sender`].
+pub struct ControllerHandle {
+    battery_receiver: watch::Receiver<Option<u8>>,
+    status_receiver: watch::Receiver<ControllerStatus>,
+    rumble: RumbleHandle,
+    settings_sender: watch::Sender<ControllerSettings>,
+}
 
 impl ControllerHandle {
     /// Spawns the complete controller subsystem with unified settings
@@ -162,6 +297,7 @@ impl ControllerHandle {
     ///
     /// * `settings` - Optional configuration; uses defaults if None
     /// * `sender` - Channel for sending processed controller output to the application
+    /// * `idle` - Activity tracker, the collector polls slower while idle
     ///
     /// # Returns
     ///
@@ -180,23 +316,27 @@ impl ControllerHandle {
     ///
     /// ```rust,no_run
     /// use opencontroller::controller::{ControllerHandle, ControllerSettings};
+    /// use opencontroller::idle::IdleTracker;
+    /// use std::sync::Arc;
     /// use tokio::sync::mpsc;
     ///
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let (tx, rx) = mpsc::channel(100);
+    /// let idle = Arc::new(IdleTracker::default());
     ///
     /// // Use default settings
-    /// let handle = ControllerHandle::spawn(None, tx)?;
+    /// let handle = ControllerHandle::spawn(None, tx, idle.clone())?;
     ///
     /// // Use custom settings
     /// let settings = ControllerSettings {
     ///     collection_interval_ms: 100,
     ///     button_press_threshold_ms: 25,
     ///     joystick_deadzone: 0.03,
+    ///     ..Default::default()
     /// };
     /// let (tx2, rx2) = mpsc::channel(100);
-    /// let handle2 = ControllerHandle::spawn(Some(settings), tx2)?;
+    /// let handle2 = ControllerHandle::spawn(Some(settings), tx2, idle)?;
     /// # Ok(())
     /// # }
     /// ```
@@ -209,6 +349,7 @@ impl ControllerHandle {
     pub fn spawn(
         settings: Option<ControllerSettings>,
         sender: mpsc::Sender<ControllerOutput>,
+        idle: Arc<IdleTracker>,
     ) -> Result<Self, ControllerError> {
         info!(
             "Initializing Controller system with settings: {:?}",
@@ -219,13 +360,8 @@ impl ControllerHandle {
         let settings = settings.unwrap_or_default();
 
         // Distribute settings to subsystem components
-        let collector_settings = CollectorSettings {
-            joystick_deadzone: settings.joystick_deadzone,
-        };
-        let processor_settings = ProcessorSettings {
-            processing_interval_ms: settings.collection_interval_ms,
-            button_press_threshold_ms: settings.button_press_threshold_ms,
-        };
+        let collector_settings = CollectorSettings::from(&settings);
+        let processor_settings = ProcessorSettings::from(&settings);
 
         debug!(
             "Split settings: collector={:?}, processor={:?}",
@@ -236,18 +372,88 @@ impl ControllerHandle {
         let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1000);
         debug!("Created event channel with buffer capacity 1000");
 
+        // Battery level is unknown until the collector reports it
+        let (battery_sender, battery_receiver) = watch::channel(None);
+        // Assume no pad until the collector has enumerated them
+        let (status_sender, status_receiver) = watch::channel(ControllerStatus::Disconnected);
+        // Rumble requests are played on the collector thread, which owns gilrs
+        let (rumble_sender, rumble_receiver) = std_mpsc::channel();
+        // Both threads re-read settings from here while running
+        let (settings_sender, settings_receiver) = watch::channel(settings);
+
         // Spawn event collection subsystem
         info!("Creating Event Collector");
-        let _collector_handle = CollectorHandle::spawn(Some(collector_settings), event_sender)?;
+        let _collector_handle = CollectorHandle::spawn(
+            Some(collector_settings),
+            event_sender,
+            battery_sender,
+            status_sender,
+            rumble_receiver,
+            settings_receiver.clone(),
+            idle,
+        )?;
         info!("Event Collector spawned successfully");
 
         // Spawn event processing subsystem
         info!("Creating Event Processor");
-        let _processor_handle =
-            ProcessorHandle::spawn(event_receiver, sender, Some(processor_settings))?;
+        let _processor_handle = ProcessorHandle::spawn(
+            event_receiver,
+            sender,
+            Some(processor_settings),
+            settings_receiver,
+        )?;
         info!("Event Processor spawned successfully");
 
         info!("Controller system initialized successfully");
-        Ok(Self {})
+        Ok(Self {
+            battery_receiver,
+            status_receiver,
+            rumble: RumbleHandle::new(rumble_sender),
+            settings_sender,
+        })
+    }
+
+    /// Returns a receiver for the primary gamepad's battery level in percent
+    ///
+    /// The value is `None` while no gamepad is selected, for wired pads, and for
+    /// pads that do not report power information.
+    pub fn battery_receiver(&self) -> watch::Receiver<Option<u8>> {
+        self.battery_receiver.clone()
+    }
+
+    /// Returns a receiver for the controller connection status
+    ///
+    /// Changes whenever the last gamepad disconnects or the first one reconnects.
+    /// Input resumes automatically on reconnect.
+    pub fn status_receiver(&self) -> watch::Receiver<ControllerStatus> {
+        self.status_receiver.clone()
+    }
+
+    /// Applies new settings to the running collector and processor
+    pub fn update_settings(&self, settings: ControllerSettings) {
+        debug!("Updating controller settings: {:?}", settings);
+        self.settings_sender.send_replace(settings);
+    }
+
+    /// Returns a sender for changing settings from other threads, e.g. the UI
+    pub fn settings_sender(&self) -> watch::Sender<ControllerSettings> {
+        self.settings_sender.clone()
+    }
+
+    /// Rumbles the primary gamepad with `strength` (0.0-1.0) for `duration_ms`
+    ///
+    /// No-op with a warning log on pads without force feedback.
+    pub fn rumble(&self, strength: f32, duration_ms: u64) {
+        self.rumble.rumble(strength, duration_ms);
+    }
+
+    /// Plays a predefined short/long/double buzz on the primary gamepad
+    pub fn rumble_pattern(&self, pattern: RumblePattern) {
+        self.rumble.rumble_pattern(pattern);
+    }
+
+    /// Returns a cloneable handle for requesting rumble from other threads
+    pub fn rumble_handle(&self) -> RumbleHandle {
+        self.rumble.clone()
     }
 }
@@ -6,21 +6,46 @@
 //! State machine: Initializing → Collecting (continuous loop)
 //!
 //! Key features:
-//! - Auto-selects first available gamepad (TODO: UI control)
-//! - Deadzone filtering for analog inputs
-//! - 100µs polling for low latency
+//! - Collects from all connected gamepads, tagging events with a gamepad id
+//! - Hotplug: pads connecting or disconnecting update the active set at runtime
+//! - Deadzone filtering for triggers, adjustable at runtime via a watch channel
+//! - 100µs polling for low latency, 10ms while the application is idle
+//! - Battery level of the primary gamepad published over a watch channel
+//! - Connection status published over a watch channel ([`ControllerStatus`])
+//! - Rumble requests played as force feedback effects on the polling thread
+//! - Buttons remapped per pad by its [`GamepadProfile`]
 
 use chrono::{DateTime, Local};
-use gilrs::{Axis, Button, Event, EventType, Gamepad, GamepadId, Gilrs};
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use gilrs::{Axis, Button, Event, EventType, Gamepad, GamepadId, Gilrs, PowerInfo};
 use serde::{Deserialize, Serialize};
 use statum::{machine, state};
-use tokio::sync::mpsc;
+use std::collections::HashMap;
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
+
+use super::controller_handle::ControllerSettings;
+use super::gamepad_profile::{GamepadProfile, PadButton, ProfileSelection};
+use super::rumble::RumbleRequest;
+use crate::idle::IdleTracker;
 use tracing::{debug, error, info, warn};
 
+/// How often the battery level of the primary gamepad is read
+const BATTERY_POLL_INTERVAL_SECS: i64 = 5;
+
+/// Wait between two polls of gilrs
+const POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+/// Wait between two polls of gilrs while the application is idle
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Raw controller events with precise timestamps
 ///
 /// All events include chrono timestamps for accurate duration calculations
-/// in the processor. Analog values are already deadzone-filtered.
+/// in the processor. Trigger values are already deadzone-filtered, stick
+/// values are raw.
 #[derive(Debug, Clone)]
 pub enum RawControllerEvent {
     JoystickMove {
@@ -28,17 +53,47 @@ pub enum RawControllerEvent {
         x: f32,
         y: f32,
         timestamp: DateTime<Local>,
+        gamepad_id: usize,
     },
     TriggerMove {
         trigger: TriggerType,
         value: f32,
         timestamp: DateTime<Local>,
+        gamepad_id: usize,
     },
     ButtonEvent {
         button_type: ButtonType,
         button_state: ButtonState,
         timestamp: DateTime<Local>,
+        gamepad_id: usize,
     },
+    /// A gamepad was unplugged; its processing state should be dropped
+    GamepadDisconnected {
+        timestamp: DateTime<Local>,
+        gamepad_id: usize,
+    },
+}
+
+impl RawControllerEvent {
+    /// Returns the id of the gamepad that produced this event
+    pub fn gamepad_id(&self) -> usize {
+        match self {
+            Self::JoystickMove { gamepad_id, .. }
+            | Self::TriggerMove { gamepad_id, .. }
+            | Self::ButtonEvent { gamepad_id, .. }
+            | Self::GamepadDisconnected { gamepad_id, .. } => *gamepad_id,
+        }
+    }
+
+    /// Returns the time the collector saw this event
+    pub fn timestamp(&self) -> DateTime<Local> {
+        match self {
+            Self::JoystickMove { timestamp, .. }
+            | Self::TriggerMove { timestamp, .. }
+            | Self::ButtonEvent { timestamp, .. }
+            | Self::GamepadDisconnected { timestamp, .. } => *timestamp,
+        }
+    }
 }
 
 // Joystick type
@@ -83,20 +138,48 @@ pub enum ButtonType {
     // Add other buttons as needed
 }
 
+impl ButtonType {
+    pub const ALL: [ButtonType; 15] = [
+        ButtonType::A,
+        ButtonType::B,
+        ButtonType::X,
+        ButtonType::Y,
+        ButtonType::Start,
+        ButtonType::Select,
+        ButtonType::LeftBumper,
+        ButtonType::RightBumper,
+        ButtonType::LeftStick,
+        ButtonType::RightStick,
+        ButtonType::DPadUp,
+        ButtonType::DPadDown,
+        ButtonType::DPadLeft,
+        ButtonType::DPadRight,
+        ButtonType::Guide,
+    ];
+}
+
 // Collector settings
 #[derive(Clone, Debug)]
 pub struct CollectorSettings {
-    /// Deadzone for analog sticks (0.0-1.0)
+    /// Deadzone for analog triggers (0.0-1.0)
     ///
     /// Values below this threshold are treated as zero and rescaled.
-    /// Prevents stick drift and accidental inputs.
+    /// Prevents accidental inputs. Sticks are filtered in the processor.
     pub joystick_deadzone: f32,
+
+    /// How the button profile of each pad is chosen
+    pub gamepad_profile: ProfileSelection,
+
+    /// User defined profiles, searched before the built-in ones
+    pub custom_profiles: Vec<GamepadProfile>,
 }
 
 impl Default for CollectorSettings {
     fn default() -> Self {
         Self {
             joystick_deadzone: 0.05,
+            gamepad_profile: ProfileSelection::default(),
+            custom_profiles: Vec::new(),
         }
     }
 }
@@ -125,30 +208,101 @@ pub enum CollectionState {
     Collecting,   // Active event polling loop
 }
 
+/// Whether any gamepad is available for input
+///
+/// Published by the collector on every hotplug event. The UI relies on it to
+/// warn that the gamepad-driven interface cannot be operated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControllerStatus {
+    /// At least one gamepad is connected
+    Connected,
+    /// No gamepad is connected
+    Disconnected,
+}
+
+/// A force feedback effect kept alive until it has finished playing
+///
+/// Dropping a gilrs `Effect` stops it, so effects are held until `until`.
+struct ActiveEffect {
+    _effect: Effect,
+    until: Instant,
+}
+
+impl std::fmt::Debug for ActiveEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActiveEffect")
+            .field("until", &self.until)
+            .finish()
+    }
+}
+
+/// Last seen stick values of one gamepad (to calculate deltas)
+#[derive(Debug, Clone, Copy, Default)]
+struct StickPositions {
+    left_x: f32,
+    left_y: f32,
+    right_x: f32,
+    right_y: f32,
+}
+
+/// Channels linking the collector thread to the rest of the application
+#[derive(Debug)]
+struct CollectorChannels {
+    // Channel for sending events to processor
+    event_sender: mpsc::Sender<RawControllerEvent>,
+
+    // Battery level of the primary gamepad in percent, None if unknown or wired
+    battery_sender: watch::Sender<Option<u8>>,
+
+    // Connection status, Connected while at least one pad is present
+    status_sender: watch::Sender<ControllerStatus>,
+
+    // Rumble requests from other threads, played on this thread
+    rumble_receiver: std_mpsc::Receiver<RumbleRequest>,
+
+    // Live settings published by the controller handle
+    settings_receiver: watch::Receiver<ControllerSettings>,
+}
+
+/// State kept per connected gamepad, dropped when it disconnects
+#[derive(Debug, Default)]
+struct PadStates {
+    // Last seen joystick values (to calculate deltas)
+    stick_positions: HashMap<GamepadId, StickPositions>,
+
+    // Button profile
+    profiles: HashMap<GamepadId, GamepadProfile>,
+}
+
 /// Event collector using gilrs and statum state machine
 ///
-/// Maintains analog stick state for delta calculations and applies deadzone filtering.
-/// Automatically selects the first available gamepad (second if multiple available).
+/// Maintains analog stick state per gamepad for delta calculations and applies
+/// deadzone filtering. Events from every connected pad are forwarded, tagged
+/// with the pad's id. The primary gamepad (second if multiple are available at
+/// startup) is only used for battery reporting.
 #[machine]
 #[derive(Debug)]
 pub struct EventCollector<S: CollectionState> {
     // Gilrs context
     gilrs: Gilrs,
 
-    // Active gamepad
+    // Primary gamepad, used for battery reporting
     active_gamepad: Option<GamepadId>,
 
     // Collector settings
     settings: CollectorSettings,
 
-    // Channel for sending events to processor
-    event_sender: mpsc::Sender<RawControllerEvent>,
+    // Channels to and from the other threads
+    channels: CollectorChannels,
 
-    // Last seen joystick values (to calculate deltas)
-    last_left_stick_x: f32,
-    last_left_stick_y: f32,
-    last_right_stick_x: f32,
-    last_right_stick_y: f32,
+    // Effects currently playing
+    active_effects: Vec<ActiveEffect>,
+
+    // Stick values and button profile of each connected gamepad
+    pads: PadStates,
+
+    // Activity shared with the other loops, slows polling while idle
+    idle: Arc<IdleTracker>,
 }
 
 // Implementation of methods available in all states
@@ -162,6 +316,64 @@ impl<S: CollectionState> EventCollector<S> {
     pub fn settings(&self) -> &CollectorSettings {
         &self.settings
     }
+
+    /// Adopts settings published since the last check
+    fn refresh_settings(&mut self) {
+        if !self
+            .channels
+            .settings_receiver
+            .has_changed()
+            .unwrap_or(false)
+        {
+            return;
+        }
+        let settings =
+            CollectorSettings::from(&*self.channels.settings_receiver.borrow_and_update());
+        info!(
+            "Collector trigger deadzone changed to {}",
+            settings.joystick_deadzone
+        );
+        let profiles_changed = settings.gamepad_profile != self.settings.gamepad_profile
+            || settings.custom_profiles != self.settings.custom_profiles;
+        self.update_settings(settings);
+
+        if profiles_changed {
+            let ids: Vec<GamepadId> = self.gilrs.gamepads().map(|(id, _)| id).collect();
+            for id in ids {
+                self.assign_profile(id);
+            }
+        }
+    }
+
+    /// Resolves the button profile of a gamepad from the current settings
+    fn assign_profile(&mut self, id: GamepadId) {
+        let name = self.gilrs.gamepad(id).name().to_string();
+        let profile = self
+            .settings
+            .gamepad_profile
+            .resolve(&self.settings.custom_profiles, &name);
+        info!("Gamepad {} ({}) uses profile {}", name, id, profile.name);
+        self.pads.profiles.insert(id, profile);
+    }
+
+    /// Publishes whether any gamepad is currently connected
+    fn publish_status(&self) {
+        let status = if self.gilrs.gamepads().next().is_some() {
+            ControllerStatus::Connected
+        } else {
+            ControllerStatus::Disconnected
+        };
+
+        self.channels.status_sender.send_if_modified(|current| {
+            if *current != status {
+                info!("Controller status: {:?}", status);
+                *current = status;
+                true
+            } else {
+                false
+            }
+        });
+    }
 }
 
 // Implementation for Initializing state
@@ -170,6 +382,11 @@ impl EventCollector<Initializing> {
     pub fn create(
         settings: Option<CollectorSettings>,
         event_sender: mpsc::Sender<RawControllerEvent>,
+        battery_sender: watch::Sender<Option<u8>>,
+        status_sender: watch::Sender<ControllerStatus>,
+        rumble_receiver: std_mpsc::Receiver<RumbleRequest>,
+        settings_receiver: watch::Receiver<ControllerSettings>,
+        idle: Arc<IdleTracker>,
     ) -> Result<Self, CollectorError> {
         let settings = settings.unwrap_or_default();
         debug!("Creating Event Collector with settings: {:?}", settings);
@@ -192,22 +409,27 @@ impl EventCollector<Initializing> {
             gilrs,
             None,
             settings,
-            event_sender,
-            0.0, // last_left_stick_x
-            0.0, // last_left_stick_y
-            0.0, // last_right_stick_x
-            0.0, // last_right_stick_y
+            CollectorChannels {
+                event_sender,
+                battery_sender,
+                status_sender,
+                rumble_receiver,
+                settings_receiver,
+            },
+            Vec::new(),
+            PadStates::default(),
+            idle,
         ))
     }
 
-    /// Finds and selects an active gamepad, transitions to Collecting    // Initialize the controller and transition to Collecting state
+    /// Finds and selects the primary gamepad, transitions to Collecting
     pub fn initialize(mut self) -> Result<EventCollector<Collecting>, CollectorError> {
         info!(
             "Initializing Event Collector with deadzone: {}",
             self.settings.joystick_deadzone
         );
 
-        // Find an active gamepad
+        // Find the primary gamepad
         let gamepads: Vec<(GamepadId, Gamepad<'_>)> = self.gilrs.gamepads().collect();
 
         if gamepads.is_empty() {
@@ -223,8 +445,8 @@ impl EventCollector<Initializing> {
                     gamepad.uuid()
                 );
             }
-            // Auto-selection logic: prefer second gamepad if available
-            // TODO: Make this controllable from UI
+            // Primary selection: prefer second gamepad if available
+            // All pads are collected, the primary only drives battery reporting
             let index = if gamepads.len() > 1 { 1 } else { 0 };
             let (id, gamepad) = &gamepads[index];
             self.active_gamepad = Some(*id);
@@ -233,6 +455,13 @@ impl EventCollector<Initializing> {
             // Log connected buttons and axes for debugging
         }
 
+        let ids: Vec<GamepadId> = gamepads.into_iter().map(|(id, _)| id).collect();
+        for id in ids {
+            self.assign_profile(id);
+        }
+
+        self.publish_status();
+
         info!("Event Collector initialized, transitioning to Collecting state");
         Ok(self.transition())
     }
@@ -247,30 +476,35 @@ impl EventCollector<Collecting> {
             id, event, time, ..
         }) = self.gilrs.next_event()
         {
-            // Only process events from the active gamepad if one is set
-            if let Some(active_id) = self.active_gamepad {
-                if id != active_id {
-                    debug!("Skipping event from non-active gamepad: {:?}", id);
-                    return Ok(()); // Skip events from other gamepads
-                }
-            }
-
             // Log the raw event at debug level
             debug!("Processing gilrs event: {:?} at time: {:?}", event, time);
 
+            // Hotplug events update the active set instead of producing input
+            let converted = match event {
+                EventType::Connected => {
+                    self.handle_connected(id);
+                    None
+                }
+                EventType::Disconnected => self.handle_disconnected(id),
+                event => self.convert_gilrs_event(id, event),
+            };
+
             // Convert gilrs event to our internal event type with chrono timestamp
-            if let Some(raw_event) = self.convert_gilrs_event(event) {
+            if let Some(raw_event) = converted {
                 // Log important button events at info level
                 match &raw_event {
                     RawControllerEvent::ButtonEvent {
                         button_type,
                         button_state,
                         timestamp,
+                        gamepad_id,
                     } => {
+                        self.idle.record();
                         info!(
-                            "Button event: {:?} {:?} at {}",
+                            "Button event: {:?} {:?} on pad {} at {}",
                             button_type,
                             button_state,
+                            gamepad_id,
                             timestamp.format("%H:%M:%S.%3f")
                         );
                     }
@@ -278,7 +512,7 @@ impl EventCollector<Collecting> {
                 }
 
                 // Send the event to the processor queue
-                match self.event_sender.try_send(raw_event) {
+                match self.channels.event_sender.try_send(raw_event) {
                     Ok(_) => debug!("Event sent to processor queue"),
                     Err(e) => {
                         error!("Failed to send event to processor: {}", e);
@@ -301,8 +535,13 @@ impl EventCollector<Collecting> {
         let mut event_count = 0;
         let mut last_log_time = Local::now();
         let log_interval = chrono::Duration::seconds(10);
+        let mut last_battery_time = Local::now();
+        let battery_interval = chrono::Duration::seconds(BATTERY_POLL_INTERVAL_SECS);
+        self.update_battery();
 
         loop {
+            self.refresh_settings();
+
             // This is a non-blocking call that checks for new events
             if let Err(e) = self.collect_next_event() {
                 error!("Error collecting event: {}", e);
@@ -325,107 +564,298 @@ impl EventCollector<Collecting> {
                 last_log_time = now;
             }
 
+            if now - last_battery_time > battery_interval {
+                self.update_battery();
+                last_battery_time = now;
+            }
+
+            self.process_rumble();
+
             // Small sleep to prevent 100% CPU usage
             // This is a compromise between responsiveness and CPU usage
-            std::thread::sleep(std::time::Duration::from_micros(100));
+            std::thread::sleep(self.idle.interval(POLL_INTERVAL, IDLE_POLL_INTERVAL));
+        }
+    }
+
+    /// Plays pending rumble requests and releases finished effects
+    fn process_rumble(&mut self) {
+        let now = Instant::now();
+        self.active_effects.retain(|active| active.until > now);
+
+        while let Ok(request) = self.channels.rumble_receiver.try_recv() {
+            self.play_rumble(request);
+        }
+    }
+
+    /// Builds and starts a force feedback effect for one request
+    ///
+    /// Pads without force feedback are skipped with a warning.
+    fn play_rumble(&mut self, request: RumbleRequest) {
+        let target = match request.gamepad_id {
+            Some(id) => self
+                .gilrs
+                .gamepads()
+                .map(|(gamepad_id, _)| gamepad_id)
+                .find(|gamepad_id| usize::from(*gamepad_id) == id),
+            None => self.active_gamepad,
+        };
+
+        let Some(id) = target else {
+            warn!("Rumble requested but no matching gamepad is connected");
+            return;
+        };
+
+        if !self
+            .gilrs
+            .connected_gamepad(id)
+            .is_some_and(|gamepad| gamepad.is_ff_supported())
+        {
+            warn!(
+                "Gamepad {} does not support force feedback, ignoring rumble",
+                id
+            );
+            return;
+        }
+
+        let total_ms = request.pulses.iter().map(|p| p.end_ms()).max().unwrap_or(0);
+        if total_ms == 0 {
+            return;
+        }
+
+        let mut builder = EffectBuilder::new();
+        for pulse in &request.pulses {
+            let magnitude = (pulse.strength * u16::MAX as f32) as u16;
+            let scheduling = Replay {
+                after: Ticks::from_ms(pulse.delay_ms as u32),
+                play_for: Ticks::from_ms(pulse.duration_ms as u32),
+                // Longer than the whole request so the pulse never repeats
+                with_delay: Ticks::from_ms(total_ms as u32),
+            };
+            // Drive both motors for a consistent feel across pads
+            builder.add_effect(BaseEffect {
+                kind: BaseEffectType::Strong { magnitude },
+                scheduling,
+                ..Default::default()
+            });
+            builder.add_effect(BaseEffect {
+                kind: BaseEffectType::Weak { magnitude },
+                scheduling,
+                ..Default::default()
+            });
+        }
+
+        let effect = builder
+            .gamepads(&[id])
+            .repeat(Repeat::For(Ticks::from_ms(total_ms as u32)))
+            .finish(&mut self.gilrs);
+
+        match effect {
+            Ok(effect) => {
+                if let Err(e) = effect.play() {
+                    warn!("Failed to play rumble on gamepad {}: {}", id, e);
+                    return;
+                }
+                debug!("Playing rumble on gamepad {} for {}ms", id, total_ms);
+                self.active_effects.push(ActiveEffect {
+                    _effect: effect,
+                    until: Instant::now() + std::time::Duration::from_millis(total_ms),
+                });
+            }
+            Err(e) => warn!("Failed to create rumble effect: {}", e),
+        }
+    }
+
+    /// Publishes the battery level of the primary gamepad
+    ///
+    /// Wired pads and pads that do not report power info publish `None`.
+    fn update_battery(&mut self) {
+        let level = self
+            .active_gamepad
+            .and_then(|id| self.gilrs.connected_gamepad(id))
+            .and_then(|gamepad| match gamepad.power_info() {
+                PowerInfo::Discharging(level) | PowerInfo::Charging(level) => Some(level),
+                PowerInfo::Charged => Some(100),
+                PowerInfo::Wired | PowerInfo::Unknown => None,
+            });
+
+        // send_if_modified avoids waking receivers when nothing changed
+        self.channels.battery_sender.send_if_modified(|current| {
+            if *current != level {
+                debug!("Controller battery: {:?}", level);
+                *current = level;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Registers a newly connected gamepad
+    ///
+    /// Becomes the primary pad if none is selected, so a pad plugged in after
+    /// startup is picked up without restarting the application.
+    fn handle_connected(&mut self, id: GamepadId) {
+        let name = self.gilrs.gamepad(id).name().to_string();
+        info!("Gamepad connected: {} ({})", name, id);
+
+        self.pads.stick_positions.entry(id).or_default();
+        self.assign_profile(id);
+        if self.active_gamepad.is_none() {
+            self.active_gamepad = Some(id);
+            info!("Selected gamepad: {} ({})", name, id);
+            self.update_battery();
+        }
+        self.publish_status();
+    }
+
+    /// Drops the state of a disconnected gamepad and tells the processor
+    ///
+    /// If the primary pad was removed, the next connected pad takes over.
+    fn handle_disconnected(&mut self, id: GamepadId) -> Option<RawControllerEvent> {
+        warn!("Gamepad disconnected: {}", id);
+
+        self.pads.stick_positions.remove(&id);
+        self.pads.profiles.remove(&id);
+        if self.active_gamepad == Some(id) {
+            self.active_gamepad = self.gilrs.gamepads().map(|(id, _)| id).next();
+            if let Some(next) = self.active_gamepad {
+                info!("Selected gamepad: {}", next);
+            }
+            self.update_battery();
+        }
+        self.publish_status();
+
+        Some(RawControllerEvent::GamepadDisconnected {
+            timestamp: Local::now(),
+            gamepad_id: id.into(),
+        })
+    }
+
+    /// Maps gilrs buttons to internal button types via the pad's profile
+    ///
+    /// Pads without a resolved profile use [`GamepadProfile::generic`].
+    fn map_button(&self, id: GamepadId, button: Button) -> Option<ButtonType> {
+        let button = PadButton::from_gilrs(button)?;
+        match self.pads.profiles.get(&id) {
+            Some(profile) => profile.map(button),
+            None => GamepadProfile::generic().map(button),
         }
     }
 
-    /// Converts gilrs events to internal format with deadzone filtering
+    /// Converts gilrs events to internal format with trigger deadzone filtering
     ///
     /// Critical function that maps all supported gilrs events to internal types.
-    /// Applies deadzone to analog inputs and tracks stick positions for deltas.
-    fn convert_gilrs_event(&mut self, event: EventType) -> Option<RawControllerEvent> {
+    /// Applies the deadzone to triggers and tracks stick positions for deltas.
+    /// Trigger values arrive either as `LeftZ`/`RightZ` axes or as the value of
+    /// the `LeftTrigger2`/`RightTrigger2` buttons, depending on the pad mapping.
+    /// Digital triggers jump between 0.0 and 1.0, a pad reporting neither keeps
+    /// both values at 0.0.
+    /// Stick values are passed on raw; the processor applies the stick deadzone
+    /// to both axes together according to the configured [`DeadzoneMode`].
+    ///
+    /// [`DeadzoneMode`]: super::event_processor::DeadzoneMode
+    fn convert_gilrs_event(
+        &mut self,
+        id: GamepadId,
+        event: EventType,
+    ) -> Option<RawControllerEvent> {
         let now = Local::now(); // Use chrono for precise timestamp
+        let gamepad_id: usize = id.into();
+        let deadzone = self.settings.joystick_deadzone;
 
         match event {
             EventType::AxisChanged(axis, value, _) => {
                 debug!("Axis changed: {:?} = {:.4}", axis, value);
+                let last = self.pads.stick_positions.entry(id).or_default();
 
                 match axis {
                     Axis::LeftStickX => {
-                        let new_value = apply_deadzone(value, self.settings.joystick_deadzone);
-                        let delta = new_value - self.last_left_stick_x;
+                        let new_value = value;
+                        let delta = new_value - last.left_x;
 
                         // Only log significant changes to avoid spam
                         if delta.abs() > 0.05 {
                             debug!(
                                 "Left stick X: {:.4} -> {:.4} (delta: {:.4})",
-                                self.last_left_stick_x, new_value, delta
+                                last.left_x, new_value, delta
                             );
                         }
 
                         let raw_event = RawControllerEvent::JoystickMove {
                             stick: JoystickType::Left,
                             x: new_value,
-                            y: self.last_left_stick_y,
+                            y: last.left_y,
                             timestamp: now,
+                            gamepad_id,
                         };
-                        self.last_left_stick_x = new_value;
+                        last.left_x = new_value;
                         Some(raw_event)
                     }
                     Axis::LeftStickY => {
-                        let new_value = apply_deadzone(value, self.settings.joystick_deadzone);
-                        let delta = new_value - self.last_left_stick_y;
+                        let new_value = value;
+                        let delta = new_value - last.left_y;
 
                         if delta.abs() > 0.05 {
                             debug!(
                                 "Left stick Y: {:.4} -> {:.4} (delta: {:.4})",
-                                self.last_left_stick_y, new_value, delta
+                                last.left_y, new_value, delta
                             );
                         }
 
                         let raw_event = RawControllerEvent::JoystickMove {
                             stick: JoystickType::Left,
-                            x: self.last_left_stick_x,
+                            x: last.left_x,
                             y: new_value,
                             timestamp: now,
+                            gamepad_id,
                         };
-                        self.last_left_stick_y = new_value;
+                        last.left_y = new_value;
                         Some(raw_event)
                     }
                     Axis::RightStickX => {
-                        let new_value = apply_deadzone(value, self.settings.joystick_deadzone);
-                        let delta = new_value - self.last_right_stick_x;
+                        let new_value = value;
+                        let delta = new_value - last.right_x;
 
                         if delta.abs() > 0.05 {
                             debug!(
                                 "Right stick X: {:.4} -> {:.4} (delta: {:.4})",
-                                self.last_right_stick_x, new_value, delta
+                                last.right_x, new_value, delta
                             );
                         }
 
                         let raw_event = RawControllerEvent::JoystickMove {
                             stick: JoystickType::Right,
                             x: new_value,
-                            y: self.last_right_stick_y,
+                            y: last.right_y,
                             timestamp: now,
+                            gamepad_id,
                         };
-                        self.last_right_stick_x = new_value;
+                        last.right_x = new_value;
                         Some(raw_event)
                     }
                     Axis::RightStickY => {
-                        let new_value = apply_deadzone(value, self.settings.joystick_deadzone);
-                        let delta = new_value - self.last_right_stick_y;
+                        let new_value = value;
+                        let delta = new_value - last.right_y;
 
                         if delta.abs() > 0.05 {
                             debug!(
                                 "Right stick Y: {:.4} -> {:.4} (delta: {:.4})",
-                                self.last_right_stick_y, new_value, delta
+                                last.right_y, new_value, delta
                             );
                         }
 
                         let raw_event = RawControllerEvent::JoystickMove {
                             stick: JoystickType::Right,
-                            x: self.last_right_stick_x,
+                            x: last.right_x,
                             y: new_value,
                             timestamp: now,
+                            gamepad_id,
                         };
-                        self.last_right_stick_y = new_value;
+                        last.right_y = new_value;
                         Some(raw_event)
                     }
                     Axis::LeftZ => {
-                        let new_value = apply_deadzone(value, self.settings.joystick_deadzone);
+                        let new_value = apply_deadzone(value, deadzone);
                         if new_value > 0.1 {
                             debug!("Left trigger: {:.4}", new_value);
                         }
@@ -434,10 +864,11 @@ impl EventCollector<Collecting> {
                             trigger: TriggerType::Left,
                             value: new_value,
                             timestamp: now,
+                            gamepad_id,
                         })
                     }
                     Axis::RightZ => {
-                        let new_value = apply_deadzone(value, self.settings.joystick_deadzone);
+                        let new_value = apply_deadzone(value, deadzone);
                         if new_value > 0.1 {
                             debug!("Right trigger: {:.4}", new_value);
                         }
@@ -446,6 +877,7 @@ impl EventCollector<Collecting> {
                             trigger: TriggerType::Right,
                             value: new_value,
                             timestamp: now,
+                            gamepad_id,
                         })
                     }
                     _ => {
@@ -460,12 +892,13 @@ impl EventCollector<Collecting> {
                     button,
                     now.format("%H:%M:%S.%3f")
                 );
-                map_button(button).map(|button_type| {
+                self.map_button(id, button).map(|button_type| {
                     debug!("Mapped to button type: {:?}", button_type);
                     RawControllerEvent::ButtonEvent {
                         button_type,
                         button_state: ButtonState::Pressed,
                         timestamp: now,
+                        gamepad_id,
                     }
                 })
             }
@@ -475,27 +908,45 @@ impl EventCollector<Collecting> {
                     button,
                     now.format("%H:%M:%S.%3f")
                 );
-                map_button(button).map(|button_type| {
+                self.map_button(id, button).map(|button_type| {
                     debug!("Mapped to button type: {:?}", button_type);
                     RawControllerEvent::ButtonEvent {
                         button_type,
                         button_state: ButtonState::Released,
                         timestamp: now,
+                        gamepad_id,
                     }
                 })
             }
+            // Analog triggers of standard-mapped pads are reported as buttons
+            // with a value. Pads with digital triggers only report 0.0 and 1.0.
+            EventType::ButtonChanged(
+                button @ (Button::LeftTrigger2 | Button::RightTrigger2),
+                value,
+                _,
+            ) => {
+                let trigger = if button == Button::LeftTrigger2 {
+                    TriggerType::Left
+                } else {
+                    TriggerType::Right
+                };
+                let new_value = apply_deadzone(value, deadzone);
+                if new_value > 0.1 {
+                    debug!("{:?} trigger: {:.4}", trigger, new_value);
+                }
+
+                Some(RawControllerEvent::TriggerMove {
+                    trigger,
+                    value: new_value,
+                    timestamp: now,
+                    gamepad_id,
+                })
+            }
             EventType::ButtonRepeated(button, _) => {
                 debug!("Button repeat ignored: {:?}", button);
                 None
             }
-            EventType::Connected => {
-                info!("Controller connected");
-                None // TODO: Trigger re-initialization
-            }
-            EventType::Disconnected => {
-                warn!("Controller disconnected");
-                None // TODO: Handle gracefully
-            }
+            // Connected/Disconnected are handled in collect_next_event
             _ => {
                 debug!("Unhandled event type: {:?}", event);
                 None
@@ -513,6 +964,11 @@ impl CollectorHandle {
     pub fn spawn(
         settings: Option<CollectorSettings>,
         event_sender: mpsc::Sender<RawControllerEvent>,
+        battery_sender: watch::Sender<Option<u8>>,
+        status_sender: watch::Sender<ControllerStatus>,
+        rumble_receiver: std_mpsc::Receiver<RumbleRequest>,
+        settings_receiver: watch::Receiver<ControllerSettings>,
+        idle: Arc<IdleTracker>,
     ) -> Result<Self, CollectorError> {
         info!("Spawning Event Collector with settings: {:?}", settings);
 
@@ -520,7 +976,15 @@ impl CollectorHandle {
         let sender_clone = event_sender.clone();
 
         // Initialize collector in Initializing state
-        let collector = EventCollector::create(settings, event_sender)?;
+        let collector = EventCollector::create(
+            settings,
+            event_sender,
+            battery_sender,
+            status_sender,
+            rumble_receiver,
+            settings_receiver,
+            idle,
+        )?;
         info!("Successfully created EventCollector instance");
 
         // Spawn tokio task for collector
@@ -556,30 +1020,6 @@ impl CollectorHandle {
     }
 }
 
-/// Maps gilrs buttons to internal button types
-///
-/// Uses Xbox controller layout as standard (South=A, East=B, etc.)
-fn map_button(button: Button) -> Option<ButtonType> {
-    match button {
-        Button::South => Some(ButtonType::A),
-        Button::East => Some(ButtonType::B),
-        Button::West => Some(ButtonType::Y),
-        Button::North => Some(ButtonType::X),
-        Button::Start => Some(ButtonType::Start),
-        Button::Select => Some(ButtonType::Select),
-        Button::LeftTrigger => Some(ButtonType::LeftBumper),
-        Button::RightTrigger => Some(ButtonType::RightBumper),
-        Button::LeftThumb => Some(ButtonType::LeftStick),
-        Button::RightThumb => Some(ButtonType::RightStick),
-        Button::DPadUp => Some(ButtonType::DPadUp),
-        Button::DPadDown => Some(ButtonType::DPadDown),
-        Button::DPadLeft => Some(ButtonType::DPadLeft),
-        Button::DPadRight => Some(ButtonType::DPadRight),
-        Button::Mode => Some(ButtonType::Guide),
-        _ => None,
-    }
-}
-
 /// Applies deadzone filtering with rescaling
 ///
 /// Values below deadzone become 0.0, values above are rescaled to maintain
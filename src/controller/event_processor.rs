@@ -4,22 +4,29 @@
 //! Waiting → Processing → Updating → repeat
 //!
 //! Key features:
+//! - Independent state per gamepad, one [`ControllerOutput`] per pad and cycle
 //! - Button release tracking across cycles for held buttons
+//! - Chord detection for configured button combinations ([`ButtonChord`])
 //! - Min/max/delta calculation for analog inputs
+//! - Stick deadzone with selectable shape ([`DeadzoneMode`])
+//! - Per-stick response curves ([`StickCurve`]) for finer control near center
 //! - 130ms processing intervals optimized for human reaction time
+//! - Interval and press threshold adjustable at runtime via a watch channel
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 use statum::{machine, state};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::SystemTime;
-use tokio::sync::mpsc;
-use tracing::{debug, error, info, warn};
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, error, info};
 
+use super::controller_handle::ControllerSettings;
 use super::event_collector::{
     ButtonState, ButtonType, JoystickType, RawControllerEvent, TriggerType,
 };
 /// Button state for tracking duration across processing cycles
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ButtonEventState {
     Held,     // Button is still being pressed
     Complete, // Button has been released
@@ -29,31 +36,93 @@ pub enum ButtonEventState {
 ///
 /// Supports key combinations through multiple simultaneous button events.
 /// All analog values include current position plus min/max/delta tracking.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ControllerOutput {
+    /// Id of the gamepad this output belongs to
+    pub gamepad_id: usize,
     pub left_stick: JoystickPosition,
     pub right_stick: JoystickPosition,
     pub left_trigger: TriggerValue,
     pub right_trigger: TriggerValue,
     pub button_events: Vec<ButtonEvent>,
+    /// Chords detected this cycle, their buttons are not in `button_events`
+    #[serde(default)]
+    pub chord_events: Vec<ChordEvent>,
     pub timestamp: SystemTime,
+    /// Collection time of the oldest raw event of this cycle, only set while
+    /// input latency is measured, see [`crate::latency`]
+    #[serde(skip)]
+    pub collected_at: Option<DateTime<Local>>,
+}
+
+impl ControllerOutput {
+    /// Whether nothing is pressed, deflected or pulled on the pad
+    pub fn is_neutral(&self) -> bool {
+        self.button_events.is_empty()
+            && self.chord_events.is_empty()
+            && [
+                self.left_stick.x,
+                self.left_stick.y,
+                self.right_stick.x,
+                self.right_stick.y,
+            ]
+            .iter()
+            .all(|value| *value == 0.0)
+            && self.left_trigger.value == 0.0
+            && self.right_trigger.value == 0.0
+    }
+
+    /// Buttons down on the pad, including those of held chords
+    ///
+    /// Held buttons are reported again every cycle, so this is the pad's full
+    /// state even if earlier outputs were missed.
+    pub fn held_buttons(&self) -> HashSet<ButtonType> {
+        let buttons = self
+            .button_events
+            .iter()
+            .filter(|event| event.state == ButtonEventState::Held)
+            .map(|event| event.button.clone());
+        let chord_buttons = self
+            .chord_events
+            .iter()
+            .filter(|chord| chord.state == ButtonEventState::Held)
+            .flat_map(|chord| chord.buttons.iter().cloned());
+        buttons.chain(chord_buttons).collect()
+    }
+
+    /// Whether a button or chord was released this cycle
+    ///
+    /// Held state is reported again every cycle, a release only once. An
+    /// output with releases must reach the mappings even when rate limited.
+    pub fn has_releases(&self) -> bool {
+        self.button_events
+            .iter()
+            .any(|event| event.state == ButtonEventState::Complete)
+            || self
+                .chord_events
+                .iter()
+                .any(|chord| chord.state == ButtonEventState::Complete)
+    }
 }
 
 impl Default for ControllerOutput {
     fn default() -> Self {
         Self {
+            gamepad_id: 0,
             left_stick: Default::default(),
             right_stick: Default::default(),
             left_trigger: Default::default(),
             right_trigger: Default::default(),
             button_events: Vec::new(),
+            chord_events: Vec::new(),
             timestamp: SystemTime::now(),
+            collected_at: None,
         }
     }
 }
 
 /// Joystick position with tracking data for mapping engines
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct JoystickPosition {
     pub x: f32,
     pub y: f32,
@@ -80,7 +149,7 @@ impl Default for JoystickPosition {
 }
 
 /// Trigger value with tracking data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TriggerValue {
     pub value: f32,
     pub min: f32,   // Min value seen this cycle
@@ -99,16 +168,54 @@ impl Default for TriggerValue {
 }
 
 /// Button event with duration tracking
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ButtonEvent {
     pub button: ButtonType,
     pub duration_ms: f64,        // How long button has been held
     pub state: ButtonEventState, // Held or Complete
 }
 
+/// Buttons that act as a single input when pressed together
+///
+/// All buttons have to go down within the chord window. Their individual
+/// button events are then replaced by one [`ChordEvent`] until release.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ButtonChord {
+    /// Name of the chord, used as key by the mapping engines
+    pub name: String,
+    pub buttons: Vec<ButtonType>,
+}
+
+impl ButtonChord {
+    pub fn contains(&self, button: &ButtonType) -> bool {
+        self.buttons.contains(button)
+    }
+}
+
+/// Chord event with duration tracking
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChordEvent {
+    pub name: String,
+    pub buttons: Vec<ButtonType>,
+    pub duration_ms: f64,        // How long all buttons have been down together
+    pub state: ButtonEventState, // Held while all buttons are down
+}
+
 #[derive(Clone, Debug)]
 struct PendingButtonRelease {
     timestamp: DateTime<Local>,
+    // Press was consumed by a chord and must not fire on its own
+    chorded: bool,
+}
+
+/// One press of a button within the current cycle
+#[derive(Clone, Debug)]
+struct ButtonPress {
+    button: ButtonType,
+    pressed_at: DateTime<Local>,
+    duration_ms: f64,
+    state: ButtonEventState,
+    chorded: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -116,11 +223,167 @@ pub struct EventBatch {
     pub events: Vec<RawControllerEvent>,
 }
 
+/// Shape of a stick's response after deadzone removal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResponseCurve {
+    /// Output equals input
+    #[default]
+    Linear,
+    /// Blends towards `x * |x|`
+    Quadratic,
+    /// Blends towards `x³` (classic RC expo)
+    CubicExpo,
+}
+
+impl ResponseCurve {
+    pub const ALL: [ResponseCurve; 3] = [
+        ResponseCurve::Linear,
+        ResponseCurve::Quadratic,
+        ResponseCurve::CubicExpo,
+    ];
+}
+
+impl std::fmt::Display for ResponseCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResponseCurve::Linear => write!(f, "Linear"),
+            ResponseCurve::Quadratic => write!(f, "Quadratic"),
+            ResponseCurve::CubicExpo => write!(f, "Cubic Expo"),
+        }
+    }
+}
+
+/// Response curve applied to both axes of one stick
+///
+/// `factor` (0.0-1.0) blends between linear (0.0) and the full curve (1.0).
+/// Sign and the [-1, 1] range are preserved, 0 maps to 0 and ±1 to ±1;
+/// only the mid-range is attenuated.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StickCurve {
+    pub curve: ResponseCurve,
+    pub factor: f32,
+}
+
+impl Default for StickCurve {
+    fn default() -> Self {
+        Self {
+            curve: ResponseCurve::Linear,
+            factor: 0.5,
+        }
+    }
+}
+
+impl StickCurve {
+    /// Maps a deadzone-filtered axis value through the curve
+    pub fn apply(&self, value: f32) -> f32 {
+        let value = value.clamp(-1.0, 1.0);
+        let shaped = match self.curve {
+            ResponseCurve::Linear => return value,
+            ResponseCurve::Quadratic => value * value.abs(),
+            ResponseCurve::CubicExpo => value * value * value,
+        };
+        let factor = self.factor.clamp(0.0, 1.0);
+        (1.0 - factor) * value + factor * shaped
+    }
+}
+
+/// How the stick deadzone is applied to the two axes of a stick
+///
+/// All modes return values in [-1, 1] and map the deadzone area to (0, 0).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeadzoneMode {
+    /// Drops the whole stick while its magnitude is below the deadzone,
+    /// values outside pass unchanged
+    Radial,
+    /// Filters and rescales each axis on its own, tolerates drift on one axis
+    #[default]
+    PerAxis,
+    /// Like `Radial`, but rescales the remaining magnitude to the full range
+    ScaledRadial,
+}
+
+impl DeadzoneMode {
+    pub const ALL: [DeadzoneMode; 3] = [
+        DeadzoneMode::Radial,
+        DeadzoneMode::PerAxis,
+        DeadzoneMode::ScaledRadial,
+    ];
+
+    /// Applies the deadzone to one stick position
+    ///
+    /// A magnitude or axis value exactly at the deadzone counts as outside.
+    pub fn apply(&self, x: f32, y: f32, deadzone: f32) -> (f32, f32) {
+        let deadzone = deadzone.clamp(0.0, 0.99);
+        let (x, y) = (x.clamp(-1.0, 1.0), y.clamp(-1.0, 1.0));
+        match self {
+            DeadzoneMode::PerAxis => (rescale_axis(x, deadzone), rescale_axis(y, deadzone)),
+            DeadzoneMode::Radial => {
+                if x.hypot(y) < deadzone {
+                    (0.0, 0.0)
+                } else {
+                    (x, y)
+                }
+            }
+            DeadzoneMode::ScaledRadial => {
+                let magnitude = x.hypot(y);
+                if magnitude < deadzone {
+                    return (0.0, 0.0);
+                }
+                // Diagonals can exceed 1.0 on square gates
+                let scaled = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0);
+                let factor = scaled / magnitude;
+                (x * factor, y * factor)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DeadzoneMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeadzoneMode::Radial => write!(f, "Radial"),
+            DeadzoneMode::PerAxis => write!(f, "Per axis"),
+            DeadzoneMode::ScaledRadial => write!(f, "Scaled radial"),
+        }
+    }
+}
+
+/// Zeroes an axis inside the deadzone and rescales the rest to [-1, 1]
+fn rescale_axis(value: f32, deadzone: f32) -> f32 {
+    if value.abs() < deadzone {
+        0.0
+    } else {
+        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+    }
+}
+
 /// Processor configuration
 #[derive(Clone, Debug)]
 pub struct ProcessorSettings {
     pub processing_interval_ms: u64,
     pub button_press_threshold_ms: u32,
+    pub joystick_deadzone: f32,
+    pub deadzone_mode: DeadzoneMode,
+    pub left_stick_curve: StickCurve,
+    pub right_stick_curve: StickCurve,
+    pub chords: Vec<ButtonChord>,
+    pub chord_window_ms: u32,
+    pub invert_left_x: bool,
+    pub invert_left_y: bool,
+    pub invert_right_x: bool,
+    pub invert_right_y: bool,
+}
+
+impl ProcessorSettings {
+    /// Returns the raw position of `stick` with its inverted axes flipped
+    fn invert(&self, stick: &JoystickType, x: f32, y: f32) -> (f32, f32) {
+        let (invert_x, invert_y) = match stick {
+            JoystickType::Left => (self.invert_left_x, self.invert_left_y),
+            JoystickType::Right => (self.invert_right_x, self.invert_right_y),
+        };
+        (if invert_x { -x } else { x }, if invert_y { -y } else { y })
+    }
 }
 
 impl Default for ProcessorSettings {
@@ -128,6 +391,16 @@ impl Default for ProcessorSettings {
         Self {
             processing_interval_ms: 130,
             button_press_threshold_ms: 30,
+            joystick_deadzone: 0.05,
+            deadzone_mode: DeadzoneMode::default(),
+            left_stick_curve: StickCurve::default(),
+            right_stick_curve: StickCurve::default(),
+            chords: Vec::new(),
+            chord_window_ms: 50,
+            invert_left_x: false,
+            invert_left_y: false,
+            invert_right_x: false,
+            invert_right_y: false,
         }
     }
 }
@@ -156,19 +429,34 @@ pub enum ProcessingState {
     Updating,               // Broadcasting processed output
 }
 
+/// Processing state of a single gamepad
+///
+/// Each pad keeps its own output and held-button tracking so input from
+/// different pads never mixes.
+#[derive(Debug)]
+struct PadState {
+    output: ControllerOutput,
+    // Critical: tracks buttons pressed in previous cycles without release events
+    pending_button_releases: HashMap<ButtonType, PendingButtonRelease>,
+}
+
 /// Event processor using statum state machine
 ///
 /// Manages button release tracking across cycles to handle held buttons correctly.
 /// Processes events in batches every 130ms for optimal responsiveness.
+/// Pads are tracked from their first event until they disconnect.
 #[machine]
 #[derive(Debug)]
 pub struct EventProcessor<S: ProcessingState> {
     event_receiver: mpsc::Receiver<RawControllerEvent>,
     settings: ProcessorSettings,
-    output: ControllerOutput,
     state_sender: mpsc::Sender<ControllerOutput>,
-    // Critical: tracks buttons pressed in previous cycles without release events
-    pending_button_releases: HashMap<ButtonType, PendingButtonRelease>,
+    // Per-gamepad processing state, keyed by gamepad id
+    pads: HashMap<usize, PadState>,
+    // Pads that disconnected this cycle, sent one last neutral output
+    disconnected: Vec<usize>,
+    // Live settings published by the controller handle
+    settings_receiver: watch::Receiver<ControllerSettings>,
 }
 
 impl<S: ProcessingState> EventProcessor<S> {
@@ -179,6 +467,16 @@ impl<S: ProcessingState> EventProcessor<S> {
     pub fn settings(&self) -> &ProcessorSettings {
         &self.settings
     }
+
+    /// Adopts settings published since the last cycle
+    fn refresh_settings(&mut self) {
+        if !self.settings_receiver.has_changed().unwrap_or(false) {
+            return;
+        }
+        let settings = ProcessorSettings::from(&*self.settings_receiver.borrow_and_update());
+        info!("Processor settings changed to {:?}", settings);
+        self.update_settings(settings);
+    }
 }
 
 impl EventProcessor<Waiting> {
@@ -187,19 +485,17 @@ impl EventProcessor<Waiting> {
         event_receiver: mpsc::Receiver<RawControllerEvent>,
         output_sender: mpsc::Sender<ControllerOutput>,
         settings: Option<ProcessorSettings>,
+        settings_receiver: watch::Receiver<ControllerSettings>,
     ) -> Result<Self, ProcessorError> {
         let settings = settings.unwrap_or_default();
 
-        // Create initial output state
-        let output = ControllerOutput::default();
-        debug!("Created initial ControllerOutput state");
-
         Ok(Self::new(
             event_receiver,
             settings,
-            output,
             output_sender,
             HashMap::new(),
+            Vec::new(),
+            settings_receiver,
         ))
     }
 
@@ -227,8 +523,9 @@ impl EventProcessor<Waiting> {
 impl EventProcessor<Processing> {
     /// Processes collected events and transitions to Updating state
     ///
-    /// Handles button release tracking across cycles - buttons without release events
-    /// are tracked as "held" with continuously updated duration.
+    /// Events are grouped by gamepad and applied to that pad's state. A pad
+    /// that disconnected is dropped together with its held buttons, and a
+    /// neutral output is queued for it so engines release what it held.
     pub fn process_events(mut self) -> Result<EventProcessor<Updating>, ProcessorError> {
         let raw_events = if let Some(event_batch) = self.get_state_data() {
             event_batch.events.clone()
@@ -236,26 +533,98 @@ impl EventProcessor<Processing> {
             Vec::new()
         };
 
+        // Group events by the pad that produced them
+        let mut events_per_pad: HashMap<usize, Vec<RawControllerEvent>> = HashMap::new();
+        for event in raw_events {
+            if let RawControllerEvent::GamepadDisconnected { gamepad_id, .. } = event {
+                debug!("Dropping state of disconnected gamepad {}", gamepad_id);
+                if self.pads.remove(&gamepad_id).is_some() {
+                    self.disconnected.push(gamepad_id);
+                }
+                events_per_pad.remove(&gamepad_id);
+                continue;
+            }
+            events_per_pad
+                .entry(event.gamepad_id())
+                .or_default()
+                .push(event);
+        }
+
+        for gamepad_id in events_per_pad.keys() {
+            self.pads
+                .entry(*gamepad_id)
+                .or_insert_with(|| PadState::new(*gamepad_id));
+        }
+
+        for (gamepad_id, pad) in &mut self.pads {
+            let events = events_per_pad
+                .get(gamepad_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            pad.process(events, &self.settings)?;
+        }
+
+        Ok(self.transition())
+    }
+}
+
+impl PadState {
+    fn new(gamepad_id: usize) -> Self {
+        Self {
+            output: ControllerOutput {
+                gamepad_id,
+                ..Default::default()
+            },
+            pending_button_releases: HashMap::new(),
+        }
+    }
+
+    /// Applies one cycle of events from this pad
+    ///
+    /// Handles button release tracking across cycles - buttons without release events
+    /// are tracked as "held" with continuously updated duration.
+    fn process(
+        &mut self,
+        raw_events: &[RawControllerEvent],
+        settings: &ProcessorSettings,
+    ) -> Result<(), ProcessorError> {
         let has_pending_releases = !self.pending_button_releases.is_empty();
 
         if raw_events.is_empty() && !has_pending_releases {
             self.output.button_events.clear();
+            self.output.chord_events.clear();
         } else {
             if !raw_events.is_empty() {
-                self.process_joystick_events(&raw_events)?;
-                self.process_trigger_events(&raw_events)?;
+                self.process_joystick_events(raw_events, settings)?;
+                self.process_trigger_events(raw_events)?;
             }
             // Always process buttons if we have new events OR pending releases
-            self.process_button_events(&raw_events)?;
+            self.process_button_events(raw_events, settings)?;
         }
 
         self.output.timestamp = SystemTime::now();
-        Ok(self.transition())
+        self.output.collected_at = if crate::latency::is_enabled() {
+            raw_events.iter().map(RawControllerEvent::timestamp).min()
+        } else {
+            None
+        };
+        Ok(())
     }
+    /// Applies stick movement after deadzone removal and response curve shaping
+    ///
+    /// Inverted axes are flipped first. The deadzone is then applied to both
+    /// axes together according to the [`DeadzoneMode`], and each axis is
+    /// shaped by the stick's curve. All of this happens before min/max/delta
+    /// tracking, so all reported values are in the shaped range.
     fn process_joystick_events(
         &mut self,
         events: &[RawControllerEvent],
+        settings: &ProcessorSettings,
     ) -> Result<(), ProcessorError> {
+        let left_curve = &settings.left_stick_curve;
+        let right_curve = &settings.right_stick_curve;
+        let deadzone = settings.joystick_deadzone;
+
         // Storage for left and right stick values
         let mut left_x_values = Vec::new();
         let mut left_y_values = Vec::new();
@@ -264,21 +633,17 @@ impl EventProcessor<Processing> {
 
         // Extract all joystick values
         for event in events {
-            if let RawControllerEvent::JoystickMove {
-                stick,
-                x,
-                y,
-                timestamp: _,
-            } = event
-            {
+            if let RawControllerEvent::JoystickMove { stick, x, y, .. } = event {
+                let (x, y) = settings.invert(stick, *x, *y);
+                let (x, y) = settings.deadzone_mode.apply(x, y, deadzone);
                 match stick {
                     JoystickType::Left => {
-                        left_x_values.push(*x);
-                        left_y_values.push(*y);
+                        left_x_values.push(left_curve.apply(x));
+                        left_y_values.push(left_curve.apply(y));
                     }
                     JoystickType::Right => {
-                        right_x_values.push(*x);
-                        right_y_values.push(*y);
+                        right_x_values.push(right_curve.apply(x));
+                        right_y_values.push(right_curve.apply(y));
                     }
                 }
             }
@@ -396,12 +761,7 @@ impl EventProcessor<Processing> {
 
         // Extract all trigger values
         for event in events {
-            if let RawControllerEvent::TriggerMove {
-                trigger,
-                value,
-                timestamp: _,
-            } = event
-            {
+            if let RawControllerEvent::TriggerMove { trigger, value, .. } = event {
                 match trigger {
                     TriggerType::Left => {
                         left_values.push(*value);
@@ -480,16 +840,26 @@ impl EventProcessor<Processing> {
         Ok(())
     }
 
+    /// Turns raw button events into button and chord events
+    ///
+    /// Presses are first paired with their releases, then configured chords
+    /// claim presses that went down within the chord window. A fresh press of
+    /// a chord button is held back for one cycle while the rest of its chord
+    /// could still follow, so it never fires on its own before the chord.
     fn process_button_events(
         &mut self,
         events: &[RawControllerEvent],
+        settings: &ProcessorSettings,
     ) -> Result<(), ProcessorError> {
         // Clear existing button events
         self.output.button_events.clear();
+        self.output.chord_events.clear();
 
         // Group events by button
         let mut events_per_button: HashMap<ButtonType, Vec<(ButtonState, DateTime<Local>)>> =
             HashMap::new();
+        // Presses already consumed by a chord, keyed by button
+        let mut chorded_presses: HashMap<ButtonType, DateTime<Local>> = HashMap::new();
 
         // Add pending button releases to the event map
         for (button, release) in &self.pending_button_releases {
@@ -501,6 +871,9 @@ impl EventProcessor<Processing> {
                 button.clone(),
                 vec![(ButtonState::Pressed, release.timestamp)],
             );
+            if release.chorded {
+                chorded_presses.insert(button.clone(), release.timestamp);
+            }
         }
 
         // Clear pending releases to rebuild them
@@ -512,6 +885,7 @@ impl EventProcessor<Processing> {
                 button_type,
                 button_state,
                 timestamp,
+                ..
             } = event
             {
                 if !events_per_button.contains_key(button_type) {
@@ -531,11 +905,11 @@ impl EventProcessor<Processing> {
         // Current time for calculating held button durations
         let now = Local::now();
 
-        // Process each button's events
-        let mut processed_button_events: Vec<ButtonEvent> = Vec::new();
+        // Pair each button's presses with their releases
+        let mut presses: Vec<ButtonPress> = Vec::new();
         for (button, events) in &mut events_per_button {
             // Sort events by timestamp
-            events.sort_by(|event1, event2| event1.1.cmp(&event2.1));
+            events.sort_by_key(|event| event.1);
             debug!("Sorted events for button {:?}: {:?}", button, events);
 
             let mut i = 0;
@@ -543,6 +917,8 @@ impl EventProcessor<Processing> {
                 let event = &events[i];
 
                 if event.0 == ButtonState::Pressed {
+                    let chorded = chorded_presses.get(button) == Some(&event.1);
+
                     // Check if there's a corresponding Release event
                     if i + 1 < events.len() && events[i + 1].0 == ButtonState::Released {
                         let next_event = &events[i + 1];
@@ -553,11 +929,12 @@ impl EventProcessor<Processing> {
 
                         debug!("Button {:?} press duration: {}ms", button, duration_ms);
 
-                        // Add button event with precise duration
-                        processed_button_events.push(ButtonEvent {
+                        presses.push(ButtonPress {
                             button: button.clone(),
+                            pressed_at: event.1,
                             duration_ms,
                             state: ButtonEventState::Complete,
+                            chorded,
                         });
 
                         // Skip both events
@@ -574,18 +951,14 @@ impl EventProcessor<Processing> {
                             button, duration_ms
                         );
 
-                        // Add button event with calculated duration and Held state
-                        processed_button_events.push(ButtonEvent {
+                        presses.push(ButtonPress {
                             button: button.clone(),
+                            pressed_at: event.1,
                             duration_ms,
                             state: ButtonEventState::Held,
+                            chorded,
                         });
 
-                        // Save as pending for next cycle
-                        debug!("Saving pending button release for {:?}", button);
-                        self.pending_button_releases
-                            .insert(button.clone(), PendingButtonRelease { timestamp: event.1 });
-
                         // Skip just this event
                         i += 1;
                     }
@@ -599,44 +972,166 @@ impl EventProcessor<Processing> {
                 }
             }
         }
+        presses.sort_by_key(|press| press.pressed_at);
+
+        let window = chrono::Duration::milliseconds(settings.chord_window_ms as i64);
+        let mut claimed: HashSet<usize> = HashSet::new();
+        self.output.chord_events = Self::detect_chords(&presses, settings, window, &mut claimed);
+
+        // Emit the remaining presses and remember held ones for the next cycle
+        let mut processed_button_events: Vec<ButtonEvent> = Vec::new();
+        for (index, press) in presses.into_iter().enumerate() {
+            let in_chord = claimed.contains(&index) || press.chorded;
+            let chord_may_follow = !in_chord
+                && press.state == ButtonEventState::Held
+                && now - press.pressed_at < window
+                && settings
+                    .chords
+                    .iter()
+                    .any(|chord| chord.contains(&press.button));
+
+            if press.state == ButtonEventState::Held {
+                // Save as pending for next cycle
+                debug!("Saving pending button release for {:?}", press.button);
+                self.pending_button_releases.insert(
+                    press.button.clone(),
+                    PendingButtonRelease {
+                        timestamp: press.pressed_at,
+                        chorded: in_chord,
+                    },
+                );
+            }
+
+            if in_chord {
+                debug!("Button {:?} suppressed by chord", press.button);
+            } else if chord_may_follow {
+                debug!("Button {:?} deferred, chord may follow", press.button);
+            } else {
+                processed_button_events.push(ButtonEvent {
+                    button: press.button,
+                    duration_ms: press.duration_ms,
+                    state: press.state,
+                });
+            }
+        }
 
         // Update output with processed button events
         self.output.button_events = processed_button_events;
         Ok(())
     }
+
+    /// Finds configured chords among this cycle's presses
+    ///
+    /// Larger chords are matched first so a three-button chord is not split
+    /// into a two-button chord plus a single press. Indices of presses used by
+    /// a chord are added to `claimed`.
+    fn detect_chords(
+        presses: &[ButtonPress],
+        settings: &ProcessorSettings,
+        window: chrono::Duration,
+        claimed: &mut HashSet<usize>,
+    ) -> Vec<ChordEvent> {
+        let mut chords: Vec<&ButtonChord> = settings
+            .chords
+            .iter()
+            .filter(|chord| chord.buttons.len() > 1)
+            .collect();
+        chords.sort_by_key(|chord| std::cmp::Reverse(chord.buttons.len()));
+
+        let mut chord_events = Vec::new();
+        for chord in chords {
+            // Presses are sorted, so the first member press anchors the window
+            for (anchor, press) in presses.iter().enumerate() {
+                if claimed.contains(&anchor) || !chord.contains(&press.button) {
+                    continue;
+                }
+                let members: Option<Vec<usize>> = chord
+                    .buttons
+                    .iter()
+                    .map(|button| {
+                        presses.iter().enumerate().position(|(index, other)| {
+                            !claimed.contains(&index)
+                                && &other.button == button
+                                && other.pressed_at >= press.pressed_at
+                                && other.pressed_at - press.pressed_at <= window
+                        })
+                    })
+                    .collect();
+                let Some(members) = members else {
+                    continue;
+                };
+
+                let held = members
+                    .iter()
+                    .all(|&index| presses[index].state == ButtonEventState::Held);
+                let duration_ms = members
+                    .iter()
+                    .map(|&index| presses[index].duration_ms)
+                    .fold(f64::INFINITY, f64::min);
+                debug!("Chord {} detected ({}ms)", chord.name, duration_ms);
+
+                claimed.extend(members);
+                chord_events.push(ChordEvent {
+                    name: chord.name.clone(),
+                    buttons: chord.buttons.clone(),
+                    duration_ms,
+                    state: if held {
+                        ButtonEventState::Held
+                    } else {
+                        ButtonEventState::Complete
+                    },
+                });
+                break;
+            }
+        }
+        chord_events
+    }
 }
 
 // Implementation for Updating state
 impl EventProcessor<Updating> {
-    /// Broadcasts processed output and transitions back to Waiting
-    pub fn update_state(self) -> Result<EventProcessor<Waiting>, ProcessorError> {
-        debug!("Updating controller state through watch channel");
-
-        // Prepare debug summary
-        let summary = format!(
-            "L:({:.2},{:.2}) R:({:.2},{:.2}) LT:{:.2} RT:{:.2} Buttons:{}",
-            self.output.left_stick.x,
-            self.output.left_stick.y,
-            self.output.right_stick.x,
-            self.output.right_stick.y,
-            self.output.left_trigger.value,
-            self.output.right_trigger.value,
-            self.output.button_events.len()
-        );
-
-        let send_result = self.state_sender.try_send(self.output.clone());
+    /// Broadcasts processed output of every pad and transitions back to Waiting
+    ///
+    /// Pads that disconnected this cycle get a neutral output instead.
+    pub fn update_state(mut self) -> Result<EventProcessor<Waiting>, ProcessorError> {
+        debug!("Updating controller state for {} gamepads", self.pads.len());
+
+        let released: Vec<ControllerOutput> = self
+            .disconnected
+            .drain(..)
+            .map(|gamepad_id| ControllerOutput {
+                gamepad_id,
+                ..Default::default()
+            })
+            .collect();
+
+        for output in self.pads.values().map(|pad| &pad.output).chain(&released) {
+            // Prepare debug summary
+            let summary = format!(
+                "Pad {} L:({:.2},{:.2}) R:({:.2},{:.2}) LT:{:.2} RT:{:.2} Buttons:{} Chords:{}",
+                output.gamepad_id,
+                output.left_stick.x,
+                output.left_stick.y,
+                output.right_stick.x,
+                output.right_stick.y,
+                output.left_trigger.value,
+                output.right_trigger.value,
+                output.button_events.len(),
+                output.chord_events.len()
+            );
 
-        // Send updated state through watch channel
-        match send_result {
-            Ok(_) => {
-                debug!("State updated successfully: {}", summary);
-            }
-            Err(e) => {
-                error!("Failed to update controller state: {}", e);
-                return Err(ProcessorError::StateUpdateError(format!(
-                    "Failed to send state update: {}",
-                    e
-                )));
+            // Send updated state through the output channel
+            match self.state_sender.try_send(output.clone()) {
+                Ok(_) => {
+                    debug!("State updated successfully: {}", summary);
+                }
+                Err(e) => {
+                    error!("Failed to update controller state: {}", e);
+                    return Err(ProcessorError::StateUpdateError(format!(
+                        "Failed to send state update: {}",
+                        e
+                    )));
+                }
             }
         }
 
@@ -657,10 +1152,16 @@ impl ProcessorHandle {
         event_receiver: mpsc::Receiver<RawControllerEvent>,
         output_sender: mpsc::Sender<ControllerOutput>,
         settings: Option<ProcessorSettings>,
+        settings_receiver: watch::Receiver<ControllerSettings>,
     ) -> Result<Self, ProcessorError> {
         info!("Spawning Event Processor with settings: {:?}", settings);
 
-        let processor = EventProcessor::create(event_receiver, output_sender.clone(), settings)?;
+        let processor = EventProcessor::create(
+            event_receiver,
+            output_sender.clone(),
+            settings,
+            settings_receiver,
+        )?;
 
         let _task_handle = tokio::spawn(async move {
             if let Err(e) = run_processor_loop(processor).await {
@@ -698,7 +1199,7 @@ async fn run_processor_loop(mut processor: EventProcessor<Waiting>) -> Result<()
     loop {
         debug!(
             "Waiting for next interval tick ({} ms)",
-            settings.processing_interval_ms
+            processor.settings().processing_interval_ms
         );
         // Wait for the next interval tick
         interval_timer.tick().await;
@@ -756,6 +1257,7 @@ async fn run_processor_loop(mut processor: EventProcessor<Waiting>) -> Result<()
         }
 
         // Check if interval time needs to be updated (in case settings changed)
+        processor.refresh_settings();
         let new_interval_time =
             tokio::time::Duration::from_millis(processor.settings().processing_interval_ms);
 
@@ -766,9 +1268,207 @@ async fn run_processor_loop(mut processor: EventProcessor<Waiting>) -> Result<()
             );
             interval_timer = tokio::time::interval(new_interval_time);
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if let Err(e) = processor.state_sender.try_send(processor.output.clone()) {
-            warn!("Failed to send controller output: {}", e);
+    fn curve(curve: ResponseCurve, factor: f32) -> StickCurve {
+        StickCurve { curve, factor }
+    }
+
+    #[test]
+    fn curves_keep_center_and_end_points() {
+        for response in ResponseCurve::ALL {
+            for factor in [0.0, 0.5, 1.0] {
+                let stick = curve(response, factor);
+                assert_eq!(stick.apply(0.0), 0.0, "{} at {}", response, factor);
+                assert_eq!(stick.apply(1.0), 1.0, "{} at {}", response, factor);
+                assert_eq!(stick.apply(-1.0), -1.0, "{} at {}", response, factor);
+            }
         }
     }
+
+    #[test]
+    fn curves_attenuate_mid_range_by_factor() {
+        assert_eq!(curve(ResponseCurve::Linear, 1.0).apply(0.5), 0.5);
+        assert_eq!(curve(ResponseCurve::Quadratic, 0.0).apply(0.5), 0.5);
+
+        // Quadratic: 0.5 blends towards 0.25, cubic towards 0.125
+        assert_eq!(curve(ResponseCurve::Quadratic, 0.5).apply(0.5), 0.375);
+        assert_eq!(curve(ResponseCurve::Quadratic, 1.0).apply(0.5), 0.25);
+        assert_eq!(curve(ResponseCurve::CubicExpo, 1.0).apply(0.5), 0.125);
+        assert_eq!(curve(ResponseCurve::CubicExpo, 1.0).apply(-0.5), -0.125);
+
+        let weak = curve(ResponseCurve::CubicExpo, 0.25).apply(0.5);
+        let strong = curve(ResponseCurve::CubicExpo, 0.75).apply(0.5);
+        assert!(0.125 < strong && strong < weak && weak < 0.5);
+    }
+
+    #[test]
+    fn curves_clamp_out_of_range_input() {
+        let stick = curve(ResponseCurve::CubicExpo, 0.5);
+        assert_eq!(stick.apply(1.5), 1.0);
+        assert_eq!(stick.apply(-3.0), -1.0);
+    }
+
+    fn assert_near((x, y): (f32, f32), (expected_x, expected_y): (f32, f32)) {
+        assert!(
+            (x - expected_x).abs() < 1e-6 && (y - expected_y).abs() < 1e-6,
+            "({}, {}) is not ({}, {})",
+            x,
+            y,
+            expected_x,
+            expected_y
+        );
+    }
+
+    #[test]
+    fn per_axis_deadzone_filters_axes_independently() {
+        let mode = DeadzoneMode::PerAxis;
+
+        assert_eq!(mode.apply(0.19, 0.0, 0.2), (0.0, 0.0));
+        // Drift on y is dropped while x passes, rescaled from the deadzone edge
+        assert_near(mode.apply(0.6, 0.19, 0.2), (0.5, 0.0));
+        assert_eq!(mode.apply(0.2, -0.2, 0.2), (0.0, 0.0));
+        assert_eq!(mode.apply(1.0, -1.0, 0.2), (1.0, -1.0));
+    }
+
+    #[test]
+    fn radial_deadzone_uses_stick_magnitude() {
+        let mode = DeadzoneMode::Radial;
+
+        assert_eq!(mode.apply(0.1, 0.1, 0.2), (0.0, 0.0));
+        // Each axis is inside the deadzone, the magnitude is not
+        assert_eq!(mode.apply(0.15, 0.15, 0.2), (0.15, 0.15));
+        assert_eq!(mode.apply(0.0, 0.2, 0.2), (0.0, 0.2));
+        assert_eq!(mode.apply(0.6, 0.0, 0.2), (0.6, 0.0));
+    }
+
+    #[test]
+    fn scaled_radial_deadzone_rescales_magnitude() {
+        let mode = DeadzoneMode::ScaledRadial;
+
+        assert_eq!(mode.apply(0.0, 0.19, 0.2), (0.0, 0.0));
+        assert_eq!(mode.apply(0.0, 0.2, 0.2), (0.0, 0.0));
+        assert_near(mode.apply(0.6, 0.0, 0.2), (0.5, 0.0));
+        assert_eq!(mode.apply(-1.0, 0.0, 0.2), (-1.0, 0.0));
+
+        // Full diagonals of a square gate are capped at magnitude 1
+        assert_near(mode.apply(1.0, 1.0, 0.2), (0.5f32.sqrt(), 0.5f32.sqrt()));
+    }
+
+    fn chord_settings() -> ProcessorSettings {
+        ProcessorSettings {
+            chords: vec![ButtonChord {
+                name: "bumpers".to_string(),
+                buttons: vec![ButtonType::LeftBumper, ButtonType::RightBumper],
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn button(button_type: ButtonType, state: ButtonState, ms_ago: i64) -> RawControllerEvent {
+        RawControllerEvent::ButtonEvent {
+            button_type,
+            button_state: state,
+            timestamp: Local::now() - chrono::Duration::milliseconds(ms_ago),
+            gamepad_id: 0,
+        }
+    }
+
+    #[test]
+    fn chord_inside_window_replaces_button_events() {
+        let settings = chord_settings();
+        let mut pad = PadState::new(0);
+
+        pad.process_button_events(
+            &[
+                button(ButtonType::LeftBumper, ButtonState::Pressed, 200),
+                button(ButtonType::RightBumper, ButtonState::Pressed, 180),
+                button(ButtonType::A, ButtonState::Pressed, 100),
+            ],
+            &settings,
+        )
+        .unwrap();
+
+        assert_eq!(pad.output.chord_events.len(), 1);
+        assert_eq!(pad.output.chord_events[0].name, "bumpers");
+        assert_eq!(pad.output.chord_events[0].state, ButtonEventState::Held);
+        let buttons: Vec<&ButtonType> = pad
+            .output
+            .button_events
+            .iter()
+            .map(|event| &event.button)
+            .collect();
+        assert_eq!(buttons, [&ButtonType::A]);
+
+        // Still held next cycle, then released as one chord
+        pad.process_button_events(&[], &settings).unwrap();
+        assert_eq!(pad.output.chord_events.len(), 1);
+        assert!(pad
+            .output
+            .button_events
+            .iter()
+            .all(|e| e.button == ButtonType::A));
+
+        pad.process_button_events(
+            &[
+                button(ButtonType::LeftBumper, ButtonState::Released, 0),
+                button(ButtonType::RightBumper, ButtonState::Released, 0),
+            ],
+            &settings,
+        )
+        .unwrap();
+        assert_eq!(pad.output.chord_events.len(), 1);
+        assert_eq!(pad.output.chord_events[0].state, ButtonEventState::Complete);
+        assert!(pad.output.has_releases());
+        assert!(pad
+            .output
+            .button_events
+            .iter()
+            .all(|e| e.button == ButtonType::A));
+    }
+
+    #[test]
+    fn presses_outside_window_stay_single_buttons() {
+        let settings = chord_settings();
+        let mut pad = PadState::new(0);
+
+        pad.process_button_events(
+            &[
+                button(ButtonType::LeftBumper, ButtonState::Pressed, 300),
+                button(ButtonType::RightBumper, ButtonState::Pressed, 200),
+            ],
+            &settings,
+        )
+        .unwrap();
+
+        assert!(pad.output.chord_events.is_empty());
+        assert_eq!(pad.output.button_events.len(), 2);
+    }
+
+    #[test]
+    fn quick_chord_tap_is_reported_as_release() {
+        let settings = chord_settings();
+        let mut pad = PadState::new(0);
+
+        pad.process_button_events(
+            &[
+                button(ButtonType::LeftBumper, ButtonState::Pressed, 100),
+                button(ButtonType::RightBumper, ButtonState::Pressed, 90),
+                button(ButtonType::LeftBumper, ButtonState::Released, 20),
+                button(ButtonType::RightBumper, ButtonState::Released, 10),
+            ],
+            &settings,
+        )
+        .unwrap();
+
+        assert_eq!(pad.output.chord_events.len(), 1);
+        assert_eq!(pad.output.chord_events[0].state, ButtonEventState::Complete);
+        assert!(pad.output.button_events.is_empty());
+        assert!(pad.output.has_releases());
+    }
 }
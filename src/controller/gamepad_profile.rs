@@ -0,0 +1,284 @@
+//! Gamepad profiles mapping physical buttons to button types per controller brand
+//!
+//! gilrs reports face buttons by position (`South`, `East`, ...), but the
+//! labels on them differ between brands: the top button is `Y` on an Xbox pad
+//! and a triangle on a DualShock. A [`GamepadProfile`] decides which
+//! [`ButtonType`] each physical button produces, so mappings written for `X`
+//! and `Y` work on any pad.
+//!
+//! The profile of a pad is picked by [`ProfileSelection`]: either detected
+//! from the gamepad name or fixed by the user. Custom profiles are stored with
+//! the controller settings and take precedence over the built-in ones.
+//!
+//! Analog triggers (`LeftTrigger2`/`RightTrigger2`) are not remapped, they
+//! always produce trigger values.
+
+use gilrs::Button;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::event_collector::ButtonType;
+
+/// Name of the built-in profile used when no other profile matches
+pub const GENERIC_PROFILE: &str = "Generic";
+
+/// Physical gamepad button as reported by gilrs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PadButton {
+    South,
+    East,
+    North,
+    West,
+    C,
+    Z,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl PadButton {
+    pub const ALL: [PadButton; 17] = [
+        PadButton::South,
+        PadButton::East,
+        PadButton::North,
+        PadButton::West,
+        PadButton::C,
+        PadButton::Z,
+        PadButton::LeftTrigger,
+        PadButton::RightTrigger,
+        PadButton::Select,
+        PadButton::Start,
+        PadButton::Mode,
+        PadButton::LeftThumb,
+        PadButton::RightThumb,
+        PadButton::DPadUp,
+        PadButton::DPadDown,
+        PadButton::DPadLeft,
+        PadButton::DPadRight,
+    ];
+
+    /// Converts a gilrs button, `None` for analog triggers and unknown buttons
+    pub fn from_gilrs(button: Button) -> Option<Self> {
+        let button = match button {
+            Button::South => PadButton::South,
+            Button::East => PadButton::East,
+            Button::North => PadButton::North,
+            Button::West => PadButton::West,
+            Button::C => PadButton::C,
+            Button::Z => PadButton::Z,
+            Button::LeftTrigger => PadButton::LeftTrigger,
+            Button::RightTrigger => PadButton::RightTrigger,
+            Button::Select => PadButton::Select,
+            Button::Start => PadButton::Start,
+            Button::Mode => PadButton::Mode,
+            Button::LeftThumb => PadButton::LeftThumb,
+            Button::RightThumb => PadButton::RightThumb,
+            Button::DPadUp => PadButton::DPadUp,
+            Button::DPadDown => PadButton::DPadDown,
+            Button::DPadLeft => PadButton::DPadLeft,
+            Button::DPadRight => PadButton::DPadRight,
+            _ => return None,
+        };
+        Some(button)
+    }
+}
+
+impl std::fmt::Display for PadButton {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PadButton::South => "South",
+            PadButton::East => "East",
+            PadButton::North => "North",
+            PadButton::West => "West",
+            PadButton::C => "C",
+            PadButton::Z => "Z",
+            PadButton::LeftTrigger => "Left Bumper",
+            PadButton::RightTrigger => "Right Bumper",
+            PadButton::Select => "Select",
+            PadButton::Start => "Start",
+            PadButton::Mode => "Mode",
+            PadButton::LeftThumb => "Left Stick",
+            PadButton::RightThumb => "Right Stick",
+            PadButton::DPadUp => "D-Pad Up",
+            PadButton::DPadDown => "D-Pad Down",
+            PadButton::DPadLeft => "D-Pad Left",
+            PadButton::DPadRight => "D-Pad Right",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Button type produced by one physical button
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ButtonRemap {
+    pub button: PadButton,
+    pub target: ButtonType,
+}
+
+/// Button assignment for one controller brand or a single pad
+///
+/// Physical buttons without an entry are ignored.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GamepadProfile {
+    /// Name shown in the settings, unique among all profiles
+    pub name: String,
+
+    /// Gamepads whose name contains one of these (case-insensitive) use the
+    /// profile when it is detected automatically
+    #[serde(default)]
+    pub name_patterns: Vec<String>,
+
+    /// Button type of each physical button
+    pub buttons: Vec<ButtonRemap>,
+}
+
+impl GamepadProfile {
+    /// Layout used before profiles existed, kept for pads of unknown brand
+    pub fn generic() -> Self {
+        Self::with_face_buttons(
+            GENERIC_PROFILE,
+            &[],
+            [ButtonType::A, ButtonType::B, ButtonType::X, ButtonType::Y],
+        )
+    }
+
+    /// Xbox layout: A bottom, B right, X left, Y top
+    pub fn xbox() -> Self {
+        Self::with_face_buttons(
+            "Xbox",
+            &["xbox", "x-box", "microsoft"],
+            [ButtonType::A, ButtonType::B, ButtonType::Y, ButtonType::X],
+        )
+    }
+
+    /// DualShock/DualSense layout, buttons named after their Xbox position:
+    /// cross is A, circle B, square X and triangle Y
+    pub fn dualshock() -> Self {
+        Self::with_face_buttons(
+            "DualShock",
+            &[
+                "dualshock",
+                "dualsense",
+                "playstation",
+                "sony",
+                "wireless controller",
+            ],
+            [ButtonType::A, ButtonType::B, ButtonType::Y, ButtonType::X],
+        )
+    }
+
+    /// Profiles shipped with the application
+    pub fn builtin() -> Vec<Self> {
+        vec![Self::generic(), Self::xbox(), Self::dualshock()]
+    }
+
+    /// Button type produced by `button`, `None` if it is not assigned
+    pub fn map(&self, button: PadButton) -> Option<ButtonType> {
+        self.buttons
+            .iter()
+            .find(|remap| remap.button == button)
+            .map(|remap| remap.target.clone())
+    }
+
+    /// Assigns `target` to `button`, `None` removes the assignment
+    pub fn set(&mut self, button: PadButton, target: Option<ButtonType>) {
+        self.buttons.retain(|remap| remap.button != button);
+        if let Some(target) = target {
+            self.buttons.push(ButtonRemap { button, target });
+        }
+    }
+
+    /// Whether the profile is meant for a gamepad with this name
+    pub fn matches(&self, gamepad_name: &str) -> bool {
+        let gamepad_name = gamepad_name.to_lowercase();
+        self.name_patterns
+            .iter()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .any(|pattern| gamepad_name.contains(&pattern.trim().to_lowercase()))
+    }
+
+    /// Builds a profile with the shared non-face buttons
+    ///
+    /// `face` holds the button types of South, East, North and West.
+    fn with_face_buttons(name: &str, patterns: &[&str], face: [ButtonType; 4]) -> Self {
+        let [south, east, north, west] = face;
+        let buttons = [
+            (PadButton::South, south),
+            (PadButton::East, east),
+            (PadButton::North, north),
+            (PadButton::West, west),
+            (PadButton::Start, ButtonType::Start),
+            (PadButton::Select, ButtonType::Select),
+            (PadButton::LeftTrigger, ButtonType::LeftBumper),
+            (PadButton::RightTrigger, ButtonType::RightBumper),
+            (PadButton::LeftThumb, ButtonType::LeftStick),
+            (PadButton::RightThumb, ButtonType::RightStick),
+            (PadButton::DPadUp, ButtonType::DPadUp),
+            (PadButton::DPadDown, ButtonType::DPadDown),
+            (PadButton::DPadLeft, ButtonType::DPadLeft),
+            (PadButton::DPadRight, ButtonType::DPadRight),
+            (PadButton::Mode, ButtonType::Guide),
+        ]
+        .into_iter()
+        .map(|(button, target)| ButtonRemap { button, target })
+        .collect();
+
+        Self {
+            name: name.to_string(),
+            name_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            buttons,
+        }
+    }
+}
+
+/// How the profile of a gamepad is chosen
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProfileSelection {
+    /// Detected from the gamepad name, [`GENERIC_PROFILE`] if nothing matches
+    #[default]
+    Auto,
+    /// The profile of this name for every gamepad
+    Fixed(String),
+}
+
+impl std::fmt::Display for ProfileSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileSelection::Auto => write!(f, "Auto-detect"),
+            ProfileSelection::Fixed(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl ProfileSelection {
+    /// Picks the profile for a gamepad
+    ///
+    /// Custom profiles are searched before the built-in ones, so a custom
+    /// profile can replace a built-in profile of the same name. A fixed
+    /// profile that no longer exists falls back to [`GENERIC_PROFILE`].
+    pub fn resolve(&self, custom: &[GamepadProfile], gamepad_name: &str) -> GamepadProfile {
+        let mut profiles = custom.iter().cloned().chain(GamepadProfile::builtin());
+        let found = match self {
+            ProfileSelection::Auto => profiles.find(|profile| profile.matches(gamepad_name)),
+            ProfileSelection::Fixed(name) => {
+                let found = profiles.find(|profile| profile.name == *name);
+                if found.is_none() {
+                    warn!(
+                        "Gamepad profile {} not found, using {}",
+                        name, GENERIC_PROFILE
+                    );
+                }
+                found
+            }
+        };
+        found.unwrap_or_else(GamepadProfile::generic)
+    }
+}
@@ -0,0 +1,312 @@
+//! Recording and playback of controller output for reproducible mapping work
+//!
+//! A trace is a timestamped stream of [`ControllerOutput`] stored as TOML.
+//! Recording sits between the controller and the mapping manager and forwards
+//! everything unchanged. Playback replaces the live controller as input source
+//! and replays the frames with their original timing, so a tricky joystick
+//! motion can be captured once and replayed while iterating on a mapping.
+//!
+//! # File Format
+//!
+//! Each frame is appended as its own `[[frames]]` table, so a trace stays
+//! readable even if recording stops abruptly.
+//!
+//! ```toml
+//! [[frames]]
+//! offset_ms = 130
+//!
+//! [frames.output]
+//! gamepad_id = 0
+//! ...
+//! ```
+//!
+//! # Selecting the Input Source
+//!
+//! [`InputMode::from_args`] reads `--record <file>` or `--replay <file>` from
+//! the command line; without either flag the live controller is used.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::controller_handle::ControllerOutput;
+
+/// Errors while recording or loading a trace
+#[derive(Debug, thiserror::Error)]
+pub enum TraceError {
+    #[error("Trace file error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialize trace frame: {0}")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("Failed to parse trace: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArgs(String),
+}
+
+/// Where controller output for the mapping manager comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputMode {
+    /// Live controller only
+    Live,
+    /// Live controller, with every output also written to the file
+    Record(PathBuf),
+    /// Frames from the file instead of the live controller
+    Replay(PathBuf),
+}
+
+impl InputMode {
+    /// Parses `--record <file>` / `--replay <file>` from command line arguments
+    ///
+    /// The first argument (program name) is expected to be skipped by the caller.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, TraceError> {
+        let mut mode = InputMode::Live;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let build: fn(PathBuf) -> InputMode = match arg.as_str() {
+                "--record" => InputMode::Record,
+                "--replay" => InputMode::Replay,
+                _ => continue,
+            };
+            if mode != InputMode::Live {
+                return Err(TraceError::InvalidArgs(
+                    "--record and --replay are mutually exclusive".to_string(),
+                ));
+            }
+            let path = args
+                .next()
+                .ok_or_else(|| TraceError::InvalidArgs(format!("{} needs a file path", arg)))?;
+            mode = build(PathBuf::from(path));
+        }
+        Ok(mode)
+    }
+}
+
+/// One recorded controller output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceFrame {
+    /// Time since the recording started
+    pub offset_ms: u64,
+    pub output: ControllerOutput,
+}
+
+/// Serialization wrapper, one per file or appended chunk
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Trace {
+    #[serde(default)]
+    frames: Vec<TraceFrame>,
+}
+
+/// Appends controller output to a trace file
+pub struct TraceRecorder {
+    writer: BufWriter<File>,
+    started: Instant,
+}
+
+impl TraceRecorder {
+    /// Creates (or truncates) the trace file
+    pub fn create(path: &Path) -> Result<Self, TraceError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+        })
+    }
+
+    /// Appends one frame, timestamped relative to the recording start
+    pub fn record(&mut self, output: &ControllerOutput) -> Result<(), TraceError> {
+        let chunk = Trace {
+            frames: vec![TraceFrame {
+                offset_ms: self.started.elapsed().as_millis() as u64,
+                output: output.clone(),
+            }],
+        };
+        self.writer.write_all(toml::to_string(&chunk)?.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        // Flush per frame so an aborted session still leaves a usable trace
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads all frames of a trace file in recording order
+pub fn load_trace(path: &Path) -> Result<Vec<TraceFrame>, TraceError> {
+    let content = fs::read_to_string(path)?;
+    let trace: Trace = toml::from_str(&content)?;
+    Ok(trace.frames)
+}
+
+/// Forwards live controller output to the mapping manager while recording it
+///
+/// Recording errors are logged and stop the recording, but forwarding continues
+/// so the application stays usable.
+pub fn spawn_recorder(
+    path: &Path,
+    mut input_rx: mpsc::Receiver<ControllerOutput>,
+    output_tx: mpsc::Sender<ControllerOutput>,
+) -> Result<JoinHandle<()>, TraceError> {
+    let mut recorder = Some(TraceRecorder::create(path)?);
+    info!("Recording controller input to {}", path.display());
+
+    Ok(tokio::spawn(async move {
+        while let Some(output) = input_rx.recv().await {
+            if let Some(active) = &mut recorder {
+                if let Err(e) = active.record(&output) {
+                    error!("Stopped recording controller input: {}", e);
+                    recorder = None;
+                }
+            }
+            if output_tx.send(output).await.is_err() {
+                warn!("Mapping manager stopped, ending recording");
+                break;
+            }
+        }
+    }))
+}
+
+/// Feeds a recorded trace to the mapping manager with its original timing
+///
+/// Frames are sent unchanged, so the mapping sees exactly the recorded output.
+pub fn spawn_playback(
+    path: &Path,
+    output_tx: mpsc::Sender<ControllerOutput>,
+) -> Result<JoinHandle<()>, TraceError> {
+    let frames = load_trace(path)?;
+    info!(
+        "Replaying {} recorded frames from {}",
+        frames.len(),
+        path.display()
+    );
+
+    Ok(tokio::spawn(async move {
+        let started = tokio::time::Instant::now();
+        for frame in frames {
+            tokio::time::sleep_until(started + Duration::from_millis(frame.offset_ms)).await;
+            if output_tx.send(frame.output).await.is_err() {
+                warn!("Mapping manager stopped, ending playback");
+                return;
+            }
+        }
+        info!("Playback finished");
+    }))
+}
+
+/// Discards live controller output while a trace is replayed
+///
+/// Keeps the controller subsystem running for status and battery display
+/// without its output competing with the replayed frames.
+pub fn spawn_discard(mut input_rx: mpsc::Receiver<ControllerOutput>) -> JoinHandle<()> {
+    tokio::spawn(async move { while input_rx.recv().await.is_some() {} })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::controller_handle::{ButtonEvent, ButtonEventState, ButtonType};
+    use crate::controller::event_processor::{ChordEvent, JoystickPosition, TriggerValue};
+
+    /// Trace file under the system temp dir, unique per test.
+    fn trace_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "opencontroller-{}-{}.toml",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn outputs() -> Vec<ControllerOutput> {
+        let tilted = ControllerOutput {
+            gamepad_id: 1,
+            left_stick: JoystickPosition {
+                x: 0.5,
+                y: -0.25,
+                delta_x: 0.125,
+                ..Default::default()
+            },
+            right_trigger: TriggerValue {
+                value: 0.75,
+                max: 0.75,
+                ..Default::default()
+            },
+            button_events: vec![ButtonEvent {
+                button: ButtonType::A,
+                duration_ms: 120.0,
+                state: ButtonEventState::Held,
+            }],
+            ..Default::default()
+        };
+        let chord = ControllerOutput {
+            chord_events: vec![ChordEvent {
+                name: "menu".to_string(),
+                buttons: vec![ButtonType::Start, ButtonType::Select],
+                duration_ms: 40.0,
+                state: ButtonEventState::Complete,
+            }],
+            ..Default::default()
+        };
+        vec![tilted, chord, ControllerOutput::default()]
+    }
+
+    #[tokio::test]
+    async fn recorded_output_replays_unchanged() {
+        let path = trace_path("trace-round-trip");
+        let (live_tx, live_rx) = mpsc::channel(10);
+        let (forward_tx, mut forward_rx) = mpsc::channel(10);
+        let recorder = spawn_recorder(&path, live_rx, forward_tx).unwrap();
+        let recorded = outputs();
+
+        for output in recorded.clone() {
+            live_tx.send(output).await.unwrap();
+        }
+        drop(live_tx);
+        recorder.await.unwrap();
+
+        let mut forwarded = Vec::new();
+        while let Some(output) = forward_rx.recv().await {
+            forwarded.push(output);
+        }
+        assert_eq!(forwarded, recorded);
+
+        let (replay_tx, mut replay_rx) = mpsc::channel(10);
+        spawn_playback(&path, replay_tx).unwrap().await.unwrap();
+        let mut replayed = Vec::new();
+        while let Some(output) = replay_rx.recv().await {
+            replayed.push(output);
+        }
+        assert_eq!(replayed, recorded);
+
+        let offsets: Vec<u64> = load_trace(&path)
+            .unwrap()
+            .iter()
+            .map(|frame| frame.offset_ms)
+            .collect();
+        assert!(offsets.is_sorted());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn input_mode_from_args() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(InputMode::from_args(args(&[])).unwrap(), InputMode::Live);
+        assert_eq!(
+            InputMode::from_args(args(&["--replay", "trace.toml"])).unwrap(),
+            InputMode::Replay(PathBuf::from("trace.toml"))
+        );
+        assert!(InputMode::from_args(args(&["--record"])).is_err());
+        assert!(InputMode::from_args(args(&["--record", "a", "--replay", "b"])).is_err());
+    }
+}
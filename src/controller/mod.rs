@@ -5,6 +5,9 @@
 //! 1. [`event_collector`] - Raw gamepad input collection
 //! 2. [`event_processor`] - Event transformation and filtering
 //! 3. [`controller_handle`] - Unified API and lifecycle management
+//! 4. [`rumble`] - Force feedback requests played by the collector
+//! 5. [`input_trace`] - Recording and replay of controller output
+//! 6. [`gamepad_profile`] - Button layouts per controller brand
 //!
 //! # Architecture
 //!
@@ -19,3 +22,6 @@
 pub mod controller_handle;
 pub mod event_collector;
 pub mod event_processor;
+pub mod gamepad_profile;
+pub mod input_trace;
+pub mod rumble;
@@ -0,0 +1,120 @@
+//! Rumble (force feedback) requests for tactile confirmation
+//!
+//! Gilrs effects can only be created from the thread that owns the `Gilrs`
+//! context, so rumble is requested through a [`RumbleHandle`] and played by
+//! the event collector on its next poll.
+//!
+//! Pads without force feedback support ignore requests with a warning, so
+//! callers never need to check capabilities first.
+
+use std::sync::mpsc;
+use tracing::warn;
+
+/// Upper bound for a single pulse to keep a lost request from buzzing forever
+pub const MAX_RUMBLE_DURATION_MS: u64 = 5000;
+
+/// Predefined rumble sequences for common feedback situations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RumblePattern {
+    /// Brief light buzz, e.g. confirming a typed letter
+    Short,
+    /// Longer strong buzz, e.g. a mode switch
+    Long,
+    /// Two short buzzes, e.g. an action that was rejected
+    Double,
+}
+
+impl RumblePattern {
+    /// Returns the pulses making up this pattern
+    pub fn pulses(self) -> Vec<RumblePulse> {
+        match self {
+            RumblePattern::Short => vec![RumblePulse::new(0.4, 60, 0)],
+            RumblePattern::Long => vec![RumblePulse::new(0.8, 400, 0)],
+            RumblePattern::Double => {
+                vec![RumblePulse::new(0.6, 80, 0), RumblePulse::new(0.6, 80, 160)]
+            }
+        }
+    }
+}
+
+/// A single motor activation within a rumble request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RumblePulse {
+    /// Motor strength (0.0-1.0)
+    pub strength: f32,
+    /// How long the motors run
+    pub duration_ms: u64,
+    /// Delay from the start of the request
+    pub delay_ms: u64,
+}
+
+impl RumblePulse {
+    /// Creates a pulse, clamping strength and duration to valid ranges
+    pub fn new(strength: f32, duration_ms: u64, delay_ms: u64) -> Self {
+        Self {
+            strength: strength.clamp(0.0, 1.0),
+            duration_ms: duration_ms.min(MAX_RUMBLE_DURATION_MS),
+            delay_ms,
+        }
+    }
+
+    /// Time from the start of the request until this pulse ends
+    pub fn end_ms(&self) -> u64 {
+        self.delay_ms + self.duration_ms
+    }
+}
+
+/// Rumble request handled by the event collector
+#[derive(Debug, Clone)]
+pub struct RumbleRequest {
+    /// Target pad, `None` for the primary gamepad
+    pub gamepad_id: Option<usize>,
+    /// Pulses played as one effect
+    pub pulses: Vec<RumblePulse>,
+}
+
+/// Cloneable handle for requesting rumble from any thread
+///
+/// Obtained from [`super::controller_handle::ControllerHandle::rumble_handle`].
+/// Requests are fire-and-forget; if the collector has stopped they are
+/// dropped with a warning.
+#[derive(Debug, Clone)]
+pub struct RumbleHandle {
+    sender: mpsc::Sender<RumbleRequest>,
+}
+
+impl RumbleHandle {
+    pub(crate) fn new(sender: mpsc::Sender<RumbleRequest>) -> Self {
+        Self { sender }
+    }
+
+    /// Rumbles the primary gamepad once
+    pub fn rumble(&self, strength: f32, duration_ms: u64) {
+        self.send(RumbleRequest {
+            gamepad_id: None,
+            pulses: vec![RumblePulse::new(strength, duration_ms, 0)],
+        });
+    }
+
+    /// Plays a predefined pattern on the primary gamepad
+    pub fn rumble_pattern(&self, pattern: RumblePattern) {
+        self.send(RumbleRequest {
+            gamepad_id: None,
+            pulses: pattern.pulses(),
+        });
+    }
+
+    /// Plays a predefined pattern on a specific gamepad
+    pub fn rumble_pattern_on(&self, gamepad_id: usize, pattern: RumblePattern) {
+        self.send(RumbleRequest {
+            gamepad_id: Some(gamepad_id),
+            pulses: pattern.pulses(),
+        });
+    }
+
+    fn send(&self, request: RumbleRequest) {
+        if let Err(e) = self.sender.send(request) {
+            warn!("Rumble request dropped, collector not running: {}", e);
+        }
+    }
+}
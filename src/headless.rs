@@ -0,0 +1,262 @@
+//! # Headless Operation
+//!
+//! Runs OpenController without the egui UI, e.g. on an embedded board that
+//! drives an RC vehicle and bridges MQTT at boot with no display attached.
+//!
+//! ## Why This Module Exists
+//!
+//! In normal operation the UI is the consumer of every mapping output
+//! channel: it injects keyboard events, forwards ELRS channel maps to the
+//! transmitter and logs received MQTT messages. Without a UI these channels
+//! would fill up and the mapping manager would drop events with warnings.
+//! This module takes the UI's place and blocks until SIGINT or SIGTERM.
+//!
+//! ## Design Rationale
+//!
+//! ### Same Startup Path
+//! Controller, mapping manager, MQTT handler and persistence are started in
+//! `main` exactly as with the UI, so the last session is loaded and used the
+//! same way. Only the consumers of their output are replaced.
+//!
+//! ### Sinks
+//! - Keyboard mapping output has no target and is discarded; custom
+//!   protocol payloads are logged, as with the UI.
+//! - ELRS channel maps are streamed to the transmitter given with
+//!   `--elrs-port`. A failed link is reopened periodically, so unplugging
+//!   the module does not require a restart.
+//! - Received MQTT messages are mirrored into the session log, as the MQTT
+//!   menu would do.
+//!
+//! ### Shutdown
+//! On a signal the MQTT handler is told to disconnect and the session is
+//! saved, mirroring the UI's exit handling.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! opencontroller --headless --elrs-port /dev/ttyUSB0
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tracing::{debug, error, info, warn};
+
+use crate::mapping::custom::spawn_custom_sink;
+use crate::mapping::elrs::{
+    ELRSOutput, ELRSTransmitter, CRSF_DEFAULT_BAUD, DEFAULT_PACKET_RATE_HZ,
+};
+use crate::mapping::KeyboardBatch;
+use crate::mqtt::message_manager::MQTTMessage;
+use crate::persistence::config_portal::{ConfigPortal, PortalAction};
+use crate::persistence::persistence_worker::SessionAction;
+
+/// How often the transmitter link is checked and, if down, reopened.
+const LINK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time between attempts to reopen a failed transmitter link.
+const LINK_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Longest time the final session save may take on shutdown.
+const SHUTDOWN_SAVE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Errors of the headless runner
+#[derive(Debug, thiserror::Error)]
+pub enum HeadlessError {
+    #[error("Invalid arguments: {0}")]
+    InvalidArgs(String),
+
+    #[error("Failed to listen for shutdown signals: {0}")]
+    Signal(#[from] std::io::Error),
+}
+
+/// Command line options of headless mode
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeadlessOptions {
+    /// Serial port of the ELRS transmitter, ELRS output is discarded if unset
+    pub elrs_port: Option<String>,
+}
+
+impl HeadlessOptions {
+    /// Parses `--headless` and `--elrs-port <port>` from command line arguments
+    ///
+    /// Returns `None` if `--headless` is not given. The first argument
+    /// (program name) is expected to be skipped by the caller.
+    pub fn from_args(
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Option<Self>, HeadlessError> {
+        let mut headless = false;
+        let mut options = HeadlessOptions::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => headless = true,
+                "--elrs-port" => {
+                    let port = args.next().ok_or_else(|| {
+                        HeadlessError::InvalidArgs("--elrs-port needs a port name".to_string())
+                    })?;
+                    options.elrs_port = Some(port);
+                }
+                _ => {}
+            }
+        }
+
+        if !headless && options.elrs_port.is_some() {
+            return Err(HeadlessError::InvalidArgs(
+                "--elrs-port is only used with --headless".to_string(),
+            ));
+        }
+        Ok(headless.then_some(options))
+    }
+}
+
+/// Output channels normally drained by the UI
+pub struct HeadlessChannels {
+    pub ui_rx: mpsc::Receiver<KeyboardBatch>,
+    pub elrs_rx: mpsc::Receiver<ELRSOutput>,
+    pub custom_rx: mpsc::Receiver<HashMap<String, Vec<u8>>>,
+    pub mqtt_msg_rx: mpsc::Receiver<MQTTMessage>,
+}
+
+/// Drains all output channels until SIGINT or SIGTERM, then shuts down.
+///
+/// Tells the MQTT handler to disconnect through `activate_mqtt_tx` and saves
+/// the current session before returning.
+///
+/// # Errors
+///
+/// Returns [`HeadlessError::Signal`] if the signal handlers cannot be
+/// installed. Nothing is started in that case.
+pub async fn run(
+    options: HeadlessOptions,
+    channels: HeadlessChannels,
+    activate_mqtt_tx: watch::Sender<bool>,
+    config_portal: Arc<ConfigPortal>,
+    session_sender: mpsc::Sender<SessionAction>,
+) -> Result<(), HeadlessError> {
+    let mut sigterm = signal(SignalKind::terminate())?;
+    info!("Running headless, stop with Ctrl+C or SIGTERM");
+
+    let sinks = [
+        spawn_discard(channels.ui_rx, "keyboard"),
+        spawn_custom_sink(channels.custom_rx),
+        spawn_elrs_sink(channels.elrs_rx, options.elrs_port),
+        spawn_mqtt_log(channels.mqtt_msg_rx, config_portal),
+    ];
+
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => {
+            if let Err(e) = result {
+                error!("Failed to wait for Ctrl+C: {}", e);
+            }
+            info!("Received SIGINT, shutting down");
+        }
+        _ = sigterm.recv() => info!("Received SIGTERM, shutting down"),
+    }
+
+    let _ = activate_mqtt_tx.send(false);
+    save_session(&session_sender).await;
+
+    for sink in sinks {
+        sink.abort();
+    }
+    Ok(())
+}
+
+/// Consumes a channel whose output has no target without a UI.
+fn spawn_discard<T: Send + 'static>(
+    mut rx: mpsc::Receiver<T>,
+    name: &'static str,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while rx.recv().await.is_some() {}
+        debug!("{} output channel closed", name);
+    })
+}
+
+/// Streams ELRS channel maps to the transmitter, reopening a failed link.
+///
+/// Without a port the channel maps are only consumed.
+fn spawn_elrs_sink(
+    mut elrs_rx: mpsc::Receiver<ELRSOutput>,
+    port: Option<String>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let Some(port) = port else {
+            info!("No ELRS port given, discarding ELRS output");
+            while elrs_rx.recv().await.is_some() {}
+            return;
+        };
+
+        let mut transmitter: Option<ELRSTransmitter> = None;
+        let mut last_attempt: Option<tokio::time::Instant> = None;
+        let mut ticker = interval(LINK_POLL_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                output = elrs_rx.recv() => {
+                    let Some(output) = output else {
+                        debug!("ELRS output channel closed");
+                        return;
+                    };
+                    if let Some(transmitter) = &transmitter {
+                        transmitter.update_channels(output.channels);
+                    }
+                }
+                _ = ticker.tick() => {
+                    if let Some(link) = transmitter.as_mut() {
+                        if let Err(e) = link.poll() {
+                            warn!("ELRS link failed: {}", e);
+                            transmitter = None;
+                        }
+                    } else if last_attempt.is_none_or(|at| at.elapsed() >= LINK_RETRY_INTERVAL) {
+                        last_attempt = Some(tokio::time::Instant::now());
+                        match ELRSTransmitter::open(&port, CRSF_DEFAULT_BAUD, DEFAULT_PACKET_RATE_HZ) {
+                            Ok(link) => transmitter = Some(link),
+                            Err(e) => warn!("ELRS transmitter unavailable: {}", e),
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Mirrors received MQTT messages into the session log.
+fn spawn_mqtt_log(
+    mut mqtt_msg_rx: mpsc::Receiver<MQTTMessage>,
+    config_portal: Arc<ConfigPortal>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(msg) = mqtt_msg_rx.recv().await {
+            let _res = config_portal
+                .execute_portal_action_async(PortalAction::AppendReceivedLogMsg(msg))
+                .await;
+        }
+        debug!("MQTT message channel closed");
+    })
+}
+
+/// Saves the current session, giving up after [`SHUTDOWN_SAVE_TIMEOUT`].
+async fn save_session(session_sender: &mpsc::Sender<SessionAction>) {
+    let (response_tx, response_rx) = oneshot::channel();
+    if let Err(e) = session_sender
+        .send(SessionAction::SaveCurrentSession { response_tx })
+        .await
+    {
+        error!("Failed to request final session save: {}", e);
+        return;
+    }
+
+    match tokio::time::timeout(SHUTDOWN_SAVE_TIMEOUT, response_rx).await {
+        Ok(Ok(Ok(()))) => info!("Session saved on shutdown"),
+        Ok(Ok(Err(e))) => error!("Final session save failed: {}", e),
+        Ok(Err(e)) => warn!("No response to final session save: {}", e),
+        Err(_) => warn!("Final session save timed out"),
+    }
+}
@@ -0,0 +1,125 @@
+//! # Idle Mode
+//!
+//! Lowers the UI frame rate and the polling rates of the input pipeline while
+//! nothing happens, and returns to full rate on the next input.
+//!
+//! ## Why This Module Exists
+//!
+//! On battery the device spends most of its time waiting for the user, yet
+//! the UI repainted at the configured frame rate, the collector polled gilrs
+//! every 100µs and the mapping loops ticked every 10-20ms regardless. An
+//! [`IdleTracker`] shared by these loops records the last activity; after
+//! [`IDLE_TIMEOUT`] without any, each loop switches to its idle interval.
+//!
+//! ## What Counts as Activity
+//!
+//! - Gamepad button presses and releases, seen by the collector
+//! - Controller output with a held button, chord, deflected stick or pulled
+//!   trigger, seen by the mapping manager; holding a stick for mouse mode
+//!   keeps full rate
+//! - MQTT messages received or published, seen by the MQTT handler
+//! - Pointer and keyboard input in the UI
+//!
+//! MQTT keep-alive pings and other protocol traffic do not count.
+//!
+//! ## Wake-Up
+//!
+//! Loops read the tracker before each sleep, so the first event after an idle
+//! phase is seen after at most one idle interval, and the loops run at full
+//! rate from then on. The UI gets an immediate repaint from [`IdleTracker::record`]
+//! instead of waiting for its next idle frame.
+//!
+//! ## Savings
+//!
+//! While idle the collector wakes 100 instead of 10000 times per second, the
+//! UI repaints at [`IDLE_FPS`] instead of the configured rate, and the mapping
+//! manager wakes per controller output (every processing interval) instead of
+//! every 20ms. These are wake-up counts, not a measurement; the CPU time saved
+//! depends on the device and should be checked with `top` on the target.
+//!
+//! The mapping engines keep their tick, as their rate limiters and idle output
+//! (keyboard repeat, ELRS neutral frames) depend on it, and so does the ELRS
+//! transmitter thread, as the link needs its packet rate.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use tracing::debug;
+
+/// Time without activity after which the idle intervals apply.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Frame rate of the UI while idle.
+pub const IDLE_FPS: u8 = 2;
+
+/// Last activity of the application, shared by all loops that slow down.
+#[derive(Debug)]
+pub struct IdleTracker {
+    /// Reference point of `last_activity_ms`
+    epoch: Instant,
+
+    /// Milliseconds since `epoch` at the last activity
+    last_activity_ms: AtomicU64,
+
+    /// UI context repainted when activity ends an idle phase
+    ui_context: OnceLock<egui::Context>,
+}
+
+impl Default for IdleTracker {
+    /// Starts active, the idle intervals apply after the first [`IDLE_TIMEOUT`].
+    fn default() -> Self {
+        Self {
+            epoch: Instant::now(),
+            last_activity_ms: AtomicU64::new(0),
+            ui_context: OnceLock::new(),
+        }
+    }
+}
+
+impl IdleTracker {
+    /// Marks the application as active now.
+    ///
+    /// Ending an idle phase also requests a UI repaint, so the UI returns to
+    /// its full frame rate right away.
+    pub fn record(&self) {
+        let was_idle = self.is_idle();
+        self.last_activity_ms
+            .store(self.elapsed_ms(), Ordering::Relaxed);
+        if was_idle {
+            debug!("Activity, leaving idle mode");
+            if let Some(ctx) = self.ui_context.get() {
+                ctx.request_repaint();
+            }
+        }
+    }
+
+    /// Whether nothing happened for [`IDLE_TIMEOUT`].
+    pub fn is_idle(&self) -> bool {
+        let idle_ms = self
+            .elapsed_ms()
+            .saturating_sub(self.last_activity_ms.load(Ordering::Relaxed));
+        idle_ms >= IDLE_TIMEOUT.as_millis() as u64
+    }
+
+    /// Picks the interval of a polling loop for the current state.
+    pub fn interval(&self, active: Duration, idle: Duration) -> Duration {
+        if self.is_idle() {
+            idle
+        } else {
+            active
+        }
+    }
+
+    /// Registers the UI context repainted when an idle phase ends.
+    ///
+    /// Only the first context is kept; the UI is created once per process.
+    pub fn set_ui_context(&self, ctx: egui::Context) {
+        let _ = self.ui_context.set(ctx);
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.epoch.elapsed().as_millis() as u64
+    }
+}
@@ -0,0 +1,143 @@
+//! # Input Latency
+//!
+//! Measures the time from a gamepad event to the injection of the keyboard
+//! events it produced into the UI.
+//!
+//! ## Why This Module Exists
+//!
+//! Input passes the collector, the processor, the mapping manager, the
+//! keyboard engine and the UI channel before it reaches a widget. Each stage
+//! has its own interval or rate limit, and tuning `collection_interval_ms` or
+//! the keyboard rate limit needs the end-to-end delay they add up to, not a
+//! guess.
+//!
+//! ## How It Is Measured
+//!
+//! While measuring is enabled, the processor stamps each [`ControllerOutput`]
+//! with the collection time of the oldest raw event of its cycle. The keyboard
+//! strategy copies the stamp onto its events, the mapping manager onto the
+//! batch sent to the UI, and `raw_input_hook` records the age of the batch
+//! when it injects it. Outputs without new raw events, e.g. held buttons, and
+//! events produced without input (key repeat, dwell, cursor movement) carry no
+//! stamp and are not measured.
+//!
+//! The stamps use the same clock as the collector's raw event timestamps, so
+//! a wall clock adjustment during a measurement shows up as one outlier.
+//!
+//! ## Design Rationale
+//!
+//! Measuring is off by default and switched on from the settings diagnostics.
+//! While it is off the processor leaves the stamp empty and nothing is
+//! recorded, so the pipeline only pays for one atomic load per cycle. Samples
+//! live in a static like the log viewer's records, so no handle has to be
+//! wired through the threads.
+//!
+//! [`ControllerOutput`]: crate::controller::event_processor::ControllerOutput
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+/// Number of latency samples kept for the statistics.
+pub const SAMPLE_LIMIT: usize = 500;
+
+/// Upper bounds in milliseconds of the histogram buckets, a last bucket
+/// takes everything above.
+pub const BUCKET_BOUNDS_MS: [u64; 6] = [5, 10, 20, 50, 100, 200];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static SAMPLES: Mutex<VecDeque<Duration>> = Mutex::new(VecDeque::new());
+
+/// Whether input is stamped and latencies are recorded.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Starts or stops measuring; starting discards the previous samples.
+pub fn set_enabled(enabled: bool) {
+    if enabled && !ENABLED.load(Ordering::Relaxed) {
+        if let Ok(mut samples) = SAMPLES.lock() {
+            samples.clear();
+        }
+    }
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Records the age of input collected at `collected_at`.
+///
+/// Does nothing while measuring is off. Stamps from the future, after a
+/// clock adjustment, count as zero.
+pub fn record_since(collected_at: DateTime<Local>) {
+    if !is_enabled() {
+        return;
+    }
+    let age = (Local::now() - collected_at).to_std().unwrap_or_default();
+    if let Ok(mut samples) = SAMPLES.lock() {
+        if samples.len() == SAMPLE_LIMIT {
+            samples.pop_front();
+        }
+        samples.push_back(age);
+    }
+}
+
+/// Summary of the recorded samples
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    /// Bucket label and number of samples, one entry per bucket
+    pub buckets: Vec<(String, usize)>,
+}
+
+/// Statistics of the last [`SAMPLE_LIMIT`] samples.
+pub fn stats() -> LatencyStats {
+    let mut sorted: Vec<Duration> = match SAMPLES.lock() {
+        Ok(samples) => samples.iter().copied().collect(),
+        Err(_) => Vec::new(),
+    };
+    sorted.sort();
+
+    let percentile = |p: usize| {
+        sorted
+            .get((sorted.len() * p / 100).min(sorted.len().saturating_sub(1)))
+            .copied()
+            .unwrap_or_default()
+    };
+
+    let mut counts = [0usize; BUCKET_BOUNDS_MS.len() + 1];
+    for sample in &sorted {
+        let ms = sample.as_millis() as u64;
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| ms < *bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        counts[bucket] += 1;
+    }
+    let buckets = counts
+        .iter()
+        .enumerate()
+        .map(|(index, count)| (bucket_label(index), *count))
+        .collect();
+
+    LatencyStats {
+        samples: sorted.len(),
+        median: percentile(50),
+        p95: percentile(95),
+        max: sorted.last().copied().unwrap_or_default(),
+        buckets,
+    }
+}
+
+fn bucket_label(index: usize) -> String {
+    match (index.checked_sub(1), BUCKET_BOUNDS_MS.get(index)) {
+        (None, Some(upper)) => format!("< {} ms", upper),
+        (Some(lower), Some(upper)) => format!("{}-{} ms", BUCKET_BOUNDS_MS[lower], upper),
+        _ => format!(">= {} ms", BUCKET_BOUNDS_MS[BUCKET_BOUNDS_MS.len() - 1]),
+    }
+}
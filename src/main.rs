@@ -15,42 +15,64 @@
 //! - **MQTT integration**: Debug and control MQTT-based Smart Home systems
 //! - **ELRS support**: Control RC vehicles through ExpressLRS protocol
 //! - **Gamepad-centric UI**: Full application control via gamepad input
+//! - **Headless mode**: Run mappings and the MQTT bridge without a display
 //!
 
 pub mod controller;
+pub mod headless;
+pub mod idle;
+pub mod latency;
 pub mod mapping;
 pub mod mqtt;
 pub mod persistence;
+pub mod platform;
+pub mod supervisor;
 pub mod ui;
 
 use crate::controller::controller_handle::{ControllerHandle, ControllerSettings};
-use crate::mapping::{keyboard::KeyboardConfig, MappingEngineManager};
-use crate::persistence::config_portal::ConfigPortal;
+use crate::controller::input_trace::{self, InputMode};
+use crate::headless::{HeadlessChannels, HeadlessOptions};
+use crate::idle::IdleTracker;
+use crate::mapping::{MappingEngineManager, MappingError};
+use crate::persistence::config_portal::{ConfigResult, PortalAction};
+use crate::persistence::integrity;
+use crate::persistence::log_file;
 use crate::persistence::persistence_worker::PersistenceManager;
-use crate::ui::OpencontrollerUI;
+use crate::supervisor::{SharedReceiver, Supervisor};
+use crate::ui::{OpencontrollerUI, UiChannels};
 use color_eyre::{eyre::eyre, Result};
 use eframe::egui;
 use mqtt::config::MqttConfig;
-use mqtt::mqtt_handler::MQTTHandle;
-use std::sync::Arc;
+use mqtt::mqtt_handler::{HandlerContext, MQTTHandle};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
 use tokio::sync::{mpsc, watch};
-use tracing::{debug, error, info, warn, Level};
-use tracing_subscriber::FmtSubscriber;
+use tracing::{debug, error, info, warn};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
 use ui::MQTTServer;
 
 /// Application entry point and system initialization
 ///
 /// Initializes all subsystems in the correct order and establishes communication
-/// channels between components. The application runs until the UI is closed.
+/// channels between components. The application runs until the UI is closed,
+/// or in headless mode until SIGINT/SIGTERM is received.
 ///
 /// # Architecture Initialization
 ///
 /// 1. **Setup Phase**: Logging, error handling, and environment configuration
-/// 2. **Persistence Layer**: Session management and configuration storage
+/// 2. **Persistence Layer**: Config files are checked for corruption, then
+///    session management and configuration storage start. Corrupt files are
+///    resolved through a prompt in the UI; headless mode refuses to start
 /// 3. **Controller Subsystem**: Gamepad input collection and processing
 /// 4. **Communication Channels**: Inter-thread message passing setup
-/// 5. **Background Services**: MQTT handler and mapping engine manager
-/// 6. **UI Launch**: Fullscreen egui application with gamepad control
+/// 5. **Background Services**: MQTT handler and mapping engine manager, restarted
+///    by the [`supervisor`] when they fail
+/// 6. **UI Launch**: Fullscreen egui application with gamepad control, or the
+///    headless sinks when started with `--headless`
+/// 7. **Shutdown**: The session is saved and the MQTT handler disconnects
 ///
 /// # Threading Model
 ///
@@ -77,6 +99,16 @@ use ui::MQTTServer;
 ///
 /// # Run with custom log level
 /// RUST_LOG=opencontroller=trace cargo run
+///
+/// # Also write logs to ~/.config/opencontroller/logs
+/// OPENCONTROLLER_LOG_FILE=1 cargo run
+///
+/// # Record controller input, then replay it instead of the live controller
+/// cargo run -- --record trace.toml
+/// cargo run -- --replay trace.toml
+///
+/// # Run without UI, streaming ELRS output to a transmitter
+/// cargo run -- --headless --elrs-port /dev/ttyUSB0
 /// ```
 ///
 /// # Panics
@@ -88,27 +120,73 @@ use ui::MQTTServer;
 #[tokio::main]
 async fn main() -> Result<()> {
     setup()?;
+    let input_mode = InputMode::from_args(std::env::args().skip(1))
+        .map_err(|e| eyre!("Invalid command line: {}", e))?;
+    let headless = HeadlessOptions::from_args(std::env::args().skip(1))
+        .map_err(|e| eyre!("Invalid command line: {}", e))?;
 
-    // Initialize controller with human-optimized timing
-    debug!("Initializing controller with default settings");
-    let controller_settings = ControllerSettings {
-        collection_interval_ms: 130,   // Based on ~100-150ms human reaction time
-        button_press_threshold_ms: 30, // Filter accidental button presses
-        joystick_deadzone: 0.05,       // 5% deadzone for analog sticks
-    };
+    // Check config files before loading replaces broken ones by defaults
+    let corrupt_files = integrity::check_config_files();
+    if headless.is_some() && !corrupt_files.is_empty() {
+        let files: Vec<String> = corrupt_files
+            .iter()
+            .map(|file| format!("{}: {}", file.path.display(), file.error))
+            .collect();
+        return Err(eyre!(
+            "Corrupt config files, start the UI to recover them or fix them by hand:\n{}",
+            files.join("\n")
+        ));
+    }
 
     // Initialize persistence layer
     let persistence_manager = PersistenceManager::new().await;
     let session_sender = persistence_manager.get_sender();
+    let autosave_tx = persistence_manager.autosave_sender();
     let config_portal = persistence_manager.get_cfg_portal().await;
 
+    // Initialize controller with the session's settings, falling back to
+    // human-optimized defaults (130ms interval, 30ms threshold, 5% deadzone)
+    let controller_settings = if let ConfigResult::ControllerSettings(settings) =
+        config_portal.execute_potal_action(PortalAction::GetControllerSettings)
+    {
+        settings
+    } else {
+        warn!("Could not load controller settings, using defaults");
+        ControllerSettings::default()
+    };
+    debug!("Initializing controller with {:?}", controller_settings);
+
     // Create controller communication channel
-    let (controller_output_sender, controller_output_receiver) = mpsc::channel(1000);
+    let (controller_output_sender, live_output_receiver) = mpsc::channel(1000);
+
+    // Shared by every loop that slows down while nothing happens
+    let idle = Arc::new(IdleTracker::default());
 
     // Spawn controller subsystem
-    let _controller_handle =
-        ControllerHandle::spawn(Some(controller_settings), controller_output_sender)
-            .map_err(|e| eyre!("Failed to spawn controller: {}", e))?;
+    let controller_handle = ControllerHandle::spawn(
+        Some(controller_settings),
+        controller_output_sender,
+        idle.clone(),
+    )
+    .map_err(|e| eyre!("Failed to spawn controller: {}", e))?;
+
+    // Select the input source for the mapping engines
+    let controller_output_receiver = match &input_mode {
+        InputMode::Live => live_output_receiver,
+        InputMode::Record(path) => {
+            let (trace_tx, trace_rx) = mpsc::channel(1000);
+            input_trace::spawn_recorder(path, live_output_receiver, trace_tx)
+                .map_err(|e| eyre!("Failed to start recording: {}", e))?;
+            trace_rx
+        }
+        InputMode::Replay(path) => {
+            let (trace_tx, trace_rx) = mpsc::channel(1000);
+            input_trace::spawn_discard(live_output_receiver);
+            input_trace::spawn_playback(path, trace_tx)
+                .map_err(|e| eyre!("Failed to start playback: {}", e))?;
+            trace_rx
+        }
+    };
 
     // Create output channels for different mapping types
     let (ui_tx, ui_rx) = mpsc::channel(100);
@@ -119,22 +197,35 @@ async fn main() -> Result<()> {
     let (activate_mqtt_tx, activate_mqtt_rx) = watch::channel(true);
     let (mqtt_ui_msg_tx, mqtt_ui_msg_rx) = mpsc::channel(100);
     let (ui_mqtt_msg_tx, ui_mqtt_msg_rx) = mpsc::channel(100);
+    let (subscription_tx, subscription_rx) = watch::channel(Default::default());
+    let (subscription_request_tx, subscription_request_rx) = mpsc::channel(10);
 
-    let session_sender_clone = session_sender.clone();
+    // Restarts the MQTT handler and mapping manager if they fail
+    let supervisor = Supervisor::default();
+    let task_health_rx = supervisor.health_receiver();
 
-    // Spawn MQTT handler
+    // Spawn MQTT handler, every attempt reads the same incoming channels
+    let ui_mqtt_msg_rx = SharedReceiver::new(ui_mqtt_msg_rx);
+    let subscription_request_rx = SharedReceiver::new(subscription_request_rx);
     let portal = config_portal.clone();
-    let _mqtt_handl = tokio::spawn(async move {
-        let mut mqtt_handle = MQTTHandle { active: true };
-        mqtt_handle
-            .start_connection(
-                ui_mqtt_msg_rx,
-                mqtt_ui_msg_tx,
-                activate_mqtt_rx,
-                portal,
-                session_sender_clone,
-            )
-            .await;
+    let session_sender_clone = session_sender.clone();
+    let mqtt_idle = idle.clone();
+    let mqtt_handl = supervisor.spawn("MQTT", move || {
+        let activation = activate_mqtt_rx.clone();
+        let context = HandlerContext {
+            msg_in: ui_mqtt_msg_rx.clone(),
+            msg_out: mqtt_ui_msg_tx.clone(),
+            config_portal: portal.clone(),
+            persistence_sender: session_sender_clone.clone(),
+            subscription_tx: subscription_tx.clone(),
+            subscription_request_rx: subscription_request_rx.clone(),
+            idle: mqtt_idle.clone(),
+        };
+        async move {
+            let mut mqtt_handle = MQTTHandle { active: true };
+            mqtt_handle.start_connection(activation, context).await;
+            Ok::<(), Infallible>(())
+        }
     });
 
     // Initialize and start mapping engine manager
@@ -145,38 +236,120 @@ async fn main() -> Result<()> {
         custom_tx,
         config_portal.clone(),
     );
+    manager.set_rumble_handle(controller_handle.rumble_handle());
+    manager.set_idle_tracker(idle.clone());
+    manager.set_settings_receiver(controller_handle.settings_sender().subscribe());
+    let mapping_reload_tx = manager.reload_sender();
+    let controller_state_rx = manager.state_receiver();
+    let mapping_command_tx = manager.command_sender();
+    let active_mappings_rx = manager.active_mappings_receiver();
+    let dropped_events = manager.dropped_events();
+    let kill_switch_rx = manager.kill_switch_receiver();
+    let (navigation_tx, navigation_rx) = mpsc::channel(10);
 
-    manager
-        .activate_mapping(mapping::MappingType::Keyboard)
-        .await?;
+    // Keyboard events need the UI as target, headless mode drives ELRS instead
+    let initial_mapping = if headless.is_some() {
+        mapping::MappingType::ELRS
+    } else {
+        manager.set_navigation_sender(navigation_tx);
+        mapping::MappingType::Keyboard
+    };
+    manager.activate_mapping(initial_mapping).await?;
 
-    let _manager_handl = tokio::spawn(async move {
-        let _res = manager.run_mapping().await;
+    // The manager outlives a panicking attempt, engines and channels included
+    let manager = Arc::new(tokio::sync::Mutex::new(manager));
+    let _manager_handl = supervisor.spawn("Mapping", move || {
+        let manager = manager.clone();
+        async move {
+            let mut manager = manager.lock().await;
+            // A failed engine restart only stops that engine, the others continue
+            loop {
+                match manager.run_mapping().await {
+                    Err(e @ MappingError::ChannelClosed(_)) => return Err(e),
+                    Err(e) => error!("Mapping engine failed, continuing without it: {}", e),
+                    Ok(()) => return Ok(()),
+                }
+            }
+        }
     });
 
-    // Launch UI in fullscreen mode
-    debug!("Starting UI with mapping manager");
-    let mut native_options = eframe::NativeOptions::default();
-    native_options.viewport = egui::ViewportBuilder::default().with_fullscreen(true);
+    if let Some(options) = headless {
+        let channels = HeadlessChannels {
+            ui_rx,
+            elrs_rx,
+            custom_rx,
+            mqtt_msg_rx: mqtt_ui_msg_rx,
+        };
+        headless::run(
+            options,
+            channels,
+            activate_mqtt_tx,
+            config_portal,
+            session_sender,
+        )
+        .await
+        .map_err(|e| eyre!("Headless mode failed: {}", e))?;
+    } else {
+        // No custom protocol transmitter yet, the payloads are only logged
+        let _custom_sink = mapping::custom::spawn_custom_sink(custom_rx);
 
-    eframe::run_native(
-        "OpenController",
-        native_options,
-        Box::new(|cc| {
-            Ok(Box::new(OpencontrollerUI::new(
-                cc,
-                ui_rx,
-                mqtt_ui_msg_rx,
-                ui_mqtt_msg_tx,
-                config_portal,
-                session_sender,
-            )))
-        }),
-    );
+        // Launch UI in fullscreen mode
+        debug!("Starting UI with mapping manager");
+        let native_options = eframe::NativeOptions {
+            viewport: egui::ViewportBuilder::default().with_fullscreen(true),
+            ..Default::default()
+        };
+
+        // Logged instead of returned, so MQTT is still shut down below
+        if let Err(e) = eframe::run_native(
+            "OpenController",
+            native_options,
+            Box::new(|cc| {
+                Ok(Box::new(OpencontrollerUI::new(
+                    cc,
+                    UiChannels {
+                        event_receiver: ui_rx,
+                        navigation_rx,
+                        elrs_rx,
+                        received_msg: mqtt_ui_msg_rx,
+                        msg_sender: ui_mqtt_msg_tx,
+                        subscription_rx,
+                        subscription_request_tx,
+                        mqtt_active_tx: activate_mqtt_tx,
+                        config_portal,
+                        session_sender,
+                        autosave_tx,
+                        mapping_reload_tx,
+                        controller_state_rx,
+                        mapping_command_tx,
+                        active_mappings_rx,
+                        kill_switch_rx,
+                        dropped_events,
+                        task_health_rx,
+                    },
+                    idle,
+                    &controller_handle,
+                    corrupt_files,
+                )))
+            }),
+        ) {
+            error!("UI failed: {}", e);
+        }
+    }
+
+    // The UI or headless runner has released the MQTT activation, which
+    // stops the MQTT handler
+    match tokio::time::timeout(SHUTDOWN_TIMEOUT, mqtt_handl).await {
+        Ok(_) => info!("MQTT handler stopped"),
+        Err(_) => warn!("MQTT handler did not stop in time"),
+    }
 
     Ok(())
 }
 
+/// Longest time to wait for background tasks after the UI or headless runner stopped.
+const SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// Configures application environment and error handling
 ///
 /// Sets up essential runtime configuration including error reporting,
@@ -239,18 +412,54 @@ fn setup() -> Result<()> {
 /// - **Thread IDs**: Enabled for multi-threaded debugging
 /// - **File/Line**: Enabled for precise error location
 /// - **Format**: Pretty-printed for human readability
+/// - **Log Viewer**: The same records are kept in memory for the settings
+///   diagnostics, see [`ui::log_viewer`]
+/// - **Log File**: With `OPENCONTROLLER_LOG_FILE` set, the records are also
+///   written without colors to a daily rotating file, see [`log_file`]
 ///
 /// # Note
 ///
 /// This function should only be called once during application startup.
 /// Multiple calls may result in logging configuration conflicts.
 fn setup_logging_env() {
-    FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .with_target(false) // Hide target for cleaner output
-        .with_thread_ids(true) // Essential for multi-threaded debugging
-        .with_file(true) // Show source file
-        .with_line_number(true) // Show line numbers
-        .pretty() // Human-readable formatting
+    let (log_file, log_file_error) = match log_file::from_env() {
+        Some(Ok(file)) => (Some(file), None),
+        Some(Err(e)) => (None, Some(e)),
+        None => (None, None),
+    };
+    let log_file_path = log_file.as_ref().map(|file| file.path());
+    let file_layer = log_file.map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false) // No escape codes in the file
+            .with_thread_ids(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_writer(Mutex::new(file))
+            .with_filter(LevelFilter::INFO)
+    });
+
+    tracing_subscriber::registry()
+        .with(ui::log_viewer::LogCapture::layer()) // Recent records for the in-UI log viewer
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_target(false) // Hide target for cleaner output
+                .with_thread_ids(true) // Essential for multi-threaded debugging
+                .with_file(true) // Show source file
+                .with_line_number(true) // Show line numbers
+                .pretty() // Human-readable formatting
+                .with_filter(LevelFilter::INFO),
+        )
+        .with(file_layer) // Optional log file for post-incident analysis
         .init();
+
+    if let Some(path) = log_file_path {
+        info!("Writing logs to {}", path.display());
+    }
+    if let Some(e) = log_file_error {
+        warn!(
+            "File logging disabled, could not open a log file in {}: {}",
+            log_file::log_dir().display(),
+            e
+        );
+    }
 }
@@ -1,153 +1,158 @@
-//! Implementierung der Custom-Mapping-Strategie für benutzerdefinierte Protokolle
-
-use crate::controller::controller_handle::{
-    ButtonType, ControllerOutput, JoystickType, TriggerType,
-};
+//! # Custom Protocol Mapping Strategy
+//!
+//! Maps button chords to named byte payloads for protocols OpenController
+//! has no dedicated mapping for, e.g. fixed 433MHz remote codes as sent by a
+//! FlipperZero-style transmitter.
+//!
+//! ## Why This Module Exists
+//!
+//! Many 433MHz devices (sockets, gates, blinds) react to one fixed code per
+//! remote button. Sending such a code needs no protocol logic on the mapping
+//! side, only a way to say "this combo sends these bytes". The strategy
+//! produces [`MappedEvent::CustomEvent`]s keyed by payload name; whatever
+//! consumes the custom output channel turns them into radio traffic.
+//!
+//! ## Design Rationale
+//!
+//! ### Chords as Combos
+//! Combos are the chords defined in the controller settings, referenced by
+//! name like the keyboard's chord mapping. The event processor already
+//! resolves them, so the buttons of a chord never fire on their own.
+//!
+//! ### One Event per Press
+//! A remote code is sent once per press, not every mapping cycle: a payload
+//! is emitted when its chord starts and again only after it was released.
+//! Cycles without a new press produce no event. Held chords are tracked per
+//! gamepad, as every pad reports its own output each cycle.
+//!
+//! ### Protocol Parameters
+//! [`ProtocolConfig::parameters`] are added to every event, so a transmitter
+//! receives e.g. its frequency or device id along with each payload.
+
+use crate::controller::controller_handle::{ButtonEventState, ControllerOutput};
 use crate::mapping::{
-    strategy::MappingContext, MappedEvent, MappingError, MappingStrategy, MappingType,
+    strategy::MappingContext, MappedEvent, MappingConfig, MappingError, MappingStrategy,
+    MappingType, RateLimiter,
 };
-use std::collections::HashMap;
-use tracing::{debug, error, info, warn};
-
-/// Konfiguration für Custom-Mapping
-#[derive(Debug, Clone)]
-pub struct CustomConfig {
-    /// Benutzerdefinierte Mapping-Funktionen für Joysticks
-    joystick_handlers: HashMap<JoystickType, JoystickHandler>,
-
-    /// Benutzerdefinierte Mapping-Funktionen für Trigger
-    trigger_handlers: HashMap<TriggerType, TriggerHandler>,
-
-    /// Benutzerdefinierte Mapping-Funktionen für Buttons
-    button_handlers: HashMap<ButtonType, ButtonHandler>,
-
-    /// Protokoll-Spezifische Konfiguration
-    protocol_config: ProtocolConfig,
-
-    /// Name der Konfiguration
-    name: String,
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+/// Default interval between custom mappings, 100Hz.
+///
+/// Chords are only checked for new presses, so the rate mostly bounds how
+/// quickly a released chord can be pressed again.
+pub const DEFAULT_RATE_LIMIT_MS: u64 = 10;
+
+/// A named byte payload sent when its chord is pressed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomPayload {
+    /// Key of the payload in the emitted event, e.g. `garage_door`
+    pub name: String,
+    /// Raw bytes handed to the transmitter
+    pub bytes: Vec<u8>,
 }
 
-/// Protokoll-Spezifische Konfiguration
-#[derive(Debug, Clone)]
+/// Protocol description passed along with every payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProtocolConfig {
-    /// Name des Protokolls
+    /// Name of the protocol, e.g. `433MHz OOK`
     pub protocol_name: String,
 
-    /// Protokoll-Version
+    /// Protocol version
     pub protocol_version: String,
 
-    /// Benutzerdefinierte Protokoll-Parameter
+    /// Parameters added to every event, keyed like payloads
+    #[serde(default)]
     pub parameters: HashMap<String, Vec<u8>>,
 }
 
-/// Typ für Joystick-Handler-Funktionen
-type JoystickHandler = fn(x: f32, y: f32) -> HashMap<String, Vec<u8>>;
+impl Default for ProtocolConfig {
+    fn default() -> Self {
+        Self {
+            protocol_name: "Raw".to_string(),
+            protocol_version: "1.0".to_string(),
+            parameters: HashMap::new(),
+        }
+    }
+}
+
+/// Configuration of the custom protocol mapping.
+///
+/// Stored in the controller config; an empty config is valid to store but
+/// cannot be activated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomConfig {
+    /// Human-readable configuration name
+    pub name: String,
 
-/// Typ für Trigger-Handler-Funktionen
-type TriggerHandler = fn(value: f32) -> HashMap<String, Vec<u8>>;
+    /// Protocol the payloads belong to
+    #[serde(default)]
+    pub protocol: ProtocolConfig,
 
-/// Typ für Button-Handler-Funktionen
-type ButtonHandler = fn(pressed: bool, duration_ms: f64) -> HashMap<String, Vec<u8>>;
+    /// Payload sent per chord, by the chord name from the controller settings
+    #[serde(default)]
+    pub chord_payloads: HashMap<String, CustomPayload>,
+
+    /// Minimum milliseconds between two mapping cycles
+    #[serde(default = "default_rate_limit_ms")]
+    pub rate_limit_ms: u64,
+}
+
+fn default_rate_limit_ms() -> u64 {
+    DEFAULT_RATE_LIMIT_MS
+}
 
-impl CustomConfig {
-    /// Erstellt eine neue Custom-Mapping-Konfiguration
-    pub fn new(
-        joystick_handlers: HashMap<JoystickType, JoystickHandler>,
-        trigger_handlers: HashMap<TriggerType, TriggerHandler>,
-        button_handlers: HashMap<ButtonType, ButtonHandler>,
-        protocol_config: ProtocolConfig,
-        name: String,
-    ) -> Self {
+impl Default for CustomConfig {
+    fn default() -> Self {
         Self {
-            joystick_handlers,
-            trigger_handlers,
-            button_handlers,
-            protocol_config,
-            name,
+            name: "Custom Mapping".to_string(),
+            protocol: ProtocolConfig::default(),
+            chord_payloads: HashMap::new(),
+            rate_limit_ms: DEFAULT_RATE_LIMIT_MS,
         }
     }
-
-    /// Erstellt eine minimale Demo-Konfiguration
-    pub fn demo_config() -> Self {
-        // Demo-Joystick-Handler
-        let joystick_handler: JoystickHandler = |x, y| {
-            let mut result = HashMap::new();
-            // X-Wert als 16-Bit-Integer (Little-Endian) kodieren
-            let x_scaled = (x * 32767.0) as i16;
-            result.insert("x_pos".to_string(), x_scaled.to_le_bytes().to_vec());
-
-            // Y-Wert als 16-Bit-Integer (Little-Endian) kodieren
-            let y_scaled = (y * 32767.0) as i16;
-            result.insert("y_pos".to_string(), y_scaled.to_le_bytes().to_vec());
-
-            result
-        };
-
-        // Demo-Trigger-Handler
-        let trigger_handler: TriggerHandler = |value| {
-            let mut result = HashMap::new();
-            // Wert als 8-Bit-Integer kodieren
-            let byte_value = (value * 255.0) as u8;
-            result.insert("trigger".to_string(), vec![byte_value]);
-            result
-        };
-
-        // Demo-Button-Handler
-        let button_handler: ButtonHandler = |pressed, _| {
-            let mut result = HashMap::new();
-            // Button-Status als einzelnes Byte kodieren
-            result.insert("button".to_string(), vec![if pressed { 1 } else { 0 }]);
-            result
-        };
-
-        // Handler-Maps erstellen
-        let mut joystick_handlers = HashMap::new();
-        joystick_handlers.insert(JoystickType::Left, joystick_handler);
-        joystick_handlers.insert(JoystickType::Right, joystick_handler);
-
-        let mut trigger_handlers = HashMap::new();
-        trigger_handlers.insert(TriggerType::Left, trigger_handler);
-        trigger_handlers.insert(TriggerType::Right, trigger_handler);
-
-        let mut button_handlers = HashMap::new();
-        button_handlers.insert(ButtonType::A, button_handler);
-        button_handlers.insert(ButtonType::B, button_handler);
-        button_handlers.insert(ButtonType::X, button_handler);
-        button_handlers.insert(ButtonType::Y, button_handler);
-
-        // Protokoll-Konfiguration erstellen
-        let mut parameters = HashMap::new();
-        parameters.insert("device_id".to_string(), vec![0x01, 0x02, 0x03, 0x04]);
-
-        let protocol_config = ProtocolConfig {
-            protocol_name: "Demo Protocol".to_string(),
-            protocol_version: "1.0".to_string(),
-            parameters,
-        };
-
-        Self::new(
-            joystick_handlers,
-            trigger_handlers,
-            button_handlers,
-            protocol_config,
-            "Demo Custom Mapping".to_string(),
-        )
-    }
 }
 
-impl crate::mapping::MappingConfig for CustomConfig {
+impl MappingConfig for CustomConfig {
     fn validate(&self) -> Result<(), MappingError> {
-        // Mindestanforderungen prüfen
-        if self.joystick_handlers.is_empty()
-            && self.trigger_handlers.is_empty()
-            && self.button_handlers.is_empty()
-        {
+        if self.chord_payloads.is_empty() {
             return Err(MappingError::ConfigError(
-                "Custom configuration must define at least one handler".to_string(),
+                "Custom mapping must map at least one chord to a payload".to_string(),
             ));
         }
 
+        let mut names = HashSet::new();
+        for (chord, payload) in &self.chord_payloads {
+            if payload.name.is_empty() {
+                return Err(MappingError::ConfigError(format!(
+                    "Payload of chord '{}' has no name",
+                    chord
+                )));
+            }
+            if payload.bytes.is_empty() {
+                return Err(MappingError::ConfigError(format!(
+                    "Payload '{}' of chord '{}' is empty",
+                    payload.name, chord
+                )));
+            }
+            if self.protocol.parameters.contains_key(&payload.name) {
+                return Err(MappingError::ConfigError(format!(
+                    "Payload '{}' has the name of a protocol parameter",
+                    payload.name
+                )));
+            }
+            if !names.insert(payload.name.as_str()) {
+                return Err(MappingError::ConfigError(format!(
+                    "Payload name '{}' is used by more than one chord",
+                    payload.name
+                )));
+            }
+        }
+
+        RateLimiter::validate_interval(self.rate_limit_ms)?;
         Ok(())
     }
 
@@ -163,117 +168,88 @@ impl crate::mapping::MappingConfig for CustomConfig {
         self.name.clone()
     }
 
+    fn rate_limit_ms(&self) -> Option<u64> {
+        Some(self.rate_limit_ms)
+    }
+
     fn get_description(&self) -> String {
         format!(
             "Custom mapping for {} protocol v{}",
-            self.protocol_config.protocol_name, self.protocol_config.protocol_version
+            self.protocol.protocol_name, self.protocol.protocol_version
         )
     }
 }
 
-/// Implementierung der Custom-Mapping-Strategie
+/// Strategy sending the configured payload once per chord press
 pub struct CustomStrategy {
-    /// Konfiguration für das Mapping
+    /// Configuration for the mapping
     config: CustomConfig,
 
-    /// Zustandskontext
-    context: MappingContext,
-
-    /// Gesammelte Ausgabedaten
-    output_data: HashMap<String, Vec<u8>>,
+    /// Tracks which chords are held per gamepad, to detect new presses
+    pads: HashMap<usize, MappingContext>,
 }
 
 impl CustomStrategy {
-    /// Erstellt eine neue Custom-Mapping-Strategie
+    /// Creates a new custom mapping strategy
     pub fn new(config: CustomConfig) -> Self {
-        // Protokoll-Parameter als Basis-Ausgabedaten verwenden
-        let output_data = config.protocol_config.parameters.clone();
-
         Self {
             config,
-            context: MappingContext::default(),
-            output_data,
+            pads: HashMap::new(),
         }
     }
 
-    /// Verarbeitet Joystick-Eingaben
-    fn process_joysticks(&mut self, input: &ControllerOutput) {
-        for (joystick_type, handler) in &self.config.joystick_handlers {
-            let (x, y) = match joystick_type {
-                JoystickType::Left => (input.left_stick.x, input.left_stick.y),
-                JoystickType::Right => (input.right_stick.x, input.right_stick.y),
+    /// Collects the payloads of chords that started this cycle.
+    ///
+    /// Chords that are no longer reported by their pad count as released.
+    fn pressed_payloads(&mut self, input: &ControllerOutput) -> HashMap<String, Vec<u8>> {
+        let context = self.pads.entry(input.gamepad_id).or_default();
+        let mut payloads = HashMap::new();
+        let mut reported = HashSet::new();
+
+        for chord in &input.chord_events {
+            let Some(payload) = self.config.chord_payloads.get(&chord.name) else {
+                continue;
             };
+            reported.insert(chord.name.as_str());
 
-            // Handler aufrufen und Ergebnis in Ausgabedaten integrieren
-            let data = handler(x, y);
-            for (key, value) in data {
-                self.output_data.insert(key, value);
-            }
-        }
-    }
-
-    /// Verarbeitet Trigger-Eingaben
-    fn process_triggers(&mut self, input: &ControllerOutput) {
-        for (trigger_type, handler) in &self.config.trigger_handlers {
-            let value = match trigger_type {
-                TriggerType::Left => input.left_trigger.value,
-                TriggerType::Right => input.right_trigger.value,
+            let started = match chord.state {
+                ButtonEventState::Held => context.held_chords.insert(chord.name.clone()),
+                // A release is only a new press if the chord was never seen held
+                ButtonEventState::Complete => !context.held_chords.remove(&chord.name),
             };
-
-            // Handler aufrufen und Ergebnis in Ausgabedaten integrieren
-            let data = handler(value);
-            for (key, value) in data {
-                self.output_data.insert(key, value);
+            if started {
+                payloads.insert(payload.name.clone(), payload.bytes.clone());
             }
         }
-    }
 
-    /// Verarbeitet Button-Eingaben
-    fn process_buttons(&mut self, input: &ControllerOutput) {
-        for button_event in &input.button_events {
-            if let Some(handler) = self.config.button_handlers.get(&button_event.button) {
-                let pressed = match button_event.state {
-                    crate::controller::controller_handle::ButtonEventState::Held => true,
-                    crate::controller::controller_handle::ButtonEventState::Complete => false,
-                };
-
-                // Handler aufrufen und Ergebnis in Ausgabedaten integrieren
-                let data = handler(pressed, button_event.duration_ms);
-                for (key, value) in data {
-                    self.output_data.insert(key, value);
-                }
-            }
-        }
+        context
+            .held_chords
+            .retain(|name| reported.contains(name.as_str()));
+        payloads
     }
 }
 
 impl MappingStrategy for CustomStrategy {
     fn map(&mut self, input: &ControllerOutput) -> Option<MappedEvent> {
-        // Benutzerdefinierte Verarbeitung aller Eingaben durchführen
-        self.process_joysticks(input);
-        self.process_triggers(input);
-        self.process_buttons(input);
-
-        // Ausgabedaten in ein Event kopieren
-        if self.output_data.is_empty() {
-            None
-        } else {
-            Some(MappedEvent::CustomEvent {
-                event_type: self.output_data.clone(),
-            })
+        let mut event_type = self.pressed_payloads(input);
+        if event_type.is_empty() {
+            return None;
+        }
+
+        debug!("Custom payloads triggered: {:?}", event_type.keys());
+        for (key, value) in &self.config.protocol.parameters {
+            event_type.insert(key.clone(), value.clone());
         }
+        Some(MappedEvent::CustomEvent { event_type })
     }
 
     fn initialize(&mut self) -> Result<(), MappingError> {
         info!("Initializing custom mapping strategy: {}", self.config.name);
         info!(
             "Protocol: {} v{}",
-            self.config.protocol_config.protocol_name, self.config.protocol_config.protocol_version
+            self.config.protocol.protocol_name, self.config.protocol.protocol_version
         );
-
-        // Protokoll-Parameter kopieren
-        self.output_data = self.config.protocol_config.parameters.clone();
-
+        self.pads.clear();
         Ok(())
     }
 
@@ -284,12 +260,130 @@ impl MappingStrategy for CustomStrategy {
         );
     }
 
-    fn get_rate_limit(&self) -> Option<u64> {
-        // Standardmäßig 100Hz für benutzerdefinierte Protokolle
-        Some(10)
-    }
-
     fn get_type(&self) -> MappingType {
         MappingType::Custom
     }
 }
+
+/// Consumes the custom output channel, logging every event.
+///
+/// There is no transmitter for custom protocols yet; this keeps the channel
+/// drained and makes triggered payloads visible in the log.
+pub fn spawn_custom_sink(
+    mut custom_rx: mpsc::Receiver<HashMap<String, Vec<u8>>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(event) = custom_rx.recv().await {
+            for (name, bytes) in &event {
+                info!("Custom output {}: {:02X?}", name, bytes);
+            }
+        }
+        debug!("Custom output channel closed");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::controller_handle::ButtonType;
+    use crate::controller::event_processor::ChordEvent;
+
+    fn config() -> CustomConfig {
+        let mut config = CustomConfig::default();
+        config.chord_payloads.insert(
+            "bumpers".to_string(),
+            CustomPayload {
+                name: "garage_door".to_string(),
+                bytes: vec![0xA5, 0x01],
+            },
+        );
+        config
+            .protocol
+            .parameters
+            .insert("frequency".to_string(), vec![0x01, 0xB3]);
+        config
+    }
+
+    fn chord(state: ButtonEventState) -> ControllerOutput {
+        ControllerOutput {
+            chord_events: vec![ChordEvent {
+                name: "bumpers".to_string(),
+                buttons: vec![ButtonType::LeftBumper, ButtonType::RightBumper],
+                duration_ms: 100.0,
+                state,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn chord_sends_its_payload_once_per_press() {
+        let mut strategy = CustomStrategy::new(config());
+
+        match strategy.map(&chord(ButtonEventState::Held)) {
+            Some(MappedEvent::CustomEvent { event_type }) => {
+                assert_eq!(event_type["garage_door"], [0xA5, 0x01]);
+                assert_eq!(event_type["frequency"], [0x01, 0xB3]);
+                assert_eq!(event_type.len(), 2);
+            }
+            other => panic!("expected a custom event, got {:?}", other),
+        }
+        assert!(strategy.map(&chord(ButtonEventState::Held)).is_none());
+        assert!(strategy.map(&chord(ButtonEventState::Complete)).is_none());
+
+        // A quick tap is only seen as released
+        assert!(strategy.map(&chord(ButtonEventState::Complete)).is_some());
+        assert!(strategy.map(&ControllerOutput::default()).is_none());
+    }
+
+    #[test]
+    fn unmapped_chords_send_nothing() {
+        let mut strategy = CustomStrategy::new(config());
+        let mut input = chord(ButtonEventState::Held);
+        input.chord_events[0].name = "sticks".to_string();
+
+        assert!(strategy.map(&input).is_none());
+    }
+
+    #[test]
+    fn validation_rejects_unusable_payloads() {
+        assert!(config().validate().is_ok());
+        assert!(CustomConfig::default().validate().is_err());
+
+        let mut empty = config();
+        empty
+            .chord_payloads
+            .get_mut("bumpers")
+            .unwrap()
+            .bytes
+            .clear();
+        assert!(empty.validate().is_err());
+
+        let mut clash = config();
+        clash.chord_payloads.get_mut("bumpers").unwrap().name = "frequency".to_string();
+        assert!(clash.validate().is_err());
+
+        let mut duplicate = config();
+        duplicate.chord_payloads.insert(
+            "sticks".to_string(),
+            CustomPayload {
+                name: "garage_door".to_string(),
+                bytes: vec![0x01],
+            },
+        );
+        assert!(duplicate.validate().is_err());
+    }
+
+    #[test]
+    fn idle_second_pad_does_not_repeat_payload() {
+        let mut strategy = CustomStrategy::new(config());
+        let mut held = chord(ButtonEventState::Held);
+        held.gamepad_id = 1;
+
+        assert!(strategy.map(&held).is_some());
+        for _ in 0..3 {
+            assert!(strategy.map(&ControllerOutput::default()).is_none());
+            assert!(strategy.map(&held).is_none());
+        }
+    }
+}
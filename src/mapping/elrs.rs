@@ -6,10 +6,11 @@
 //!
 //! ## ⚠️ Experimental Implementation Notice
 //!
-//! **This is currently an experimental implementation that performs data conversion only.**
-//! While the module correctly transforms controller input into ELRS channel format,
-//! it does NOT actually transmit packets or communicate with real ELRS hardware.
-//! The generated data remains within the application for testing and development purposes.
+//! **This is currently an experimental implementation.** The mapping strategy
+//! transforms controller input into ELRS channel format, and [`ELRSTransmitter`]
+//! streams the resulting channels as CRSF frames (see [`crsf`]) to a
+//! CRSF-capable transmitter module over serial. Telemetry coming back on the
+//! same port is decoded into a [`TelemetryState`] for the ELRS menu.
 //!
 //! ## Why This Module Exists
 //!
@@ -58,14 +59,69 @@
 //! Runtime value conversion includes bounds checking to prevent invalid channel
 //! values that could cause unsafe vehicle behavior.
 
-use crate::controller::controller_handle::{ControllerOutput, JoystickType, TriggerType};
+pub mod crsf;
+
+use crate::controller::controller_handle::{
+    ButtonType, ControllerOutput, JoystickPosition, JoystickType, TriggerType,
+};
 use crate::mapping::{
-    strategy::MappingContext, MappedEvent, MappingError, MappingStrategy, MappingType,
+    strategy::MappingContext, MappedEvent, MappingError, MappingStrategy, MappingType, RateLimiter,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serialport::SerialPort;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc as std_mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+/// Default CRSF baud rate used by ExpressLRS transmitter modules.
+pub const CRSF_DEFAULT_BAUD: u32 = 420_000;
+
+/// Default rate at which RC channel frames are sent to the transmitter (Hz).
+pub const DEFAULT_PACKET_RATE_HZ: u16 = 50;
+
+/// Default interval between channel updates, one per packet at the default rate.
+pub const DEFAULT_RATE_LIMIT_MS: u64 = 1000 / DEFAULT_PACKET_RATE_HZ as u64;
+
+fn default_rate_limit_ms() -> u64 {
+    DEFAULT_RATE_LIMIT_MS
+}
+
+/// Button combo that arms the vehicle unless configured otherwise.
+fn default_arm_combo() -> Vec<ButtonType> {
+    vec![
+        ButtonType::LeftBumper,
+        ButtonType::RightBumper,
+        ButtonType::Start,
+    ]
+}
+
+/// Button combo that disarms the vehicle unless configured otherwise.
+fn default_disarm_combo() -> Vec<ButtonType> {
+    vec![
+        ButtonType::LeftBumper,
+        ButtonType::RightBumper,
+        ButtonType::Select,
+    ]
+}
+
+/// Stick and trigger deflection below which an input counts as centered.
+///
+/// Inputs arrive after the controller's deadzone, so a resting stick reads
+/// exactly zero; the band only absorbs rounding.
+pub const NEUTRAL_DEADBAND: f32 = 0.02;
+
+/// Endpoints accepted for a [`ChannelRange`], the span CRSF can represent.
+pub const CHANNEL_US_LIMITS: std::ops::RangeInclusive<u16> = 988..=2012;
+
+/// Read/write timeout for the transmitter port.
+///
+/// Kept short so a stalled port never delays the next RC frame.
+const SERIAL_TIMEOUT: Duration = Duration::from_millis(5);
+
 /// Standard ELRS channel assignments following RC conventions.
 ///
 /// ## Design Rationale
@@ -126,6 +182,93 @@ impl From<ELRSChannel> for u16 {
     }
 }
 
+impl ELRSChannel {
+    /// All channels in channel number order.
+    pub const ALL: [ELRSChannel; 12] = [
+        ELRSChannel::Roll,
+        ELRSChannel::Pitch,
+        ELRSChannel::Throttle,
+        ELRSChannel::Yaw,
+        ELRSChannel::Aux1,
+        ELRSChannel::Aux2,
+        ELRSChannel::Aux3,
+        ELRSChannel::Aux4,
+        ELRSChannel::Aux5,
+        ELRSChannel::Aux6,
+        ELRSChannel::Aux7,
+        ELRSChannel::Aux8,
+    ];
+}
+
+/// An analog input that can drive a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl InputAxis {
+    /// All axes in display order.
+    pub const ALL: [InputAxis; 6] = [
+        InputAxis::LeftX,
+        InputAxis::LeftY,
+        InputAxis::RightX,
+        InputAxis::RightY,
+        InputAxis::LeftTrigger,
+        InputAxis::RightTrigger,
+    ];
+}
+
+impl std::fmt::Display for InputAxis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputAxis::LeftX => write!(f, "Left X"),
+            InputAxis::LeftY => write!(f, "Left Y"),
+            InputAxis::RightX => write!(f, "Right X"),
+            InputAxis::RightY => write!(f, "Right Y"),
+            InputAxis::LeftTrigger => write!(f, "Left Trigger"),
+            InputAxis::RightTrigger => write!(f, "Right Trigger"),
+        }
+    }
+}
+
+/// Output endpoints and trim of one channel, in microseconds.
+///
+/// Full negative deflection sends `min`, full positive `max`, and a resting
+/// input sends the midpoint shifted by `trim`. Each half of the travel is
+/// scaled separately, so trim moves the center without clipping an end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelRange {
+    pub min: u16,
+    pub max: u16,
+    #[serde(default)]
+    pub trim: i16,
+}
+
+impl ChannelRange {
+    /// Value sent for a resting input.
+    pub fn center(&self) -> u16 {
+        ((self.min as i32 + self.max as i32) / 2 + self.trim as i32)
+            .clamp(self.min as i32, self.max as i32) as u16
+    }
+
+    /// Scales a normalized value (-1.0 to 1.0) onto the range.
+    pub fn scale(&self, normalized: f32) -> u16 {
+        let normalized = normalized.clamp(-1.0, 1.0);
+        let center = self.center() as f32;
+        let span = if normalized < 0.0 {
+            center - self.min as f32
+        } else {
+            self.max as f32 - center
+        };
+        ((center + normalized * span).round() as u16).clamp(self.min, self.max)
+    }
+}
+
 /// Configuration for gamepad-to-ELRS channel mapping.
 ///
 /// ## Design Rationale
@@ -142,13 +285,29 @@ impl From<ELRSChannel> for u16 {
 /// ## Channel Value System
 /// Uses standard RC microsecond timing (1000-2000µs) for universal compatibility
 /// with RC hardware and flight controllers.
+///
+/// ## Arming
+/// The strategy starts disarmed and only sends failsafe values until every
+/// button of `arm_combo` is held at once on one pad. Holding `disarm_combo`
+/// returns it to failsafe. With `hold_to_arm` set, releasing any arm button
+/// disarms as well (dead man's switch). An empty arm combo means the vehicle
+/// can never be armed.
+///
+/// ## Idle Output
+/// The controller only reports changes, so a resting gamepad produces no
+/// input at all. With `neutral_on_idle` set, the strategy keeps sending
+/// frames at the packet rate while idle, and once every mapped stick and
+/// trigger is centered the channels in `neutral_values` are sent instead of
+/// their mapped values, e.g. minimum throttle rather than half throttle from
+/// a spring-centered stick. Input frames with centered sticks send the same
+/// neutral values, so the output does not change between input and idle.
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct ELRSConfig {
     /// Maps joysticks to channel pairs (e.g., Right stick → Roll + Pitch).
     pub joystick_mapping: HashMap<JoystickType, (ELRSChannel, ELRSChannel)>,
 
     /// Maps analog triggers to individual channels for proportional control.
-    trigger_mapping: HashMap<TriggerType, ELRSChannel>,
+    pub trigger_mapping: HashMap<TriggerType, ELRSChannel>,
 
     /// Maps buttons to auxiliary channels with pressed/released values.
     /// Format: (channel, pressed_value, released_value)
@@ -168,6 +327,34 @@ pub struct ELRSConfig {
     channel_min: u16,
     channel_max: u16,
     channel_mid: u16,
+
+    /// Buttons that must be held together to arm.
+    #[serde(default = "default_arm_combo")]
+    pub arm_combo: Vec<ButtonType>,
+
+    /// Buttons that must be held together to disarm.
+    #[serde(default = "default_disarm_combo")]
+    pub disarm_combo: Vec<ButtonType>,
+
+    /// Disarm as soon as any arm button is released.
+    #[serde(default)]
+    pub hold_to_arm: bool,
+
+    /// Minimum milliseconds between two channel updates.
+    #[serde(default = "default_rate_limit_ms")]
+    pub rate_limit_ms: u64,
+
+    /// Keep sending frames without input and apply `neutral_values` while centered.
+    #[serde(default)]
+    pub neutral_on_idle: bool,
+
+    /// Values sent while idle and centered; channels not listed keep their value.
+    #[serde(default)]
+    pub neutral_values: HashMap<ELRSChannel, u16>,
+
+    /// Endpoints and trim per channel; unlisted channels use the global range.
+    #[serde(default)]
+    pub channel_ranges: HashMap<ELRSChannel, ChannelRange>,
 }
 
 impl ELRSConfig {
@@ -201,6 +388,86 @@ impl ELRSConfig {
             channel_min,
             channel_max,
             channel_mid,
+            arm_combo: default_arm_combo(),
+            disarm_combo: default_disarm_combo(),
+            hold_to_arm: false,
+            rate_limit_ms: DEFAULT_RATE_LIMIT_MS,
+            neutral_on_idle: false,
+            neutral_values: HashMap::new(),
+            channel_ranges: HashMap::new(),
+        }
+    }
+
+    /// Returns the endpoints and trim a channel is scaled onto.
+    pub fn channel_range(&self, channel: ELRSChannel) -> ChannelRange {
+        self.channel_ranges
+            .get(&channel)
+            .copied()
+            .unwrap_or(ChannelRange {
+                min: self.channel_min,
+                max: self.channel_max,
+                trim: 0,
+            })
+    }
+
+    /// Whether the channel's direction is reversed.
+    pub fn is_inverted(&self, channel: ELRSChannel) -> bool {
+        self.invert_channel.get(&channel).copied().unwrap_or(false)
+    }
+
+    /// Reverses or restores the channel's direction.
+    pub fn set_inverted(&mut self, channel: ELRSChannel, inverted: bool) {
+        self.invert_channel.insert(channel, inverted);
+    }
+
+    /// Returns the channel an axis drives, if it is mapped.
+    pub fn axis_channel(&self, axis: InputAxis) -> Option<ELRSChannel> {
+        match axis {
+            InputAxis::LeftX => self.joystick_mapping.get(&JoystickType::Left).map(|c| c.0),
+            InputAxis::LeftY => self.joystick_mapping.get(&JoystickType::Left).map(|c| c.1),
+            InputAxis::RightX => self.joystick_mapping.get(&JoystickType::Right).map(|c| c.0),
+            InputAxis::RightY => self.joystick_mapping.get(&JoystickType::Right).map(|c| c.1),
+            InputAxis::LeftTrigger => self.trigger_mapping.get(&TriggerType::Left).copied(),
+            InputAxis::RightTrigger => self.trigger_mapping.get(&TriggerType::Right).copied(),
+        }
+    }
+
+    /// Points an axis at another channel.
+    ///
+    /// Sticks are mapped as X/Y pairs, so a stick axis can only be reassigned
+    /// while its stick is mapped; triggers are added if unmapped.
+    pub fn set_axis_channel(&mut self, axis: InputAxis, channel: ELRSChannel) {
+        match axis {
+            InputAxis::LeftX | InputAxis::LeftY | InputAxis::RightX | InputAxis::RightY => {
+                let stick = match axis {
+                    InputAxis::LeftX | InputAxis::LeftY => JoystickType::Left,
+                    _ => JoystickType::Right,
+                };
+                if let Some(pair) = self.joystick_mapping.get_mut(&stick) {
+                    match axis {
+                        InputAxis::LeftX | InputAxis::RightX => pair.0 = channel,
+                        _ => pair.1 = channel,
+                    }
+                }
+            }
+            InputAxis::LeftTrigger => {
+                self.trigger_mapping.insert(TriggerType::Left, channel);
+            }
+            InputAxis::RightTrigger => {
+                self.trigger_mapping.insert(TriggerType::Right, channel);
+            }
+        }
+    }
+
+    /// Returns the value a channel is held at while disarmed.
+    ///
+    /// Uses the configured failsafe value, falling back to the range minimum
+    /// for throttle and the trimmed center for everything else.
+    pub fn disarmed_value(&self, channel: ELRSChannel) -> u16 {
+        match self.failsafe_values.get(&channel) {
+            Some(value) => *value,
+            None if channel == ELRSChannel::Throttle => self.channel_range(channel).min,
+            None => self.channel_range(channel).center(),
         }
     }
 
@@ -271,16 +538,27 @@ impl ELRSConfig {
         failsafe_values.insert(ELRSChannel::Aux3, 1000); // Disarmed
         failsafe_values.insert(ELRSChannel::Aux4, 1000); // Default flight mode
 
-        Self::new(
-            joystick_mapping,
-            trigger_mapping,
-            button_mapping,
-            invert_channel,
-            failsafe_values,
-            "Default ELRS Configuration".to_string(),
-            channel_min,
-            channel_max,
-        )
+        // Values held while the controller rests; aux switches keep their state
+        let mut neutral_values = HashMap::new();
+        neutral_values.insert(ELRSChannel::Roll, 1500);
+        neutral_values.insert(ELRSChannel::Pitch, 1500);
+        neutral_values.insert(ELRSChannel::Throttle, channel_min);
+        neutral_values.insert(ELRSChannel::Yaw, 1500);
+
+        Self {
+            neutral_on_idle: true,
+            neutral_values,
+            ..Self::new(
+                joystick_mapping,
+                trigger_mapping,
+                button_mapping,
+                invert_channel,
+                failsafe_values,
+                "Default ELRS Configuration".to_string(),
+                channel_min,
+                channel_max,
+            )
+        }
     }
 }
 
@@ -303,6 +581,11 @@ impl crate::mapping::MappingConfig for ELRSConfig {
     /// Returns [`MappingError::ConfigError`] when:
     /// - No joystick mappings defined (primary control missing)
     /// - Essential channels not mapped (unsafe configuration)
+    /// - Idle output enabled without neutral values, or a neutral value
+    ///   outside the channel range
+    /// - A channel driven by more than one axis
+    /// - A channel range outside [`CHANNEL_US_LIMITS`], with `min` not below
+    ///   `max`, or trimmed past an endpoint
     fn validate(&self) -> Result<(), MappingError> {
         if self.joystick_mapping.is_empty() {
             return Err(MappingError::ConfigError(
@@ -340,6 +623,59 @@ impl crate::mapping::MappingConfig for ELRSConfig {
             )));
         }
 
+        if self.neutral_on_idle && self.neutral_values.is_empty() {
+            return Err(MappingError::ConfigError(
+                "Neutral output on idle needs at least one neutral value".to_string(),
+            ));
+        }
+        let channel_range = self.channel_min..=self.channel_max;
+        if let Some((channel, value)) = self
+            .neutral_values
+            .iter()
+            .find(|(_, value)| !channel_range.contains(value))
+        {
+            return Err(MappingError::ConfigError(format!(
+                "Neutral value {} of {:?} is outside {}-{}",
+                value, channel, self.channel_min, self.channel_max
+            )));
+        }
+
+        let mut driven = HashSet::new();
+        for channel in InputAxis::ALL
+            .iter()
+            .filter_map(|axis| self.axis_channel(*axis))
+        {
+            if !driven.insert(channel) {
+                return Err(MappingError::ConfigError(format!(
+                    "{:?} is driven by more than one axis",
+                    channel
+                )));
+            }
+        }
+
+        for (channel, range) in &self.channel_ranges {
+            if !CHANNEL_US_LIMITS.contains(&range.min)
+                || !CHANNEL_US_LIMITS.contains(&range.max)
+                || range.min >= range.max
+            {
+                return Err(MappingError::ConfigError(format!(
+                    "Range {}-{} of {:?} must be ascending within {}-{}",
+                    range.min,
+                    range.max,
+                    channel,
+                    CHANNEL_US_LIMITS.start(),
+                    CHANNEL_US_LIMITS.end()
+                )));
+            }
+            if range.trim.unsigned_abs() >= (range.max - range.min) / 2 {
+                return Err(MappingError::ConfigError(format!(
+                    "Trim {} of {:?} reaches past an endpoint",
+                    range.trim, channel
+                )));
+            }
+        }
+
+        RateLimiter::validate_interval(self.rate_limit_ms)?;
         Ok(())
     }
 
@@ -354,6 +690,10 @@ impl crate::mapping::MappingConfig for ELRSConfig {
     fn get_name(&self) -> String {
         self.name.clone()
     }
+
+    fn rate_limit_ms(&self) -> Option<u64> {
+        Some(self.rate_limit_ms)
+    }
 }
 
 /// Core implementation of gamepad-to-ELRS channel conversion.
@@ -366,6 +706,8 @@ impl crate::mapping::MappingConfig for ELRSConfig {
 /// ## State Management
 /// - **Channel Values**: Current state of all RC channels
 /// - **Failsafe Integration**: Automatic fallback to safe values
+/// - **Arm State**: Failsafe values are sent instead of input while disarmed
+/// - **Idle State**: Neutral values are sent while idle with centered input
 /// - **Context Preservation**: Maintains state across mapping operations
 ///
 /// ## Conversion Strategy
@@ -376,6 +718,14 @@ pub struct ELRSStrategy {
     context: MappingContext,
     /// Current RC channel values in microseconds (1000-2000µs range)
     channel_values: HashMap<ELRSChannel, u16>,
+    /// Whether input is passed through to the channels
+    armed: bool,
+    /// Buttons held on each pad as of its latest input, used for combo detection
+    held_buttons: HashMap<usize, HashSet<ButtonType>>,
+    /// Latest input of each pad, the sticks and triggers are taken from here
+    pad_inputs: HashMap<usize, ControllerOutput>,
+    /// Whether every mapped stick and trigger was centered in the last input
+    centered: bool,
 }
 
 impl ELRSStrategy {
@@ -393,47 +743,151 @@ impl ELRSStrategy {
             config,
             context: MappingContext::default(),
             channel_values,
+            armed: false,
+            held_buttons: HashMap::new(),
+            pad_inputs: HashMap::new(),
+            centered: false,
         }
     }
 
+    /// Tracks held buttons and applies the arm/disarm combos.
+    ///
+    /// The held set of the input's pad is rebuilt from every input, so a
+    /// release lost to rate limiting cannot leave a button stuck. A combo
+    /// only counts if a single pad holds all of its buttons.
+    fn update_arm_state(&mut self, input: &ControllerOutput) {
+        self.held_buttons
+            .insert(input.gamepad_id, input.held_buttons());
+
+        let combo_held = |combo: &[ButtonType]| {
+            !combo.is_empty()
+                && self
+                    .held_buttons
+                    .values()
+                    .any(|held| combo.iter().all(|b| held.contains(b)))
+        };
+
+        if self.armed {
+            let arm_released = self.config.hold_to_arm && !combo_held(&self.config.arm_combo);
+            if combo_held(&self.config.disarm_combo) || arm_released {
+                warn!("ELRS disarmed");
+                self.armed = false;
+            }
+        } else if combo_held(&self.config.arm_combo) && !combo_held(&self.config.disarm_combo) {
+            warn!("ELRS armed");
+            self.armed = true;
+        }
+    }
+
+    /// Builds the outgoing channel map, substituting failsafe values while disarmed.
+    ///
+    /// With `neutral` set, armed channels that have a neutral value send it
+    /// instead of their mapped value.
+    fn output_channels(&self, neutral: bool) -> HashMap<u16, u16> {
+        self.channel_values
+            .iter()
+            .map(|(channel, value)| {
+                let value = if !self.armed {
+                    self.config.disarmed_value(*channel)
+                } else if neutral {
+                    self.config
+                        .neutral_values
+                        .get(channel)
+                        .copied()
+                        .unwrap_or(*value)
+                } else {
+                    *value
+                };
+                (*channel as u16, value)
+            })
+            .collect()
+    }
+
+    /// Wraps the current channels in an output event.
+    fn channel_event(&self, neutral: bool) -> Option<MappedEvent> {
+        let pre_package = self.output_channels(neutral);
+
+        if pre_package.is_empty() {
+            None
+        } else {
+            Some(MappedEvent::ELRSData {
+                pre_package,
+                armed: self.armed,
+            })
+        }
+    }
+
+    /// Combines the sticks and triggers of all pads into one input.
+    ///
+    /// Each stick follows the pad deflecting it furthest and each trigger the
+    /// pad pulling it furthest, so a resting second pad never overrides the
+    /// one in use. With a single pad this is that pad's input.
+    fn analog_input(&self) -> ControllerOutput {
+        let mut merged = ControllerOutput::default();
+        let deflection = |stick: &JoystickPosition| stick.x.hypot(stick.y);
+        for input in self.pad_inputs.values() {
+            if deflection(&input.left_stick) > deflection(&merged.left_stick) {
+                merged.left_stick = input.left_stick.clone();
+            }
+            if deflection(&input.right_stick) > deflection(&merged.right_stick) {
+                merged.right_stick = input.right_stick.clone();
+            }
+            if input.left_trigger.value > merged.left_trigger.value {
+                merged.left_trigger = input.left_trigger.clone();
+            }
+            if input.right_trigger.value > merged.right_trigger.value {
+                merged.right_trigger = input.right_trigger.clone();
+            }
+        }
+        merged
+    }
+
+    /// Whether every mapped stick and trigger rests within [`NEUTRAL_DEADBAND`].
+    fn input_centered(&self, input: &ControllerOutput) -> bool {
+        let sticks_centered = self.config.joystick_mapping.keys().all(|joystick| {
+            let stick = match joystick {
+                JoystickType::Left => &input.left_stick,
+                JoystickType::Right => &input.right_stick,
+            };
+            stick.x.abs() < NEUTRAL_DEADBAND && stick.y.abs() < NEUTRAL_DEADBAND
+        });
+        let triggers_centered = self.config.trigger_mapping.keys().all(|trigger| {
+            let value = match trigger {
+                TriggerType::Left => input.left_trigger.value,
+                TriggerType::Right => input.right_trigger.value,
+            };
+            value < NEUTRAL_DEADBAND
+        });
+        sticks_centered && triggers_centered
+    }
+
     /// Converts normalized joystick values to RC channel microsecond values.
     ///
     /// ## Conversion Algorithm
     /// 1. Clamp input to valid range (-1.0 to +1.0)
     /// 2. Apply channel inversion if configured
-    /// 3. Scale to microsecond range around center point
+    /// 3. Scale onto the channel's [`ChannelRange`] around its trimmed center
     /// 4. Round and bounds-check final value
     ///
     /// ## Mathematical Formula
-    /// $$ \text{channel\_value} = \text{mid} + (\text{normalized} \times \frac{\text{range}}{2}) $$
+    /// $$ \text{channel\_value} = \text{center} + \text{normalized} \times \text{span} $$
     ///
     /// Where:
-    /// - $\text{mid} = \frac{\text{channel\_max} + \text{channel\_min}}{2}$
-    /// - $\text{range} = \text{channel\_max} - \text{channel\_min}$
+    /// - $\text{center} = \frac{\text{max} + \text{min}}{2} + \text{trim}$
+    /// - $\text{span} = \text{center} - \text{min}$ below center, $\text{max} - \text{center}$ above
     /// - $\text{normalized} \in [-1.0, 1.0]$
     ///
     /// ## Safety Features
-    /// Output is always clamped to valid channel range to prevent hardware
+    /// Output is always clamped to the channel's range to prevent hardware
     /// damage or unexpected vehicle behavior from out-of-range values.
-    fn convert_joystick_value(&self, value: f32, invert: bool) -> u16 {
-        let range = (self.config.channel_max - self.config.channel_min) as f32;
-        let mid = self.config.channel_mid;
-
-        // Normalize and clamp input value
-        let mut normalized = value.clamp(-1.0, 1.0);
-
-        // Apply inversion if configured
-        if invert {
-            normalized = -normalized;
-        }
-
-        // Convert to microsecond value
-        let channel_value = mid as f32 + (normalized * range / 2.0);
-
-        // Round and enforce bounds for safety
-        let out = channel_value.round() as u16;
-        out.min(self.config.channel_max)
-            .max(self.config.channel_min)
+    fn convert_channel_value(&self, channel: ELRSChannel, value: f32) -> u16 {
+        let normalized = value.clamp(-1.0, 1.0);
+        let normalized = if self.config.is_inverted(channel) {
+            -normalized
+        } else {
+            normalized
+        };
+        self.config.channel_range(channel).scale(normalized)
     }
 
     /// Updates RC channels based on joystick positions.
@@ -448,23 +902,11 @@ impl ELRSStrategy {
             };
 
             // Process X-axis (typically Roll or Yaw)
-            let invert_x = self
-                .config
-                .invert_channel
-                .get(x_channel)
-                .copied()
-                .unwrap_or(false);
-            let x_value = self.convert_joystick_value(x, invert_x);
+            let x_value = self.convert_channel_value(*x_channel, x);
             self.channel_values.insert(*x_channel, x_value);
 
             // Process Y-axis (typically Pitch or Throttle)
-            let invert_y = self
-                .config
-                .invert_channel
-                .get(y_channel)
-                .copied()
-                .unwrap_or(false);
-            let y_value = self.convert_joystick_value(y, invert_y);
+            let y_value = self.convert_channel_value(*y_channel, y);
             self.channel_values.insert(*y_channel, y_value);
         }
     }
@@ -483,13 +925,7 @@ impl ELRSStrategy {
             // Convert trigger range (0.0-1.0) to joystick range (-1.0-1.0)
             let scaled_value = value * 2.0 - 1.0;
 
-            let invert = self
-                .config
-                .invert_channel
-                .get(channel)
-                .copied()
-                .unwrap_or(false);
-            let channel_value = self.convert_joystick_value(scaled_value, invert);
+            let channel_value = self.convert_channel_value(*channel, scaled_value);
             self.channel_values.insert(*channel, channel_value);
         }
     }
@@ -520,39 +956,45 @@ impl MappingStrategy for ELRSStrategy {
     /// Converts controller input to ELRS channel data format.
     ///
     /// ## Processing Order
-    /// 1. Update joystick channels (primary flight controls)
-    /// 2. Update trigger channels (auxiliary analog controls)  
-    /// 3. Update button channels (auxiliary digital controls)
-    /// 4. Convert to output format for transmission
+    /// 1. Update arm state from the arm/disarm combos
+    /// 2. Update joystick channels (primary flight controls), combining all
+    ///    pads as described in [`ELRSStrategy::analog_input`]
+    /// 3. Update trigger channels (auxiliary analog controls)  
+    /// 4. Update button channels (auxiliary digital controls)
+    /// 5. Convert to output format, using failsafe values while disarmed and
+    ///    neutral values while centered with `neutral_on_idle`
     ///
     /// ## Output Format
     /// Returns HashMap with channel numbers as keys and microsecond values
     /// as values, ready for CRSF packet construction and transmission.
     ///
-    /// ## ⚠️ Experimental Status
-    /// The generated data is correctly formatted but not transmitted to
-    /// actual ELRS hardware in this experimental implementation.
-    ///
     /// # Returns
     /// `Some(MappedEvent::ELRSData)` with current channel values,
     /// `None` if no channels are configured (should not occur after validation).
     fn map(&mut self, input: &ControllerOutput) -> Option<MappedEvent> {
+        self.update_arm_state(input);
+        self.pad_inputs.insert(input.gamepad_id, input.clone());
+        let analog = self.analog_input();
+        self.centered = self.input_centered(&analog);
+
         // Update all channel types in priority order
-        self.update_joystick_channels(input);
-        self.update_trigger_channels(input);
+        self.update_joystick_channels(&analog);
+        self.update_trigger_channels(&analog);
         self.update_button_channels(input);
 
-        // Convert to output format
-        let mut pre_package = HashMap::new();
-        for (channel, value) in &self.channel_values {
-            pre_package.insert(*channel as u16, *value);
-        }
+        // Convert to output format, matching the idle frames while centered
+        self.channel_event(self.config.neutral_on_idle && self.centered)
+    }
 
-        if pre_package.is_empty() {
-            None
-        } else {
-            Some(MappedEvent::ELRSData { pre_package })
+    /// Repeats the current channels while the controller reports nothing.
+    ///
+    /// Only active with `neutral_on_idle`; centered input is replaced by the
+    /// configured neutral values.
+    fn map_idle(&mut self) -> Option<MappedEvent> {
+        if !self.config.neutral_on_idle {
+            return None;
         }
+        self.channel_event(self.centered)
     }
 
     /// Initializes ELRS strategy with failsafe values.
@@ -562,6 +1004,11 @@ impl MappingStrategy for ELRSStrategy {
     fn initialize(&mut self) -> Result<(), MappingError> {
         info!("Initializing ELRS mapping strategy: {}", self.config.name);
 
+        self.armed = false;
+        self.held_buttons.clear();
+        self.pad_inputs.clear();
+        self.centered = false;
+
         for (channel, value) in &self.config.failsafe_values {
             self.channel_values.insert(*channel, *value);
         }
@@ -576,22 +1023,511 @@ impl MappingStrategy for ELRSStrategy {
     fn shutdown(&mut self) {
         info!("Shutting down ELRS mapping strategy: {}", self.config.name);
 
+        self.armed = false;
+
         // Reset to failsafe values for safe shutdown
         for (channel, value) in &self.config.failsafe_values {
             self.channel_values.insert(*channel, *value);
         }
     }
 
-    /// Returns rate limit appropriate for RC communication.
+    fn get_type(&self) -> MappingType {
+        MappingType::ELRS
+    }
+}
+
+/// Channel output of the ELRS mapping engine as delivered to the transmitter.
+#[derive(Debug, Clone, Default)]
+pub struct ELRSOutput {
+    /// Channel values in microseconds, keyed by channel number
+    pub channels: HashMap<u16, u16>,
+
+    /// Whether the strategy was armed when the channels were produced
+    pub armed: bool,
+}
+
+/// Time without telemetry after which [`TelemetryState::is_stale`] reports true.
+pub const TELEMETRY_STALE_AFTER: Duration = Duration::from_secs(2);
+
+/// Latest telemetry values reported by the vehicle.
+///
+/// Each field keeps the last decoded value of its frame type, so a vehicle
+/// without GPS still shows battery and link data.
+#[derive(Debug, Clone, Default)]
+pub struct TelemetryState {
+    /// Last battery sensor frame
+    pub battery: Option<crsf::BatterySensor>,
+
+    /// Last link statistics frame
+    pub link: Option<crsf::LinkStatistics>,
+
+    /// Last GPS frame
+    pub gps: Option<crsf::GpsFix>,
+
+    /// When the last telemetry frame arrived
+    pub last_update: Option<Instant>,
+}
+
+impl TelemetryState {
+    /// Stores a decoded frame and refreshes the update timestamp.
+    pub fn apply(&mut self, frame: crsf::TelemetryFrame) {
+        match frame {
+            crsf::TelemetryFrame::Battery(battery) => self.battery = Some(battery),
+            crsf::TelemetryFrame::LinkStatistics(link) => self.link = Some(link),
+            crsf::TelemetryFrame::Gps(gps) => self.gps = Some(gps),
+        }
+        self.last_update = Some(Instant::now());
+    }
+
+    /// Returns true if no telemetry arrived within [`TELEMETRY_STALE_AFTER`].
+    pub fn is_stale(&self) -> bool {
+        self.last_update
+            .is_none_or(|last| last.elapsed() > TELEMETRY_STALE_AFTER)
+    }
+}
+
+/// Data drained from the transmitter link by [`ELRSTransmitter::poll`].
+#[derive(Debug, Default)]
+pub struct LinkUpdate {
+    /// Number of raw bytes received since the last poll
+    pub bytes_received: usize,
+
+    /// Telemetry frames decoded since the last poll
+    pub telemetry: Vec<crsf::TelemetryFrame>,
+}
+
+/// Events reported by the transmitter link thread.
+enum LinkEvent {
+    /// Number of raw bytes received from the transmitter
+    Received(usize),
+
+    /// A telemetry frame decoded from the received bytes
+    Telemetry(crsf::TelemetryFrame),
+
+    /// The link failed and the thread stopped
+    Failed(MappingError),
+}
+
+/// Serial connection to an ELRS transmitter module.
+///
+/// ## Design Rationale
+/// The port is owned by a dedicated link thread that streams CRSF RC channel
+/// frames at a fixed packet rate, independent of the UI frame rate. The
+/// transmitter expects a continuous stream, so the latest channel values are
+/// resent every tick even when the mapping engine produced nothing new.
+/// Received bytes are decoded into telemetry on the same thread. Callers only
+/// update the channel values and poll for telemetry; errors
+/// come back as [`MappingError`]s instead of panics.
+///
+/// ## Usage Context
+/// Discovered with [`ELRSTransmitter::available_ports`] and opened from the
+/// ELRS menu when the user requests a live connection. Dropping the handle
+/// stops the link thread and closes the port.
+pub struct ELRSTransmitter {
+    /// System name of the opened port (e.g. `/dev/ttyUSB0`)
+    port_name: String,
+
+    /// Baud rate the port was opened with
+    baud_rate: u32,
+
+    /// Rate at which RC channel frames are written (Hz)
+    packet_rate_hz: u16,
+
+    /// Latest channel values to be encoded by the link thread
+    channels: Arc<Mutex<HashMap<u16, u16>>>,
+
+    /// Cleared to stop the link thread
+    running: Arc<AtomicBool>,
+
+    /// Events from the link thread
+    events: std_mpsc::Receiver<LinkEvent>,
+
+    /// Link thread handle, joined on drop
+    link_thread: Option<thread::JoinHandle<()>>,
+}
+
+impl ELRSTransmitter {
+    /// Lists the names of all serial ports present on the system.
+    ///
+    /// # Errors
     ///
-    /// Set to 20Hz (50ms) which is typical for RC systems and provides
-    /// adequate control responsiveness while avoiding unnecessary bandwidth
-    /// usage and processing overhead.
-    fn get_rate_limit(&self) -> Option<u64> {
-        Some(50)
+    /// Returns [`MappingError::SerialError`] if the OS port enumeration fails.
+    pub fn available_ports() -> Result<Vec<String>, MappingError> {
+        let ports = serialport::available_ports()
+            .map_err(|e| MappingError::SerialError(format!("Port scan failed: {}", e)))?;
+
+        Ok(ports.into_iter().map(|p| p.port_name).collect())
     }
 
-    fn get_type(&self) -> MappingType {
-        MappingType::ELRS
+    /// Opens the given port and starts streaming RC frames at `packet_rate_hz`.
+    ///
+    /// Channels start at center until [`ELRSTransmitter::update_channels`]
+    /// is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MappingError::SerialError`] if the port does not exist, is
+    /// busy, or rejects the baud rate, and [`MappingError::ThreadError`] if
+    /// the link thread cannot be spawned.
+    pub fn open(
+        port_name: &str,
+        baud_rate: u32,
+        packet_rate_hz: u16,
+    ) -> Result<Self, MappingError> {
+        let port = serialport::new(port_name, baud_rate)
+            .timeout(SERIAL_TIMEOUT)
+            .open()
+            .map_err(|e| {
+                MappingError::SerialError(format!("Failed to open {}: {}", port_name, e))
+            })?;
+
+        let packet_rate_hz = packet_rate_hz.max(1);
+        let channels = Arc::new(Mutex::new(HashMap::new()));
+        let running = Arc::new(AtomicBool::new(true));
+        let (event_tx, events) = std_mpsc::channel();
+
+        let link_thread = thread::Builder::new()
+            .name("elrs-link".to_string())
+            .spawn({
+                let channels = channels.clone();
+                let running = running.clone();
+                let interval = Duration::from_secs_f64(1.0 / packet_rate_hz as f64);
+                move || Self::run_link(port, channels, running, event_tx, interval)
+            })
+            .map_err(|e| MappingError::ThreadError(format!("Failed to spawn ELRS link: {}", e)))?;
+
+        info!(
+            "Opened ELRS transmitter on {} at {} baud, {} Hz",
+            port_name, baud_rate, packet_rate_hz
+        );
+
+        Ok(Self {
+            port_name: port_name.to_string(),
+            baud_rate,
+            packet_rate_hz,
+            channels,
+            running,
+            events,
+            link_thread: Some(link_thread),
+        })
+    }
+
+    /// Link thread body: write one RC frame per tick and forward incoming bytes.
+    fn run_link(
+        mut port: Box<dyn SerialPort>,
+        channels: Arc<Mutex<HashMap<u16, u16>>>,
+        running: Arc<AtomicBool>,
+        event_tx: std_mpsc::Sender<LinkEvent>,
+        interval: Duration,
+    ) {
+        let mut decoder = crsf::CrsfDecoder::new();
+        let mut next_tick = Instant::now();
+
+        while running.load(Ordering::Relaxed) {
+            let frame = match channels.lock() {
+                Ok(channels) => crsf::encode_rc_channels(&channels),
+                Err(_) => {
+                    let _ = event_tx.send(LinkEvent::Failed(MappingError::ThreadError(
+                        "Channel state poisoned".to_string(),
+                    )));
+                    break;
+                }
+            };
+
+            if let Err(e) = port.write_all(&frame) {
+                let _ = event_tx.send(LinkEvent::Failed(MappingError::SerialError(format!(
+                    "Write failed: {}",
+                    e
+                ))));
+                break;
+            }
+
+            match Self::read_pending(port.as_mut()) {
+                Ok(bytes) if !bytes.is_empty() => {
+                    let _ = event_tx.send(LinkEvent::Received(bytes.len()));
+                    for frame in decoder.push(&bytes) {
+                        let _ = event_tx.send(LinkEvent::Telemetry(frame));
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    let _ = event_tx.send(LinkEvent::Failed(e));
+                    break;
+                }
+            }
+
+            next_tick += interval;
+            let now = Instant::now();
+            if next_tick > now {
+                thread::sleep(next_tick - now);
+            } else {
+                // Fell behind (slow port), resynchronise instead of bursting
+                next_tick = now;
+            }
+        }
+
+        debug!("ELRS link thread stopped");
+    }
+
+    /// Reads whatever bytes are currently buffered by the port.
+    fn read_pending(port: &mut dyn SerialPort) -> Result<Vec<u8>, MappingError> {
+        let pending = port
+            .bytes_to_read()
+            .map_err(|e| MappingError::SerialError(format!("Read failed: {}", e)))?;
+
+        if pending == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut buffer = vec![0u8; pending as usize];
+        match port.read(&mut buffer) {
+            Ok(read) => {
+                buffer.truncate(read);
+                Ok(buffer)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => Ok(Vec::new()),
+            Err(e) => Err(MappingError::SerialError(format!("Read failed: {}", e))),
+        }
+    }
+
+    /// Returns the name of the opened port.
+    pub fn port_name(&self) -> &str {
+        &self.port_name
+    }
+
+    /// Returns the baud rate the port was opened with.
+    pub fn baud_rate(&self) -> u32 {
+        self.baud_rate
+    }
+
+    /// Returns the RC frame rate of the link.
+    pub fn packet_rate_hz(&self) -> u16 {
+        self.packet_rate_hz
+    }
+
+    /// Replaces the channel values sent with the next frames.
+    ///
+    /// Takes the `pre_package` of a [`MappedEvent::ELRSData`] directly.
+    pub fn update_channels(&self, channels: HashMap<u16, u16>) {
+        if let Ok(mut current) = self.channels.lock() {
+            *current = channels;
+        }
+    }
+
+    /// Drains byte counts and decoded telemetry reported since the last call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the link thread's error if the link failed (e.g. the module
+    /// was unplugged). The link is stopped at that point.
+    pub fn poll(&mut self) -> Result<LinkUpdate, MappingError> {
+        let mut update = LinkUpdate::default();
+
+        loop {
+            match self.events.try_recv() {
+                Ok(LinkEvent::Received(count)) => update.bytes_received += count,
+                Ok(LinkEvent::Telemetry(frame)) => update.telemetry.push(frame),
+                Ok(LinkEvent::Failed(e)) => return Err(e),
+                Err(std_mpsc::TryRecvError::Empty) => return Ok(update),
+                Err(std_mpsc::TryRecvError::Disconnected) => {
+                    return Err(MappingError::ThreadError(
+                        "ELRS link thread stopped".to_string(),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ELRSTransmitter {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.link_thread.take() {
+            let _ = handle.join();
+        }
+        info!("Closed ELRS transmitter on {}", self.port_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::controller_handle::{ButtonEvent, ButtonEventState};
+
+    fn held(buttons: &[ButtonType]) -> Vec<ButtonEvent> {
+        buttons
+            .iter()
+            .map(|button| ButtonEvent {
+                button: button.clone(),
+                duration_ms: 200.0,
+                state: ButtonEventState::Held,
+            })
+            .collect()
+    }
+
+    fn input(gamepad_id: usize, throttle: f32, buttons: &[ButtonType]) -> ControllerOutput {
+        let mut input = ControllerOutput {
+            gamepad_id,
+            button_events: held(buttons),
+            ..Default::default()
+        };
+        input.left_stick.y = throttle;
+        input
+    }
+
+    fn throttle(event: Option<MappedEvent>) -> u16 {
+        match event {
+            Some(MappedEvent::ELRSData { pre_package, .. }) => {
+                pre_package[&(ELRSChannel::Throttle as u16)]
+            }
+            other => panic!("expected ELRS data, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn disarmed_state_always_emits_neutral_throttle() {
+        let config = ELRSConfig::default_config();
+        let neutral = config.disarmed_value(ELRSChannel::Throttle);
+        let mut strategy = ELRSStrategy::new(config);
+
+        for value in [-1.0, -0.5, 0.0, 0.5, 1.0] {
+            assert_eq!(throttle(strategy.map(&input(0, value, &[]))), neutral);
+        }
+
+        // Disarm combo held together with the arm combo never arms
+        let both = [
+            ButtonType::LeftBumper,
+            ButtonType::RightBumper,
+            ButtonType::Start,
+            ButtonType::Select,
+        ];
+        assert_eq!(throttle(strategy.map(&input(0, -1.0, &both))), neutral);
+    }
+
+    #[test]
+    fn released_button_is_not_stuck_after_a_missed_release() {
+        let mut config = ELRSConfig::default_config();
+        config.hold_to_arm = true;
+        let mut strategy = ELRSStrategy::new(config);
+        let arm = default_arm_combo();
+
+        strategy.map(&input(0, 0.0, &arm));
+        assert!(strategy.armed);
+
+        // The release of Start was rate limited away, the next input no longer holds it
+        strategy.map(&input(0, 0.0, &arm[..2]));
+        assert!(!strategy.armed);
+    }
+
+    #[test]
+    fn combo_split_across_pads_does_not_arm() {
+        let mut strategy = ELRSStrategy::new(ELRSConfig::default_config());
+
+        strategy.map(&input(
+            0,
+            0.0,
+            &[ButtonType::LeftBumper, ButtonType::RightBumper],
+        ));
+        strategy.map(&input(1, 0.0, &[ButtonType::Start]));
+        assert!(!strategy.armed);
+
+        strategy.map(&input(1, 0.0, &default_arm_combo()));
+        assert!(strategy.armed);
+    }
+
+    #[test]
+    fn resting_second_pad_does_not_override_sticks() {
+        let mut strategy = ELRSStrategy::new(ELRSConfig::default_config());
+        strategy.map(&input(0, 0.0, &default_arm_combo()));
+        assert!(strategy.armed);
+
+        let active = throttle(strategy.map(&input(0, -1.0, &[])));
+        let after_idle_pad = throttle(strategy.map(&input(1, 0.0, &[])));
+        assert_eq!(active, 2000);
+        assert_eq!(after_idle_pad, active);
+    }
+
+    #[test]
+    fn centered_and_idle_input_send_neutral_throttle() {
+        let config = ELRSConfig::default_config();
+        let neutral = config.neutral_values[&ELRSChannel::Throttle];
+        let mut strategy = ELRSStrategy::new(config);
+
+        strategy.map(&input(0, 0.0, &default_arm_combo()));
+        assert!(strategy.armed);
+
+        assert_eq!(throttle(strategy.map(&input(0, 0.0, &[]))), neutral);
+        assert_eq!(throttle(strategy.map_idle()), neutral);
+
+        // Deflected sticks leave the neutral values
+        assert_eq!(throttle(strategy.map(&input(0, -1.0, &[]))), 2000);
+        assert_eq!(throttle(strategy.map_idle()), 2000);
+    }
+
+    #[test]
+    fn range_scales_full_deflection_to_endpoints() {
+        let range = ChannelRange {
+            min: 1000,
+            max: 2000,
+            trim: 0,
+        };
+        assert_eq!(range.scale(-1.0), 1000);
+        assert_eq!(range.scale(0.0), 1500);
+        assert_eq!(range.scale(1.0), 2000);
+        assert_eq!(range.scale(0.5), 1750);
+
+        // Out of range input stays on the endpoints
+        assert_eq!(range.scale(-3.0), 1000);
+        assert_eq!(range.scale(3.0), 2000);
+    }
+
+    #[test]
+    fn trim_moves_center_but_keeps_endpoints() {
+        let range = ChannelRange {
+            min: 1000,
+            max: 2000,
+            trim: 100,
+        };
+        assert_eq!(range.center(), 1600);
+        assert_eq!(range.scale(0.0), 1600);
+        assert_eq!(range.scale(-1.0), 1000);
+        assert_eq!(range.scale(1.0), 2000);
+
+        let clipped = ChannelRange {
+            min: 1000,
+            max: 2000,
+            trim: 900,
+        };
+        assert_eq!(clipped.center(), 2000);
+    }
+
+    #[test]
+    fn reversed_channel_swaps_endpoints() {
+        let mut config = ELRSConfig::default_config();
+        let channel = config.axis_channel(InputAxis::RightX).unwrap();
+        config.channel_ranges.insert(
+            channel,
+            ChannelRange {
+                min: 1100,
+                max: 1900,
+                trim: 0,
+            },
+        );
+        let mut strategy = ELRSStrategy::new(config.clone());
+        strategy.map(&input(0, 0.0, &default_arm_combo()));
+        assert!(strategy.armed);
+
+        let mut right = input(0, 0.0, &[]);
+        right.right_stick.x = 1.0;
+        let roll = |event: Option<MappedEvent>| match event {
+            Some(MappedEvent::ELRSData { pre_package, .. }) => pre_package[&(channel as u16)],
+            other => panic!("expected ELRS data, got {:?}", other),
+        };
+        assert_eq!(roll(strategy.map(&right)), 1900);
+
+        config.set_inverted(channel, true);
+        let mut reversed = ELRSStrategy::new(config);
+        reversed.map(&input(0, 0.0, &default_arm_combo()));
+        assert_eq!(roll(reversed.map(&right)), 1100);
     }
 }
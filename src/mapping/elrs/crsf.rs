@@ -0,0 +1,384 @@
+//! # CRSF Frame Encoding
+//!
+//! Turns the channel map produced by [`super::ELRSStrategy`] into CRSF
+//! (Crossfire) RC channel frames that an ExpressLRS transmitter module accepts
+//! on its serial port.
+//!
+//! ## Frame Layout
+//!
+//! ```text
+//! [sync 0xC8] [len] [type 0x16] [22 bytes packed channels] [crc8]
+//! ```
+//!
+//! - **len**: Number of bytes following the length byte (type + payload + crc = 24)
+//! - **payload**: 16 channels × 11 bits, packed little-endian
+//! - **crc8**: DVB-S2 polynomial (0xD5) over type and payload
+//!
+//! ## Value Conversion
+//!
+//! Channels arrive as RC microseconds (1000-2000µs) and are converted to CRSF
+//! ticks with the standard ExpressLRS scaling, where 1500µs maps to 992 and
+//! the usable range is 172-1811. Channels missing from the map are sent
+//! at center.
+//!
+//! ## Telemetry
+//!
+//! The transmitter module forwards telemetry from the vehicle using the same
+//! framing. [`CrsfDecoder`] reassembles frames from the raw serial stream,
+//! drops anything with a bad CRC, and decodes battery (0x08), link statistics
+//! (0x14) and GPS (0x02) frames into [`TelemetryFrame`]s. Multi-byte fields
+//! are big-endian on the wire.
+
+use std::collections::HashMap;
+
+/// Sync byte addressing the flight controller / transmitter module.
+pub const CRSF_SYNC_BYTE: u8 = 0xC8;
+
+/// Frame type for packed RC channel data.
+pub const CRSF_FRAME_TYPE_RC_CHANNELS: u8 = 0x16;
+
+/// Sync byte used by transmitter modules when talking to the handset.
+pub const CRSF_RADIO_SYNC_BYTE: u8 = 0xEA;
+
+/// Frame type for GPS position telemetry.
+pub const CRSF_FRAME_TYPE_GPS: u8 = 0x02;
+
+/// Frame type for battery sensor telemetry.
+pub const CRSF_FRAME_TYPE_BATTERY: u8 = 0x08;
+
+/// Frame type for link statistics telemetry.
+pub const CRSF_FRAME_TYPE_LINK_STATISTICS: u8 = 0x14;
+
+/// Largest value of the length byte (frames are at most 64 bytes).
+const CRSF_MAX_FRAME_LEN: usize = 62;
+
+/// Number of RC channels carried in one frame.
+pub const CRSF_CHANNEL_COUNT: usize = 16;
+
+/// Size of the packed channel payload (16 × 11 bits).
+const RC_PAYLOAD_LEN: usize = 22;
+
+/// Lowest valid CRSF channel value (≈988µs).
+pub const CRSF_CHANNEL_MIN: u16 = 172;
+
+/// Center CRSF channel value (1500µs).
+pub const CRSF_CHANNEL_MID: u16 = 992;
+
+/// Highest valid CRSF channel value (≈2012µs).
+pub const CRSF_CHANNEL_MAX: u16 = 1811;
+
+/// Computes the CRSF CRC8 (DVB-S2, polynomial 0xD5) over `data`.
+pub fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0xD5
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// Converts an RC microsecond value to CRSF ticks.
+///
+/// Uses the ExpressLRS scaling `ticks = 992 + (µs - 1500) × 8/5` and clamps
+/// the result to the valid CRSF range.
+pub fn us_to_crsf(us: u16) -> u16 {
+    let ticks = CRSF_CHANNEL_MID as i32 + (us as i32 - 1500) * 8 / 5;
+    ticks.clamp(CRSF_CHANNEL_MIN as i32, CRSF_CHANNEL_MAX as i32) as u16
+}
+
+/// Encodes a channel map into a complete CRSF RC channels frame.
+///
+/// Keys are channel indices (0-15), values are microseconds. Keys outside the
+/// 16 channel range are ignored; missing channels are sent at center.
+///
+/// ## Example
+/// All channels at 1500µs encode to:
+/// ```text
+/// C8 18 16 E0 03 1F F8 C0 07 3E F0 81 0F 7C E0 03 1F F8 C0 07 3E F0 81 0F 7C AD
+/// ```
+pub fn encode_rc_channels(channels: &HashMap<u16, u16>) -> Vec<u8> {
+    let mut values = [CRSF_CHANNEL_MID; CRSF_CHANNEL_COUNT];
+    for (channel, us) in channels {
+        if let Some(slot) = values.get_mut(*channel as usize) {
+            *slot = us_to_crsf(*us);
+        }
+    }
+
+    let mut frame = Vec::with_capacity(RC_PAYLOAD_LEN + 4);
+    frame.push(CRSF_SYNC_BYTE);
+    // Type + payload + crc
+    frame.push((RC_PAYLOAD_LEN + 2) as u8);
+    frame.push(CRSF_FRAME_TYPE_RC_CHANNELS);
+
+    // Pack 11-bit values little-endian into the payload
+    let mut bit_buffer: u32 = 0;
+    let mut bit_count = 0;
+    for value in values {
+        bit_buffer |= ((value & 0x07FF) as u32) << bit_count;
+        bit_count += 11;
+        while bit_count >= 8 {
+            frame.push((bit_buffer & 0xFF) as u8);
+            bit_buffer >>= 8;
+            bit_count -= 8;
+        }
+    }
+
+    let crc = crc8(&frame[2..]);
+    frame.push(crc);
+    frame
+}
+
+/// Battery sensor telemetry (frame type 0x08).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatterySensor {
+    /// Pack voltage in volts
+    pub voltage: f32,
+
+    /// Current draw in amps
+    pub current: f32,
+
+    /// Consumed capacity in mAh
+    pub capacity_mah: u32,
+
+    /// Remaining charge in percent
+    pub remaining_pct: u8,
+}
+
+/// Link statistics telemetry (frame type 0x14).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkStatistics {
+    /// Uplink RSSI of the active antenna in dBm
+    pub uplink_rssi_dbm: i16,
+
+    /// Uplink link quality in percent
+    pub uplink_link_quality: u8,
+
+    /// Uplink signal-to-noise ratio in dB
+    pub uplink_snr: i8,
+
+    /// Downlink RSSI in dBm
+    pub downlink_rssi_dbm: i16,
+
+    /// Downlink link quality in percent
+    pub downlink_link_quality: u8,
+}
+
+/// GPS telemetry (frame type 0x02).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsFix {
+    /// Latitude in degrees
+    pub latitude: f64,
+
+    /// Longitude in degrees
+    pub longitude: f64,
+
+    /// Ground speed in km/h
+    pub groundspeed_kmh: f32,
+
+    /// Heading in degrees
+    pub heading_deg: f32,
+
+    /// Altitude in meters
+    pub altitude_m: i32,
+
+    /// Number of satellites in view
+    pub satellites: u8,
+}
+
+/// A decoded telemetry frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TelemetryFrame {
+    Battery(BatterySensor),
+    LinkStatistics(LinkStatistics),
+    Gps(GpsFix),
+}
+
+impl TelemetryFrame {
+    /// Decodes the payload of a frame with the given type.
+    ///
+    /// Returns `None` for unsupported frame types and truncated payloads.
+    pub fn decode(frame_type: u8, payload: &[u8]) -> Option<Self> {
+        match frame_type {
+            CRSF_FRAME_TYPE_BATTERY if payload.len() >= 8 => Some(Self::Battery(BatterySensor {
+                voltage: u16::from_be_bytes([payload[0], payload[1]]) as f32 / 10.0,
+                current: u16::from_be_bytes([payload[2], payload[3]]) as f32 / 10.0,
+                capacity_mah: u32::from_be_bytes([0, payload[4], payload[5], payload[6]]),
+                remaining_pct: payload[7],
+            })),
+            CRSF_FRAME_TYPE_LINK_STATISTICS if payload.len() >= 10 => {
+                // Byte 4 selects the antenna the uplink RSSI is reported for
+                let uplink_rssi = if payload[4] == 0 {
+                    payload[0]
+                } else {
+                    payload[1]
+                };
+                Some(Self::LinkStatistics(LinkStatistics {
+                    uplink_rssi_dbm: -(uplink_rssi as i16),
+                    uplink_link_quality: payload[2],
+                    uplink_snr: payload[3] as i8,
+                    downlink_rssi_dbm: -(payload[7] as i16),
+                    downlink_link_quality: payload[8],
+                }))
+            }
+            CRSF_FRAME_TYPE_GPS if payload.len() >= 15 => Some(Self::Gps(GpsFix {
+                latitude: i32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]])
+                    as f64
+                    / 1e7,
+                longitude: i32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]])
+                    as f64
+                    / 1e7,
+                groundspeed_kmh: u16::from_be_bytes([payload[8], payload[9]]) as f32 / 10.0,
+                heading_deg: u16::from_be_bytes([payload[10], payload[11]]) as f32 / 100.0,
+                altitude_m: u16::from_be_bytes([payload[12], payload[13]]) as i32 - 1000,
+                satellites: payload[14],
+            })),
+            _ => None,
+        }
+    }
+}
+
+/// Reassembles CRSF frames from a raw serial byte stream.
+///
+/// ## Design Rationale
+/// Serial reads return arbitrary chunks, so bytes are buffered until a full
+/// frame is available. On a bad length or CRC only the sync byte is skipped,
+/// which lets the decoder resynchronise on the next frame boundary without
+/// losing a valid frame that starts inside the corrupt one.
+#[derive(Debug, Default)]
+pub struct CrsfDecoder {
+    /// Bytes received but not yet consumed as a frame
+    buffer: Vec<u8>,
+}
+
+impl CrsfDecoder {
+    /// Creates an empty decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds received bytes and returns all telemetry frames completed by them.
+    ///
+    /// Frames with an invalid CRC and unsupported frame types are dropped.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<TelemetryFrame> {
+        self.buffer.extend_from_slice(bytes);
+        let mut frames = Vec::new();
+        let mut start = 0;
+
+        while start < self.buffer.len() {
+            let sync = self.buffer[start];
+            if sync != CRSF_SYNC_BYTE && sync != CRSF_RADIO_SYNC_BYTE {
+                start += 1;
+                continue;
+            }
+
+            // Need at least the length byte
+            let Some(&len) = self.buffer.get(start + 1) else {
+                break;
+            };
+            let len = len as usize;
+            if !(2..=CRSF_MAX_FRAME_LEN).contains(&len) {
+                start += 1;
+                continue;
+            }
+
+            let end = start + 2 + len;
+            if end > self.buffer.len() {
+                break;
+            }
+
+            // Type + payload, followed by the crc
+            let body = &self.buffer[start + 2..end - 1];
+            if crc8(body) != self.buffer[end - 1] {
+                start += 1;
+                continue;
+            }
+
+            if let Some(frame) = TelemetryFrame::decode(body[0], &body[1..]) {
+                frames.push(frame);
+            }
+            start = end;
+        }
+
+        self.buffer.drain(..start);
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frame with every channel at 1500µs, as given in the docs.
+    const CENTER_FRAME: [u8; 26] = [
+        0xC8, 0x18, 0x16, 0xE0, 0x03, 0x1F, 0xF8, 0xC0, 0x07, 0x3E, 0xF0, 0x81, 0x0F, 0x7C, 0xE0,
+        0x03, 0x1F, 0xF8, 0xC0, 0x07, 0x3E, 0xF0, 0x81, 0x0F, 0x7C, 0xAD,
+    ];
+
+    /// Unpacks the 11-bit channel values of an RC channels frame.
+    fn unpack(frame: &[u8]) -> Vec<u16> {
+        let payload = &frame[3..3 + RC_PAYLOAD_LEN];
+        (0..CRSF_CHANNEL_COUNT)
+            .map(|channel| {
+                let bit = channel * 11;
+                let word = payload[bit / 8] as u32
+                    | (payload[bit / 8 + 1] as u32) << 8
+                    | (payload.get(bit / 8 + 2).copied().unwrap_or(0) as u32) << 16;
+                ((word >> (bit % 8)) & 0x07FF) as u16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn center_channels_encode_to_known_frame() {
+        let channels = (0..16).map(|channel| (channel, 1500)).collect();
+
+        let frame = encode_rc_channels(&channels);
+
+        assert_eq!(frame, CENTER_FRAME);
+        assert_eq!(crc8(&frame[2..frame.len() - 1]), 0xAD);
+    }
+
+    #[test]
+    fn missing_channels_are_sent_at_center() {
+        assert_eq!(encode_rc_channels(&HashMap::new()), CENTER_FRAME);
+    }
+
+    #[test]
+    fn channels_are_packed_in_order_and_clamped() {
+        let channels = HashMap::from([(0, 1000), (1, 2000), (15, 2500), (16, 1000)]);
+
+        let frame = encode_rc_channels(&channels);
+        let values = unpack(&frame);
+
+        assert_eq!(values[0], 192);
+        assert_eq!(values[1], 1792);
+        assert_eq!(values[2], CRSF_CHANNEL_MID);
+        assert_eq!(values[15], CRSF_CHANNEL_MAX);
+        assert_eq!(crc8(&frame[2..frame.len() - 1]), frame[frame.len() - 1]);
+    }
+
+    #[test]
+    fn decoder_drops_frames_with_bad_crc() {
+        let mut battery = vec![CRSF_RADIO_SYNC_BYTE, 10, CRSF_FRAME_TYPE_BATTERY];
+        battery.extend_from_slice(&[0x00, 0x7E, 0x00, 0x0A, 0x00, 0x01, 0xF4, 0x32]);
+        battery.push(crc8(&battery[2..]));
+        let mut corrupt = battery.clone();
+        corrupt[4] ^= 0xFF;
+
+        let mut decoder = CrsfDecoder::new();
+
+        assert!(decoder.push(&corrupt).is_empty());
+        match decoder.push(&battery).as_slice() {
+            [TelemetryFrame::Battery(sensor)] => {
+                assert_eq!(sensor.voltage, 12.6);
+                assert_eq!(sensor.remaining_pct, 0x32);
+            }
+            frames => panic!("expected one battery frame, got {:?}", frames),
+        }
+    }
+}
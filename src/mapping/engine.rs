@@ -28,10 +28,11 @@ use crate::mapping::{
     strategy::MappingContext, MappedEvent, MappingError, MappingStrategy, MappingType, RateLimiter,
 };
 use statum::{machine, state};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, trace, warn};
 
 /// States for mapping engine lifecycle using statum
 #[state]
@@ -50,7 +51,7 @@ pub enum MappingEngineState {
 /// Each state has specific allowed operations enforced at compile time.
 #[machine]
 pub struct MappingEngine<S: MappingEngineState> {
-    input_receiver: mpsc::Receiver<ControllerOutput>,
+    input_receiver: mpsc::Receiver<Arc<ControllerOutput>>,
     output_sender: mpsc::Sender<MappedEvent>,
     engine_type: MappingType,
     name: String,
@@ -70,7 +71,7 @@ impl<S: MappingEngineState> MappingEngine<S> {
 
 impl MappingEngine<Initializing> {
     pub fn create(
-        input_receiver: mpsc::Receiver<ControllerOutput>,
+        input_receiver: mpsc::Receiver<Arc<ControllerOutput>>,
         output_sender: mpsc::Sender<MappedEvent>,
         engine_type: MappingType,
         name: String,
@@ -90,11 +91,12 @@ impl MappingEngine<Initializing> {
 
     /// Configures engine with strategy and transitions to Configured state
     ///
-    /// Initializes the strategy, sets up rate limiting if requested by strategy,
-    /// and transitions to Configured state on success.
+    /// Initializes the strategy, takes over the rate limiter built from the
+    /// mapping config, and transitions to Configured state on success.
     pub fn configure(
         mut self,
         mut strategy: Box<dyn MappingStrategy>,
+        rate_limiter: Option<RateLimiter>,
     ) -> Result<MappingEngine<Configured>, MappingError> {
         info!("Configuring mapping engine: {}", self.name);
 
@@ -102,7 +104,6 @@ impl MappingEngine<Initializing> {
             Ok(_) => {
                 debug!("Strategy initialized successfully");
 
-                let rate_limiter = strategy.get_rate_limit().map(RateLimiter::new);
                 if let Some(ref limiter) = rate_limiter {
                     debug!(
                         "Rate limiter configured with {}ms interval",
@@ -118,10 +119,10 @@ impl MappingEngine<Initializing> {
             }
             Err(e) => {
                 error!("Failed to initialize strategy: {}", e);
-                Err(MappingError::InitializationError(format!(
-                    "Failed to initialize strategy: {}",
-                    e
-                )))
+                Err(MappingError::StrategyInitFailed {
+                    mapping_type: self.engine_type,
+                    reason: e.to_string(),
+                })
             }
         }
     }
@@ -138,7 +139,11 @@ impl MappingEngine<Active> {
     /// Processes a single controller event through the strategy
     ///
     /// Applies rate limiting if configured, then calls the strategy's map method.
-    /// Returns None if no input available, rate limited, or strategy produces no output.
+    /// Input with button or chord releases skips the rate limit: held state is
+    /// reported again next cycle, but a release, e.g. of a quick tap, is not.
+    /// Without input the strategy's idle output is used instead; an idle cycle
+    /// only counts against the rate limit if it produced an event.
+    /// Returns None if rate limited or the strategy produces no output.
     pub fn process_event(&mut self) -> Result<Option<MappedEvent>, MappingError> {
         let strategy = match &mut self.strategy {
             Some(s) => s,
@@ -153,14 +158,14 @@ impl MappingEngine<Active> {
 
         if let Ok(controller_output) = controller_state {
             if let Some(limiter) = &mut self.rate_limiter {
-                if !limiter.should_process() {
+                if !controller_output.has_releases() && !limiter.should_process() {
                     return Ok(None);
                 }
             }
 
             match strategy.map(&controller_output) {
                 Some(mapped_event) => {
-                    info!("Successfully mapped event to {:?}", mapped_event);
+                    trace!("Successfully mapped event to {:?}", mapped_event);
                     return Ok(Some(mapped_event));
                 }
                 None => {
@@ -169,14 +174,29 @@ impl MappingEngine<Active> {
                 }
             }
         }
-        Ok(None)
+
+        if self
+            .rate_limiter
+            .as_ref()
+            .is_some_and(|limiter| !limiter.is_due())
+        {
+            return Ok(None);
+        }
+        let idle_event = strategy.map_idle();
+        if idle_event.is_some() {
+            if let Some(limiter) = &mut self.rate_limiter {
+                limiter.restart();
+            }
+            trace!("Idle event mapped: {:?}", idle_event);
+        }
+        Ok(idle_event)
     }
 
     /// Sends mapped event to output channel
     pub async fn send_event(&self, event: MappedEvent) -> Result<(), MappingError> {
         match self.output_sender.try_send(event) {
             Ok(_) => {
-                info!("Event sent successfully");
+                trace!("Event sent successfully");
                 Ok(())
             }
             Err(e) => {
@@ -277,8 +297,8 @@ impl MappingEngineHandle {
     }
     /// Starts engine in tokio task and returns communication channels
     ///
-    /// Creates engine, configures it with strategy, activates it, and spawns
-    /// the main processing loop in a background task.
+    /// Creates engine, configures it with strategy and rate limiter, activates
+    /// it, and spawns the main processing loop in a background task.
     ///
     /// # Returns
     ///
@@ -287,7 +307,14 @@ impl MappingEngineHandle {
     pub fn start(
         &mut self,
         strategy: Box<dyn MappingStrategy>,
-    ) -> Result<(mpsc::Receiver<MappedEvent>, mpsc::Sender<ControllerOutput>), MappingError> {
+        rate_limiter: Option<RateLimiter>,
+    ) -> Result<
+        (
+            mpsc::Receiver<MappedEvent>,
+            mpsc::Sender<Arc<ControllerOutput>>,
+        ),
+        MappingError,
+    > {
         let (controller_state_sender, controller_state_receiver) = mpsc::channel(100);
         let (mapped_event_sender, mapped_event_receiver) = mpsc::channel(100);
         let engine_name = self.name.clone();
@@ -297,7 +324,7 @@ impl MappingEngineHandle {
             self.engine_type,
             engine_name.clone(),
         )
-        .configure(strategy)?;
+        .configure(strategy, rate_limiter)?;
 
         let active_engine = engine.activate();
 
@@ -327,6 +354,13 @@ impl MappingEngineHandle {
         Ok((mapped_event_receiver, controller_state_sender))
     }
 
+    /// Whether the engine task has ended, e.g. after a panic
+    pub fn is_finished(&self) -> bool {
+        self.task_handle
+            .as_ref()
+            .is_some_and(|handle| handle.is_finished())
+    }
+
     /// Gracefully shuts down engine and waits for task completion
     pub async fn shutdown(&mut self) -> Result<(), MappingError> {
         debug!("Sending shutdown signal to engine: {}", self.name);
@@ -359,3 +393,54 @@ impl MappingEngineHandle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::controller_handle::{ButtonEvent, ButtonEventState, ButtonType};
+    use crate::mapping::keyboard::{KeyboardConfig, KeyboardStrategy};
+
+    fn dpad_up(state: ButtonEventState) -> Arc<ControllerOutput> {
+        Arc::new(ControllerOutput {
+            button_events: vec![ButtonEvent {
+                button: ButtonType::DPadUp,
+                duration_ms: 50.0,
+                state,
+            }],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn releases_are_not_rate_limited() {
+        let (input_tx, input_rx) = mpsc::channel(10);
+        let (output_tx, _output_rx) = mpsc::channel(10);
+        let strategy = KeyboardStrategy::new(KeyboardConfig::default_config());
+        let mut engine = MappingEngine::create(
+            input_rx,
+            output_tx,
+            MappingType::Keyboard,
+            "keyboard".to_string(),
+        )
+        .configure(Box::new(strategy), Some(RateLimiter::new(500)))
+        .unwrap()
+        .activate();
+
+        input_tx.try_send(dpad_up(ButtonEventState::Held)).unwrap();
+        assert!(engine.process_event().unwrap().is_some());
+
+        // Held state is dropped while rate limited, it is reported again
+        input_tx.try_send(dpad_up(ButtonEventState::Held)).unwrap();
+        assert!(engine.process_event().unwrap().is_none());
+
+        input_tx
+            .try_send(dpad_up(ButtonEventState::Complete))
+            .unwrap();
+        match engine.process_event().unwrap() {
+            Some(MappedEvent::KeyboardEvent { key_code, .. }) => assert!(key_code
+                .iter()
+                .any(|event| matches!(event, egui::Event::Key { pressed: false, .. }))),
+            other => panic!("expected a key release, got {:?}", other),
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! Defines specific error categories for mapping engine operations, strategy
 //! configuration, and inter-thread communication failures.
 
+use crate::mapping::MappingType;
 use thiserror::Error;
 
 /// Error types for mapping engine operations
@@ -20,10 +21,14 @@ pub enum MappingError {
 
     /// Strategy initialization failed
     ///
-    /// Returned when a mapping strategy's `initialize()` method fails,
-    /// typically due to resource allocation or setup problems.
-    #[error("Initialization error: {0}")]
-    InitializationError(String),
+    /// Returned when a mapping strategy's `initialize()` method fails while
+    /// its engine starts, typically due to resource allocation or setup
+    /// problems.
+    #[error("{mapping_type} strategy failed to initialize: {reason}")]
+    StrategyInitFailed {
+        mapping_type: MappingType,
+        reason: String,
+    },
 
     /// Inter-thread channel communication failed
     ///
@@ -32,6 +37,13 @@ pub enum MappingError {
     #[error("Channel error: {0}")]
     ChannelError(String),
 
+    /// A channel the mapping depends on was closed
+    ///
+    /// Fatal for the manager: once e.g. the controller input is gone no
+    /// further input can arrive. Holds the name of the channel.
+    #[error("{0} channel closed")]
+    ChannelClosed(String),
+
     /// Background task management failed
     ///
     /// Returned when tokio tasks panic, fail to spawn, or encounter
@@ -45,4 +57,11 @@ pub enum MappingError {
     /// encounters runtime errors during event processing.
     #[error("Strategy error: {0}")]
     StrategyError(String),
+
+    /// Serial transmitter communication failed
+    ///
+    /// Returned when enumerating, opening, reading from or writing to the
+    /// serial port of an output device (e.g. an ELRS transmitter) fails.
+    #[error("Serial error: {0}")]
+    SerialError(String),
 }
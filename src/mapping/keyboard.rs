@@ -23,16 +23,65 @@
 //!
 //! ## Region Layout Strategy
 //!
-//! The alphabet mapping follows a logical pattern:
+//! The default alphabet mapping follows a logical pattern:
 //! - A-H: Left joystick directions + right joystick center
 //! - I-P: Left joystick center + right joystick directions  
 //! - Q-Z: Symmetric combinations for remaining letters
 //!
+//! Other [`LayoutPreset`]s reuse the same region slots in a different letter
+//! order, e.g. placing frequent letters on single-stick movements.
+//!
+//! ## Symbol Mode
+//!
+//! While the symbol trigger (left trigger by default) is pulled, the same region
+//! combinations produce digits and the symbols needed for MQTT payloads
+//! (`/ . : { } " ,`) from a second table.
+//!
+//! ## Key Up and Repeat
+//!
+//! A button or chord press produces a key down and its release a key up, so
+//! egui never sees a key stuck down. Modifiers are not keys in egui; every
+//! key event carries the modifier buttons held at that moment instead, so a
+//! released Shift or Ctrl applies to no further event.
+//!
+//! While a button stays held, its key repeats after
+//! [`KeyboardConfig::repeat_delay_ms`] every
+//! [`KeyboardConfig::repeat_interval_ms`], like a held keyboard key, so the
+//! D-pad can move a cursor continuously.
+//!
+//! ## Dwell to Select
+//!
+//! With [`KeyboardConfig::dwell_ms`] set, a letter is emitted once the sticks
+//! stayed in its combo for that long, instead of on every input reaching the
+//! combo. It is emitted only once; the sticks have to leave the combo and
+//! come back for the next one. This allows typing without precise flicks,
+//! e.g. one-handed. A steady stick reports no input, so the dwell is also
+//! checked on idle engine cycles.
+//!
+//! ## Virtual Mouse
+//!
+//! Sliders and color pickers are awkward to operate with arrow keys. With
+//! [`KeyboardConfig::input_mode`] set to [`InputMode::Mouse`], the right stick
+//! moves a pointer and [`MOUSE_CLICK_BUTTON`] clicks; stick typing is off and
+//! all other buttons keep their keys. The strategy has no idea of the screen,
+//! so it emits relative [`Event::MouseMoved`] steps and clicks without a
+//! position. The UI owns the cursor, applies the steps clamped to the screen
+//! and fills in the position. The stick keeps moving the cursor while held
+//! still, so motion is also produced on idle engine cycles.
+//!
+//! ## Multiple Gamepads
+//!
+//! Without a gamepad filter the strategy receives one input per pad and
+//! cycle. Held keys, stick regions, dwell and click state are kept per pad,
+//! so input of one pad never releases keys or resets combos of another.
+//!
 //! ## Hysteresis Rationale
 //!
 //! Without hysteresis, small joystick movements near region boundaries would cause
 //! rapid region switching, making precise text input impossible. The implementation
-//! uses 8% hysteresis factor to create stable region detection.
+//! uses 8% hysteresis factor to create stable region detection by default. The
+//! factor and the center radius are part of [`KeyboardConfig`] so they can be
+//! tuned for worn sticks.
 //!
 //! ## Error Handling Strategy
 //!
@@ -40,29 +89,74 @@
 //! Runtime errors (e.g., unmapped combinations) simply produce no output rather than
 //! failing, maintaining system stability during user interaction.
 
-use crate::controller::controller_handle::{ButtonType, ControllerOutput};
+use crate::controller::controller_handle::{ButtonType, ControllerOutput, TriggerType};
+use crate::controller::rumble::{RumbleHandle, RumblePattern};
 use crate::mapping::{
-    strategy::MappingContext, MappedEvent, MappingError, MappingStrategy, MappingType,
+    navigation::NavigationAction,
+    strategy::{Dwell, HeldButton, MappingContext},
+    MappedEvent, MappingError, MappingStrategy, MappingType, RateLimiter,
 };
 use eframe::egui::{self, Event, Key, Modifiers};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
-use tracing::{debug, error, info, warn};
-
-macro_rules! map_insert {
-    ($map:expr, $regions:expr, $key:expr, $upper:expr, $lower:expr) => {
-        $map.insert($regions, ($key, $upper.to_string(), $lower.to_string()));
-    };
-}
+use std::time::{Duration, Instant};
+use tracing::{info, trace, warn};
 
 /// Hysteresis factor for region detection to prevent boundary flickering.
 ///
 /// Set to 8% of region size to provide stable region transitions while
 /// maintaining responsive input. Higher values increase stability but
 /// reduce precision; lower values increase sensitivity but may cause flickering.
+///
+/// Default for [`KeyboardConfig::hysteresis`].
 pub const REGION_HYSTERESIS: f32 = 0.08;
 
+/// Default stick deflection below which a stick counts as centered.
+///
+/// Default for [`KeyboardConfig::center_radius`]; directional regions start here.
+pub const DEFAULT_CENTER_RADIUS: f32 = 0.3;
+
+/// Valid range for [`KeyboardConfig::hysteresis`], upper bound exclusive.
+pub const HYSTERESIS_RANGE: std::ops::Range<f32> = 0.0..0.4;
+
+/// Valid range for [`KeyboardConfig::center_radius`], both bounds exclusive.
+pub const CENTER_RADIUS_RANGE: std::ops::Range<f32> = 0.0..0.5;
+
+/// Trigger travel (0.0-1.0) above which symbol mode is active.
+pub const SYMBOL_TRIGGER_THRESHOLD: f32 = 0.5;
+
+/// Default interval between keyboard mappings, ~22Hz.
+///
+/// Faster rates don't improve user experience for text input but
+/// increase CPU usage and event queue pressure.
+pub const DEFAULT_RATE_LIMIT_MS: u64 = 45;
+
+/// Default hold time before a held button starts repeating its key.
+pub const DEFAULT_REPEAT_DELAY_MS: u64 = 400;
+
+/// Default time between two key repeats, ~10Hz.
+pub const DEFAULT_REPEAT_INTERVAL_MS: u64 = 100;
+
+/// Default cursor speed in points per second at full stick deflection.
+pub const DEFAULT_CURSOR_SPEED: f32 = 600.0;
+
+/// Valid range for [`KeyboardConfig::cursor_speed`].
+pub const CURSOR_SPEED_RANGE: std::ops::RangeInclusive<f32> = 100.0..=3000.0;
+
+/// Button that clicks in [`InputMode::Mouse`], instead of its mapped key.
+pub const MOUSE_CLICK_BUTTON: ButtonType = ButtonType::A;
+
+/// Longest time one cursor step covers.
+///
+/// Keeps the cursor from jumping after a stalled cycle.
+const MAX_CURSOR_STEP: Duration = Duration::from_millis(100);
+
+/// Most key repeats emitted for one button in a single mapping cycle.
+///
+/// Caps the catch-up burst after a stalled cycle, so a cursor does not jump.
+const MAX_REPEATS_PER_CYCLE: u32 = 3;
+
 /// Represents the 8 cardinal and intercardinal directions plus center position.
 ///
 /// ## Design Rationale
@@ -111,6 +205,9 @@ pub const REGION_NORTHWEST: Region = Region::new(315.0, 360.0, 0.3, 1.0, Section
 
 pub const ALL_REGIONS: [Region; 8] = standard_regions();
 
+/// Angle covered by each directional region, in degrees.
+const SECTOR_SPAN: f32 = 360.0 / ALL_REGIONS.len() as f32;
+
 /// Returns all standard directional regions as a compile-time constant array.
 pub const fn standard_regions() -> [Region; 8] {
     [
@@ -172,7 +269,46 @@ impl PartialEq for Region {
 
 impl Eq for Region {}
 
-impl Region {
+/// The nine regions of one joystick, built from a hysteresis and center radius.
+///
+/// ## Design Rationale
+/// The const regions above use the default tuning. Users with worn sticks need
+/// other values, so the keyboard strategy builds its own set from
+/// [`KeyboardConfig`] when it is constructed. Lookups by section are unaffected
+/// because regions compare by section only.
+#[derive(Clone, Debug)]
+pub struct RegionSet {
+    center: Region,
+    directions: [Region; 8],
+}
+
+impl Default for RegionSet {
+    fn default() -> Self {
+        Self::new(REGION_HYSTERESIS, DEFAULT_CENTER_RADIUS)
+    }
+}
+
+impl RegionSet {
+    /// Builds all regions with the given hysteresis factor and center radius.
+    pub fn new(hysteresis: f32, center_radius: f32) -> Self {
+        let center = Region {
+            max_magnitute: center_radius,
+            inner_max_magnitute: center_radius,
+            ..REGION_CENTER
+        };
+        let directions = ALL_REGIONS.map(|region| {
+            Region::with_hysteresis(
+                region.min_angle,
+                region.max_angle,
+                center_radius,
+                region.max_magnitute,
+                region.section,
+                hysteresis,
+            )
+        });
+        Self { center, directions }
+    }
+
     /// Determines which region contains the given joystick position.
     ///
     /// Uses hysteresis-aware detection to prevent boundary flickering.
@@ -180,22 +316,45 @@ impl Region {
     /// beyond outer boundaries to exit that region.
     ///
     /// ## Algorithm
-    /// 1. Check each directional region using hysteresis logic
-    /// 2. If no directional region matches, default to center
-    /// 3. Previous section influences which boundaries are used for detection
+    /// 1. Convert the position to polar coordinates once
+    /// 2. Keep the previous directional region while inside its outer boundaries
+    /// 3. Otherwise pick the region whose angle bucket contains the position and
+    ///    check its inner boundaries
+    /// 4. If no directional region matches, default to center
     ///
     /// # Performance Notes
-    /// Iterates through all 8 regions on each call. Could be optimized with
-    /// angle-based lookup if this becomes a bottleneck, but current performance
-    /// is acceptable for typical input rates.
-    fn region_from_pos(x: f32, y: f32, old_section: Option<Section>) -> Option<Region> {
-        for region in ALL_REGIONS {
-            if region.contains(x, y, old_section) {
-                info!("New Region: {:?}", region.section);
-                return Some(region);
+    /// Directions cover equal, ordered 45° sectors starting at North, so the
+    /// candidate region is found by index instead of testing all eight.
+    pub fn region_from_pos(&self, x: f32, y: f32, old_section: Option<Section>) -> Region {
+        let (angle, magnitude) = Region::to_polar(x, y);
+
+        let previous = self
+            .directions
+            .iter()
+            .find(|region| Some(region.section) == old_section);
+        if let Some(previous) = previous {
+            if previous.contains_polar_outer(angle, magnitude) {
+                return previous.clone();
             }
         }
-        Some(REGION_CENTER)
+
+        let bucket = (angle / SECTOR_SPAN) as usize % self.directions.len();
+        let candidate = &self.directions[bucket];
+        if candidate.contains_polar(angle, magnitude, old_section) {
+            candidate.clone()
+        } else {
+            self.center.clone()
+        }
+    }
+}
+
+impl Region {
+    /// Returns the standard region for a section, usable as mapping key.
+    pub fn for_section(section: Section) -> Region {
+        ALL_REGIONS
+            .into_iter()
+            .find(|region| region.section == section)
+            .unwrap_or(REGION_CENTER)
     }
 
     /// Converts cartesian coordinates to polar coordinates with North orientation.
@@ -215,6 +374,9 @@ impl Region {
     /// # Mathematical Formula
     /// $$ \theta_{north} = (360° + 112.5° - \theta_{atan2}) \bmod 360° $$
     fn to_polar(x: f32, y: f32) -> (f32, f32) {
+        #[cfg(test)]
+        tests::POLAR_CONVERSIONS.with(|count| count.set(count.get() + 1));
+
         let angle_rad = y.atan2(x);
         let mut angle_deg = angle_rad.to_degrees();
 
@@ -247,7 +409,25 @@ impl Region {
         mag_max: f32,
         section: Section,
     ) -> Self {
-        let hysteresis = REGION_HYSTERESIS;
+        Self::with_hysteresis(
+            angle_min,
+            angle_max,
+            mag_min,
+            mag_max,
+            section,
+            REGION_HYSTERESIS,
+        )
+    }
+
+    /// Creates a new region with a custom hysteresis factor.
+    pub const fn with_hysteresis(
+        angle_min: f32,
+        angle_max: f32,
+        mag_min: f32,
+        mag_max: f32,
+        section: Section,
+        hysteresis: f32,
+    ) -> Self {
         let angle_span = angle_max - angle_min;
         let mag_span = mag_max - mag_min;
 
@@ -276,21 +456,48 @@ impl Region {
     /// Checks if position is within outer region boundaries (for exiting region).
     pub fn contains_outer(&self, x: f32, y: f32) -> bool {
         let (angle, magnitute) = Region::to_polar(x, y);
+        self.contains_polar_outer(angle, magnitute)
+    }
+
+    /// Checks if position is within inner region boundaries (for entering region).
+    pub fn contains_inner(&self, x: f32, y: f32) -> bool {
+        let (angle, magnitute) = Region::to_polar(x, y);
+        self.contains_polar_inner(angle, magnitute)
+    }
+
+    /// Outer boundary check on an already converted polar position.
+    fn contains_polar_outer(&self, angle: f32, magnitute: f32) -> bool {
         angle >= self.min_angle
             && angle <= self.max_angle
             && magnitute >= self.min_magnitute
             && magnitute <= self.max_magnitute
     }
 
-    /// Checks if position is within inner region boundaries (for entering region).
-    pub fn contains_inner(&self, x: f32, y: f32) -> bool {
-        let (angle, magnitute) = Region::to_polar(x, y);
+    /// Inner boundary check on an already converted polar position.
+    fn contains_polar_inner(&self, angle: f32, magnitute: f32) -> bool {
         angle >= self.inner_min_angle
             && angle <= self.inner_max_angle
             && magnitute >= self.inner_min_magnitute
             && magnitute <= self.inner_max_magnitute
     }
 
+    /// Hysteresis-aware containment check on an already converted polar position.
+    ///
+    /// Same logic as [`Self::contains`], for callers that test several regions
+    /// against one position.
+    pub fn contains_polar(
+        &self,
+        angle: f32,
+        magnitute: f32,
+        previous_section: Option<Section>,
+    ) -> bool {
+        if previous_section == Some(self.section) {
+            self.contains_polar_outer(angle, magnitute)
+        } else {
+            self.contains_polar_inner(angle, magnitute)
+        }
+    }
+
     /// Implements hysteresis-aware region containment check.
     ///
     /// ## Hysteresis Logic
@@ -301,17 +508,11 @@ impl Region {
     /// is near a boundary, which would cause unusable text input behavior.
     ///
     /// ## Performance Notes
-    /// The polar coordinate conversion happens for every region check.
-    /// Could be optimized by converting once per frame, but current performance
-    /// is adequate for real-time input processing.
+    /// Converts to polar coordinates on every call. When testing several regions
+    /// against one position, convert once and use [`Self::contains_polar`].
     pub fn contains(&self, x: f32, y: f32, previous_section: Option<Section>) -> bool {
-        if previous_section == Some(self.section) {
-            // Use outer boundaries when staying in same region
-            return self.contains_outer(x, y);
-        }
-
-        // Use inner boundaries when entering from different region
-        self.contains_inner(x, y)
+        let (angle, magnitute) = Region::to_polar(x, y);
+        self.contains_polar(angle, magnitute, previous_section)
     }
 }
 
@@ -340,11 +541,376 @@ pub struct KeyboardConfig {
     /// Key: (left_region, right_region), Value: (key, uppercase, lowercase)
     joystick_mapping: HashMap<(Region, Region), (Key, String, String)>,
 
+    /// Maps joystick region combinations to digits and symbols in symbol mode.
+    /// Same layout as `joystick_mapping`.
+    #[serde(default = "symbol_mapping")]
+    symbol_mapping: HashMap<(Region, Region), (Key, String, String)>,
+
+    /// Trigger that switches to `symbol_mapping` while pulled, `None` disables symbol mode.
+    #[serde(default = "default_symbol_trigger")]
+    pub symbol_trigger: Option<TriggerType>,
+
+    /// Region hysteresis factor, valid in [0.0, 0.4).
+    #[serde(default = "default_hysteresis")]
+    pub hysteresis: f32,
+
+    /// Stick deflection below which a stick counts as centered, valid in (0.0, 0.5).
+    #[serde(default = "default_center_radius")]
+    pub center_radius: f32,
+
     /// Maps buttons to keyboard modifiers (Shift, Ctrl, Alt, etc.).
     modifier_mapping: HashMap<ButtonType, Modifiers>,
 
+    /// Maps chords, by the name configured in the controller settings, to keys.
+    #[serde(default)]
+    pub chord_mapping: HashMap<String, Key>,
+
+    /// Buttons that switch UI menus instead of typing, see [`crate::mapping::navigation`].
+    #[serde(default)]
+    pub navigation_mapping: HashMap<ButtonType, NavigationAction>,
+
     /// Human-readable name for this configuration.
     name: String,
+
+    /// Alphabet layout the joystick mapping was built from.
+    #[serde(default)]
+    layout: LayoutPreset,
+
+    /// Minimum milliseconds between two mapped inputs.
+    #[serde(default = "default_rate_limit_ms")]
+    pub rate_limit_ms: u64,
+
+    /// Pulse a short rumble on the originating pad for every typed letter.
+    #[serde(default)]
+    pub rumble_on_letter: bool,
+
+    /// Milliseconds a button has to be held before its key repeats.
+    #[serde(default = "default_repeat_delay_ms")]
+    pub repeat_delay_ms: u64,
+
+    /// Milliseconds between two repeats of a held key, 0 disables repeat.
+    #[serde(default = "default_repeat_interval_ms")]
+    pub repeat_interval_ms: u64,
+
+    /// Milliseconds the sticks have to rest in a combo before its letter is
+    /// emitted, `None` emits on every input instead. See the module docs.
+    #[serde(default)]
+    pub dwell_ms: Option<u64>,
+
+    /// Whether the sticks type or drive a virtual mouse, see the module docs.
+    #[serde(default)]
+    pub input_mode: InputMode,
+
+    /// Cursor speed of [`InputMode::Mouse`] in points per second at full
+    /// deflection, valid in [`CURSOR_SPEED_RANGE`].
+    #[serde(default = "default_cursor_speed")]
+    pub cursor_speed: f32,
+
+    /// Rumble handle injected at activation, not persisted.
+    #[serde(skip)]
+    rumble: Option<RumbleHandle>,
+}
+
+/// Joystick region combinations available for letters, easiest to reach first.
+///
+/// Single-stick directions come first, then both sticks in the same direction,
+/// then opposite directions. Layout presets assign letters to these slots in
+/// their own order.
+const LETTER_SLOTS: [(Region, Region); 26] = [
+    // Left directions + right center
+    (REGION_NORTH, REGION_CENTER),
+    (REGION_NORTHEAST, REGION_CENTER),
+    (REGION_EAST, REGION_CENTER),
+    (REGION_SOUTHEAST, REGION_CENTER),
+    (REGION_SOUTH, REGION_CENTER),
+    (REGION_SOUTHWEST, REGION_CENTER),
+    (REGION_WEST, REGION_CENTER),
+    (REGION_NORTHWEST, REGION_CENTER),
+    // Left center + right directions
+    (REGION_CENTER, REGION_NORTH),
+    (REGION_CENTER, REGION_NORTHEAST),
+    (REGION_CENTER, REGION_EAST),
+    (REGION_CENTER, REGION_SOUTHEAST),
+    (REGION_CENTER, REGION_SOUTH),
+    (REGION_CENTER, REGION_SOUTHWEST),
+    (REGION_CENTER, REGION_WEST),
+    (REGION_CENTER, REGION_NORTHWEST),
+    // Symmetric directional combinations
+    (REGION_NORTH, REGION_NORTH),
+    (REGION_NORTHEAST, REGION_NORTHEAST),
+    (REGION_EAST, REGION_EAST),
+    (REGION_SOUTHEAST, REGION_SOUTHEAST),
+    (REGION_SOUTH, REGION_SOUTH),
+    (REGION_SOUTHWEST, REGION_SOUTHWEST),
+    (REGION_WEST, REGION_WEST),
+    (REGION_NORTHWEST, REGION_NORTHWEST),
+    // Opposite directions
+    (REGION_NORTH, REGION_SOUTH),
+    (REGION_SOUTH, REGION_NORTH),
+];
+
+/// Letters in alphabetical order, assigned to [`LETTER_SLOTS`] one by one.
+const ALPHABETICAL_LETTERS: [Key; 26] = [
+    Key::A,
+    Key::B,
+    Key::C,
+    Key::D,
+    Key::E,
+    Key::F,
+    Key::G,
+    Key::H,
+    Key::I,
+    Key::J,
+    Key::K,
+    Key::L,
+    Key::M,
+    Key::N,
+    Key::O,
+    Key::P,
+    Key::Q,
+    Key::R,
+    Key::S,
+    Key::T,
+    Key::U,
+    Key::V,
+    Key::W,
+    Key::X,
+    Key::Y,
+    Key::Z,
+];
+
+/// Letters ordered by frequency in English text (ETAOIN SHRDLU...).
+///
+/// The most common letters land on single-stick movements, the rarest ones on
+/// two-stick combinations.
+const FREQUENCY_LETTERS: [Key; 26] = [
+    Key::E,
+    Key::T,
+    Key::A,
+    Key::O,
+    Key::I,
+    Key::N,
+    Key::S,
+    Key::H,
+    Key::R,
+    Key::D,
+    Key::L,
+    Key::C,
+    Key::U,
+    Key::M,
+    Key::W,
+    Key::F,
+    Key::G,
+    Key::Y,
+    Key::P,
+    Key::B,
+    Key::V,
+    Key::K,
+    Key::J,
+    Key::X,
+    Key::Q,
+    Key::Z,
+];
+
+/// Digits and symbols for symbol mode as (key, text), assigned to [`LETTER_SLOTS`] in order.
+///
+/// Digits take the single-stick slots in keyboard row order (1-9, 0). egui has
+/// no dedicated key for `"`, so it is sent as [`Key::Quote`] with the proper text.
+const SYMBOLS: [(Key, &str); 17] = [
+    (Key::Num1, "1"),
+    (Key::Num2, "2"),
+    (Key::Num3, "3"),
+    (Key::Num4, "4"),
+    (Key::Num5, "5"),
+    (Key::Num6, "6"),
+    (Key::Num7, "7"),
+    (Key::Num8, "8"),
+    (Key::Num9, "9"),
+    (Key::Num0, "0"),
+    (Key::Slash, "/"),
+    (Key::Period, "."),
+    (Key::Colon, ":"),
+    (Key::Comma, ","),
+    (Key::OpenCurlyBracket, "{"),
+    (Key::CloseCurlyBracket, "}"),
+    (Key::Quote, "\""),
+];
+
+/// Alphabet layouts selectable for the joystick letter mapping.
+///
+/// ## Presets
+/// - **Alphabetical**: Letters in A-Z order, easiest to learn
+/// - **FrequencyOptimized**: Common letters on the easiest movements, faster typing
+/// - **Custom**: Hand-edited `joystick_mapping` from the session config
+///
+/// Persisted with [`KeyboardConfig`] so a session keeps its layout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutPreset {
+    #[default]
+    Alphabetical,
+    FrequencyOptimized,
+    Custom,
+}
+
+impl LayoutPreset {
+    pub const ALL: [LayoutPreset; 3] = [
+        LayoutPreset::Alphabetical,
+        LayoutPreset::FrequencyOptimized,
+        LayoutPreset::Custom,
+    ];
+}
+
+impl std::fmt::Display for LayoutPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutPreset::Alphabetical => write!(f, "Alphabetical"),
+            LayoutPreset::FrequencyOptimized => write!(f, "Frequency Optimized"),
+            LayoutPreset::Custom => write!(f, "Custom"),
+        }
+    }
+}
+
+/// What the sticks of the keyboard mapping control.
+///
+/// Persisted with [`KeyboardConfig`], so a session starts in its last mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputMode {
+    /// Stick combos type letters, the UI is navigated with keys
+    #[default]
+    Keyboard,
+    /// The right stick moves a pointer, [`MOUSE_CLICK_BUTTON`] clicks
+    Mouse,
+}
+
+impl InputMode {
+    pub const ALL: [InputMode; 2] = [InputMode::Keyboard, InputMode::Mouse];
+}
+
+impl std::fmt::Display for InputMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputMode::Keyboard => write!(f, "Keyboard navigation"),
+            InputMode::Mouse => write!(f, "Virtual mouse"),
+        }
+    }
+}
+
+/// Modifiers a button can be assigned to, with their display names.
+pub const MODIFIER_CHOICES: [(Modifiers, &str); 4] = [
+    (Modifiers::SHIFT, "Shift"),
+    (Modifiers::CTRL, "Ctrl"),
+    (Modifiers::ALT, "Alt"),
+    (Modifiers::COMMAND, "Command"),
+];
+
+/// What a gamepad button produces in the keyboard mapping.
+///
+/// A button is either a regular key, a modifier applied to other keys, a
+/// menu navigation action, or unmapped. Modifier and navigation buttons never
+/// emit key events of their own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ButtonAssignment {
+    Unmapped,
+    Key(Key),
+    Modifier(Modifiers),
+    Navigation(NavigationAction),
+}
+
+impl std::fmt::Display for ButtonAssignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ButtonAssignment::Unmapped => write!(f, "-"),
+            ButtonAssignment::Key(key) => write!(f, "{}", key.name()),
+            ButtonAssignment::Modifier(modifiers) => {
+                match MODIFIER_CHOICES.iter().find(|(m, _)| m == modifiers) {
+                    Some((_, name)) => write!(f, "{}", name),
+                    None => write!(f, "{:?}", modifiers),
+                }
+            }
+            ButtonAssignment::Navigation(action) => write!(f, "{}", action),
+        }
+    }
+}
+
+/// Key down event, plus the text special keys type into text fields.
+fn key_press(key: Key, modifiers: Modifiers, repeat: bool) -> Vec<Event> {
+    let mut events = vec![Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat,
+        modifiers,
+    }];
+    match key {
+        Key::Enter => events.push(Event::Text("\n".to_string())),
+        Key::Tab => events.push(Event::Text("\t".to_string())),
+        Key::Space => events.push(Event::Text(" ".to_string())),
+        _ => {}
+    };
+    events
+}
+
+/// Key up event ending a press from [`key_press`].
+fn key_release(key: Key, modifiers: Modifiers) -> Event {
+    Event::Key {
+        key,
+        physical_key: None,
+        pressed: false,
+        repeat: false,
+        modifiers,
+    }
+}
+
+/// Builds the symbol mode mapping from [`SYMBOLS`].
+///
+/// Symbols have no case, so both text variants are identical.
+fn symbol_mapping() -> HashMap<(Region, Region), (Key, String, String)> {
+    LETTER_SLOTS
+        .iter()
+        .zip(SYMBOLS)
+        .map(|(regions, (key, text))| (regions.clone(), (key, text.to_string(), text.to_string())))
+        .collect()
+}
+
+fn default_symbol_trigger() -> Option<TriggerType> {
+    Some(TriggerType::Left)
+}
+
+fn default_rate_limit_ms() -> u64 {
+    DEFAULT_RATE_LIMIT_MS
+}
+
+fn default_repeat_delay_ms() -> u64 {
+    DEFAULT_REPEAT_DELAY_MS
+}
+
+fn default_repeat_interval_ms() -> u64 {
+    DEFAULT_REPEAT_INTERVAL_MS
+}
+
+fn default_cursor_speed() -> f32 {
+    DEFAULT_CURSOR_SPEED
+}
+
+fn default_hysteresis() -> f32 {
+    REGION_HYSTERESIS
+}
+
+fn default_center_radius() -> f32 {
+    DEFAULT_CENTER_RADIUS
+}
+
+/// Builds a joystick mapping by assigning `letters` to [`LETTER_SLOTS`] in order.
+fn letter_mapping(letters: &[Key; 26]) -> HashMap<(Region, Region), (Key, String, String)> {
+    LETTER_SLOTS
+        .iter()
+        .zip(letters)
+        .map(|(regions, key)| {
+            let upper = key.name().to_string();
+            let lower = upper.to_lowercase();
+            (regions.clone(), (*key, upper, lower))
+        })
+        .collect()
 }
 
 impl KeyboardConfig {
@@ -357,19 +923,170 @@ impl KeyboardConfig {
     /// - Bumpers → Ctrl/Shift (modifiers)
     /// - Start/Select → Command/Alt (system actions)
     ///
-    /// ## Default Alphabet Layout  
-    /// Systematic assignment using dual-joystick combinations:
+    /// ## Default Alphabet Layout
+    /// Uses [`LayoutPreset::Alphabetical`], which prioritizes learnability over
+    /// frequency optimization. See [`KeyboardConfig::from_preset`] for others.
+    pub fn default_config() -> Self {
+        Self::alphabetical()
+    }
+
+    /// Creates the default configuration with letters in A-Z order.
+    ///
     /// - A-H: Left directions + right center
     /// - I-P: Left center + right directions
-    /// - Q-Z: Symmetric directional combinations
+    /// - Q-Z: Symmetric and opposite directional combinations
+    pub fn alphabetical() -> Self {
+        Self::with_joystick_mapping(
+            letter_mapping(&ALPHABETICAL_LETTERS),
+            LayoutPreset::Alphabetical,
+        )
+    }
+
+    /// Creates the default configuration with frequency-ordered letters.
+    ///
+    /// E, T, A, O, I, N, S, H sit on the left stick alone, so most text needs
+    /// only one stick at a time.
+    pub fn frequency_optimized() -> Self {
+        Self::with_joystick_mapping(
+            letter_mapping(&FREQUENCY_LETTERS),
+            LayoutPreset::FrequencyOptimized,
+        )
+    }
+
+    /// Creates the default configuration with a user-defined letter mapping.
+    pub fn custom(joystick_mapping: HashMap<(Region, Region), (Key, String, String)>) -> Self {
+        Self::with_joystick_mapping(joystick_mapping, LayoutPreset::Custom)
+    }
+
+    /// Creates the default configuration for a preset.
     ///
-    /// This layout prioritizes learnability over frequency optimization.
-    /// Future versions could implement QWERTY-based or frequency-optimized layouts.
+    /// `Custom` starts from the alphabetical mapping, ready to be edited.
+    pub fn from_preset(preset: LayoutPreset) -> Self {
+        match preset {
+            LayoutPreset::Alphabetical => Self::alphabetical(),
+            LayoutPreset::FrequencyOptimized => Self::frequency_optimized(),
+            LayoutPreset::Custom => Self::custom(letter_mapping(&ALPHABETICAL_LETTERS)),
+        }
+    }
+
+    /// Switches the alphabet layout, keeping button and modifier mappings.
     ///
-    /// ## Performance Notes
-    /// HashMap creation happens once during initialization. The large number
-    /// of map_insert! calls is compile-time overhead only.
-    pub fn default_config() -> Self {
+    /// Switching to `Custom` keeps the current letter mapping so it can be
+    /// edited from there.
+    pub fn set_layout(&mut self, preset: LayoutPreset) {
+        match preset {
+            LayoutPreset::Alphabetical => {
+                self.joystick_mapping = letter_mapping(&ALPHABETICAL_LETTERS)
+            }
+            LayoutPreset::FrequencyOptimized => {
+                self.joystick_mapping = letter_mapping(&FREQUENCY_LETTERS)
+            }
+            LayoutPreset::Custom => {}
+        }
+        self.layout = preset;
+    }
+
+    /// Returns the active alphabet layout.
+    pub fn layout(&self) -> LayoutPreset {
+        self.layout
+    }
+
+    /// Returns whether the symbol trigger is pulled far enough for symbol mode.
+    pub fn is_symbol_mode(&self, controller_state: &ControllerOutput) -> bool {
+        let trigger = match self.symbol_trigger {
+            Some(TriggerType::Left) => &controller_state.left_trigger,
+            Some(TriggerType::Right) => &controller_state.right_trigger,
+            None => return false,
+        };
+        trigger.value > SYMBOL_TRIGGER_THRESHOLD
+    }
+
+    /// Builds the joystick regions for this config's hysteresis and center radius.
+    pub fn regions(&self) -> RegionSet {
+        RegionSet::new(self.hysteresis, self.center_radius)
+    }
+
+    /// Returns the (uppercase) text a section combination produces, if mapped.
+    pub fn label_for(&self, left: Section, right: Section, symbol_mode: bool) -> Option<&str> {
+        self.table(symbol_mode)
+            .get(&(Region::for_section(left), Region::for_section(right)))
+            .map(|(_, upper, _)| upper.as_str())
+    }
+
+    /// Letter or symbol table, depending on the mode.
+    fn table(&self, symbol_mode: bool) -> &HashMap<(Region, Region), (Key, String, String)> {
+        if symbol_mode {
+            &self.symbol_mapping
+        } else {
+            &self.joystick_mapping
+        }
+    }
+
+    /// Returns what `button` is currently mapped to.
+    pub fn assignment(&self, button: &ButtonType) -> ButtonAssignment {
+        if let Some(action) = self.navigation_mapping.get(button) {
+            ButtonAssignment::Navigation(*action)
+        } else if let Some(modifiers) = self.modifier_mapping.get(button) {
+            ButtonAssignment::Modifier(*modifiers)
+        } else if let Some(key) = self.button_mapping.get(button) {
+            ButtonAssignment::Key(*key)
+        } else {
+            ButtonAssignment::Unmapped
+        }
+    }
+
+    /// Maps `button` to `assignment`, replacing its previous key, modifier or
+    /// navigation action.
+    ///
+    /// Other buttons are left untouched; use [`Self::conflicting_button`] to
+    /// check for duplicates first.
+    pub fn assign(&mut self, button: ButtonType, assignment: ButtonAssignment) {
+        self.button_mapping.remove(&button);
+        self.modifier_mapping.remove(&button);
+        self.navigation_mapping.remove(&button);
+        match assignment {
+            ButtonAssignment::Unmapped => {}
+            ButtonAssignment::Key(key) => {
+                self.button_mapping.insert(button, key);
+            }
+            ButtonAssignment::Modifier(modifiers) => {
+                self.modifier_mapping.insert(button, modifiers);
+            }
+            ButtonAssignment::Navigation(action) => {
+                self.navigation_mapping.insert(button, action);
+            }
+        }
+    }
+
+    /// Returns another button already mapped to `assignment`, if any.
+    pub fn conflicting_button(
+        &self,
+        button: &ButtonType,
+        assignment: ButtonAssignment,
+    ) -> Option<ButtonType> {
+        if assignment == ButtonAssignment::Unmapped {
+            return None;
+        }
+        ButtonType::ALL
+            .into_iter()
+            .find(|other| other != button && self.assignment(other) == assignment)
+    }
+
+    /// Restores the default button, modifier and navigation mapping.
+    ///
+    /// Letter layout and region tuning are kept.
+    pub fn reset_button_mapping(&mut self) {
+        let defaults = Self::default_config();
+        self.button_mapping = defaults.button_mapping;
+        self.modifier_mapping = defaults.modifier_mapping;
+        self.navigation_mapping = defaults.navigation_mapping;
+    }
+
+    /// Default button and modifier layout around the given letter mapping.
+    fn with_joystick_mapping(
+        joystick_mapping: HashMap<(Region, Region), (Key, String, String)>,
+        layout: LayoutPreset,
+    ) -> Self {
         let mut button_mapping = HashMap::new();
         button_mapping.insert(ButtonType::A, Key::Space);
         button_mapping.insert(ButtonType::B, Key::Enter);
@@ -388,202 +1105,49 @@ impl KeyboardConfig {
         modifier_mapping.insert(ButtonType::Select, Modifiers::ALT);
         modifier_mapping.insert(ButtonType::Start, Modifiers::COMMAND);
 
-        let mut joystick_mapping = HashMap::new();
-
-        // Alphabet mapping: A-I with left joystick + right center
-        map_insert!(
-            joystick_mapping,
-            (REGION_NORTH, REGION_CENTER),
-            Key::A,
-            "A",
-            "a"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_NORTHEAST, REGION_CENTER),
-            Key::B,
-            "B",
-            "b"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_EAST, REGION_CENTER),
-            Key::C,
-            "C",
-            "c"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_SOUTHEAST, REGION_CENTER),
-            Key::D,
-            "D",
-            "d"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_SOUTH, REGION_CENTER),
-            Key::E,
-            "E",
-            "e"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_SOUTHWEST, REGION_CENTER),
-            Key::F,
-            "F",
-            "f"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_WEST, REGION_CENTER),
-            Key::G,
-            "G",
-            "g"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_NORTHWEST, REGION_CENTER),
-            Key::H,
-            "H",
-            "h"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_NORTH),
-            Key::I,
-            "I",
-            "i"
-        );
-
-        // J-P with left center + right joystick directions
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_NORTHEAST),
-            Key::J,
-            "J",
-            "j"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_EAST),
-            Key::K,
-            "K",
-            "k"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_SOUTHEAST),
-            Key::L,
-            "L",
-            "l"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_SOUTH),
-            Key::M,
-            "M",
-            "m"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_SOUTHWEST),
-            Key::N,
-            "N",
-            "n"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_WEST),
-            Key::O,
-            "O",
-            "o"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_CENTER, REGION_NORTHWEST),
-            Key::P,
-            "P",
-            "p"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_NORTH, REGION_NORTH),
-            Key::Q,
-            "Q",
-            "q"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_NORTHEAST, REGION_NORTHEAST),
-            Key::R,
-            "R",
-            "r"
-        );
-
-        // S-Z with symmetric directional combinations
-        map_insert!(
-            joystick_mapping,
-            (REGION_EAST, REGION_EAST),
-            Key::S,
-            "S",
-            "s"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_SOUTHEAST, REGION_SOUTHEAST),
-            Key::T,
-            "T",
-            "t"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_SOUTH, REGION_SOUTH),
-            Key::U,
-            "U",
-            "u"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_SOUTHWEST, REGION_SOUTHWEST),
-            Key::V,
-            "V",
-            "v"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_WEST, REGION_WEST),
-            Key::W,
-            "W",
-            "w"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_NORTHWEST, REGION_NORTHWEST),
-            Key::X,
-            "X",
-            "x"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_NORTH, REGION_SOUTH),
-            Key::Y,
-            "Y",
-            "y"
-        );
-        map_insert!(
-            joystick_mapping,
-            (REGION_SOUTH, REGION_NORTH),
-            Key::Z,
-            "Z",
-            "z"
-        );
+        let mut navigation_mapping = HashMap::new();
+        navigation_mapping.insert(ButtonType::Guide, NavigationAction::NextMenu);
 
         KeyboardConfig {
             button_mapping,
             joystick_mapping,
+            symbol_mapping: symbol_mapping(),
+            symbol_trigger: default_symbol_trigger(),
+            hysteresis: REGION_HYSTERESIS,
+            center_radius: DEFAULT_CENTER_RADIUS,
             modifier_mapping,
+            chord_mapping: HashMap::new(),
+            navigation_mapping,
             name: "Default Keyboard Configuration".to_string(),
+            layout,
+            rate_limit_ms: DEFAULT_RATE_LIMIT_MS,
+            rumble_on_letter: false,
+            repeat_delay_ms: DEFAULT_REPEAT_DELAY_MS,
+            repeat_interval_ms: DEFAULT_REPEAT_INTERVAL_MS,
+            dwell_ms: None,
+            input_mode: InputMode::Keyboard,
+            cursor_speed: DEFAULT_CURSOR_SPEED,
+            rumble: None,
+        }
+    }
+
+    /// Number of key repeats due after a button was held for `held_ms`.
+    ///
+    /// None before `repeat_delay_ms`, then one more every
+    /// `repeat_interval_ms`. Always none with an interval of 0.
+    pub fn repeats_due(&self, held_ms: f64) -> u32 {
+        let delay = self.repeat_delay_ms as f64;
+        if self.repeat_interval_ms == 0 || held_ms < delay {
+            return 0;
         }
+        ((held_ms - delay) / self.repeat_interval_ms as f64) as u32 + 1
+    }
+
+    /// Sets the rumble handle used for letter feedback.
+    ///
+    /// Only has an effect when `rumble_on_letter` is enabled.
+    pub fn set_rumble(&mut self, rumble: Option<RumbleHandle>) {
+        self.rumble = rumble;
     }
 }
 
@@ -594,6 +1158,40 @@ impl crate::mapping::MappingConfig for KeyboardConfig {
                 "Button mapping cannot be empty".to_string(),
             ));
         }
+        if let Some(button) = self.navigation_mapping.keys().find(|button| {
+            self.button_mapping.contains_key(button) || self.modifier_mapping.contains_key(button)
+        }) {
+            return Err(MappingError::ConfigError(format!(
+                "{:?} is mapped to menu navigation and a key or modifier",
+                button
+            )));
+        }
+        if !HYSTERESIS_RANGE.contains(&self.hysteresis) {
+            return Err(MappingError::ConfigError(format!(
+                "Hysteresis {} outside of [{}, {})",
+                self.hysteresis, HYSTERESIS_RANGE.start, HYSTERESIS_RANGE.end
+            )));
+        }
+        if self.center_radius <= CENTER_RADIUS_RANGE.start
+            || self.center_radius >= CENTER_RADIUS_RANGE.end
+        {
+            return Err(MappingError::ConfigError(format!(
+                "Center radius {} outside of ({}, {})",
+                self.center_radius, CENTER_RADIUS_RANGE.start, CENTER_RADIUS_RANGE.end
+            )));
+        }
+        if !CURSOR_SPEED_RANGE.contains(&self.cursor_speed) {
+            return Err(MappingError::ConfigError(format!(
+                "Cursor speed {} outside of [{}, {}]",
+                self.cursor_speed,
+                CURSOR_SPEED_RANGE.start(),
+                CURSOR_SPEED_RANGE.end()
+            )));
+        }
+        RateLimiter::validate_interval(self.rate_limit_ms)?;
+        for warning in self.alphabet_warnings() {
+            warn!("{}", warning);
+        }
         Ok(())
     }
 
@@ -608,6 +1206,292 @@ impl crate::mapping::MappingConfig for KeyboardConfig {
     fn get_name(&self) -> String {
         self.name.clone()
     }
+
+    fn rate_limit_ms(&self) -> Option<u64> {
+        Some(self.rate_limit_ms)
+    }
+}
+
+/// File extension of standalone keyboard layout files.
+pub const LAYOUT_FILE_EXTENSION: &str = "toml";
+
+/// One joystick slot of a [`KeyboardLayoutFile`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RegionAssignment {
+    /// Section of the left stick
+    pub left: Section,
+    /// Section of the right stick
+    pub right: Section,
+    /// Key event emitted for this slot
+    pub key: Key,
+    /// Text typed with Shift held
+    pub upper: String,
+    /// Text typed without Shift
+    pub lower: String,
+}
+
+/// Standalone, hand-editable form of a [`KeyboardConfig`].
+///
+/// ## Design Rationale
+/// The region tables of [`KeyboardConfig`] are keyed by region pairs, which
+/// TOML cannot express as table keys. The layout file lists them as
+/// `[[letters]]` / `[[symbols]]` entries naming both stick sections instead,
+/// so region assignments can be edited in any text editor. Entries are sorted
+/// by their text for stable diffs.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeyboardLayoutFile {
+    /// Human-readable name of the layout
+    pub name: String,
+    /// Alphabet preset the letters were built from
+    #[serde(default)]
+    pub layout: LayoutPreset,
+    /// Trigger that enables symbol mode
+    #[serde(default = "default_symbol_trigger")]
+    pub symbol_trigger: Option<TriggerType>,
+    /// Region hysteresis factor
+    #[serde(default = "default_hysteresis")]
+    pub hysteresis: f32,
+    /// Center radius of the sticks
+    #[serde(default = "default_center_radius")]
+    pub center_radius: f32,
+    /// Minimum milliseconds between mapped inputs
+    #[serde(default = "default_rate_limit_ms")]
+    pub rate_limit_ms: u64,
+    /// Rumble on every typed letter
+    #[serde(default)]
+    pub rumble_on_letter: bool,
+    /// Hold time before a held key repeats
+    #[serde(default = "default_repeat_delay_ms")]
+    pub repeat_delay_ms: u64,
+    /// Time between key repeats, 0 disables repeat
+    #[serde(default = "default_repeat_interval_ms")]
+    pub repeat_interval_ms: u64,
+    /// Dwell time before a combo emits its letter, unset emits right away
+    #[serde(default)]
+    pub dwell_ms: Option<u64>,
+    /// Cursor speed of the virtual mouse
+    #[serde(default = "default_cursor_speed")]
+    pub cursor_speed: f32,
+    /// Button to key assignments
+    pub buttons: HashMap<ButtonType, Key>,
+    /// Button to modifier assignments
+    #[serde(default)]
+    pub modifiers: HashMap<ButtonType, Modifiers>,
+    /// Chord name to key assignments
+    #[serde(default)]
+    pub chords: HashMap<String, Key>,
+    /// Button to menu navigation assignments
+    #[serde(default)]
+    pub navigation: HashMap<ButtonType, NavigationAction>,
+    /// Letter slots
+    pub letters: Vec<RegionAssignment>,
+    /// Symbol slots, defaults to the built-in symbols when missing
+    #[serde(default)]
+    pub symbols: Vec<RegionAssignment>,
+}
+
+impl From<&KeyboardConfig> for KeyboardLayoutFile {
+    fn from(config: &KeyboardConfig) -> Self {
+        fn entries(
+            table: &HashMap<(Region, Region), (Key, String, String)>,
+        ) -> Vec<RegionAssignment> {
+            let mut entries: Vec<RegionAssignment> = table
+                .iter()
+                .map(|((left, right), (key, upper, lower))| RegionAssignment {
+                    left: left.section,
+                    right: right.section,
+                    key: *key,
+                    upper: upper.clone(),
+                    lower: lower.clone(),
+                })
+                .collect();
+            entries.sort_by(|a, b| a.upper.cmp(&b.upper));
+            entries
+        }
+
+        KeyboardLayoutFile {
+            name: config.name.clone(),
+            layout: config.layout,
+            symbol_trigger: config.symbol_trigger,
+            hysteresis: config.hysteresis,
+            center_radius: config.center_radius,
+            rate_limit_ms: config.rate_limit_ms,
+            rumble_on_letter: config.rumble_on_letter,
+            repeat_delay_ms: config.repeat_delay_ms,
+            repeat_interval_ms: config.repeat_interval_ms,
+            dwell_ms: config.dwell_ms,
+            cursor_speed: config.cursor_speed,
+            buttons: config.button_mapping.clone(),
+            modifiers: config.modifier_mapping.clone(),
+            chords: config.chord_mapping.clone(),
+            navigation: config.navigation_mapping.clone(),
+            letters: entries(&config.joystick_mapping),
+            symbols: entries(&config.symbol_mapping),
+        }
+    }
+}
+
+impl From<KeyboardLayoutFile> for KeyboardConfig {
+    fn from(file: KeyboardLayoutFile) -> Self {
+        fn table(
+            entries: Vec<RegionAssignment>,
+        ) -> HashMap<(Region, Region), (Key, String, String)> {
+            entries
+                .into_iter()
+                .map(|entry| {
+                    (
+                        (
+                            Region::for_section(entry.left),
+                            Region::for_section(entry.right),
+                        ),
+                        (entry.key, entry.upper, entry.lower),
+                    )
+                })
+                .collect()
+        }
+
+        let symbol_mapping = if file.symbols.is_empty() {
+            symbol_mapping()
+        } else {
+            table(file.symbols)
+        };
+
+        KeyboardConfig {
+            button_mapping: file.buttons,
+            joystick_mapping: table(file.letters),
+            symbol_mapping,
+            symbol_trigger: file.symbol_trigger,
+            hysteresis: file.hysteresis,
+            center_radius: file.center_radius,
+            modifier_mapping: file.modifiers,
+            chord_mapping: file.chords,
+            navigation_mapping: file.navigation,
+            name: file.name,
+            layout: file.layout,
+            rate_limit_ms: file.rate_limit_ms,
+            rumble_on_letter: file.rumble_on_letter,
+            repeat_delay_ms: file.repeat_delay_ms,
+            repeat_interval_ms: file.repeat_interval_ms,
+            dwell_ms: file.dwell_ms,
+            input_mode: InputMode::Keyboard,
+            cursor_speed: file.cursor_speed,
+            rumble: None,
+        }
+    }
+}
+
+impl KeyboardLayoutFile {
+    /// Checks that no stick combination is assigned twice.
+    ///
+    /// Regions compare by section only, so a second entry for the same pair
+    /// of sections would silently replace the first when the file becomes a
+    /// [`KeyboardConfig`].
+    ///
+    /// # Errors
+    /// Returns [`MappingError::ConfigError`] listing every repeated
+    /// combination with the texts assigned to it.
+    pub fn check_duplicate_slots(&self) -> Result<(), MappingError> {
+        for (kind, entries) in [("Letter", &self.letters), ("Symbol", &self.symbols)] {
+            let mut seen: HashMap<(Section, Section), &str> = HashMap::new();
+            let duplicates: Vec<String> = entries
+                .iter()
+                .filter_map(|entry| {
+                    seen.insert((entry.left, entry.right), &entry.upper)
+                        .map(|first| {
+                            format!(
+                                "{:?}/{:?} ({} and {})",
+                                entry.left, entry.right, first, entry.upper
+                            )
+                        })
+                })
+                .collect();
+            if !duplicates.is_empty() {
+                return Err(MappingError::ConfigError(format!(
+                    "{} slots assigned more than once: {}",
+                    kind,
+                    duplicates.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl KeyboardConfig {
+    /// Letters without a stick combination or with more than one.
+    ///
+    /// Neither stops the layout from typing, so they are logged as warnings
+    /// by [`MappingConfig::validate`] instead of failing it. Only the 26
+    /// letters are checked: they use 26 of the 81 combinations, the others
+    /// are free by design.
+    ///
+    /// [`MappingConfig::validate`]: crate::mapping::MappingConfig::validate
+    fn alphabet_warnings(&self) -> Vec<String> {
+        let mut combos: HashMap<&str, Vec<(Section, Section)>> = HashMap::new();
+        for ((left, right), (_, upper, _)) in &self.joystick_mapping {
+            combos
+                .entry(upper.as_str())
+                .or_default()
+                .push((left.section, right.section));
+        }
+
+        let mut warnings = Vec::new();
+        let missing: Vec<String> = ('A'..='Z')
+            .map(String::from)
+            .filter(|letter| !combos.contains_key(letter.as_str()))
+            .collect();
+        if !missing.is_empty() {
+            warnings.push(format!(
+                "Keyboard layout {} has no stick combination for {}",
+                self.name,
+                missing.join(", ")
+            ));
+        }
+        for (text, slots) in combos.iter().filter(|(_, slots)| slots.len() > 1) {
+            warnings.push(format!(
+                "Keyboard layout {} types {} on several stick combinations: {:?}",
+                self.name, text, slots
+            ));
+        }
+        warnings
+    }
+
+    /// Writes this config as a standalone layout file.
+    ///
+    /// # Errors
+    /// Returns [`MappingError::ConfigError`] if the layout cannot be
+    /// serialized or the file cannot be written.
+    pub fn export_layout(&self, path: &std::path::Path) -> Result<(), MappingError> {
+        let content = toml::to_string_pretty(&KeyboardLayoutFile::from(self))
+            .map_err(|e| MappingError::ConfigError(format!("Failed to serialize layout: {}", e)))?;
+        std::fs::write(path, content).map_err(|e| {
+            MappingError::ConfigError(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+
+    /// Reads and validates a standalone layout file.
+    ///
+    /// # Errors
+    /// Returns [`MappingError::ConfigError`] if the file cannot be read or
+    /// parsed, assigns a stick combination twice, or the resulting config
+    /// fails [`MappingConfig::validate`], e.g. because it has no button
+    /// mapping.
+    ///
+    /// [`MappingConfig::validate`]: crate::mapping::MappingConfig::validate
+    pub fn import_layout(path: &std::path::Path) -> Result<Self, MappingError> {
+        use crate::mapping::MappingConfig;
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            MappingError::ConfigError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let file: KeyboardLayoutFile = toml::from_str(&content)
+            .map_err(|e| MappingError::ConfigError(format!("Invalid layout file: {}", e)))?;
+        file.check_duplicate_slots()?;
+
+        let config = KeyboardConfig::from(file);
+        config.validate()?;
+        Ok(config)
+    }
 }
 
 /// Core implementation of gamepad-to-keyboard event conversion.
@@ -624,15 +1508,144 @@ impl crate::mapping::MappingConfig for KeyboardConfig {
 pub struct KeyboardStrategy {
     config: KeyboardConfig,
     context: MappingContext,
+    /// Regions built from the config's hysteresis and center radius
+    regions: RegionSet,
+    /// Latest input, to complete a dwell or keep the cursor moving while
+    /// the sticks rest
+    last_input: Option<ControllerOutput>,
+    /// When the cursor last moved, `None` while the right stick is centered
+    last_cursor_step: Option<Instant>,
+    /// Whether the click button is down in mouse mode
+    click_held: bool,
+    /// Pad the fields above belong to
+    gamepad_id: usize,
+    /// State of every other pad, swapped in by [`KeyboardStrategy::select_pad`]
+    other_pads: HashMap<usize, PadState>,
+}
+
+/// Per-pad state of a [`KeyboardStrategy`] while another pad is selected
+#[derive(Default)]
+struct PadState {
+    context: MappingContext,
+    last_input: Option<ControllerOutput>,
+    last_cursor_step: Option<Instant>,
+    click_held: bool,
 }
 
 impl KeyboardStrategy {
     /// Creates a new keyboard mapping strategy with the given configuration.
     pub fn new(config: KeyboardConfig) -> Self {
         Self {
+            regions: config.regions(),
             config,
             context: MappingContext::default(),
+            last_input: None,
+            last_cursor_step: None,
+            click_held: false,
+            gamepad_id: 0,
+            other_pads: HashMap::new(),
+        }
+    }
+
+    /// Makes the state of `gamepad_id` the current one.
+    ///
+    /// The state of the previously selected pad is parked until that pad is
+    /// selected again; a pad seen for the first time starts fresh.
+    fn select_pad(&mut self, gamepad_id: usize) {
+        if gamepad_id == self.gamepad_id {
+            return;
+        }
+        let selected = self.other_pads.remove(&gamepad_id).unwrap_or_default();
+        let parked = PadState {
+            context: std::mem::replace(&mut self.context, selected.context),
+            last_input: std::mem::replace(&mut self.last_input, selected.last_input),
+            last_cursor_step: std::mem::replace(
+                &mut self.last_cursor_step,
+                selected.last_cursor_step,
+            ),
+            click_held: std::mem::replace(&mut self.click_held, selected.click_held),
+        };
+        self.other_pads.insert(self.gamepad_id, parked);
+        self.gamepad_id = gamepad_id;
+    }
+
+    /// Moves the virtual mouse by the right stick deflection.
+    ///
+    /// Deflection inside the center radius is ignored, beyond it the speed
+    /// rises quadratically up to [`KeyboardConfig::cursor_speed`] for precise
+    /// small moves. The step covers the time since the previous one, so the
+    /// speed does not depend on the rate limit.
+    fn map_cursor(&mut self, controller_state: &ControllerOutput) -> Vec<Event> {
+        let (x, y) = (
+            controller_state.right_stick.x,
+            controller_state.right_stick.y,
+        );
+        let magnitude = x.hypot(y);
+        let radius = self.config.center_radius;
+        if magnitude <= radius {
+            self.last_cursor_step = None;
+            return Vec::new();
+        }
+
+        let now = Instant::now();
+        let elapsed = self
+            .last_cursor_step
+            .map_or(Duration::from_millis(self.config.rate_limit_ms), |last| {
+                now.duration_since(last)
+            })
+            .min(MAX_CURSOR_STEP);
+        self.last_cursor_step = Some(now);
+
+        let response = ((magnitude - radius) / (1.0 - radius)).min(1.0).powi(2);
+        let distance = response * self.config.cursor_speed * elapsed.as_secs_f32();
+        // Stick up is positive, screen up is negative
+        let step = egui::vec2(x, -y) / magnitude * distance;
+        vec![Event::MouseMoved(step)]
+    }
+
+    /// Turns [`MOUSE_CLICK_BUTTON`] into primary pointer button events.
+    ///
+    /// Takes the button out of `input`, so it does not type its key as well.
+    /// The position is left for the UI to fill in.
+    fn map_click(&mut self, input: &mut ControllerOutput) -> Vec<Event> {
+        let modifiers = self.map_modifiers(&input.button_events);
+        let click = |pressed| Event::PointerButton {
+            pos: egui::Pos2::ZERO,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers,
+        };
+
+        let mut events = Vec::new();
+        let mut reported = false;
+        input.button_events.retain(|event| {
+            if event.button != MOUSE_CLICK_BUTTON {
+                return true;
+            }
+            reported = true;
+            match event.state {
+                crate::controller::controller_handle::ButtonEventState::Held => {
+                    if !self.click_held {
+                        events.push(click(true));
+                        self.click_held = true;
+                    }
+                }
+                crate::controller::controller_handle::ButtonEventState::Complete => {
+                    if !self.click_held {
+                        events.push(click(true));
+                    }
+                    events.push(click(false));
+                    self.click_held = false;
+                }
+            }
+            false
+        });
+
+        if self.click_held && !reported {
+            events.push(click(false));
+            self.click_held = false;
         }
+        events
     }
 
     /// Converts joystick positions to keyboard events using region-based mapping.
@@ -640,8 +1653,10 @@ impl KeyboardStrategy {
     /// ## Algorithm Overview
     /// 1. Get current joystick positions from controller state
     /// 2. Convert positions to regions using hysteresis-aware detection
-    /// 3. Look up letter mapping for (left_region, right_region) combination
-    /// 4. Generate Key events and Text events with appropriate modifiers
+    /// 3. Look up the (left_region, right_region) combination in the letter or,
+    ///    with `symbol_mode`, the symbol table
+    /// 4. Generate Key events and Text events with appropriate modifiers,
+    ///    in dwell mode only once the dwell for the combo has elapsed
     /// 5. Update context for next frame's hysteresis calculation
     ///
     /// ## Event Generation
@@ -658,7 +1673,11 @@ impl KeyboardStrategy {
     /// # Returns
     /// Vector of egui Events ready for injection into the UI event stream.
     /// Empty vector if no mapping exists for current joystick combination.
-    fn map_joystick(&mut self, controller_state: &ControllerOutput) -> Vec<Event> {
+    fn map_joystick(
+        &mut self,
+        controller_state: &ControllerOutput,
+        symbol_mode: bool,
+    ) -> Vec<Event> {
         let (prev_left_section, prev_right_section) = self.context.last_sections;
 
         let left_x = controller_state.left_stick.x;
@@ -666,17 +1685,29 @@ impl KeyboardStrategy {
         let right_x = controller_state.right_stick.x;
         let right_y = controller_state.right_stick.y;
 
-        let left_region =
-            Region::region_from_pos(left_x, left_y, Some(prev_left_section)).unwrap_or_default();
-        let right_region =
-            Region::region_from_pos(right_x, right_y, Some(prev_right_section)).unwrap_or_default();
+        let left_region = self
+            .regions
+            .region_from_pos(left_x, left_y, Some(prev_left_section));
+        let right_region = self
+            .regions
+            .region_from_pos(right_x, right_y, Some(prev_right_section));
+        if (left_region.section, right_region.section) != self.context.last_sections {
+            trace!(
+                "New regions: {:?} / {:?}",
+                left_region.section,
+                right_region.section
+            );
+        }
 
         // Update context for next frame's hysteresis
         self.context.last_sections = (left_region.section, right_region.section);
+        if !self.dwell_elapsed(self.context.last_sections, symbol_mode) {
+            return Vec::new();
+        }
 
         let map = self
             .config
-            .joystick_mapping
+            .table(symbol_mode)
             .get(&(left_region, right_region));
         let modifier = self.map_modifiers(&controller_state.button_events);
 
@@ -707,11 +1738,45 @@ impl KeyboardStrategy {
         }
 
         if !events.is_empty() {
-            info!("Joysticks successfully mapped: {:?}", events);
+            trace!("Joysticks successfully mapped: {:?}", events);
+
+            // Tactile confirmation that a letter was emitted
+            if self.config.rumble_on_letter {
+                if let Some(rumble) = &self.config.rumble {
+                    rumble.rumble_pattern_on(controller_state.gamepad_id, RumblePattern::Short);
+                }
+            }
         }
         events
     }
 
+    /// Returns whether the current combo may emit its letter.
+    ///
+    /// Always true without [`KeyboardConfig::dwell_ms`]. With it, true exactly
+    /// once per entry into a combo, after the sticks stayed there for the
+    /// dwell time. Switching symbol mode counts as entering a new combo.
+    fn dwell_elapsed(&mut self, sections: (Section, Section), symbol_mode: bool) -> bool {
+        let Some(dwell_ms) = self.config.dwell_ms else {
+            return true;
+        };
+
+        let now = Instant::now();
+        let dwell = match &mut self.context.dwell {
+            Some(dwell) if dwell.sections == sections && dwell.symbol_mode == symbol_mode => dwell,
+            slot => slot.insert(Dwell {
+                sections,
+                symbol_mode,
+                since: now,
+                fired: false,
+            }),
+        };
+        if dwell.fired || now.duration_since(dwell.since) < Duration::from_millis(dwell_ms) {
+            return false;
+        }
+        dwell.fired = true;
+        true
+    }
+
     /// Converts button events to modifier flags for use with other mappings.
     ///
     /// Scans active button events for modifier buttons (Shift, Ctrl, Alt, etc.)
@@ -738,14 +1803,17 @@ impl KeyboardStrategy {
     /// 4. Handle both held and completed button states appropriately
     ///
     /// ## Event Generation Logic
-    /// - Held buttons: Generate press events continuously (for key repeat)
-    /// - Completed buttons: Generate single press event (for one-shot actions)
+    /// - Newly held buttons: Generate a key down
+    /// - Still held buttons: Generate key repeats once the repeat delay has
+    ///   passed, at the configured repeat interval
+    /// - Released buttons: Generate a key up, preceded by a key down for
+    ///   presses that were never reported as held
     /// - Special keys: Generate both key events and corresponding text
     ///
     /// ## Modifier Button Separation
     /// Modifier buttons are extracted for modifier state calculation, then
     /// filtered out from regular button processing to prevent duplicate events.
-    /// Uses pattern matching for clean, readable filtering logic.
+    /// Which buttons act as modifiers is taken from `modifier_mapping`.
     fn map_buttons(
         &mut self,
         button_events: &[crate::controller::controller_handle::ButtonEvent],
@@ -758,76 +1826,156 @@ impl KeyboardStrategy {
         // Extract modifier buttons for modifier state calculation
         let raw_modifiers: Vec<crate::controller::controller_handle::ButtonEvent> = button_events
             .iter()
-            .filter(|&x| {
-                x.button.eq(&ButtonType::LeftBumper)
-                    || x.button.eq(&ButtonType::RightBumper)
-                    || x.button.eq(&ButtonType::Start)
-                    || x.button.eq(&ButtonType::Select)
-            })
+            .filter(|&x| self.config.modifier_mapping.contains_key(&x.button))
             .cloned()
             .collect();
 
         let modifier = self.map_modifiers(raw_modifiers.as_slice());
 
         // Filter out modifier buttons from regular processing
-        button_events.retain(|x| {
-            !matches!(
-                x.button,
-                ButtonType::LeftBumper
-                    | ButtonType::RightBumper
-                    | ButtonType::Start
-                    | ButtonType::Select
-            )
-        });
+        button_events.retain(|x| !self.config.modifier_mapping.contains_key(&x.button));
 
+        let mut reported = HashSet::new();
         for button_event in button_events {
-            if let Some(key) = self.config.button_mapping.get(&button_event.button) {
-                match button_event.state {
-                    crate::controller::controller_handle::ButtonEventState::Held => {
-                        events.push(Event::Key {
-                            key: *key,
-                            physical_key: None,
-                            pressed: true,
-                            repeat: false,
-                            modifiers: modifier,
-                        });
-
-                        // Generate text for special keys
-                        match key {
-                            Key::Enter => events.push(Event::Text("\n".to_string())),
-                            Key::Tab => events.push(Event::Text("\t".to_string())),
-                            Key::Space => events.push(Event::Text(" ".to_string())),
-                            _ => {}
-                        };
+            let Some(key) = self
+                .config
+                .button_mapping
+                .get(&button_event.button)
+                .copied()
+            else {
+                continue;
+            };
+            reported.insert(button_event.button.clone());
+
+            match button_event.state {
+                crate::controller::controller_handle::ButtonEventState::Held => {
+                    match self.context.held_buttons.get_mut(&button_event.button) {
+                        None => {
+                            events.extend(key_press(key, modifier, false));
+                            self.context.held_buttons.insert(
+                                button_event.button.clone(),
+                                HeldButton {
+                                    duration_ms: button_event.duration_ms,
+                                    repeats: 0,
+                                },
+                            );
+                        }
+                        Some(held) => {
+                            held.duration_ms = button_event.duration_ms;
+                            let due = self.config.repeats_due(held.duration_ms);
+                            let pending = due.saturating_sub(held.repeats);
+                            held.repeats = held.repeats.max(due);
+                            for _ in 0..pending.min(MAX_REPEATS_PER_CYCLE) {
+                                events.extend(key_press(key, modifier, true));
+                            }
+                        }
+                    }
+                }
+                crate::controller::controller_handle::ButtonEventState::Complete => {
+                    // A press shorter than one cycle was never reported as held
+                    if self
+                        .context
+                        .held_buttons
+                        .remove(&button_event.button)
+                        .is_none()
+                    {
+                        events.extend(key_press(key, modifier, false));
+                    }
+                    events.push(key_release(key, modifier));
+                }
+            };
+
+            // Update context state tracking
+            self.context
+                .last_button_states
+                .insert(button_event.button.clone(), button_event.state);
+        }
+
+        // Held buttons that stopped being reported without a release, e.g.
+        // because they became part of a chord, are released as well
+        let vanished: Vec<ButtonType> = self
+            .context
+            .held_buttons
+            .keys()
+            .filter(|button| !reported.contains(*button))
+            .cloned()
+            .collect();
+        for button in vanished {
+            self.context.held_buttons.remove(&button);
+            if let Some(key) = self.config.button_mapping.get(&button) {
+                events.push(key_release(*key, modifier));
+            }
+        }
+
+        if !events.is_empty() {
+            trace!("Buttons successfully mapped: {:?}", events);
+        }
+        events
+    }
+
+    /// Idle output of the selected pad, see [`MappingStrategy::map_idle`].
+    fn map_idle_pad(&mut self) -> Vec<Event> {
+        let Some(input) = self.last_input.clone() else {
+            return Vec::new();
+        };
+        if self.config.input_mode == InputMode::Mouse {
+            return self.map_cursor(&input);
+        }
+        if self.context.dwell.is_none_or(|dwell| dwell.fired) {
+            return Vec::new();
+        }
+        let symbol_mode = self.config.is_symbol_mode(&input);
+        self.map_joystick(&input, symbol_mode)
+    }
+
+    /// Converts chord events to key presses.
+    ///
+    /// Chords are keyed by name in `chord_mapping`; unmapped chords produce
+    /// nothing. Modifiers come from the buttons held alongside the chord.
+    /// Like buttons, a chord sends a key down when it starts and a key up
+    /// when it is released or stops being reported.
+    fn map_chords(&mut self, input: &ControllerOutput) -> Vec<egui::Event> {
+        let modifier = self.map_modifiers(&input.button_events);
+        let mut events = Vec::new();
+        let mut reported = HashSet::new();
+
+        for chord in &input.chord_events {
+            let Some(key) = self.config.chord_mapping.get(&chord.name).copied() else {
+                continue;
+            };
+            reported.insert(chord.name.clone());
+
+            match chord.state {
+                crate::controller::controller_handle::ButtonEventState::Held => {
+                    if self.context.held_chords.insert(chord.name.clone()) {
+                        events.extend(key_press(key, modifier, false));
                     }
-                    crate::controller::controller_handle::ButtonEventState::Complete => {
-                        events.push(Event::Key {
-                            key: *key,
-                            physical_key: None,
-                            pressed: true,
-                            repeat: false,
-                            modifiers: modifier,
-                        });
-
-                        // Generate text for special keys
-                        match key {
-                            Key::Enter => events.push(Event::Text("\n".to_string())),
-                            Key::Tab => events.push(Event::Text("\t".to_string())),
-                            Key::Space => events.push(Event::Text(" ".to_string())),
-                            _ => {}
-                        };
+                }
+                crate::controller::controller_handle::ButtonEventState::Complete => {
+                    if !self.context.held_chords.remove(&chord.name) {
+                        events.extend(key_press(key, modifier, false));
                     }
-                };
+                    events.push(key_release(key, modifier));
+                }
+            }
+        }
 
-                // Update context state tracking
-                self.context
-                    .last_button_states
-                    .insert(button_event.button.clone(), button_event.state);
+        let vanished: Vec<String> = self
+            .context
+            .held_chords
+            .iter()
+            .filter(|name| !reported.contains(*name))
+            .cloned()
+            .collect();
+        for name in vanished {
+            self.context.held_chords.remove(&name);
+            if let Some(key) = self.config.chord_mapping.get(&name) {
+                events.push(key_release(*key, modifier));
             }
         }
 
         if !events.is_empty() {
-            info!("Buttons successfully mapped: {:?}", events);
+            trace!("Chords successfully mapped: {:?}", events);
         }
         events
     }
@@ -838,23 +1986,64 @@ impl MappingStrategy for KeyboardStrategy {
     ///
     /// Combines button and joystick mapping results into a single event collection.
     /// The order of processing (buttons first, then joysticks) ensures modifier
-    /// state is correctly applied to joystick-generated events.
+    /// state is correctly applied to joystick-generated events. The symbol
+    /// trigger selects whether joysticks produce letters or symbols. In
+    /// [`InputMode::Mouse`] the click button and the right stick drive the
+    /// virtual mouse instead.
     ///
     /// # Returns
     /// `Some(MappedEvent::KeyboardEvent)` if any events were generated,
     /// `None` if no input mappings were active this frame.
     fn map(&mut self, input: &ControllerOutput) -> Option<MappedEvent> {
+        self.select_pad(input.gamepad_id);
         let mut events = Vec::new();
 
-        // Process button events first to establish modifier state
-        events.extend(self.map_buttons(&input.button_events));
-        events.extend(self.map_joystick(input));
+        if self.config.input_mode == InputMode::Mouse {
+            let mut input = input.clone();
+            events.extend(self.map_click(&mut input));
+            events.extend(self.map_buttons(&input.button_events));
+            events.extend(self.map_chords(&input));
+            events.extend(self.map_cursor(&input));
+            self.last_input = Some(input);
+        } else {
+            // Process button events first to establish modifier state
+            events.extend(self.map_buttons(&input.button_events));
+            events.extend(self.map_chords(input));
+            let symbol_mode = self.config.is_symbol_mode(input);
+            events.extend(self.map_joystick(input, symbol_mode));
+            if self.config.dwell_ms.is_some() {
+                self.last_input = Some(input.clone());
+            }
+        }
 
         if events.is_empty() {
             None
         } else {
-            Some(MappedEvent::KeyboardEvent { key_code: events })
+            Some(MappedEvent::KeyboardEvent {
+                key_code: events,
+                collected_at: input.collected_at,
+            })
+        }
+    }
+
+    /// Completes a pending dwell while the sticks rest in their combo, or
+    /// keeps the cursor moving while the right stick is held deflected.
+    ///
+    /// Every pad is checked. Buttons are not mapped again, so held keys do
+    /// not repeat here.
+    fn map_idle(&mut self) -> Option<MappedEvent> {
+        let mut pads: Vec<usize> = self.other_pads.keys().copied().collect();
+        pads.push(self.gamepad_id);
+
+        let mut events = Vec::new();
+        for gamepad_id in pads {
+            self.select_pad(gamepad_id);
+            events.extend(self.map_idle_pad());
         }
+        (!events.is_empty()).then_some(MappedEvent::KeyboardEvent {
+            key_code: events,
+            collected_at: None,
+        })
     }
 
     fn initialize(&mut self) -> Result<(), MappingError> {
@@ -872,16 +2061,302 @@ impl MappingStrategy for KeyboardStrategy {
         );
     }
 
-    /// Returns rate limit for keyboard event generation.
-    ///
-    /// Set to ~22Hz (45ms) to balance responsiveness with system load.
-    /// Faster rates don't improve user experience for text input but
-    /// increase CPU usage and event queue pressure.
-    fn get_rate_limit(&self) -> Option<u64> {
-        Some(45)
-    }
-
     fn get_type(&self) -> MappingType {
         MappingType::Keyboard
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::controller_handle::{ButtonEvent, ButtonEventState};
+    use crate::controller::event_processor::ChordEvent;
+    use std::cell::Cell;
+
+    thread_local! {
+        /// Calls of [`Region::to_polar`] on this test thread.
+        pub(super) static POLAR_CONVERSIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Region lookup by testing every direction, as done before the angle buckets.
+    fn scan_regions(set: &RegionSet, x: f32, y: f32, old_section: Option<Section>) -> Region {
+        let previous = set
+            .directions
+            .iter()
+            .find(|region| Some(region.section) == old_section);
+        if let Some(previous) = previous {
+            if previous.contains_outer(x, y) {
+                return previous.clone();
+            }
+        }
+        set.directions
+            .iter()
+            .find(|region| region.contains(x, y, old_section))
+            .unwrap_or(&set.center)
+            .clone()
+    }
+
+    fn pad_input(gamepad_id: usize, held: &[ButtonType]) -> ControllerOutput {
+        ControllerOutput {
+            gamepad_id,
+            button_events: held
+                .iter()
+                .map(|button| ButtonEvent {
+                    button: button.clone(),
+                    duration_ms: 100.0,
+                    state: ButtonEventState::Held,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn key_events(event: Option<MappedEvent>) -> Vec<(Key, bool)> {
+        match event {
+            Some(MappedEvent::KeyboardEvent { key_code, .. }) => key_code
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::Key { key, pressed, .. } => Some((key, pressed)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn second_pad_does_not_release_held_keys() {
+        let mut strategy = KeyboardStrategy::new(KeyboardConfig::default_config());
+
+        let first = key_events(strategy.map(&pad_input(0, &[ButtonType::DPadUp])));
+        assert_eq!(first, vec![(Key::ArrowUp, true)]);
+
+        // Both pads report every cycle, the idle one must not touch pad 0's key
+        for _ in 0..3 {
+            assert!(key_events(strategy.map(&pad_input(1, &[]))).is_empty());
+            assert!(key_events(strategy.map(&pad_input(0, &[ButtonType::DPadUp]))).is_empty());
+        }
+
+        let released = key_events(strategy.map(&pad_input(0, &[])));
+        assert_eq!(released, vec![(Key::ArrowUp, false)]);
+    }
+
+    #[test]
+    fn region_lookup_matches_scan_of_all_regions() {
+        let set = RegionSet::default();
+        let previous = ALL_REGIONS
+            .iter()
+            .map(|region| Some(region.section))
+            .chain([None, Some(Section::Center)]);
+
+        for old_section in previous {
+            for step_x in -20..=20 {
+                for step_y in -20..=20 {
+                    let (x, y) = (step_x as f32 / 20.0, step_y as f32 / 20.0);
+                    assert_eq!(
+                        set.region_from_pos(x, y, old_section).section,
+                        scan_regions(&set, x, y, old_section).section,
+                        "at ({}, {}) coming from {:?}",
+                        x,
+                        y,
+                        old_section
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn region_lookup_converts_to_polar_once() {
+        let set = RegionSet::default();
+
+        for (x, y, old_section) in [
+            (0.0, 0.0, None),
+            (0.0, 1.0, None),
+            (0.7, -0.7, Some(Section::SouthEast)),
+            (-0.9, 0.1, Some(Section::North)),
+        ] {
+            POLAR_CONVERSIONS.with(|count| count.set(0));
+            set.region_from_pos(x, y, old_section);
+            assert_eq!(POLAR_CONVERSIONS.with(Cell::get), 1);
+        }
+    }
+
+    /// Writes `layout` to a layout file under the system temp dir.
+    fn layout_path(name: &str, layout: &KeyboardLayoutFile) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "opencontroller-{}-{}.{}",
+            name,
+            std::process::id(),
+            LAYOUT_FILE_EXTENSION
+        ));
+        std::fs::write(&path, toml::to_string_pretty(layout).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn layout_with_duplicate_combo_is_rejected() {
+        let mut layout = KeyboardLayoutFile::from(&KeyboardConfig::default_config());
+        let mut duplicate = layout.letters[0].clone();
+        duplicate.upper = "#".to_string();
+        duplicate.lower = "#".to_string();
+        layout.letters.push(duplicate);
+        let path = layout_path("layout-duplicate", &layout);
+
+        let result = KeyboardConfig::import_layout(&path);
+
+        match result {
+            Err(MappingError::ConfigError(message)) => {
+                assert!(message.contains("Letter slots assigned more than once"))
+            }
+            other => panic!("expected a duplicate slot error, got {:?}", other.err()),
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn layout_with_gap_loads_with_warning() {
+        let default = KeyboardConfig::default_config();
+        assert!(default.alphabet_warnings().is_empty());
+
+        let mut layout = KeyboardLayoutFile::from(&default);
+        layout.letters.retain(|entry| entry.upper != "Q");
+        let path = layout_path("layout-gap", &layout);
+
+        let config = KeyboardConfig::import_layout(&path).unwrap();
+
+        let warnings = config.alphabet_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].ends_with("has no stick combination for Q"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    fn output(
+        buttons: &[(ButtonType, ButtonEventState)],
+        chords: &[(&str, ButtonEventState)],
+    ) -> ControllerOutput {
+        ControllerOutput {
+            button_events: buttons
+                .iter()
+                .map(|(button, state)| ButtonEvent {
+                    button: button.clone(),
+                    duration_ms: 100.0,
+                    state: state.clone(),
+                })
+                .collect(),
+            chord_events: chords
+                .iter()
+                .map(|(name, state)| ChordEvent {
+                    name: name.to_string(),
+                    buttons: vec![ButtonType::X, ButtonType::Y],
+                    duration_ms: 100.0,
+                    state: state.clone(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn every_key_down_is_matched_by_a_key_up() {
+        use ButtonEventState::{Complete, Held};
+        use ButtonType::{DPadUp, RightBumper, A, B};
+
+        let mut config = KeyboardConfig::default_config();
+        config.chord_mapping.insert("xy".to_string(), Key::F1);
+        config.chord_mapping.insert("tap".to_string(), Key::F2);
+        let mut strategy = KeyboardStrategy::new(config);
+
+        let inputs = [
+            output(&[(A, Held)], &[]),
+            output(&[(A, Held), (RightBumper, Held)], &[]),
+            output(&[(A, Complete), (RightBumper, Held)], &[("xy", Held)]),
+            output(&[(B, Complete)], &[("xy", Held), ("tap", Complete)]),
+            output(&[(DPadUp, Held)], &[("xy", Complete)]),
+            output(&[(DPadUp, Held)], &[("xy", Held)]),
+            // Releases of DPadUp and the chord were missed
+            output(&[], &[]),
+        ];
+
+        let mut down: HashMap<Key, usize> = HashMap::new();
+        for input in &inputs {
+            let Some(MappedEvent::KeyboardEvent { key_code, .. }) = strategy.map(input) else {
+                continue;
+            };
+            for event in key_code {
+                match event {
+                    Event::Key {
+                        key,
+                        pressed: true,
+                        repeat: false,
+                        ..
+                    } => *down.entry(key).or_default() += 1,
+                    Event::Key {
+                        key,
+                        pressed: false,
+                        ..
+                    } => {
+                        let count = down.entry(key).or_default();
+                        assert!(*count > 0, "{:?} released without a key down", key);
+                        *count -= 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for key in [Key::Space, Key::Enter, Key::F1, Key::F2, Key::ArrowUp] {
+            assert!(down.contains_key(&key), "{:?} was never pressed", key);
+        }
+        let stuck: Vec<&Key> = down
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(key, _)| key)
+            .collect();
+        assert!(stuck.is_empty(), "keys left down: {:?}", stuck);
+    }
+
+    fn texts(event: Option<MappedEvent>) -> Vec<String> {
+        match event {
+            Some(MappedEvent::KeyboardEvent { key_code, .. }) => key_code
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::Text(text) => Some(text),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn dwell_fires_once_per_entry_into_a_combo() {
+        let mut config = KeyboardConfig::default_config();
+        config.dwell_ms = Some(20);
+        let mut strategy = KeyboardStrategy::new(config);
+
+        let mut combo = pad_input(0, &[]);
+        combo.left_stick.y = 1.0;
+        combo.right_stick.y = 1.0;
+        let centered = pad_input(0, &[]);
+
+        // Entering the combo starts the dwell without emitting
+        assert!(texts(strategy.map(&combo)).is_empty());
+        assert!(texts(strategy.map_idle()).is_empty());
+
+        std::thread::sleep(Duration::from_millis(30));
+        let letter = texts(strategy.map_idle());
+        assert_eq!(letter.len(), 1);
+
+        // Staying in the combo does not repeat the letter
+        assert!(texts(strategy.map_idle()).is_empty());
+        assert!(texts(strategy.map(&combo)).is_empty());
+
+        // Leaving and entering again starts a new dwell
+        strategy.map(&centered);
+        assert!(texts(strategy.map(&combo)).is_empty());
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(texts(strategy.map(&combo)), letter);
+        assert!(texts(strategy.map_idle()).is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! # Output Kill Switch
+//!
+//! Disables every mapping output at once when a button combo is held.
+//!
+//! ## Why This Module Exists
+//!
+//! With ELRS driving a vehicle, stopping everything must not depend on which
+//! menu is open or which mappings are running. Holding the kill combo
+//! (Start+Select unless configured otherwise in the controller settings)
+//! disables all output and disarms ELRS from anywhere.
+//!
+//! ## Design Rationale
+//!
+//! ### Checked Before Everything Else
+//! The manager runs every controller input through the [`KillSwitch`] before
+//! navigation and the engines, and regardless of gamepad filters, so any pad
+//! can stop the outputs and bound buttons cannot shadow the combo.
+//!
+//! ### Latched
+//! Once engaged the switch stays engaged after the combo is released. Outputs
+//! resume only after it was cleared explicitly with
+//! [`MappingCommand::ClearKillSwitch`](super::MappingCommand::ClearKillSwitch),
+//! and ELRS comes back disarmed. A combo still held when clearing engages the
+//! switch again right away.
+//! The UI clears it with the "Resume outputs" button of its banner; headless
+//! mode has no way to clear it, there outputs resume after a restart.
+//!
+//! ### Chords
+//! The combo counts as held no matter whether its buttons arrive as single
+//! presses or as part of a configured chord, so defining a chord on the same
+//! buttons does not disable the switch.
+//!
+//! ### One Pad
+//! All buttons of the combo have to be held on the same pad. Buttons held on
+//! different pads, e.g. Start by one operator and Select by the other, never
+//! add up to the combo.
+
+use crate::controller::controller_handle::{ButtonType, ControllerOutput};
+use tokio::sync::watch;
+
+/// Latched "all outputs disabled" state engaged by a button combo
+#[derive(Debug)]
+pub struct KillSwitch {
+    /// Buttons that engage the switch when held together, empty disables it
+    combo: Vec<ButtonType>,
+    /// Whether outputs are disabled, shared with the UI
+    engaged_tx: watch::Sender<bool>,
+}
+
+impl KillSwitch {
+    pub fn new(combo: Vec<ButtonType>) -> Self {
+        Self {
+            combo,
+            engaged_tx: watch::Sender::new(false),
+        }
+    }
+
+    /// Replaces the combo, an empty combo can never engage
+    pub fn set_combo(&mut self, combo: Vec<ButtonType>) {
+        self.combo = combo;
+    }
+
+    /// Returns a receiver for whether outputs are disabled
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.engaged_tx.subscribe()
+    }
+
+    pub fn is_engaged(&self) -> bool {
+        *self.engaged_tx.borrow()
+    }
+
+    /// Engages if a single pad holds the whole combo in `input`.
+    ///
+    /// Held buttons are reported with every input of their pad, so nothing is
+    /// tracked across inputs and a missed release cannot leave a button held.
+    /// Returns true only for the input that engaged the switch.
+    pub fn update(&mut self, input: &ControllerOutput) -> bool {
+        let held = input.held_buttons();
+        let combo_held = !self.combo.is_empty() && self.combo.iter().all(|b| held.contains(b));
+        combo_held && !self.engaged_tx.send_replace(true)
+    }
+
+    /// Releases the latch, returns whether it was engaged
+    pub fn clear(&mut self) -> bool {
+        self.engaged_tx.send_replace(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::controller_handle::{ButtonEvent, ButtonEventState, ChordEvent};
+
+    fn input(gamepad_id: usize, held: &[ButtonType]) -> ControllerOutput {
+        ControllerOutput {
+            gamepad_id,
+            button_events: held
+                .iter()
+                .map(|button| ButtonEvent {
+                    button: button.clone(),
+                    duration_ms: 100.0,
+                    state: ButtonEventState::Held,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn kill_switch() -> KillSwitch {
+        KillSwitch::new(vec![ButtonType::Start, ButtonType::Select])
+    }
+
+    #[test]
+    fn combo_engages_once_and_latches() {
+        let mut switch = kill_switch();
+        let combo = [ButtonType::Start, ButtonType::Select];
+
+        assert!(!switch.update(&input(0, &[ButtonType::Start])));
+        assert!(switch.update(&input(0, &combo)));
+        assert!(!switch.update(&input(0, &combo)));
+        assert!(!switch.update(&input(0, &[])));
+        assert!(switch.is_engaged());
+
+        assert!(switch.clear());
+        assert!(!switch.is_engaged());
+        assert!(switch.update(&input(0, &combo)));
+    }
+
+    #[test]
+    fn combo_split_across_pads_does_not_engage() {
+        let mut switch = kill_switch();
+
+        assert!(!switch.update(&input(0, &[ButtonType::Start])));
+        assert!(!switch.update(&input(1, &[ButtonType::Select])));
+        assert!(!switch.is_engaged());
+    }
+
+    #[test]
+    fn missed_release_does_not_keep_a_button_held() {
+        let mut switch = kill_switch();
+
+        // Start was released in an input the switch never saw
+        assert!(!switch.update(&input(0, &[ButtonType::Start])));
+        assert!(!switch.update(&input(0, &[ButtonType::Select])));
+        assert!(!switch.is_engaged());
+    }
+
+    #[test]
+    fn combo_inside_a_chord_engages() {
+        let mut switch = kill_switch();
+        let chorded = ControllerOutput {
+            chord_events: vec![ChordEvent {
+                name: "menu".to_string(),
+                buttons: vec![ButtonType::Start, ButtonType::Select],
+                duration_ms: 100.0,
+                state: ButtonEventState::Held,
+            }],
+            ..Default::default()
+        };
+
+        assert!(switch.update(&chorded));
+    }
+
+    #[test]
+    fn empty_combo_never_engages() {
+        let mut switch = KillSwitch::new(Vec::new());
+
+        assert!(!switch.update(&input(0, &[ButtonType::Start, ButtonType::Select])));
+    }
+}
@@ -12,41 +12,182 @@
 //! ```
 //!
 //! Engines run independently with their own rate limiting and state machines.
+//! The rate limiter of each engine is built from its mapping config.
 //! Manager handles lifecycle, configuration loading, and output routing.
-use crate::controller::controller_handle::ControllerOutput;
+//!
+//! # Multiple Gamepads
+//!
+//! Every [`ControllerOutput`] carries the id of the pad it came from. An engine
+//! can be bound to one pad with [`MappingEngineManager::set_gamepad_filter`],
+//! e.g. one operator drives via ELRS while the other navigates the UI. Engines
+//! without a filter receive input from all pads, so with a single pad connected
+//! existing configurations behave exactly as before. They still get one input
+//! per pad, so strategies keep held buttons and stick state per gamepad id.
+//!
+//! # Live Reconfiguration
+//!
+//! Other threads can request an engine rebuild through the sender returned by
+//! [`MappingEngineManager::reload_sender`]. The manager then re-runs
+//! [`MappingEngineManager::activate_mapping`], picking up the configuration
+//! currently stored in the ConfigPortal.
+//!
+//! The latest controller input is also published over a watch channel
+//! ([`MappingEngineManager::state_receiver`]) for UI feedback such as the
+//! keyboard overlay.
+//!
+//! # Switching Mappings
+//!
+//! The UI controls the running engines through [`MappingCommand`]s sent over
+//! [`MappingEngineManager::command_sender`]. A switch makes a different mapping
+//! the only active one, e.g. to go from typing into the UI to flying a vehicle.
+//! Switches are handled between input iterations: the new engine is started
+//! first, then the old ones are shut down and whatever they already produced is
+//! still routed. Pending controller input stays queued and is picked up by the
+//! new engine, so nothing is mapped twice. The running engines are published
+//! through [`MappingEngineManager::active_mappings_receiver`].
+//!
+//! # Concurrent Engines
+//!
+//! Engines can also be added next to each other, so the same input types into
+//! the UI and drives ELRS at once. Every input is shared with the engines as an
+//! `Arc`, and each engine has its own rate limiter and output channel. Sends
+//! never wait, so one slow consumer never stalls the others. Disabled engines
+//! keep running but receive no input.
+//!
+//! # Backpressure
+//!
+//! What happens when an output channel is full depends on the output:
+//! - **Keyboard**: Events are held back and coalesced into one batch that is
+//!   sent once the UI catches up. Beyond [`KEYBOARD_BACKLOG_LIMIT`] events
+//!   the oldest are dropped, so a long stall loses early input, not recent.
+//! - **ELRS**: The frame is dropped; the next one carries the full channel
+//!   state and supersedes it.
+//! - **Custom**: The payload is dropped. Payloads are sent once per press, so
+//!   a drop loses that press.
+//!
+//! Every dropped event is counted in [`DroppedEvents`] for the diagnostics
+//! view. A warning is logged when an output starts dropping and an info when
+//! it recovers, not for every event.
+//!
+//! # Menu Navigation
+//!
+//! With a sender set through [`MappingEngineManager::set_navigation_sender`],
+//! buttons bound to menu navigation in the keyboard config are taken out of
+//! every input before it reaches the engines, and the actions they start are
+//! sent to the UI. The bindings follow the stored keyboard config whenever it
+//! is reloaded, whether or not the keyboard engine runs.
+//!
+//! # Kill Switch
+//!
+//! Every input is checked against the kill combo of the controller settings
+//! first (see [`crate::mapping::kill_switch`]). Once engaged, no input reaches
+//! the engines and their output is discarded, except the failsafe frames of a
+//! restarted, disarmed ELRS engine. Menu navigation keeps working. Outputs
+//! resume after [`MappingCommand::ClearKillSwitch`].
+//!
+//! # Idle Mode
+//!
+//! Input that is not neutral counts as activity for the shared
+//! [`IdleTracker`]. While the application is idle and no engine produced
+//! output, the loop waits up to [`IDLE_TICK`] instead of [`TICK`], and the
+//! next controller input ends the wait early. Engines producing output on
+//! their own, like ELRS with `neutral_on_idle`, keep the loop at full rate.
+use crate::controller::controller_handle::{ControllerOutput, ControllerSettings};
+use crate::controller::rumble::RumbleHandle;
+use crate::idle::IdleTracker;
 use crate::mapping::custom::CustomConfig;
-use crate::mapping::elrs::ELRSConfig;
+use crate::mapping::elrs::{ELRSConfig, ELRSOutput};
 use crate::mapping::keyboard::KeyboardConfig;
+use crate::mapping::kill_switch::KillSwitch;
+use crate::mapping::navigation::{NavigationAction, NavigationInterceptor};
 use crate::mapping::MappingStrategy;
 use crate::mapping::{
-    engine::MappingEngineHandle, MappedEvent, MappingConfig, MappingError, MappingType,
+    engine::MappingEngineHandle, KeyboardBatch, MappedEvent, MappingConfig, MappingError,
+    MappingType, RateLimiter,
 };
 use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
-use color_eyre::{eyre::Report, Result};
+use chrono::{DateTime, Local};
 use eframe::egui;
 use rumqttc::tokio_rustls::rustls::KeyLog;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::{TryRecvError, TrySendError};
+use tokio::sync::{mpsc, watch};
 use tokio::time::Duration;
 use tracing::{debug, error, info, warn};
 
+/// Runtime control of the running mapping engines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingCommand {
+    /// Make this the only active mapping
+    Switch(MappingType),
+    /// Start an engine next to the running ones
+    Add(MappingType),
+    /// Stop a single engine
+    Remove(MappingType),
+    /// Pause or resume input to a running engine
+    SetEnabled(MappingType, bool),
+    /// Resume outputs disabled by the kill switch
+    ClearKillSwitch,
+}
+
+/// Wait between two iterations of the mapping loop
+pub const TICK: Duration = Duration::from_millis(20);
+
+/// Longest wait between two iterations while the application is idle
+pub const IDLE_TICK: Duration = Duration::from_millis(100);
+
+/// Most keyboard events held back while the UI channel is full
+///
+/// Roughly ten seconds of fast typing; older events are dropped beyond it.
+pub const KEYBOARD_BACKLOG_LIMIT: usize = 200;
+
+/// Events dropped per output channel since startup
+///
+/// See the backpressure policy in the module documentation.
+#[derive(Debug, Default)]
+pub struct DroppedEvents {
+    keyboard: AtomicU64,
+    elrs: AtomicU64,
+    custom: AtomicU64,
+}
+
+impl DroppedEvents {
+    fn counter(&self, output: MappingType) -> &AtomicU64 {
+        match output {
+            MappingType::Keyboard => &self.keyboard,
+            MappingType::ELRS => &self.elrs,
+            MappingType::Custom => &self.custom,
+        }
+    }
+
+    fn add(&self, output: MappingType, count: u64) {
+        self.counter(output).fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Dropped events of one output
+    pub fn get(&self, output: MappingType) -> u64 {
+        self.counter(output).load(Ordering::Relaxed)
+    }
+}
+
+/// A running engine with its channels
+struct EngineSlot {
+    handle: MappingEngineHandle,
+    output_rx: mpsc::Receiver<MappedEvent>,
+    input_tx: mpsc::Sender<Arc<ControllerOutput>>,
+    /// Disabled engines keep running but receive no input
+    enabled: bool,
+}
+
 /// Manager for parallel mapping engine execution
 ///
 /// Handles the lifecycle of multiple mapping engines and routes their outputs.
 /// Each engine runs in its own thread with independent rate limiting.
 pub struct MappingEngineManager {
-    /// Active engines with their communication channels
-    ///
-    /// Tuple contains: (engine handle, output receiver, input sender)
-    active_engines: HashMap<
-        MappingType,
-        (
-            MappingEngineHandle,
-            mpsc::Receiver<MappedEvent>,
-            mpsc::Sender<ControllerOutput>,
-        ),
-    >,
+    /// Running engines, at most one per mapping type
+    active_engines: HashMap<MappingType, EngineSlot>,
 
     /// Event deduplication for keyboard mapping
     ///
@@ -54,22 +195,71 @@ pub struct MappingEngineManager {
     old_events: Vec<egui::Event>,
     /// Input and output channels
     controller_rx: mpsc::Receiver<ControllerOutput>,
-    ui_tx: mpsc::Sender<Vec<egui::Event>>,
-    elrs_tx: mpsc::Sender<HashMap<u16, u16>>,
+    ui_tx: mpsc::Sender<KeyboardBatch>,
+    elrs_tx: mpsc::Sender<ELRSOutput>,
     custom_tx: mpsc::Sender<HashMap<String, Vec<u8>>>,
 
     config_portal: Arc<ConfigPortal>,
+
+    /// Gamepad each engine is bound to; engines not listed take all pads
+    gamepad_filter: HashMap<MappingType, usize>,
+
+    /// Rumble access handed to strategies that give haptic feedback
+    rumble: Option<RumbleHandle>,
+
+    /// Requests to rebuild an engine from the current configuration
+    reload_tx: mpsc::Sender<MappingType>,
+    reload_rx: mpsc::Receiver<MappingType>,
+
+    /// Latest controller input, `None` until the first input arrives
+    state_tx: watch::Sender<Option<ControllerOutput>>,
+
+    /// Runtime control requests, e.g. from the UI
+    command_tx: mpsc::Sender<MappingCommand>,
+    command_rx: mpsc::Receiver<MappingCommand>,
+
+    /// Running engines with their enabled flag, updated on every change
+    active_tx: watch::Sender<Vec<(MappingType, bool)>>,
+
+    /// Keyboard events waiting for room in the UI channel, oldest first
+    keyboard_backlog: Vec<egui::Event>,
+
+    /// Oldest input stamp of the backlog while input latency is measured
+    backlog_collected_at: Option<DateTime<Local>>,
+
+    /// Events dropped per output, shared with the diagnostics view
+    dropped: Arc<DroppedEvents>,
+
+    /// Outputs that dropped since their last successful send
+    dropping: HashSet<MappingType>,
+
+    /// Takes menu navigation buttons out of the input
+    navigation: NavigationInterceptor,
+
+    /// Receives navigation actions, navigation is off while `None`
+    navigation_tx: Option<mpsc::Sender<NavigationAction>>,
+
+    /// Disables all outputs while engaged
+    kill_switch: KillSwitch,
+
+    /// Live controller settings, source of the kill combo
+    settings_rx: Option<watch::Receiver<ControllerSettings>>,
+
+    /// Activity shared with the other loops, slows the loop while idle
+    idle: Arc<IdleTracker>,
 }
 
 impl MappingEngineManager {
     /// Creates new manager with output channels for each mapping type
     pub fn new(
         controller_rx: mpsc::Receiver<ControllerOutput>,
-        ui_tx: mpsc::Sender<Vec<egui::Event>>,
-        elrs_tx: mpsc::Sender<HashMap<u16, u16>>,
+        ui_tx: mpsc::Sender<KeyboardBatch>,
+        elrs_tx: mpsc::Sender<ELRSOutput>,
         custom_tx: mpsc::Sender<HashMap<String, Vec<u8>>>,
         config_portal: Arc<ConfigPortal>,
     ) -> Self {
+        let (reload_tx, reload_rx) = mpsc::channel(10);
+        let (command_tx, command_rx) = mpsc::channel(10);
         Self {
             active_engines: HashMap::new(),
             old_events: Vec::new(),
@@ -78,6 +268,122 @@ impl MappingEngineManager {
             elrs_tx,
             custom_tx,
             config_portal,
+            gamepad_filter: HashMap::new(),
+            rumble: None,
+            reload_tx,
+            reload_rx,
+            state_tx: watch::Sender::new(None),
+            command_tx,
+            command_rx,
+            active_tx: watch::Sender::new(Vec::new()),
+            keyboard_backlog: Vec::new(),
+            backlog_collected_at: None,
+            dropped: Arc::new(DroppedEvents::default()),
+            dropping: HashSet::new(),
+            navigation: NavigationInterceptor::default(),
+            navigation_tx: None,
+            kill_switch: KillSwitch::new(ControllerSettings::default().kill_combo),
+            settings_rx: None,
+            idle: Arc::new(IdleTracker::default()),
+        }
+    }
+
+    /// Returns the dropped event counters of the output channels
+    pub fn dropped_events(&self) -> Arc<DroppedEvents> {
+        self.dropped.clone()
+    }
+
+    /// Returns a sender for requesting an engine rebuild from the stored config
+    ///
+    /// Requests for engines that are not running are ignored.
+    pub fn reload_sender(&self) -> mpsc::Sender<MappingType> {
+        self.reload_tx.clone()
+    }
+
+    /// Returns a receiver for the latest controller input from any pad
+    pub fn state_receiver(&self) -> watch::Receiver<Option<ControllerOutput>> {
+        self.state_tx.subscribe()
+    }
+
+    /// Returns a sender for controlling the running engines at runtime
+    pub fn command_sender(&self) -> mpsc::Sender<MappingCommand> {
+        self.command_tx.clone()
+    }
+
+    /// Returns a receiver for all running engines and whether they are enabled
+    pub fn active_mappings_receiver(&self) -> watch::Receiver<Vec<(MappingType, bool)>> {
+        self.active_tx.subscribe()
+    }
+
+    /// Shares the activity tracker with the rest of the application
+    ///
+    /// Without it the manager tracks activity on its own, still slowing down
+    /// while idle.
+    pub fn set_idle_tracker(&mut self, idle: Arc<IdleTracker>) {
+        self.idle = idle;
+    }
+
+    /// Enables haptic feedback for strategies activated from now on
+    pub fn set_rumble_handle(&mut self, rumble: RumbleHandle) {
+        self.rumble = Some(rumble);
+    }
+
+    /// Enables menu navigation, sending the actions of navigation buttons
+    ///
+    /// Without a sender navigation buttons are passed on like any other.
+    pub fn set_navigation_sender(&mut self, navigation_tx: mpsc::Sender<NavigationAction>) {
+        self.navigation_tx = Some(navigation_tx);
+    }
+
+    /// Follows the kill combo of the live controller settings
+    ///
+    /// Without a receiver the default combo is used.
+    pub fn set_settings_receiver(&mut self, settings_rx: watch::Receiver<ControllerSettings>) {
+        self.kill_switch
+            .set_combo(settings_rx.borrow().kill_combo.clone());
+        self.settings_rx = Some(settings_rx);
+    }
+
+    /// Returns a receiver for whether the kill switch disabled all outputs
+    pub fn kill_switch_receiver(&self) -> watch::Receiver<bool> {
+        self.kill_switch.subscribe()
+    }
+
+    /// Takes the navigation bindings from the stored keyboard config
+    ///
+    /// An invalid config keeps the current bindings.
+    async fn reload_navigation(&mut self) {
+        if let ConfigResult::KeyboardConfig(config) = self
+            .config_portal
+            .execute_portal_action_async(PortalAction::GetKeyboardConfig)
+            .await
+        {
+            // An empty config is replaced by the defaults on activation as well
+            let config = if config.button_mapping.is_empty() {
+                KeyboardConfig::default_config()
+            } else {
+                config
+            };
+            match config.validate() {
+                Ok(()) => self.navigation.set_bindings(config.navigation_mapping),
+                Err(e) => warn!("Keeping menu navigation bindings: {}", e),
+            }
+        }
+    }
+
+    /// Binds an engine type to a single gamepad, or to all pads with `None`
+    ///
+    /// Takes effect with the next controller input; the engine keeps running.
+    pub fn set_gamepad_filter(&mut self, mapping_type: MappingType, gamepad_id: Option<usize>) {
+        match gamepad_id {
+            Some(id) => {
+                info!("Routing gamepad {} to {} mapping", id, mapping_type);
+                self.gamepad_filter.insert(mapping_type, id);
+            }
+            None => {
+                info!("Routing all gamepads to {} mapping", mapping_type);
+                self.gamepad_filter.remove(&mapping_type);
+            }
         }
     }
 
@@ -90,9 +396,10 @@ impl MappingEngineManager {
         mapping_type: MappingType,
     ) -> Result<(), MappingError> {
         // Load configurations from ConfigPortal
-        let keyboard_config: KeyboardConfig = if let ConfigResult::KeyboardConfig(config) = self
+        let mut keyboard_config: KeyboardConfig = if let ConfigResult::KeyboardConfig(config) = self
             .config_portal
-            .execute_potal_action(PortalAction::GetKeyboardConfig)
+            .execute_portal_action_async(PortalAction::GetKeyboardConfig)
+            .await
         {
             if config.button_mapping.is_empty() {
                 KeyboardConfig::default_config()
@@ -105,17 +412,30 @@ impl MappingEngineManager {
 
         let elrs_config: ELRSConfig = if let ConfigResult::ElrsConfig(config) = self
             .config_portal
-            .execute_potal_action(PortalAction::GetElrsConfig)
+            .execute_portal_action_async(PortalAction::GetElrsConfig)
+            .await
         {
             if config.joystick_mapping.is_empty() {
                 ELRSConfig::default_config()
             } else {
-                config
+                *config
             }
         } else {
             ELRSConfig::default_config()
         };
 
+        let custom_config: CustomConfig = if let ConfigResult::CustomConfig(config) = self
+            .config_portal
+            .execute_portal_action_async(PortalAction::GetCustomConfig)
+            .await
+        {
+            config
+        } else {
+            CustomConfig::default()
+        };
+
+        keyboard_config.set_rumble(self.rumble.clone());
+
         // Validate configurations
         if let Err(e) = elrs_config.validate() {
             error!("Invalid configuration: {}", e);
@@ -131,13 +451,28 @@ impl MappingEngineManager {
                 e
             )));
         }
+        self.navigation
+            .set_bindings(keyboard_config.navigation_mapping.clone());
+
+        // Only checked when activated; an unused custom mapping may be empty
+        if mapping_type == MappingType::Custom {
+            if let Err(e) = custom_config.validate() {
+                error!("Invalid configuration: {}", e);
+                return Err(MappingError::ConfigError(format!(
+                    "Invalid configuration: {}",
+                    e
+                )));
+            }
+        }
 
         // Shutdown existing engine of same type if present
+        let mut enabled = true;
         if let Some(mut engine) = self.active_engines.remove(&mapping_type) {
             debug!("Deactivating existing mapping engine: {}", mapping_type);
+            enabled = engine.enabled;
 
             // Bestehende Engine herunterfahren
-            if let Err(e) = engine.0.shutdown().await {
+            if let Err(e) = engine.handle.shutdown().await {
                 warn!("Error shutting down existing engine: {}", e);
                 // Weitermachen trotz Fehler
             }
@@ -149,20 +484,22 @@ impl MappingEngineManager {
                 debug!("Activating mapping: Keyboard ({})", mapping_type);
 
                 let strategy = keyboard_config.create_strategy()?;
+                let rate_limiter = keyboard_config.rate_limit_ms().map(RateLimiter::new);
 
                 let mut mapping_engine_handle =
                     MappingEngineHandle::new(mapping_type, mapping_type.to_string());
 
                 let (mapped_event_receiver, controller_state_sender) =
-                    mapping_engine_handle.start(strategy)?;
+                    mapping_engine_handle.start(strategy, rate_limiter)?;
 
                 self.active_engines.insert(
                     mapping_type,
-                    (
-                        mapping_engine_handle,
-                        mapped_event_receiver,
-                        controller_state_sender,
-                    ),
+                    EngineSlot {
+                        handle: mapping_engine_handle,
+                        output_rx: mapped_event_receiver,
+                        input_tx: controller_state_sender,
+                        enabled,
+                    },
                 );
             }
             MappingType::ELRS => {
@@ -170,75 +507,460 @@ impl MappingEngineManager {
 
                 // Strategie aus Konfiguration erstellen
                 let strategy = elrs_config.create_strategy()?;
+                let rate_limiter = elrs_config.rate_limit_ms().map(RateLimiter::new);
 
                 let mut mapping_engine_handle =
                     MappingEngineHandle::new(mapping_type, mapping_type.to_string());
 
                 let (mapped_event_receiver, controller_state_sender) =
-                    mapping_engine_handle.start(strategy)?;
+                    mapping_engine_handle.start(strategy, rate_limiter)?;
 
                 self.active_engines.insert(
                     mapping_type,
-                    (
-                        mapping_engine_handle,
-                        mapped_event_receiver,
-                        controller_state_sender,
-                    ),
+                    EngineSlot {
+                        handle: mapping_engine_handle,
+                        output_rx: mapped_event_receiver,
+                        input_tx: controller_state_sender,
+                        enabled,
+                    },
                 );
             }
             MappingType::Custom => {
-                // TODO: Implement custom mapping activation
+                debug!("Activating mapping: Custom ({})", mapping_type);
+
+                let strategy = custom_config.create_strategy()?;
+                let rate_limiter = custom_config.rate_limit_ms().map(RateLimiter::new);
+
+                let mut mapping_engine_handle =
+                    MappingEngineHandle::new(mapping_type, mapping_type.to_string());
+
+                let (mapped_event_receiver, controller_state_sender) =
+                    mapping_engine_handle.start(strategy, rate_limiter)?;
+
+                self.active_engines.insert(
+                    mapping_type,
+                    EngineSlot {
+                        handle: mapping_engine_handle,
+                        output_rx: mapped_event_receiver,
+                        input_tx: controller_state_sender,
+                        enabled,
+                    },
+                );
+            }
+        }
+
+        self.publish_active_mappings();
+        Ok(())
+    }
+
+    /// Makes `mapping_type` the only active mapping
+    ///
+    /// The new engine is started before the others are stopped, so a failed
+    /// activation leaves the current mapping running. Events the old engines
+    /// produced before stopping are routed as usual.
+    pub async fn switch_mapping(&mut self, mapping_type: MappingType) -> Result<(), MappingError> {
+        info!("Switching to {} mapping", mapping_type);
+
+        if !self.is_mapping_active(mapping_type) {
+            self.activate_mapping(mapping_type).await?;
+        }
+
+        let others: Vec<MappingType> = self
+            .active_engines
+            .keys()
+            .filter(|other| **other != mapping_type)
+            .copied()
+            .collect();
+        for other in others {
+            if let Some(mut engine) = self.active_engines.remove(&other) {
+                if let Err(e) = engine.handle.shutdown().await {
+                    warn!("Error shutting down {} engine: {}", other, e);
+                }
+
+                // The engine task has finished, so all its output is buffered
+                while let Ok(event) = engine.output_rx.try_recv() {
+                    self.route_event(event);
+                }
             }
         }
 
+        // Dedup state belongs to the previous keyboard engine
+        self.old_events.clear();
+        self.publish_active_mappings();
         Ok(())
     }
 
+    /// Starts an engine next to the already running ones
+    ///
+    /// Does nothing if an engine of this type is already running.
+    pub async fn add_engine(&mut self, mapping_type: MappingType) -> Result<(), MappingError> {
+        if self.is_mapping_active(mapping_type) {
+            debug!("{} engine already running", mapping_type);
+            return Ok(());
+        }
+        info!("Adding {} engine", mapping_type);
+        self.activate_mapping(mapping_type).await
+    }
+
+    /// Stops a single engine, leaving the others running
+    pub async fn remove_engine(&mut self, mapping_type: MappingType) -> Result<(), MappingError> {
+        info!("Removing {} engine", mapping_type);
+        self.deactivate_mapping(mapping_type).await
+    }
+
+    /// Pauses or resumes input to a running engine
+    ///
+    /// A disabled engine keeps its strategy state, e.g. an armed ELRS link
+    /// keeps sending its last channels, but gets no new controller input.
+    pub fn set_engine_enabled(&mut self, mapping_type: MappingType, enabled: bool) {
+        match self.active_engines.get_mut(&mapping_type) {
+            Some(engine) => {
+                info!(
+                    "{} {} engine",
+                    if enabled { "Enabling" } else { "Disabling" },
+                    mapping_type
+                );
+                engine.enabled = enabled;
+                self.publish_active_mappings();
+            }
+            None => warn!("No active mapping of type: {}", mapping_type),
+        }
+    }
+
+    /// Executes a runtime control request
+    async fn handle_command(&mut self, command: MappingCommand) -> Result<(), MappingError> {
+        match command {
+            MappingCommand::Switch(mapping_type) => self.switch_mapping(mapping_type).await,
+            MappingCommand::Add(mapping_type) => self.add_engine(mapping_type).await,
+            MappingCommand::Remove(mapping_type) => self.remove_engine(mapping_type).await,
+            MappingCommand::SetEnabled(mapping_type, enabled) => {
+                self.set_engine_enabled(mapping_type, enabled);
+                Ok(())
+            }
+            MappingCommand::ClearKillSwitch => {
+                if self.kill_switch.clear() {
+                    info!("Kill switch cleared, mapping outputs resumed");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Disables all outputs and disarms ELRS.
+    ///
+    /// Output the engines produced before is discarded. The ELRS engine is
+    /// restarted, which disarms it, and fed one empty input so the transmitter
+    /// gets failsafe values instead of repeating the last armed channels. If
+    /// the restart fails the engine is stopped instead.
+    async fn engage_kill_switch(&mut self) {
+        warn!("Kill switch engaged, all mapping outputs disabled");
+        self.keyboard_backlog.clear();
+        self.backlog_collected_at = None;
+        self.old_events.clear();
+        for engine in self.active_engines.values_mut() {
+            while engine.output_rx.try_recv().is_ok() {}
+        }
+
+        if !self.is_mapping_active(MappingType::ELRS) {
+            return;
+        }
+        if let Err(e) = self.activate_mapping(MappingType::ELRS).await {
+            error!("Failed to restart ELRS engine disarmed, stopping it: {}", e);
+            if let Err(e) = self.deactivate_mapping(MappingType::ELRS).await {
+                error!("Failed to stop ELRS engine: {}", e);
+            }
+            return;
+        }
+        if let Some(engine) = self.active_engines.get(&MappingType::ELRS) {
+            let neutral = Arc::new(ControllerOutput::default());
+            if let Err(e) = engine.input_tx.try_send(neutral) {
+                warn!("ELRS failsafe input dropped: {}", e);
+            }
+        }
+    }
+
+    /// Sends the running engines to `active_mappings_receiver` subscribers
+    fn publish_active_mappings(&self) {
+        let mut active: Vec<(MappingType, bool)> = self
+            .active_engines
+            .iter()
+            .map(|(mapping_type, engine)| (*mapping_type, engine.enabled))
+            .collect();
+        active.sort_by_key(|(mapping_type, _)| mapping_type.to_string());
+        self.active_tx.send_replace(active);
+    }
+
     /// Main processing loop - distributes input and routes output
     ///
-    /// Runs continuously with 20ms intervals. For each controller input:
-    /// 1. Sends input to all active engines
+    /// Runs continuously with [`TICK`] intervals, see the idle mode in the
+    /// module documentation for the exception. For each controller input:
+    /// 1. Sends input to all active engines bound to its gamepad
     /// 2. Collects outputs from engines  
     /// 3. Routes outputs to appropriate channels
     /// 4. Handles event deduplication for keyboard events
-    pub async fn run_mapping(&mut self) -> Result<(), Report> {
+    ///
+    /// Pending reload and switch requests are handled before input each iteration.
+    /// While the kill switch is engaged no input reaches the engines.
+    ///
+    /// Errors of single events are logged and counted, and an engine whose
+    /// task ended is restarted. Only returns on errors the loop cannot recover
+    /// from itself:
+    /// - [`MappingError::ChannelClosed`] when the controller input is gone
+    /// - the activation error of an engine that could not be restarted; the
+    ///   engine is removed and calling `run_mapping` again continues without it
+    pub async fn run_mapping(&mut self) -> Result<(), MappingError> {
         debug!("Start Mapping");
+        let mut engines_busy = false;
         loop {
-            tokio::time::sleep(Duration::from_millis(20)).await;
+            // While idle, the next controller input ends the wait early
+            let mut received = None;
+            if self.idle.is_idle() && !engines_busy {
+                tokio::select! {
+                    input = self.controller_rx.recv() => match input {
+                        Some(input) => received = Some(input),
+                        None => {
+                            return Err(MappingError::ChannelClosed("Controller input".to_string()));
+                        }
+                    },
+                    _ = tokio::time::sleep(IDLE_TICK) => {}
+                }
+            } else {
+                tokio::time::sleep(TICK).await;
+            }
+            self.flush_keyboard_backlog();
+
+            if let Some(settings_rx) = &mut self.settings_rx {
+                if settings_rx.has_changed().unwrap_or(false) {
+                    let combo = settings_rx.borrow_and_update().kill_combo.clone();
+                    self.kill_switch.set_combo(combo);
+                }
+            }
+
+            while let Ok(mapping_type) = self.reload_rx.try_recv() {
+                // A stopped engine picks up the new config when it is started
+                if !self.is_mapping_active(mapping_type) {
+                    if mapping_type == MappingType::Keyboard {
+                        self.reload_navigation().await;
+                    }
+                    debug!("{} mapping not running, nothing to reload", mapping_type);
+                    continue;
+                }
+                info!("Reloading {} mapping", mapping_type);
+                if let Err(e) = self.activate_mapping(mapping_type).await {
+                    error!("Failed to reload {} mapping: {}", mapping_type, e);
+                }
+            }
+
+            while let Ok(command) = self.command_rx.try_recv() {
+                if let Err(e) = self.handle_command(command).await {
+                    error!("Failed to execute {:?}: {}", command, e);
+                }
+            }
+
             // Process controller input if available
-            if let Ok(controller_output) = self.controller_rx.try_recv() {
-                for (_mapping_type, (_engine, receiver, sender)) in &mut self.active_engines {
-                    // Send input to engine (non_blocking)
-                    let sending_result = sender.try_send(controller_output.clone());
-                    if let Err(e) = sending_result {
-                        warn!("{}", e);
+            let input = match received.map_or_else(|| self.controller_rx.try_recv(), Ok) {
+                Ok(input) => Some(input),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => {
+                    return Err(MappingError::ChannelClosed("Controller input".to_string()));
+                }
+            };
+            if let Some(mut controller_output) = input {
+                if !controller_output.is_neutral() {
+                    self.idle.record();
+                }
+                self.state_tx.send_replace(Some(controller_output.clone()));
+
+                if self.kill_switch.update(&controller_output) {
+                    self.engage_kill_switch().await;
+                }
+
+                if let Some(navigation_tx) = &self.navigation_tx {
+                    for action in self.navigation.intercept(&mut controller_output) {
+                        debug!("Menu navigation: {}", action);
+                        if let Err(e) = navigation_tx.try_send(action) {
+                            warn!("Menu navigation dropped: {}", e);
+                        }
                     }
-                    //Collect engine output and route to appropriate channel
-                    let mapped_events = receiver.try_recv();
-                    if let Ok(events) = mapped_events {
-                        match events {
-                            MappedEvent::KeyboardEvent { key_code } => {
-                                debug!("Message to send: {:?}", key_code);
-                                //Deduplicate consecutive identical keyboard events
-                                if key_code != self.old_events {
-                                    self.old_events = key_code.clone();
-                                    self.ui_tx.try_send(key_code)?;
-                                } else {
-                                    self.old_events = Vec::new();
-                                }
-                            }
-                            MappedEvent::ELRSData { pre_package } => {
-                                self.elrs_tx.try_send(pre_package)?;
-                            }
-                            MappedEvent::CustomEvent { event_type } => {
-                                self.custom_tx.try_send(event_type)?;
-                            }
+                }
+
+                // Shared by all engines instead of cloning the input per engine
+                let controller_output = Arc::new(controller_output);
+
+                for (mapping_type, engine) in &mut self.active_engines {
+                    // Skip disabled engines and engines bound to a different gamepad
+                    let filtered = self
+                        .gamepad_filter
+                        .get(mapping_type)
+                        .is_some_and(|id| *id != controller_output.gamepad_id);
+                    if engine.enabled && !filtered && !self.kill_switch.is_engaged() {
+                        // Send input to engine (non_blocking)
+                        if let Err(e) = engine.input_tx.try_send(controller_output.clone()) {
+                            warn!("{} engine input dropped: {}", mapping_type, e);
                         }
                     }
                 }
             }
+
+            // Collect engine output every cycle, idle output arrives without input
+            let mapped_events: Vec<MappedEvent> = self
+                .active_engines
+                .values_mut()
+                .filter_map(|engine| engine.output_rx.try_recv().ok())
+                .collect();
+            engines_busy = !mapped_events.is_empty();
+            for event in mapped_events {
+                self.route_event(event);
+            }
+
+            self.restart_stopped_engines().await?;
+        }
+    }
+
+    /// Restarts engines whose task ended without being shut down.
+    ///
+    /// The task's error or panic is logged. An engine that cannot be restarted
+    /// is removed and its activation error returned.
+    async fn restart_stopped_engines(&mut self) -> Result<(), MappingError> {
+        let stopped: Vec<MappingType> = self
+            .active_engines
+            .iter()
+            .filter(|(_, engine)| engine.handle.is_finished())
+            .map(|(mapping_type, _)| *mapping_type)
+            .collect();
+
+        for mapping_type in stopped {
+            if let Some(engine) = self.active_engines.get_mut(&mapping_type) {
+                match engine.handle.shutdown().await {
+                    Ok(()) => error!("{} engine stopped unexpectedly", mapping_type),
+                    Err(e) => error!("{} engine failed: {}", mapping_type, e),
+                }
+            }
+
+            info!("Restarting {} engine", mapping_type);
+            if let Err(e) = self.activate_mapping(mapping_type).await {
+                self.active_engines.remove(&mapping_type);
+                self.publish_active_mappings();
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Routes one mapped event to the output channel of its type
+    ///
+    /// Consecutive identical keyboard events are deduplicated. Sends never
+    /// wait; a full channel is handled as described under backpressure in the
+    /// module documentation. While the kill switch is engaged only disarmed
+    /// ELRS frames are routed.
+    fn route_event(&mut self, event: MappedEvent) {
+        if self.kill_switch.is_engaged()
+            && !matches!(event, MappedEvent::ELRSData { armed: false, .. })
+        {
+            debug!("Kill switch engaged, discarding mapped event");
+            return;
+        }
+
+        match event {
+            MappedEvent::KeyboardEvent {
+                key_code,
+                collected_at,
+            } => {
+                debug!("Message to send: {:?}", key_code);
+                //Deduplicate consecutive identical keyboard events, key
+                // repeats are identical by design and always pass
+                let is_repeat = key_code
+                    .iter()
+                    .any(|event| matches!(event, egui::Event::Key { repeat: true, .. }));
+                if is_repeat || key_code != self.old_events {
+                    self.old_events = key_code.clone();
+                    self.keyboard_backlog.extend(key_code);
+                    self.backlog_collected_at = match (self.backlog_collected_at, collected_at) {
+                        (Some(held), Some(new)) => Some(held.min(new)),
+                        (held, new) => held.or(new),
+                    };
+                    self.flush_keyboard_backlog();
+                } else {
+                    self.old_events = Vec::new();
+                }
+            }
+            MappedEvent::ELRSData { pre_package, armed } => {
+                let result = self.elrs_tx.try_send(ELRSOutput {
+                    channels: pre_package,
+                    armed,
+                });
+                self.record_send(MappingType::ELRS, result);
+            }
+            MappedEvent::CustomEvent { event_type } => {
+                let result = self.custom_tx.try_send(event_type);
+                self.record_send(MappingType::Custom, result);
+            }
         }
     }
+
+    /// Sends the held back keyboard events as one batch if the UI has room.
+    ///
+    /// While the channel is full the backlog is trimmed to
+    /// [`KEYBOARD_BACKLOG_LIMIT`], dropping the oldest events.
+    fn flush_keyboard_backlog(&mut self) {
+        if self.keyboard_backlog.is_empty() {
+            return;
+        }
+
+        let reserved = self.ui_tx.try_reserve().map(|permit| {
+            permit.send(KeyboardBatch {
+                events: std::mem::take(&mut self.keyboard_backlog),
+                collected_at: self.backlog_collected_at.take(),
+            });
+        });
+        match reserved {
+            Ok(()) => self.output_recovered(MappingType::Keyboard),
+            Err(TrySendError::Full(())) => {
+                let excess = self
+                    .keyboard_backlog
+                    .len()
+                    .saturating_sub(KEYBOARD_BACKLOG_LIMIT);
+                if excess > 0 {
+                    self.keyboard_backlog.drain(..excess);
+                    self.output_dropped(MappingType::Keyboard, excess as u64, "channel full");
+                }
+            }
+            Err(TrySendError::Closed(())) => {
+                let count = self.keyboard_backlog.len() as u64;
+                self.keyboard_backlog.clear();
+                self.backlog_collected_at = None;
+                self.output_dropped(MappingType::Keyboard, count, "channel closed");
+            }
+        }
+    }
+
+    /// Counts a failed send of a single event, the event itself is dropped.
+    fn record_send<T>(&mut self, output: MappingType, result: Result<(), TrySendError<T>>) {
+        match result {
+            Ok(()) => self.output_recovered(output),
+            Err(TrySendError::Full(_)) => self.output_dropped(output, 1, "channel full"),
+            Err(TrySendError::Closed(_)) => self.output_dropped(output, 1, "channel closed"),
+        }
+    }
+
+    /// Counts dropped events, warning only when an output starts dropping.
+    fn output_dropped(&mut self, output: MappingType, count: u64, reason: &str) {
+        self.dropped.add(output, count);
+        if self.dropping.insert(output) {
+            warn!("{} output {}, dropping mapped events", output, reason);
+        }
+    }
+
+    fn output_recovered(&mut self, output: MappingType) {
+        if self.dropping.remove(&output) {
+            info!(
+                "{} output recovered, {} events dropped since startup",
+                output,
+                self.dropped.get(output)
+            );
+        }
+    }
+
     /// Deactivates a specific mapping engine
     pub async fn deactivate_mapping(
         &mut self,
@@ -247,12 +969,13 @@ impl MappingEngineManager {
         debug!("Deactivating mapping of type: {}", mapping_type);
 
         if let Some(mut engine) = self.active_engines.remove(&mapping_type) {
-            if let Err(e) = engine.0.shutdown().await {
+            self.publish_active_mappings();
+            if let Err(e) = engine.handle.shutdown().await {
                 error!("Error shutting down engine: {}", e);
                 return Err(e);
             }
 
-            debug!("Mapping engine deactivated: {}", engine.0.name);
+            debug!("Mapping engine deactivated: {}", engine.handle.name);
             Ok(())
         } else {
             warn!("No active mapping of type: {}", mapping_type);
@@ -285,7 +1008,117 @@ impl MappingEngineManager {
     pub fn get_active_mappings(&self) -> Vec<(MappingType, String)> {
         self.active_engines
             .iter()
-            .map(|(t, h)| (*t, h.0.name.clone()))
+            .map(|(t, engine)| (*t, engine.handle.name.clone()))
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Outputs {
+        ui_rx: mpsc::Receiver<KeyboardBatch>,
+        elrs_rx: mpsc::Receiver<ELRSOutput>,
+        _custom_rx: mpsc::Receiver<HashMap<String, Vec<u8>>>,
+        _controller_tx: mpsc::Sender<ControllerOutput>,
+    }
+
+    /// Manager whose output channels hold a single message each.
+    fn manager() -> (MappingEngineManager, Outputs) {
+        let (controller_tx, controller_rx) = mpsc::channel(1);
+        let (ui_tx, ui_rx) = mpsc::channel(1);
+        let (elrs_tx, elrs_rx) = mpsc::channel(1);
+        let (custom_tx, custom_rx) = mpsc::channel(1);
+        let manager = MappingEngineManager::new(
+            controller_rx,
+            ui_tx,
+            elrs_tx,
+            custom_tx,
+            Arc::new(ConfigPortal::default()),
+        );
+        let outputs = Outputs {
+            ui_rx,
+            elrs_rx,
+            _custom_rx: custom_rx,
+            _controller_tx: controller_tx,
+        };
+        (manager, outputs)
+    }
+
+    fn key(text: &str) -> MappedEvent {
+        MappedEvent::KeyboardEvent {
+            key_code: vec![egui::Event::Text(text.to_string())],
+            collected_at: None,
+        }
+    }
+
+    fn texts(batch: KeyboardBatch) -> Vec<String> {
+        batch
+            .events
+            .into_iter()
+            .map(|event| match event {
+                egui::Event::Text(text) => text,
+                other => panic!("expected text, got {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keyboard_events_are_coalesced_while_ui_is_full() {
+        let (mut manager, mut outputs) = manager();
+
+        manager.route_event(key("a"));
+        manager.route_event(key("b"));
+        manager.route_event(key("c"));
+        assert_eq!(manager.dropped.get(MappingType::Keyboard), 0);
+
+        assert_eq!(texts(outputs.ui_rx.try_recv().unwrap()), ["a"]);
+        assert!(outputs.ui_rx.try_recv().is_err());
+
+        // Once the UI has room the held back events arrive as one batch
+        manager.flush_keyboard_backlog();
+        assert_eq!(texts(outputs.ui_rx.try_recv().unwrap()), ["b", "c"]);
+        assert!(manager.keyboard_backlog.is_empty());
+    }
+
+    #[test]
+    fn keyboard_backlog_drops_oldest_beyond_limit() {
+        let (mut manager, mut outputs) = manager();
+        manager.route_event(key("first"));
+
+        let extra = 5;
+        for i in 0..KEYBOARD_BACKLOG_LIMIT + extra {
+            manager.route_event(key(&i.to_string()));
+        }
+        assert_eq!(manager.dropped.get(MappingType::Keyboard), extra as u64);
+        assert!(manager.dropping.contains(&MappingType::Keyboard));
+
+        outputs.ui_rx.try_recv().unwrap();
+        manager.flush_keyboard_backlog();
+        let batch = texts(outputs.ui_rx.try_recv().unwrap());
+        assert_eq!(batch.len(), KEYBOARD_BACKLOG_LIMIT);
+        assert_eq!(batch.first().unwrap(), &extra.to_string());
+        assert!(!manager.dropping.contains(&MappingType::Keyboard));
+    }
+
+    #[test]
+    fn elrs_frames_are_dropped_and_counted_while_full() {
+        let (mut manager, mut outputs) = manager();
+        let frame = |value| MappedEvent::ELRSData {
+            pre_package: HashMap::from([(0, value)]),
+            armed: true,
+        };
+
+        manager.route_event(frame(1000));
+        manager.route_event(frame(1500));
+        manager.route_event(frame(2000));
+        assert_eq!(manager.dropped.get(MappingType::ELRS), 2);
+        assert_eq!(manager.dropped.get(MappingType::Keyboard), 0);
+
+        assert_eq!(outputs.elrs_rx.try_recv().unwrap().channels[&0], 1000);
+        manager.route_event(frame(1200));
+        assert_eq!(outputs.elrs_rx.try_recv().unwrap().channels[&0], 1200);
+        assert_eq!(manager.dropped.get(MappingType::ELRS), 2);
+    }
+}
@@ -19,15 +19,18 @@ pub mod elrs;
 pub mod engine;
 pub mod error;
 pub mod keyboard;
+pub mod kill_switch;
 pub mod manager;
+pub mod navigation;
 pub mod strategy;
 
 // Re-exports for simpler API access
 pub use engine::{MappingEngine, MappingEngineHandle, MappingEngineState};
 pub use error::MappingError;
-pub use manager::MappingEngineManager;
+pub use manager::{DroppedEvents, MappingCommand, MappingEngineManager};
 pub use strategy::{MappingConfig, MappingStrategy, MappingType};
 
+use chrono::{DateTime, Local};
 use eframe::egui;
 use std::collections::HashMap;
 
@@ -41,13 +44,22 @@ pub enum MappedEvent {
     ///
     /// Contains egui events that can be injected into the UI event loop
     /// for gamepad-controlled navigation and text input.
-    KeyboardEvent { key_code: Vec<egui::Event> },
+    /// `collected_at` is the input's collection time while input latency is
+    /// measured, see [`crate::latency`].
+    KeyboardEvent {
+        key_code: Vec<egui::Event>,
+        collected_at: Option<DateTime<Local>>,
+    },
 
     /// ELRS data for RC vehicle control
     ///
     /// Pre-formatted channel data ready for CRSF protocol transmission.
     /// Keys are channel numbers (0-15), values are microsecond pulse widths.
-    ELRSData { pre_package: HashMap<u16, u16> },
+    /// `armed` is false while the safety gate holds channels at failsafe.
+    ELRSData {
+        pre_package: HashMap<u16, u16>,
+        armed: bool,
+    },
 
     /// Custom events for protocol extensions
     ///
@@ -58,10 +70,23 @@ pub enum MappedEvent {
     },
 }
 
+/// Keyboard events sent to the UI in one batch
+#[derive(Debug, Clone, Default)]
+pub struct KeyboardBatch {
+    pub events: Vec<egui::Event>,
+    /// Collection time of the oldest stamped input in the batch, see
+    /// [`crate::latency`]
+    pub collected_at: Option<DateTime<Local>>,
+}
+
+/// Smallest accepted rate limit; anything lower would busy-loop the engine
+pub const MIN_RATE_LIMIT_MS: u64 = 1;
+
 /// Rate limiter for CPU efficiency on SBCs
 ///
 /// Prevents mapping engines from consuming excessive CPU when idle.
-/// Each engine can configure its own rate based on protocol requirements.
+/// Each engine gets its own limiter, built by the manager from the
+/// interval in its mapping config ([`MappingConfig::rate_limit_ms`]).
 #[derive(Debug, Clone)]
 pub struct RateLimiter {
     min_interval_ms: u64,
@@ -79,19 +104,40 @@ impl RateLimiter {
         }
     }
 
+    /// Rejects intervals below [`MIN_RATE_LIMIT_MS`]
+    pub fn validate_interval(min_interval_ms: u64) -> Result<(), MappingError> {
+        if min_interval_ms < MIN_RATE_LIMIT_MS {
+            return Err(MappingError::ConfigError(format!(
+                "Rate limit of {}ms is below the minimum of {}ms",
+                min_interval_ms, MIN_RATE_LIMIT_MS
+            )));
+        }
+        Ok(())
+    }
+
     /// Checks if enough time has passed since last event
     ///
     /// Updates internal timestamp when returning true. This ensures
     /// consistent timing between events.
     pub fn should_process(&mut self) -> bool {
-        let now = std::time::Instant::now();
-        let elapsed = now.duration_since(self.last_event_time);
-
-        if elapsed.as_millis() as u64 >= self.min_interval_ms {
-            self.last_event_time = now;
+        if self.is_due() {
+            self.restart();
             true
         } else {
             false
         }
     }
+
+    /// Checks if enough time has passed without updating the timestamp
+    ///
+    /// Lets callers decide whether they actually produce an event before
+    /// starting a new interval with [`RateLimiter::restart`].
+    pub fn is_due(&self) -> bool {
+        self.last_event_time.elapsed().as_millis() as u64 >= self.min_interval_ms
+    }
+
+    /// Starts a new interval from now
+    pub fn restart(&mut self) {
+        self.last_event_time = std::time::Instant::now();
+    }
 }
@@ -0,0 +1,115 @@
+//! # Menu Navigation Mapping
+//!
+//! Switches the top level menus of the UI with gamepad buttons.
+//!
+//! ## Why This Module Exists
+//!
+//! The device has no mouse or keyboard, so the MainMenu/MQTT/ELRS/Settings
+//! buttons of the top panel are out of reach without a touchscreen. Buttons
+//! bound to a [`NavigationAction`] switch menus directly instead.
+//!
+//! ## Design Rationale
+//!
+//! ### Intercepted Before the Engines
+//! The manager runs every controller input through a [`NavigationInterceptor`]
+//! before handing it to the engines. Bound buttons are removed from the input,
+//! so a navigation button never types a key or reaches any other mapping, and
+//! navigation keeps working while only ELRS is active.
+//!
+//! ### Part of the Button Mapping
+//! Bindings are stored in the keyboard config next to keys and modifiers, and
+//! a button has exactly one of them. Assigning navigation in the settings
+//! therefore replaces a key mapping instead of silently shadowing it.
+//!
+//! ### One Action per Press
+//! Like custom payloads, an action fires when its button goes down and again
+//! only after it was released. Holding a bumper does not cycle through menus.
+
+use crate::controller::controller_handle::{ButtonEventState, ButtonType, ControllerOutput};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Menu change requested by a navigation button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NavigationAction {
+    /// Next menu in top panel order, wrapping around
+    NextMenu,
+    /// Previous menu in top panel order, wrapping around
+    PreviousMenu,
+    MainMenu,
+    Mqtt,
+    Elrs,
+    Settings,
+}
+
+impl NavigationAction {
+    pub const ALL: [NavigationAction; 6] = [
+        NavigationAction::NextMenu,
+        NavigationAction::PreviousMenu,
+        NavigationAction::MainMenu,
+        NavigationAction::Mqtt,
+        NavigationAction::Elrs,
+        NavigationAction::Settings,
+    ];
+}
+
+impl std::fmt::Display for NavigationAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            NavigationAction::NextMenu => "Next menu",
+            NavigationAction::PreviousMenu => "Previous menu",
+            NavigationAction::MainMenu => "Open MainMenu",
+            NavigationAction::Mqtt => "Open MQTT",
+            NavigationAction::Elrs => "Open ELRS",
+            NavigationAction::Settings => "Open Settings",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Takes navigation buttons out of controller input
+#[derive(Debug, Default)]
+pub struct NavigationInterceptor {
+    bindings: HashMap<ButtonType, NavigationAction>,
+    /// Bound buttons reported as held, to detect new presses
+    held: HashSet<ButtonType>,
+}
+
+impl NavigationInterceptor {
+    /// Replaces the bindings, forgetting buttons held under the old ones
+    pub fn set_bindings(&mut self, bindings: HashMap<ButtonType, NavigationAction>) {
+        self.held.retain(|button| bindings.contains_key(button));
+        self.bindings = bindings;
+    }
+
+    /// Removes bound buttons from `input` and returns the actions they started.
+    ///
+    /// Bound buttons that are no longer reported count as released.
+    pub fn intercept(&mut self, input: &mut ControllerOutput) -> Vec<NavigationAction> {
+        if self.bindings.is_empty() {
+            return Vec::new();
+        }
+
+        let mut actions = Vec::new();
+        let mut reported = HashSet::new();
+        input.button_events.retain(|event| {
+            let Some(action) = self.bindings.get(&event.button) else {
+                return true;
+            };
+            reported.insert(event.button.clone());
+
+            let started = match event.state {
+                ButtonEventState::Held => self.held.insert(event.button.clone()),
+                // A release is only a new press if the button was never seen held
+                ButtonEventState::Complete => !self.held.remove(&event.button),
+            };
+            if started {
+                actions.push(*action);
+            }
+            false
+        });
+
+        self.held.retain(|button| reported.contains(button));
+        actions
+    }
+}
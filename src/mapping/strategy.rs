@@ -75,13 +75,27 @@ pub trait MappingConfig: Send + Sync + 'static {
     fn get_description(&self) -> String {
         format!("Configuration for {} mapping", self.get_type())
     }
+
+    /// Rate limiting interval in milliseconds
+    ///
+    /// If specified, the mapping engine will skip calls to `map()` until
+    /// this interval has elapsed since the last call. Useful for protocols
+    /// with limited bandwidth or to reduce CPU usage on SBCs.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ms)` - Minimum milliseconds between map() calls
+    /// * `None` - No rate limiting (default implementation)
+    fn rate_limit_ms(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// Core mapping strategy trait
 ///
 /// Transforms controller input into protocol-specific output events.
-/// Strategies maintain internal state through `MappingContext` and can
-/// implement rate limiting for CPU efficiency.
+/// Strategies maintain internal state through `MappingContext`. Rate
+/// limiting is configured per mapping config and applied by the engine.
 ///
 /// # Lifecycle
 ///
@@ -110,6 +124,15 @@ pub trait MappingStrategy: Send + Sync + 'static {
     /// * `None` - No output for this input (filtered, rate limited, etc.)
     fn map(&mut self, input: &ControllerOutput) -> Option<MappedEvent>;
 
+    /// Output for an engine cycle without new controller input
+    ///
+    /// The controller only reports changes, so a resting gamepad produces no
+    /// input. Strategies that must keep a link alive return an event here; it
+    /// is rate limited like regular output. Defaults to no output.
+    fn map_idle(&mut self) -> Option<MappedEvent> {
+        None
+    }
+
     /// One-time initialization when strategy is activated
     ///
     /// Load configuration, initialize internal state, setup connections.
@@ -122,20 +145,6 @@ pub trait MappingStrategy: Send + Sync + 'static {
     /// Called once when the mapping engine shuts down this strategy.
     fn shutdown(&mut self);
 
-    /// Rate limiting interval in milliseconds
-    ///
-    /// If specified, the mapping engine will skip calls to `map()` until
-    /// this interval has elapsed since the last call. Useful for protocols
-    /// with limited bandwidth or to reduce CPU usage on SBCs.
-    ///
-    /// # Returns
-    ///
-    /// * `Some(ms)` - Minimum milliseconds between map() calls
-    /// * `None` - No rate limiting (default implementation)
-    fn get_rate_limit(&self) -> Option<u64> {
-        None
-    }
-
     /// Returns the mapping type this strategy implements
     fn get_type(&self) -> MappingType;
 }
@@ -179,4 +188,44 @@ pub struct MappingContext {
     /// Enables time-based logic like rate limiting, timeouts,
     /// or time-delta calculations within strategies.
     pub last_timestamp: Option<std::time::SystemTime>,
+
+    /// Buttons currently held down, for key auto-repeat
+    ///
+    /// An entry is added on the first held cycle of a button and removed on
+    /// its release, so strategies can tell a new press from a held one.
+    pub held_buttons:
+        std::collections::HashMap<crate::controller::controller_handle::ButtonType, HeldButton>,
+
+    /// Chords currently held down, by name
+    ///
+    /// Lets strategies send a key up when a chord is released.
+    pub held_chords: std::collections::HashSet<String>,
+
+    /// Joystick combo currently dwelled on, for dwell-to-select input
+    ///
+    /// Replaced whenever the combo changes, so a letter is emitted once per
+    /// entry into its combo.
+    pub dwell: Option<Dwell>,
+}
+
+/// Hold progress of a button, see [`MappingContext::held_buttons`]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HeldButton {
+    /// How long the button has been held, as reported by the controller
+    pub duration_ms: f64,
+    /// Key repeats already emitted for this hold
+    pub repeats: u32,
+}
+
+/// Dwell progress of a joystick combo, see [`MappingContext::dwell`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dwell {
+    /// Sections of the left and right stick
+    pub sections: (Section, Section),
+    /// Whether the combo was entered in symbol mode
+    pub symbol_mode: bool,
+    /// When the combo was entered
+    pub since: std::time::Instant,
+    /// Whether the combo already emitted its letter
+    pub fired: bool,
 }
@@ -0,0 +1,289 @@
+//! # Protocol-Independent MQTT Client
+//!
+//! Wraps the rumqttc v3.1.1 and v5 clients behind one interface so the
+//! connection state machine does not care which protocol version is spoken.
+//!
+//! ## Why This Module Exists
+//!
+//! rumqttc implements MQTT 5 as a separate client with its own packet, event
+//! and error types. Only a handful of operations and events matter to
+//! OpenController, so both versions are mapped onto [`MqttClient`],
+//! [`MqttEventLoop`] and [`Notification`] here instead of duplicating the
+//! handler for each version.
+//!
+//! ## Design Rationale
+//!
+//! ### Version as Enum Variant
+//! The version is fixed when a client is built and only changes through a
+//! reconnect, so plain enums are enough; no trait objects are needed.
+//!
+//! ### Reduced Event Set
+//! [`Notification`] only carries what the handler acts on: CONNACKs,
+//! publishes, SUBACKs and the outgoing SUBSCRIBE/DISCONNECT markers. All
+//! other protocol traffic is reported as [`Notification::Other`].
+//!
+//! ### Message Metadata
+//! Received publishes keep their QoS, retain and dup flags in the
+//! [`MQTTMessage`]. Over v5 user properties and content type are kept as
+//! well; v3 messages simply leave them empty.
+
+use rumqttc::v5;
+use rumqttc::v5::mqttbytes::v5::Packet as V5Packet;
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Outgoing, Packet, QoS};
+use std::time::Duration;
+
+use super::config::MqttVersion;
+use super::message_manager::MQTTMessage;
+
+/// Capacity of the request queue between client and event loop.
+const REQUEST_CAPACITY: usize = 10;
+
+/// Connection parameters shared by both protocol versions.
+#[derive(Debug, Clone)]
+pub struct ConnectOptions {
+    pub version: MqttVersion,
+    pub client_id: String,
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub pw: String,
+    pub keep_alive: Duration,
+}
+
+/// Errors from queueing a request on either client.
+#[derive(Debug, thiserror::Error)]
+pub enum MqttClientError {
+    #[error(transparent)]
+    V3(#[from] rumqttc::ClientError),
+
+    #[error(transparent)]
+    V5(#[from] v5::ClientError),
+}
+
+/// Errors from polling either event loop.
+#[derive(Debug, thiserror::Error)]
+pub enum MqttConnectionError {
+    #[error(transparent)]
+    V3(#[from] rumqttc::ConnectionError),
+
+    #[error(transparent)]
+    V5(#[from] v5::ConnectionError),
+}
+
+impl MqttConnectionError {
+    /// Return code if the broker refused the connection.
+    pub fn refusal(&self) -> Option<String> {
+        match self {
+            Self::V3(rumqttc::ConnectionError::ConnectionRefused(code)) => {
+                Some(format!("{:?}", code))
+            }
+            Self::V5(v5::ConnectionError::ConnectionRefused(code)) => Some(format!("{:?}", code)),
+            _ => None,
+        }
+    }
+}
+
+/// Event loop notification reduced to what the handler acts on.
+#[derive(Debug)]
+pub enum Notification {
+    /// Broker answered the CONNECT; `refusal` holds the code if it declined
+    ConnAck { refusal: Option<String> },
+    /// Message received on a subscribed topic
+    Publish(MQTTMessage),
+    /// Broker answered a SUBSCRIBE; `rejection` holds the reason if it declined
+    SubAck {
+        pkid: u16,
+        rejection: Option<String>,
+    },
+    /// A queued SUBSCRIBE was written with this packet id
+    SubscribeSent(u16),
+    /// The queued DISCONNECT was written
+    DisconnectSent,
+    /// Any other protocol traffic
+    Other,
+}
+
+/// Client handle of either protocol version.
+#[derive(Debug, Clone)]
+pub enum MqttClient {
+    V3(AsyncClient),
+    V5(v5::AsyncClient),
+}
+
+/// Event loop of either protocol version.
+///
+/// Both event loops are boxed, they are large and differ in size.
+pub enum MqttEventLoop {
+    V3(Box<EventLoop>),
+    V5(Box<v5::EventLoop>),
+}
+
+impl std::fmt::Debug for MqttEventLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V3(_) => write!(f, "MqttEventLoop::V3"),
+            Self::V5(_) => write!(f, "MqttEventLoop::V5"),
+        }
+    }
+}
+
+/// Creates a client and event loop speaking the requested protocol version.
+///
+/// Nothing is sent until the event loop is polled.
+pub fn connect(options: ConnectOptions) -> (MqttClient, MqttEventLoop) {
+    match options.version {
+        MqttVersion::V3 => {
+            let mut mqtt_options = MqttOptions::new(options.client_id, options.host, options.port);
+            mqtt_options
+                .set_credentials(options.user, options.pw)
+                .set_keep_alive(options.keep_alive);
+            let (client, event_loop) = AsyncClient::new(mqtt_options, REQUEST_CAPACITY);
+            (
+                MqttClient::V3(client),
+                MqttEventLoop::V3(Box::new(event_loop)),
+            )
+        }
+        MqttVersion::V5 => {
+            let mut mqtt_options =
+                v5::MqttOptions::new(options.client_id, options.host, options.port);
+            mqtt_options
+                .set_credentials(options.user, options.pw)
+                .set_keep_alive(options.keep_alive);
+            let (client, event_loop) = v5::AsyncClient::new(mqtt_options, REQUEST_CAPACITY);
+            (
+                MqttClient::V5(client),
+                MqttEventLoop::V5(Box::new(event_loop)),
+            )
+        }
+    }
+}
+
+impl MqttClient {
+    /// Queues a QoS 1 subscription.
+    pub async fn subscribe(&self, topic: &str) -> Result<(), MqttClientError> {
+        match self {
+            Self::V3(client) => client.subscribe(topic, QoS::AtLeastOnce).await?,
+            Self::V5(client) => {
+                client
+                    .subscribe(topic, v5::mqttbytes::QoS::AtLeastOnce)
+                    .await?
+            }
+        }
+        Ok(())
+    }
+
+    /// Queues an unsubscribe.
+    pub async fn unsubscribe(&self, topic: &str) -> Result<(), MqttClientError> {
+        match self {
+            Self::V3(client) => client.unsubscribe(topic).await?,
+            Self::V5(client) => client.unsubscribe(topic).await?,
+        }
+        Ok(())
+    }
+
+    /// Queues a QoS 1 publish.
+    pub async fn publish(
+        &self,
+        topic: &str,
+        retain: bool,
+        payload: Vec<u8>,
+    ) -> Result<(), MqttClientError> {
+        match self {
+            Self::V3(client) => {
+                client
+                    .publish(topic, QoS::AtLeastOnce, retain, payload)
+                    .await?
+            }
+            Self::V5(client) => {
+                client
+                    .publish(topic, v5::mqttbytes::QoS::AtLeastOnce, retain, payload)
+                    .await?
+            }
+        }
+        Ok(())
+    }
+
+    /// Queues a DISCONNECT.
+    pub async fn disconnect(&self) -> Result<(), MqttClientError> {
+        match self {
+            Self::V3(client) => client.disconnect().await?,
+            Self::V5(client) => client.disconnect().await?,
+        }
+        Ok(())
+    }
+}
+
+impl MqttEventLoop {
+    /// Drives the connection and returns the next notification.
+    pub async fn poll(&mut self) -> Result<Notification, MqttConnectionError> {
+        match self {
+            Self::V3(event_loop) => Ok(Self::from_v3(event_loop.poll().await?)),
+            Self::V5(event_loop) => Ok(Self::from_v5(event_loop.poll().await?)),
+        }
+    }
+
+    fn from_v3(event: Event) -> Notification {
+        match event {
+            Event::Incoming(Packet::ConnAck(ack)) => Notification::ConnAck {
+                refusal: (ack.code != rumqttc::ConnectReturnCode::Success)
+                    .then(|| format!("{:?}", ack.code)),
+            },
+            Event::Incoming(Packet::Publish(publish)) => Notification::Publish(
+                MQTTMessage::from_bytes(publish.topic, &publish.payload).with_delivery(
+                    publish.qos as u8,
+                    publish.retain,
+                    publish.dup,
+                ),
+            ),
+            Event::Incoming(Packet::SubAck(ack)) => Notification::SubAck {
+                pkid: ack.pkid,
+                rejection: match ack.return_codes.first() {
+                    Some(rumqttc::SubscribeReasonCode::Success(_)) => None,
+                    _ => Some("Rejected by broker".to_string()),
+                },
+            },
+            Event::Outgoing(outgoing) => Self::from_outgoing(outgoing),
+            Event::Incoming(_) => Notification::Other,
+        }
+    }
+
+    fn from_v5(event: v5::Event) -> Notification {
+        match event {
+            v5::Event::Incoming(V5Packet::ConnAck(ack)) => Notification::ConnAck {
+                refusal: (ack.code != v5::mqttbytes::v5::ConnectReturnCode::Success)
+                    .then(|| format!("{:?}", ack.code)),
+            },
+            v5::Event::Incoming(V5Packet::Publish(publish)) => {
+                let topic = String::from_utf8_lossy(&publish.topic).to_string();
+                let mut msg = MQTTMessage::from_bytes(topic, &publish.payload).with_delivery(
+                    publish.qos as u8,
+                    publish.retain,
+                    publish.dup,
+                );
+                if let Some(properties) = publish.properties {
+                    msg.user_properties = properties.user_properties;
+                    msg.content_type = properties.content_type;
+                }
+                Notification::Publish(msg)
+            }
+            v5::Event::Incoming(V5Packet::SubAck(ack)) => Notification::SubAck {
+                pkid: ack.pkid,
+                rejection: match ack.return_codes.first() {
+                    Some(v5::mqttbytes::v5::SubscribeReasonCode::Success(_)) => None,
+                    Some(code) => Some(format!("{:?}", code)),
+                    None => Some("Rejected by broker".to_string()),
+                },
+            },
+            v5::Event::Outgoing(outgoing) => Self::from_outgoing(outgoing),
+            v5::Event::Incoming(_) => Notification::Other,
+        }
+    }
+
+    fn from_outgoing(outgoing: Outgoing) -> Notification {
+        match outgoing {
+            Outgoing::Subscribe(pkid) => Notification::SubscribeSent(pkid),
+            Outgoing::Disconnect => Notification::DisconnectSent,
+            _ => Notification::Other,
+        }
+    }
+}
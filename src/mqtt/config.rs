@@ -25,10 +25,117 @@
 //! - Switch between servers without losing topic subscriptions
 //! - Maintain separate topic lists per server configuration
 //! - Adjust polling frequency based on use case requirements
+//! - Bundle a server with its subscriptions in a named [`MqttProfile`] and
+//!   switch both at once
 
 use crate::ui::{self, MQTTServer};
 use chrono::SecondsFormat;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// Prefix used for generated MQTT client identifiers.
+const CLIENT_ID_PREFIX: &str = "OpenController";
+
+/// Smallest keep-alive interval accepted by the connection handler (seconds).
+pub const MIN_KEEP_ALIVE_SECS: u16 = 1;
+
+/// Keep-alive interval used when none is configured (seconds).
+pub const DEFAULT_KEEP_ALIVE_SECS: u16 = 5;
+
+/// Accepted config poll frequencies in Hz, see [`MqttConfig::poll_frequency`]
+pub const POLL_FREQUENCY_RANGE: RangeInclusive<usize> = 1..=100;
+
+/// Config poll frequency in Hz of new configurations
+pub const DEFAULT_POLL_FREQUENCY: usize = 10;
+
+/// Broker port used when a plain endpoint has no explicit port.
+pub const DEFAULT_MQTT_PORT: u16 = 1883;
+
+/// Broker port used when an `mqtts://` endpoint has no explicit port.
+pub const DEFAULT_MQTTS_PORT: u16 = 8883;
+
+/// MQTT protocol version spoken with the broker.
+///
+/// v5 adds user properties, content types and detailed reason codes. v3.1.1
+/// stays the default so existing sessions connect exactly as before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MqttVersion {
+    /// MQTT 3.1.1
+    #[default]
+    V3,
+    /// MQTT 5
+    V5,
+}
+
+impl MqttVersion {
+    /// All versions in display order
+    pub const ALL: [MqttVersion; 2] = [MqttVersion::V3, MqttVersion::V5];
+}
+
+impl std::fmt::Display for MqttVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MqttVersion::V3 => write!(f, "MQTT 3.1.1"),
+            MqttVersion::V5 => write!(f, "MQTT 5"),
+        }
+    }
+}
+
+/// Which topics a message sent from the editor is published to.
+///
+/// Received messages are unaffected; only outgoing messages are routed by
+/// this mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PublishTarget {
+    /// Publish a copy to every subscribed topic, useful to poke all monitored
+    /// topics at once
+    Broadcast,
+    /// Publish only to [`MqttConfig::publish_topic`]; nothing is sent while
+    /// no topic is selected
+    #[default]
+    SelectedTopic,
+    /// Publish to the topic stored in the message itself, e.g. a resent
+    /// history entry goes back to the topic it was saved with
+    MessageTopic,
+}
+
+impl PublishTarget {
+    /// All modes in display order
+    pub const ALL: [PublishTarget; 3] = [
+        PublishTarget::Broadcast,
+        PublishTarget::SelectedTopic,
+        PublishTarget::MessageTopic,
+    ];
+}
+
+impl std::fmt::Display for PublishTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PublishTarget::Broadcast => write!(f, "All subscribed"),
+            PublishTarget::SelectedTopic => write!(f, "Selected topic"),
+            PublishTarget::MessageTopic => write!(f, "Message topic"),
+        }
+    }
+}
+
+/// Reasons a broker URL cannot be turned into a host and port.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum EndpointError {
+    #[error("Server URL is empty")]
+    Empty,
+
+    #[error("Unsupported scheme '{0}', use mqtt:// or mqtts://")]
+    UnsupportedScheme(String),
+
+    #[error("Server URL has no host")]
+    MissingHost,
+
+    #[error("Invalid port '{0}', expected a number between 1 and 65535")]
+    InvalidPort(String),
+}
 
 /// Central configuration for all MQTT connection and subscription settings.
 ///
@@ -65,6 +172,14 @@ pub struct MqttConfig {
     /// subscribe/unsubscribe operations on the active MQTT connection.
     pub subbed_topics: Vec<String>,
 
+    /// Topics pinned to the quick-bar of the MQTT menu.
+    ///
+    /// Kept independent of `available_topics`, so a pin survives removing
+    /// the topic from the list; such pins are shown but cannot be toggled.
+    /// Defaults to empty so older session files still load.
+    #[serde(default)]
+    pub pinned_topics: Vec<String>,
+
     /// Active MQTT server configuration for current connection.
     ///
     /// Contains broker URL, credentials, and connection status. This is the
@@ -90,7 +205,247 @@ pub struct MqttConfig {
     /// - **High frequency (>20Hz)**: Responsive UI, higher CPU usage
     /// - **Medium frequency (5-15Hz)**: Good balance for most use cases  
     /// - **Low frequency (<5Hz)**: Minimal overhead, acceptable for background monitoring
+    ///
+    /// Every check reads the config through the ConfigPortal and triggers a
+    /// session save, so the cost grows linearly with the frequency. Values
+    /// outside [`POLL_FREQUENCY_RANGE`] are clamped by [`MqttConfig::validate`].
     pub poll_frequency: usize,
+
+    /// Client identifier presented to the broker.
+    ///
+    /// Brokers disconnect the older session when two clients share an ID,
+    /// so the default carries a random suffix to let several OpenController
+    /// instances run against the same broker.
+    #[serde(default = "default_client_id")]
+    pub client_id: String,
+
+    /// MQTT keep-alive interval in seconds.
+    ///
+    /// Longer intervals tolerate flaky links better, shorter ones detect
+    /// dead connections faster. Must be at least [`MIN_KEEP_ALIVE_SECS`].
+    #[serde(default = "default_keep_alive_secs")]
+    pub keep_alive_secs: u16,
+
+    /// Protocol version used for all servers, v3.1.1 unless set.
+    #[serde(default)]
+    pub protocol_version: MqttVersion,
+
+    /// Where outgoing messages are published, see [`PublishTarget`].
+    ///
+    /// Defaults to the selected topic so older sessions no longer broadcast
+    /// to every subscription.
+    #[serde(default)]
+    pub publish_target: PublishTarget,
+
+    /// Topic used by [`PublishTarget::SelectedTopic`], empty if none selected.
+    #[serde(default)]
+    pub publish_topic: String,
+
+    /// Named server and subscription bundles for quick switching.
+    ///
+    /// Defaults to empty so older session files still load.
+    #[serde(default)]
+    pub profiles: Vec<MqttProfile>,
+}
+
+/// A named server together with the topics subscribed on it.
+///
+/// Applying a profile replaces the active server and the subscriptions in one
+/// config change, so the connection handler reconnects and subscribes in a
+/// single reconfiguration instead of one per changed setting.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct MqttProfile {
+    /// Unique display name
+    pub name: String,
+    /// Broker account connected to when the profile is applied
+    pub server: MQTTServer,
+    /// Topics subscribed when the profile is applied
+    pub topics: Vec<String>,
+}
+
+/// Generates a client ID of the form `OpenController-<rand>`.
+///
+/// Uses the randomly seeded std hasher so no extra dependency is needed.
+pub fn default_client_id() -> String {
+    let suffix = RandomState::new().build_hasher().finish() as u32;
+    format!("{}-{:08x}", CLIENT_ID_PREFIX, suffix)
+}
+
+fn default_keep_alive_secs() -> u16 {
+    DEFAULT_KEEP_ALIVE_SECS
+}
+
+impl MqttConfig {
+    /// Clamps values the connection handler cannot work with.
+    ///
+    /// A poll frequency of 0 would never return to check the config, huge
+    /// ones make the handler re-read it in a hot loop; both are clamped into
+    /// [`POLL_FREQUENCY_RANGE`].
+    ///
+    /// Returns a description of every repaired value.
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut repairs = Vec::new();
+        let clamped = self
+            .poll_frequency
+            .clamp(*POLL_FREQUENCY_RANGE.start(), *POLL_FREQUENCY_RANGE.end());
+        if clamped != self.poll_frequency {
+            repairs.push(format!(
+                "Poll frequency {}Hz clamped to {}Hz",
+                self.poll_frequency, clamped
+            ));
+            self.poll_frequency = clamped;
+        }
+        repairs
+    }
+
+    /// Time between two config checks of the connection handler.
+    ///
+    /// Clamps like [`MqttConfig::validate`], so an unvalidated config is safe too.
+    pub fn poll_interval(&self) -> Duration {
+        let frequency = self
+            .poll_frequency
+            .clamp(*POLL_FREQUENCY_RANGE.start(), *POLL_FREQUENCY_RANGE.end());
+        Duration::from_millis(1000 / frequency as u64)
+    }
+
+    /// Whether switching from this config to `new` needs a new client.
+    ///
+    /// Only the effective connection parameters count: the broker endpoint,
+    /// trimmed credentials and client ID, keep-alive and protocol version.
+    /// Topic, profile and publish settings are applied on the running client.
+    pub fn needs_reconnect(&self, new: &MqttConfig) -> bool {
+        !self.server.same_connection(&new.server)
+            || self.client_id.trim() != new.client_id.trim()
+            || self.keep_alive_secs != new.keep_alive_secs
+            || self.protocol_version != new.protocol_version
+    }
+
+    /// Checks that the keep-alive interval is usable.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if `keep_alive_secs` is below
+    /// [`MIN_KEEP_ALIVE_SECS`].
+    pub fn validate_keep_alive(keep_alive_secs: u16) -> Result<(), String> {
+        if keep_alive_secs < MIN_KEEP_ALIVE_SECS {
+            return Err(format!(
+                "Keep-alive must be at least {} second(s)",
+                MIN_KEEP_ALIVE_SECS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Splits a broker URL into host and port.
+    ///
+    /// Accepts `mqtt://host[:port]`, `mqtts://host[:port]` and `host[:port]`.
+    /// Missing ports default to [`DEFAULT_MQTT_PORT`], or [`DEFAULT_MQTTS_PORT`]
+    /// for `mqtts://`. The scheme only selects the default port; the
+    /// connection itself does not negotiate TLS.
+    ///
+    /// # Errors
+    /// Returns an [`EndpointError`] for empty URLs, other schemes such as
+    /// `http://`, a missing host, or a port that is not a valid non-zero `u16`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// assert_eq!(
+    ///     MqttConfig::parse_endpoint("mqtt://broker.local"),
+    ///     Ok(("broker.local".to_string(), 1883))
+    /// );
+    /// assert!(MqttConfig::parse_endpoint("broker:99999").is_err());
+    /// ```
+    pub fn parse_endpoint(url: &str) -> Result<(String, u16), EndpointError> {
+        let url = url.trim();
+        if url.is_empty() {
+            return Err(EndpointError::Empty);
+        }
+
+        let (rest, default_port) = match url.split_once("://") {
+            Some(("mqtt", rest)) => (rest, DEFAULT_MQTT_PORT),
+            Some(("mqtts", rest)) => (rest, DEFAULT_MQTTS_PORT),
+            Some((scheme, _)) => return Err(EndpointError::UnsupportedScheme(scheme.to_string())),
+            None => (url, DEFAULT_MQTT_PORT),
+        };
+
+        let (host, port) = match rest.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse::<u16>()
+                    .ok()
+                    .filter(|p| *p != 0)
+                    .ok_or_else(|| EndpointError::InvalidPort(port.to_string()))?;
+                (host, port)
+            }
+            None => (rest, default_port),
+        };
+
+        if host.is_empty() {
+            return Err(EndpointError::MissingHost);
+        }
+        Ok((host.to_string(), port))
+    }
+
+    /// Removes saved servers that duplicate an earlier entry.
+    ///
+    /// Uses [`MQTTServer::same_account`] as the uniqueness key and keeps the
+    /// first occurrence. Returns the number of removed entries.
+    pub fn dedup_servers(servers: &mut Vec<MQTTServer>) -> usize {
+        let before = servers.len();
+        let mut unique: Vec<MQTTServer> = Vec::with_capacity(before);
+        for server in servers.drain(..) {
+            if !unique.iter().any(|kept| kept.same_account(&server)) {
+                unique.push(server);
+            }
+        }
+        *servers = unique;
+        before - servers.len()
+    }
+
+    /// Switches server and subscriptions to those of `profile`.
+    ///
+    /// The profile's server and topics are added to the saved servers and
+    /// available topics if missing, so they stay selectable by hand.
+    pub fn apply_profile(&mut self, profile: &MqttProfile) {
+        let server = MQTTServer {
+            connected: false,
+            ..profile.server.clone()
+        };
+        if !self
+            .available_servers
+            .iter()
+            .any(|saved| saved.same_account(&server))
+        {
+            self.available_servers.push(server.clone());
+        }
+        for topic in &profile.topics {
+            if !self.available_topics.contains(topic) {
+                self.available_topics.push(topic.clone());
+            }
+        }
+        self.server = server;
+        self.subbed_topics = profile.topics.clone();
+    }
+
+    /// Returns the topics a message with topic `message_topic` is published to.
+    ///
+    /// Empty if [`PublishTarget::SelectedTopic`] has no topic selected, or
+    /// [`PublishTarget::Broadcast`] has no subscriptions.
+    pub fn publish_topics(&self, message_topic: &str) -> Vec<String> {
+        match self.publish_target {
+            PublishTarget::Broadcast => self.subbed_topics.clone(),
+            PublishTarget::SelectedTopic if self.publish_topic.is_empty() => Vec::new(),
+            PublishTarget::SelectedTopic => vec![self.publish_topic.clone()],
+            PublishTarget::MessageTopic => vec![message_topic.to_string()],
+        }
+    }
+
+    /// Returns the keep-alive as a `Duration`, clamped to the minimum.
+    ///
+    /// Guards the connection handler against hand-edited session files that
+    /// bypass the UI validation.
+    pub fn keep_alive(&self) -> Duration {
+        Duration::from_secs(self.keep_alive_secs.max(MIN_KEEP_ALIVE_SECS) as u64)
+    }
 }
 
 impl Default for MqttConfig {
@@ -100,6 +455,8 @@ impl Default for MqttConfig {
     /// - **Empty lists**: No assumptions about user's MQTT environment
     /// - **Default server**: Uses MQTTServer::default() for consistent empty state
     /// - **10Hz polling**: Balances responsiveness with CPU efficiency
+    /// - **Random client ID**: Avoids collisions between multiple instances
+    /// - **5s keep-alive**: Responsive connection monitoring on stable links
     ///
     /// ## Usage Context
     /// Used when:
@@ -114,15 +471,106 @@ impl Default for MqttConfig {
     fn default() -> Self {
         Self {
             // 10Hz provides good responsiveness for UI changes without excessive overhead
-            poll_frequency: 10,
+            poll_frequency: DEFAULT_POLL_FREQUENCY,
 
             // Start with empty topic lists - user will add as needed
             available_topics: Vec::new(),
             subbed_topics: Vec::new(),
+            pinned_topics: Vec::new(),
 
             // No default server - prevents unintended connections
             server: MQTTServer::default(),
             available_servers: Vec::new(),
+
+            client_id: default_client_id(),
+            keep_alive_secs: DEFAULT_KEEP_ALIVE_SECS,
+            protocol_version: MqttVersion::default(),
+            publish_target: PublishTarget::default(),
+            publish_topic: String::new(),
+            profiles: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(url: &str, user: &str, pw: &str) -> MQTTServer {
+        MQTTServer {
+            url: url.to_string(),
+            user: user.to_string(),
+            pw: pw.to_string(),
+            connected: false,
+        }
+    }
+
+    #[test]
+    fn same_account_compares_endpoint_and_user() {
+        let saved = server("broker.local:1883", "pi", "old");
+
+        assert!(saved.same_account(&server("mqtt://broker.local", "pi", "new")));
+        assert!(saved.same_account(&server(" broker.local:1883 ", "pi", "")));
+        assert!(!saved.same_account(&server("broker.local:1883", "admin", "old")));
+        assert!(!saved.same_account(&server("mqtts://broker.local", "pi", "old")));
+    }
+
+    #[test]
+    fn dedup_keeps_first_entry_per_account() {
+        let mut servers = vec![
+            server("broker.local", "pi", "first"),
+            server("other.local", "pi", "other"),
+            server("mqtt://broker.local:1883", "pi", "second"),
+            server("broker.local", "admin", "admin"),
+        ];
+
+        assert_eq!(MqttConfig::dedup_servers(&mut servers), 1);
+
+        assert_eq!(servers.len(), 3);
+        assert_eq!(servers[0].pw, "first");
+        assert_eq!(servers[1].url, "other.local");
+        assert_eq!(servers[2].user, "admin");
+        assert_eq!(MqttConfig::dedup_servers(&mut servers), 0);
+    }
+
+    fn config(url: &str, user: &str, pw: &str) -> MqttConfig {
+        MqttConfig {
+            server: server(url, user, pw),
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn topic_changes_do_not_reconnect() {
+        let current = config("broker.local:1883", "pi", "secret");
+        let mut new = current.clone();
+        new.available_topics = vec!["sensors/#".to_string(), "lights".to_string()];
+        new.subbed_topics = vec!["sensors/#".to_string()];
+        new.pinned_topics = vec!["lights".to_string()];
+
+        assert!(!current.needs_reconnect(&new));
+    }
+
+    #[test]
+    fn password_change_reconnects() {
+        let current = config("broker.local:1883", "pi", "secret");
+
+        assert!(current.needs_reconnect(&config("broker.local:1883", "pi", "other")));
+        assert!(current.needs_reconnect(&config("broker.local:1883", "admin", "secret")));
+        assert!(current.needs_reconnect(&config("broker.local:8883", "pi", "secret")));
+    }
+
+    #[test]
+    fn no_effective_change_does_not_reconnect() {
+        let current = config("broker.local:1883", "pi", "secret");
+        let mut new = config(" mqtt://broker.local ", " pi", "secret \n");
+        new.server.connected = true;
+        new.client_id = format!(" {} ", current.client_id);
+
+        assert!(!current.needs_reconnect(&new));
+        assert!(!current.needs_reconnect(&current.clone()));
+
+        new.keep_alive_secs = current.keep_alive_secs + 1;
+        assert!(current.needs_reconnect(&new));
+    }
 }
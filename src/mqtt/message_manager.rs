@@ -14,6 +14,7 @@
 //! ## Design Rationale
 //!
 //! - **Simple Message Model**: Focus on essential data (topic, content, timestamp)
+//! - **Binary as Hex**: Binary payloads are carried as hex text, see [`parse_hex`]
 //! - **UI-Friendly Formatting**: Multiple display formats for different UI contexts
 //! - **Channel-Based Routing**: Clean separation between message reception and distribution
 //! - **Serializable State**: Messages can be persisted for session management
@@ -26,11 +27,20 @@
 //! 3. **Transmission**: UI sends messages via `distribution_msg` receiver
 //! 4. **Display**: UI formats messages using built-in display methods
 
+use super::config::{EndpointError, MqttConfig, MqttVersion};
+use crate::persistence::TimestampSettings;
+use crate::supervisor::SharedReceiver;
+use crate::ui::MQTTServer;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// Number of content characters shown when a message is displayed with `{}`.
+const PREVIEW_CHARS: usize = 10;
+
 /// Represents a single MQTT message with metadata for application processing.
 ///
 /// ## Design Rationale
@@ -41,9 +51,9 @@ use tokio::sync::mpsc;
 ///
 /// ## Timestamp Strategy
 /// Uses `NaiveDateTime` instead of timezone-aware DateTime to simplify
-/// serialization and avoid timezone complexity in UI display. Since messages
-/// are typically displayed in local context, the timezone information is
-/// usually not critical for user understanding.
+/// serialization. The timestamp is always local time; [`TimestampSettings`]
+/// decides on display whether it is shown in local time or UTC and in which
+/// format.
 ///
 /// ## Serialization Support
 /// Implements Serde traits for:
@@ -60,9 +70,8 @@ pub struct MQTTMessage {
 
     /// Message payload content as UTF-8 string.
     ///
-    /// Assumes text-based content for UI display. Binary payloads should
-    /// be converted to appropriate string representation before creating
-    /// MQTTMessage instances.
+    /// Text payloads are stored as-is. Binary payloads are stored as hex
+    /// bytes (e.g. `DE AD BE EF`) and flagged with [`MQTTMessage::binary`].
     pub content: String,
 
     /// When the message was received or created by the application.
@@ -70,35 +79,60 @@ pub struct MQTTMessage {
     /// Set automatically during message creation. Used for chronological
     /// ordering in UI display and message history management.
     pub timestamp: NaiveDateTime,
+
+    /// Whether the broker should retain this message for the topic.
+    ///
+    /// Retained messages are delivered to every future subscriber of the
+    /// topic, which makes them suitable for device state. Publishing an
+    /// empty payload with `retain` set clears the retained message on the
+    /// broker. Defaults to `false` so older session files still load.
+    #[serde(default)]
+    pub retain: bool,
+
+    /// QoS level a received message was delivered with.
+    ///
+    /// Messages published by the application always go out with QoS 1.
+    /// Defaults to 0 so older session files still load.
+    #[serde(default)]
+    pub qos: u8,
+
+    /// Whether the broker flagged a received message as a redelivery.
+    ///
+    /// Defaults to `false` so older session files still load.
+    #[serde(default)]
+    pub dup: bool,
+
+    /// Whether `content` holds hex bytes instead of text.
+    ///
+    /// Keeping binary payloads as hex leaves them readable in the UI and in
+    /// session files; [`MQTTMessage::payload`] decodes them for publishing.
+    /// Defaults to `false` so older session files still load.
+    #[serde(default)]
+    pub binary: bool,
+
+    /// MQTT 5 user properties of a received message, empty for v3.1.1.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_properties: Vec<(String, String)>,
+
+    /// MQTT 5 content type of a received message, if the publisher set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
 }
 
 impl fmt::Display for MQTTMessage {
     /// Provides a compact preview format for message list display.
     ///
-    /// ## Display Strategy
-    /// Shows timestamp followed by content preview for quick message scanning
-    /// in UI lists. The content is truncated to the first 10 characters
-    /// to provide context while maintaining compact display.
-    ///
-    /// ## Content Preview Logic
-    /// - **Short content (≤10 chars)**: Shows full content
-    /// - **Long content (>10 chars)**: Shows first 11 characters (positions 0-10)
-    ///
-    /// This provides a meaningful preview of message content while keeping
-    /// list entries compact and scannable in the UI.
+    /// Same as [`MQTTMessage::preview`] with [`PREVIEW_CHARS`] content
+    /// characters and the default [`TimestampSettings`]; the UI calls
+    /// `preview` with the session's settings instead.
     ///
     /// ## Output Format
     /// ```text
-    /// 2023-12-01 14:30:25 - Hello World
-    /// 2023-12-01 14:30:26 - This is a l... (truncated)
+    /// 2023-12-01 14:30:25 sensors/temperature: 23.5
+    /// 2023-12-01 14:30:26 notes: This is a …
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let preview = if self.content.len() > 10 {
-            &self.content[..=10] // First 11 characters (0-10 inclusive)
-        } else {
-            &self.content // Use entire string if short
-        };
-        write!(f, "{} - {}", self.timestamp, preview)
+        f.write_str(&self.preview(PREVIEW_CHARS, &TimestampSettings::default()))
     }
 }
 
@@ -128,9 +162,161 @@ impl MQTTMessage {
             topic,
             content,
             timestamp: chrono::Local::now().naive_local(),
+            retain: false,
+            qos: 0,
+            dup: false,
+            binary: false,
+            user_properties: Vec::new(),
+            content_type: None,
+        }
+    }
+
+    /// Creates a message from a raw payload received from the broker.
+    ///
+    /// UTF-8 payloads become text messages; anything else is kept as a
+    /// binary message with hex content instead of being dropped.
+    pub fn from_bytes(topic: String, payload: &[u8]) -> Self {
+        match std::str::from_utf8(payload) {
+            Ok(text) => Self::from_topic(topic, text.to_string()),
+            Err(_) => Self::from_topic(topic, format_hex(payload)).with_binary(true),
+        }
+    }
+
+    /// Marks the message as retained (or not) for publishing.
+    ///
+    /// See [`MQTTMessage::retain`] for broker semantics, including clearing
+    /// a retained topic by publishing an empty retained payload.
+    pub fn with_retain(mut self, retain: bool) -> Self {
+        self.retain = retain;
+        self
+    }
+
+    /// Sets the delivery flags of a received message.
+    pub fn with_delivery(mut self, qos: u8, retain: bool, dup: bool) -> Self {
+        self.qos = qos;
+        self.retain = retain;
+        self.dup = dup;
+        self
+    }
+
+    /// Compact labels of the delivery flags for list display.
+    ///
+    /// Always starts with the QoS level (`Q0`, `Q1`, `Q2`), followed by `R`
+    /// for retained and `D` for redelivered messages.
+    pub fn badges(&self) -> Vec<String> {
+        let mut badges = vec![format!("Q{}", self.qos)];
+        if self.retain {
+            badges.push("R".to_string());
+        }
+        if self.dup {
+            badges.push("D".to_string());
+        }
+        badges
+    }
+
+    /// Marks the content as hex bytes (or text) for publishing.
+    pub fn with_binary(mut self, binary: bool) -> Self {
+        self.binary = binary;
+        self
+    }
+
+    /// Returns the bytes to publish.
+    ///
+    /// # Errors
+    /// Fails with a [`HexError`] if a binary message's content is not valid hex.
+    pub fn payload(&self) -> Result<Vec<u8>, HexError> {
+        if self.binary {
+            parse_hex(&self.content)
+        } else {
+            Ok(self.content.clone().into_bytes())
         }
     }
 
+    /// Builds a `mosquitto_pub` command line that publishes this message.
+    ///
+    /// Host, port and user come from `server`; the password is only added
+    /// with `-P` if `include_password` is set. Topic and payload are single
+    /// quoted for POSIX shells. Binary messages are piped in through
+    /// `printf` and sent with `-s`, since raw bytes cannot be passed as an
+    /// argument. The message is sent with QoS 1 like from the application.
+    ///
+    /// # Errors
+    /// Fails with an [`EndpointError`] if the server URL cannot be parsed.
+    ///
+    /// # Examples
+    /// ```text
+    /// mosquitto_pub -h broker -p 1883 -u alice -q 1 -t 'home/light' -m 'ON'
+    /// printf '\xde\xad' | mosquitto_pub -h broker -p 1883 -q 1 -t 'raw' -s
+    /// ```
+    pub fn mosquitto_pub_command(
+        &self,
+        server: &MQTTServer,
+        version: MqttVersion,
+        include_password: bool,
+    ) -> Result<String, EndpointError> {
+        let (host, port) = MqttConfig::parse_endpoint(&server.url)?;
+
+        let mut args = vec![
+            "mosquitto_pub".to_string(),
+            format!("-h {}", shell_quote(&host)),
+            format!("-p {}", port),
+        ];
+        if !server.user.is_empty() {
+            args.push(format!("-u {}", shell_quote(&server.user)));
+        }
+        if include_password && !server.pw.is_empty() {
+            args.push(format!("-P {}", shell_quote(&server.pw)));
+        }
+        if version == MqttVersion::V5 {
+            args.push("-V mqttv5".to_string());
+        }
+        args.push("-q 1".to_string());
+        if self.retain {
+            args.push("-r".to_string());
+        }
+        args.push(format!("-t {}", shell_quote(&self.topic)));
+
+        // Hex that does not parse is passed on as text rather than dropped
+        match self.payload() {
+            Ok(bytes) if self.binary => {
+                let escaped: String = bytes.iter().map(|b| format!("\\x{:02x}", b)).collect();
+                args.push("-s".to_string());
+                Ok(format!("printf '{}' | {}", escaped, args.join(" ")))
+            }
+            _ => {
+                args.push(format!("-m {}", shell_quote(&self.content)));
+                Ok(args.join(" "))
+            }
+        }
+    }
+
+    /// Single-line preview: timestamp, topic and the start of the content.
+    ///
+    /// ## Content Preview Logic
+    /// - **Short content (≤ `max_chars`)**: Shows the full content
+    /// - **Long content**: Shows the first `max_chars` characters and `…`
+    ///
+    /// Counts characters rather than bytes, so multibyte content such as
+    /// umlauts or emoji is never cut inside a character. Line breaks and
+    /// other control characters are shown as spaces to keep the preview on
+    /// one line. The timestamp is formatted with `timestamps`.
+    pub fn preview(&self, max_chars: usize, timestamps: &TimestampSettings) -> String {
+        let mut chars = self.content.chars();
+        let preview: String = chars
+            .by_ref()
+            .take(max_chars)
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        let ellipsis = if chars.next().is_some() { "…" } else { "" };
+        format!(
+            "{} {}: {}{}",
+            timestamps.format(self.timestamp),
+            self.topic,
+            preview,
+            ellipsis
+        )
+    }
+
     /// Renders message in detailed format for full message display.
     ///
     /// Provides complete message information including timestamp, topic,
@@ -146,12 +332,123 @@ impl MQTTMessage {
     /// ## Design Choice
     /// Separates topic and content with newline for improved readability
     /// when displaying full message details, especially for longer topics
-    /// or multi-line content.
-    pub fn render(&self) -> String {
-        format!("{}: {}\n{}", self.timestamp, self.topic, self.content)
+    /// or multi-line content. The payload is formatted according to
+    /// `format`, see [`PayloadFormat`]. MQTT 5 content type and user
+    /// properties are listed between topic and payload when present. The
+    /// timestamp is formatted with `timestamps`.
+    pub fn render(&self, format: PayloadFormat, timestamps: &TimestampSettings) -> String {
+        let kind = if self.binary { " (hex)" } else { "" };
+        let mut header = format!(
+            "{}: {}{}",
+            timestamps.format(self.timestamp),
+            self.topic,
+            kind
+        );
+        if let Some(content_type) = &self.content_type {
+            header.push_str(&format!("\ncontent-type: {}", content_type));
+        }
+        for (key, value) in &self.user_properties {
+            header.push_str(&format!("\n{}: {}", key, value));
+        }
+        format!("{}\n{}", header, format.apply(&self.content))
     }
 }
 
+/// Reasons a binary payload cannot be decoded from hex.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum HexError {
+    #[error("Invalid hex digit '{found}' in '{token}'")]
+    InvalidDigit { token: String, found: char },
+
+    #[error("'{0}' has an odd number of hex digits")]
+    OddLength(String),
+}
+
+/// Decodes hex bytes such as `DE AD BE EF`, `deadbeef` or `0xDE 0xAD`.
+///
+/// Tokens are separated by whitespace, may carry a `0x` prefix and may hold
+/// several bytes each, but every token must have an even number of digits.
+pub fn parse_hex(input: &str) -> Result<Vec<u8>, HexError> {
+    let mut bytes = Vec::new();
+    for token in input.split_whitespace() {
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+        if let Some(found) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(HexError::InvalidDigit {
+                token: token.to_string(),
+                found,
+            });
+        }
+        if digits.len() % 2 != 0 {
+            return Err(HexError::OddLength(token.to_string()));
+        }
+        for pair in digits.as_bytes().chunks(2) {
+            // Only ASCII hex digits remain, so both conversions succeed
+            let pair = std::str::from_utf8(pair).unwrap_or_default();
+            bytes.push(u8::from_str_radix(pair, 16).unwrap_or_default());
+        }
+    }
+    Ok(bytes)
+}
+
+/// Formats bytes as space-separated uppercase hex, e.g. `DE AD BE EF`.
+pub fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Quotes a string for POSIX shells.
+///
+/// Wraps it in single quotes, inside which nothing is expanded; embedded
+/// single quotes are closed, escaped and reopened (`'\''`).
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Formatting mode for message payloads in detailed views.
+///
+/// ## Detection Strategy
+/// `PrettyJson` only attempts a parse when the trimmed payload starts with
+/// `{` or `[`, so plain values like `23.5` or `ON` never hit the JSON parser.
+/// Payloads that fail to parse are shown unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PayloadFormat {
+    /// Payload is shown exactly as received
+    #[default]
+    Raw,
+    /// JSON payloads are pretty-printed with indentation
+    PrettyJson,
+}
+
+impl PayloadFormat {
+    /// Formats a payload according to this mode.
+    ///
+    /// Returns the original content whenever pretty-printing does not apply.
+    pub fn apply(self, content: &str) -> String {
+        match self {
+            PayloadFormat::Raw => content.to_string(),
+            PayloadFormat::PrettyJson => {
+                pretty_json(content).unwrap_or_else(|| content.to_string())
+            }
+        }
+    }
+}
+
+/// Pretty-prints `content` if it is a JSON object or array.
+fn pretty_json(content: &str) -> Option<String> {
+    let trimmed = content.trim_start();
+    if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    serde_json::to_string_pretty(&value).ok()
+}
+
 /// Channel-based message router for MQTT message flow management.
 ///
 /// ## Design Rationale
@@ -170,7 +467,7 @@ impl MQTTMessage {
 /// manager.received_msg.send(message).await?;
 ///
 /// // MQTT handler receives outgoing messages
-/// if let Some(message) = manager.distribution_msg.recv().await {
+/// if let Ok(message) = manager.distribution_msg.try_recv() {
 ///     // Send to MQTT broker
 /// }
 /// ```
@@ -191,6 +488,163 @@ pub struct MsgManager {
     ///
     /// MQTT connection handler polls this receiver to get messages
     /// that should be published to the broker. UI components use
-    /// the corresponding sender to queue outgoing messages.
-    pub distribution_msg: mpsc::Receiver<MQTTMessage>,
+    /// the corresponding sender to queue outgoing messages. Shared, so
+    /// a restarted handler keeps reading the same channel.
+    pub distribution_msg: SharedReceiver<MQTTMessage>,
+}
+
+/// Length of the sliding window used for message rate measurement.
+pub const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Sliding-window arrival counter for per-topic message rates.
+///
+/// ## Design Rationale
+/// Stores one timestamp per arrival within [`RATE_WINDOW`] and drops older
+/// entries on every update or query, so topics that go quiet decay to zero
+/// without a background timer. Memory is bounded by the arrival rate of the
+/// busiest topic within a single window.
+#[derive(Debug, Default)]
+pub struct TopicRates {
+    arrivals: HashMap<String, VecDeque<Instant>>,
+}
+
+impl TopicRates {
+    /// Records an arrival for `topic` at the current time.
+    pub fn record(&mut self, topic: &str) {
+        let now = Instant::now();
+        let arrivals = self.arrivals.entry(topic.to_string()).or_default();
+        arrivals.push_back(now);
+        Self::prune(arrivals, now);
+    }
+
+    /// Drops expired arrivals and forgets topics without recent traffic.
+    pub fn decay(&mut self) {
+        let now = Instant::now();
+        self.arrivals.retain(|_, arrivals| {
+            Self::prune(arrivals, now);
+            !arrivals.is_empty()
+        });
+    }
+
+    /// Returns the messages per second for all topics matching `filter`.
+    ///
+    /// Supports MQTT wildcard filters, see [`topic_matches`]. Call
+    /// [`TopicRates::decay`] first so idle topics report zero.
+    pub fn rate(&self, filter: &str) -> usize {
+        self.arrivals
+            .iter()
+            .filter(|(topic, _)| topic_matches(filter, topic))
+            .map(|(_, arrivals)| arrivals.len())
+            .sum()
+    }
+
+    fn prune(arrivals: &mut VecDeque<Instant>, now: Instant) {
+        while arrivals
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > RATE_WINDOW)
+        {
+            arrivals.pop_front();
+        }
+    }
+}
+
+/// Checks whether a concrete `topic` matches an MQTT subscription `filter`.
+///
+/// Handles the single-level (`+`) and multi-level (`#`) wildcards.
+///
+/// # Examples
+/// ```rust
+/// assert!(topic_matches("sensors/+/temp", "sensors/kitchen/temp"));
+/// assert!(topic_matches("sensors/#", "sensors/kitchen/temp"));
+/// assert!(!topic_matches("sensors/+", "sensors/kitchen/temp"));
+/// ```
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    let mut filter_levels = filter.split('/');
+    let mut topic_levels = topic.split('/');
+    loop {
+        match (filter_levels.next(), topic_levels.next()) {
+            (Some("#"), _) => return true,
+            (Some("+"), Some(_)) => {}
+            (Some(f), Some(t)) if f == t => {}
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> MQTTMessage {
+        MQTTMessage::from_topic("notes".to_string(), content.to_string())
+    }
+
+    #[test]
+    fn display_handles_short_content() {
+        assert!(message("").to_string().ends_with("notes: "));
+        assert!(message("abc").to_string().ends_with("notes: abc"));
+        assert!(message("0123456789")
+            .to_string()
+            .ends_with("notes: 0123456789"));
+    }
+
+    #[test]
+    fn display_truncates_on_char_boundaries() {
+        // Each thermometer is two chars, the emoji and a variation selector
+        assert!(message("🌡️🌡️🌡️🌡️🌡️🌡️")
+            .to_string()
+            .ends_with("notes: 🌡️🌡️🌡️🌡️🌡️…"));
+        assert!(message("Grüße aus Köln")
+            .to_string()
+            .ends_with("notes: Grüße aus …"));
+        assert!(message("🚀").to_string().ends_with("notes: 🚀"));
+    }
+
+    fn payload(preview: &str) -> &str {
+        preview.split_once("notes: ").unwrap().1
+    }
+
+    #[test]
+    fn preview_keeps_multibyte_characters_whole() {
+        let timestamps = TimestampSettings::default();
+
+        let preview = message("Grüße 🌡️ aus Köln").preview(7, &timestamps);
+        assert_eq!(payload(&preview), "Grüße 🌡…");
+
+        // Exactly max_chars characters need no ellipsis
+        let preview = message("äöü").preview(3, &timestamps);
+        assert_eq!(payload(&preview), "äöü");
+
+        let preview = message("日本語のテキスト").preview(3, &timestamps);
+        assert_eq!(payload(&preview), "日本語…");
+    }
+
+    #[test]
+    fn preview_shortens_very_long_payloads() {
+        let timestamps = TimestampSettings::default();
+        let long = "x".repeat(1_000_000);
+
+        let preview = message(&long).preview(40, &timestamps);
+        assert_eq!(payload(&preview).chars().count(), 41);
+        assert!(preview.ends_with('…'));
+    }
+
+    #[test]
+    fn preview_stays_on_one_line_with_topic_and_timestamp() {
+        let timestamps = TimestampSettings {
+            format: "%Y".to_string(),
+            utc: false,
+        };
+        let message = message("line one\nline\ttwo");
+
+        let preview = message.preview(80, &timestamps);
+        assert_eq!(
+            preview,
+            format!(
+                "{} notes: line one line two",
+                message.timestamp.format("%Y")
+            )
+        );
+    }
 }
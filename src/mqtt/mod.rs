@@ -15,13 +15,16 @@
 //!
 //! ## Module Architecture
 //!
-//! The MQTT system is organized into three focused submodules:
+//! The MQTT system is organized into six focused submodules:
 //!
 //! ```text
 //! mqtt/
+//! ├── client.rs           - v3.1.1/v5 client wrapper used by the handler
 //! ├── config.rs           - Configuration structures and defaults
 //! ├── message_manager.rs  - Message representation and routing
-//! └── mqtt_handler.rs     - Connection state machine and protocol handling
+//! ├── mqtt_handler.rs     - Connection state machine and protocol handling
+//! ├── template.rs         - Placeholder substitution for composed payloads
+//! └── topic_tree.rs       - Hierarchy of the known topics for browsing
 //! ```
 //!
 //! ## Design Philosophy
@@ -57,6 +60,9 @@
 //! 3. Monitor system health and automation triggers
 //! 4. Test automation scenarios with manual message publishing
 
+pub mod client;
 pub mod config;
 pub mod message_manager;
 pub mod mqtt_handler;
+pub mod template;
+pub mod topic_tree;
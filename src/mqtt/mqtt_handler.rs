@@ -23,6 +23,8 @@
 //!   responsive behavior during user interaction
 //! - **Graceful Error Handling**: Continues operation despite individual message failures,
 //!   prioritizing connection stability over perfect message delivery
+//! - **Isolated Connection Tests**: [`test_connection`] checks credentials with a throwaway
+//!   client, leaving the live connection untouched
 //!
 //! ## State Machine Design Rationale
 //!
@@ -58,22 +60,24 @@
 //! - **Configuration errors**: Fall back to safe defaults to maintain operation
 //! - **Subscription errors**: Continue with partial subscriptions rather than failing completely
 
+use std::collections::{HashMap, VecDeque};
 use std::thread;
 use std::time::Duration;
 
-use super::message_manager::{MQTTMessage, MsgManager};
+use super::client::{self, ConnectOptions, MqttClient, MqttEventLoop, Notification};
+use super::message_manager::{topic_matches, MQTTMessage, MsgManager};
 use super::{config, message_manager};
-use crate::mqtt::config::MqttConfig;
+use crate::idle::IdleTracker;
+use crate::mqtt::config::{EndpointError, MqttConfig, MqttVersion, DEFAULT_MQTT_PORT};
 use crate::persistence;
 use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
 use crate::persistence::persistence_worker::SessionAction;
+use crate::supervisor::SharedReceiver;
+use crate::ui::MQTTServer;
 use chrono::NaiveDateTime;
-use rumqttc::{
-    AsyncClient, Event, EventLoop, Incoming, MqttOptions, MqttState, Packet, PacketType, QoS,
-};
 use statum::{machine, state};
 use std::sync::Arc;
-use tokio::sync::{mpsc, watch};
+use tokio::sync::{mpsc, oneshot, watch};
 use tracing::{error, info, warn};
 use tracing_subscriber::fmt::time;
 
@@ -139,6 +143,223 @@ pub struct MQTTStatus {
     pub last_activity: Option<chrono::DateTime<chrono::Local>>,
 }
 
+/// Broker answer to a topic subscription, as shown in the UI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubscriptionState {
+    /// SUBSCRIBE queued or sent, no SUBACK yet
+    Pending,
+    /// Broker granted the subscription
+    Subscribed,
+    /// Broker rejected the subscription or the request could not be queued
+    Failed(String),
+}
+
+/// Broker answer and traffic of one subscribed topic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopicSubscription {
+    pub state: SubscriptionState,
+    /// Messages received through this subscription since it was made or the
+    /// counts were reset; wildcard filters count every matching message
+    pub received: u64,
+}
+
+/// Subscription state per topic, published to the UI.
+pub type SubscriptionStates = HashMap<String, TopicSubscription>;
+
+/// Request of the UI concerning the subscriptions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubscriptionRequest {
+    /// Subscribe to the topic again, e.g. after the broker rejected it
+    Retry(String),
+    /// Set the received count of every topic back to zero
+    ResetCounts,
+}
+
+/// Channels and shared state the MQTT handler is started with.
+///
+/// Bundled so a new feature adds a field here instead of another parameter
+/// to [`MQTTHandle::start_connection`] and [`MQTTConnection::create`]. The
+/// supervisor clones it for every restart; the receivers are
+/// [`SharedReceiver`]s, so each attempt reads the same channels.
+#[derive(Clone, Debug)]
+pub struct HandlerContext {
+    /// Messages from the UI to publish
+    pub msg_in: SharedReceiver<MQTTMessage>,
+    /// Messages received from the broker, forwarded to the UI
+    pub msg_out: mpsc::Sender<MQTTMessage>,
+    /// Shared access to application configuration for live updates
+    pub config_portal: Arc<ConfigPortal>,
+    /// Channel for triggering session persistence operations
+    pub persistence_sender: mpsc::Sender<SessionAction>,
+    /// Per-topic subscription results reported to the UI
+    pub subscription_tx: watch::Sender<SubscriptionStates>,
+    /// Subscription requests of the UI
+    pub subscription_request_rx: SharedReceiver<SubscriptionRequest>,
+    /// Activity tracker, messages in either direction keep the application active
+    pub idle: Arc<IdleTracker>,
+}
+
+/// Parts of the application a connection reports to, kept across states.
+#[derive(Debug)]
+struct AppLinks {
+    /// Shared access to application configuration for live updates
+    config_portal: Arc<ConfigPortal>,
+    /// Channel for triggering session persistence operations
+    persistence_sender: mpsc::Sender<SessionAction>,
+    /// Activity tracker, messages in either direction keep the application active
+    idle: Arc<IdleTracker>,
+}
+
+/// Matches SUBACKs to the topics they answer and reports them to the UI.
+///
+/// ## Packet Id Pairing
+/// rumqttc does not return packet ids when a subscription is queued. It
+/// reports them as outgoing SUBSCRIBE events in queue order, so queued topics are
+/// paired with ids in that order and SUBACKs are looked up by id.
+///
+/// ## Retries
+/// The UI requests a retry with [`SubscriptionRequest::Retry`]; the
+/// processing loop resubscribes if the topic is still subscribed.
+///
+/// ## Message Counts
+/// Incoming messages are counted here, per subscription they match, so the
+/// UI only displays the numbers. Counts start at zero with every new client
+/// and on [`SubscriptionRequest::ResetCounts`].
+#[derive(Debug)]
+struct SubscriptionTracker {
+    /// Topics whose SUBSCRIBE has not been sent yet, oldest first
+    queued: VecDeque<String>,
+    /// Sent SUBSCRIBE packet ids awaiting their SUBACK
+    in_flight: HashMap<u16, String>,
+    /// Last known state per subscribed topic
+    states: SubscriptionStates,
+    /// Publishes `states` to the UI
+    state_tx: watch::Sender<SubscriptionStates>,
+    /// Retries and count resets requested by the UI
+    request_rx: SharedReceiver<SubscriptionRequest>,
+}
+
+impl SubscriptionTracker {
+    fn new(
+        state_tx: watch::Sender<SubscriptionStates>,
+        request_rx: SharedReceiver<SubscriptionRequest>,
+    ) -> Self {
+        Self {
+            queued: VecDeque::new(),
+            in_flight: HashMap::new(),
+            states: HashMap::new(),
+            state_tx,
+            request_rx,
+        }
+    }
+
+    /// Subscribes to a topic with QoS 1 and tracks the broker's answer.
+    async fn subscribe(&mut self, client: &MqttClient, topic: &str) {
+        match client.subscribe(topic).await {
+            Ok(_) => {
+                info!("Subscription to topic {} requested", topic);
+                self.queued.push_back(topic.to_string());
+                self.set(topic, SubscriptionState::Pending);
+            }
+            Err(e) => {
+                error!("Failed to subscribe to topic {}: {}", topic, e);
+                self.set(topic, SubscriptionState::Failed(e.to_string()));
+            }
+        }
+    }
+
+    /// Unsubscribes from a topic and forgets its state.
+    async fn unsubscribe(&mut self, client: &MqttClient, topic: &str) {
+        if let Err(e) = client.unsubscribe(topic).await {
+            error!("Failed to unsubscribe from topic {}: {}", topic, e);
+        }
+        self.states.remove(topic);
+        self.publish();
+    }
+
+    /// Forgets all subscriptions, used when the client is replaced.
+    fn reset(&mut self) {
+        self.queued.clear();
+        self.in_flight.clear();
+        self.states.clear();
+        self.publish();
+    }
+
+    /// Updates the tracked states from an event loop notification.
+    fn observe(&mut self, notification: &Notification) {
+        match notification {
+            Notification::SubscribeSent(pkid) => {
+                if let Some(topic) = self.queued.pop_front() {
+                    self.in_flight.insert(*pkid, topic);
+                }
+            }
+            Notification::SubAck { pkid, rejection } => {
+                let Some(topic) = self.in_flight.remove(pkid) else {
+                    return;
+                };
+                let state = match rejection {
+                    None => {
+                        info!("Successfully subscribed to topic: {}", topic);
+                        SubscriptionState::Subscribed
+                    }
+                    Some(reason) => {
+                        warn!(
+                            "Broker rejected subscription to topic {}: {}",
+                            topic, reason
+                        );
+                        SubscriptionState::Failed(reason.clone())
+                    }
+                };
+                self.set(&topic, state);
+            }
+            _ => {}
+        }
+    }
+
+    /// Takes the next request of the UI.
+    fn next_request(&mut self) -> Option<SubscriptionRequest> {
+        self.request_rx.try_recv().ok()
+    }
+
+    /// Counts a message received on `topic` for every subscription it matches.
+    fn count(&mut self, topic: &str) {
+        let mut matched = false;
+        for (filter, subscription) in self.states.iter_mut() {
+            if topic_matches(filter, topic) {
+                subscription.received += 1;
+                matched = true;
+            }
+        }
+        if matched {
+            self.publish();
+        }
+    }
+
+    /// Sets all received counts back to zero.
+    fn reset_counts(&mut self) {
+        for subscription in self.states.values_mut() {
+            subscription.received = 0;
+        }
+        self.publish();
+    }
+
+    /// Updates the state of a topic, keeping its received count.
+    fn set(&mut self, topic: &str, state: SubscriptionState) {
+        match self.states.get_mut(topic) {
+            Some(subscription) => subscription.state = state,
+            None => {
+                self.states
+                    .insert(topic.to_string(), TopicSubscription { state, received: 0 });
+            }
+        }
+        self.publish();
+    }
+
+    fn publish(&self) {
+        self.state_tx.send_replace(self.states.clone());
+    }
+}
+
 /// State machine implementation for MQTT connection management.
 ///
 /// ## Architecture Overview
@@ -161,23 +382,23 @@ pub struct MQTTConnection<S: MQTTState> {
     /// Current connection status and metrics for monitoring
     status: MQTTStatus,
 
-    /// rumqttc async client for MQTT broker communication
-    client: AsyncClient,
+    /// rumqttc async client (v3.1.1 or v5) for MQTT broker communication
+    client: MqttClient,
 
     /// Event loop for processing MQTT protocol events (moved to background tasks)
-    event_loop: Option<EventLoop>,
+    event_loop: Option<MqttEventLoop>,
 
     /// Current MQTT configuration (topics, server settings, polling frequency)
     config: config::MqttConfig,
 
-    /// Shared access to application configuration for live updates
-    config_portal: Arc<ConfigPortal>,
+    /// Config portal, persistence and activity tracking
+    links: AppLinks,
 
     /// Message routing channels for bidirectional communication
     msg_manager: MsgManager,
 
-    /// Channel for triggering session persistence operations
-    persistence_sender: mpsc::Sender<SessionAction>,
+    /// Per-topic subscription results reported to the UI
+    subscriptions: SubscriptionTracker,
 }
 
 impl MQTTConnection<Initializing> {
@@ -196,55 +417,41 @@ impl MQTTConnection<Initializing> {
     /// This prevents total MQTT failure due to temporary configuration problems.
     ///
     /// ## Connection Parameters
-    /// - **Client ID**: "OpenController" for broker identification
-    /// - **Keep-alive**: 5 seconds for responsive connection monitoring
+    /// - **Client ID**: `MqttConfig::client_id` (random `OpenController-<rand>` by default)
+    /// - **Keep-alive**: `MqttConfig::keep_alive_secs` (5 seconds by default)
     /// - **Queue size**: 10 messages for reasonable buffering without memory bloat
     ///
     /// ## Error Handling
     /// Configuration errors result in default settings rather than failure,
     /// allowing users to fix configuration through the UI after connection establishment.
-    pub async fn create(
-        msg_in: mpsc::Receiver<MQTTMessage>,
-        msg_out: mpsc::Sender<MQTTMessage>,
-        config_portal: Arc<ConfigPortal>,
-        persistence_sender: mpsc::Sender<SessionAction>,
-    ) -> Self {
+    pub async fn create(context: HandlerContext) -> Self {
         let msg_manager = MsgManager {
-            received_msg: msg_out,
-            distribution_msg: msg_in,
+            received_msg: context.msg_out,
+            distribution_msg: context.msg_in,
         };
 
         // Load configuration with fallback to defaults
-        let config_result = config_portal.execute_potal_action(PortalAction::GetMqttConfig);
-        let config = match config_result {
+        let config_result = context
+            .config_portal
+            .execute_portal_action_async(PortalAction::GetMqttConfig)
+            .await;
+        let mut config = match config_result {
             ConfigResult::MqttConfig(config) => config,
             _ => {
                 warn!("Failed to get MqttConfig from ConfigPortal, using defaults");
                 MqttConfig::default()
             }
         };
+        for repair in config.validate() {
+            warn!("Invalid MQTT config: {}", repair);
+        }
 
         info!(
             "Initializing MQTT connection with broker: {}",
             config.server.url
         );
 
-        // Parse server URL with reasonable defaults
-        let server_comps: Vec<&str> = config.server.url.split(':').collect();
-        let server_addr = server_comps.first().copied().unwrap_or("localhost");
-        let port = server_comps
-            .get(1)
-            .unwrap_or(&"1883")
-            .parse()
-            .unwrap_or(1883);
-
-        // Configure MQTT client with standard parameters
-        let mut mqtt_options = MqttOptions::new("OpenController", server_addr, port);
-        mqtt_options
-            .set_credentials(config.server.user.clone(), config.server.pw.clone())
-            .set_keep_alive(Duration::from_secs(5));
-
-        let (client, eventloop) = AsyncClient::new(mqtt_options, 10);
+        let (client, eventloop) = build_client(&config);
         let status = MQTTStatus::default();
 
         Self::new(
@@ -252,9 +459,13 @@ impl MQTTConnection<Initializing> {
             client,
             Some(eventloop),
             config,
-            config_portal,
+            AppLinks {
+                config_portal: context.config_portal,
+                persistence_sender: context.persistence_sender,
+                idle: context.idle,
+            },
             msg_manager,
-            persistence_sender,
+            SubscriptionTracker::new(context.subscription_tx, context.subscription_request_rx),
         )
     }
 
@@ -272,10 +483,11 @@ impl MQTTConnection<Initializing> {
     /// - Appropriate for debugging and monitoring use cases
     ///
     /// ## Error Handling
-    /// Individual topic subscription failures are logged but don't fail the entire
+    /// Individual topic subscription failures don't fail the entire
     /// configuration process. This allows partial functionality rather than complete
-    /// failure when some topics are problematic.
-    pub async fn configure(self) -> MQTTConnection<Configured> {
+    /// failure when some topics are problematic. Each topic's outcome, including
+    /// a rejection in the broker's SUBACK, is reported to the UI.
+    pub async fn configure(mut self) -> MQTTConnection<Configured> {
         info!(
             "Configuring MQTT connection with {} subscribed topics",
             self.config.subbed_topics.len()
@@ -283,10 +495,7 @@ impl MQTTConnection<Initializing> {
 
         // Subscribe to all configured topics
         for topic in &self.config.subbed_topics {
-            match self.client.subscribe(topic, QoS::AtLeastOnce).await {
-                Ok(_) => info!("Successfully subscribed to topic: {}", topic),
-                Err(e) => error!("Failed to subscribe to topic {}: {}", topic, e),
-            }
+            self.subscriptions.subscribe(&self.client, topic).await;
         }
 
         self.transition()
@@ -294,6 +503,53 @@ impl MQTTConnection<Initializing> {
 }
 
 impl MQTTConnection<Configured> {
+    /// Sends a DISCONNECT to the broker and waits until it has been written.
+    ///
+    /// rumqttc only transmits packets while the event loop is polled, so the
+    /// loop is driven until the outgoing DISCONNECT shows up, the connection
+    /// fails, or [`DISCONNECT_TIMEOUT`] passes. A clean DISCONNECT tells the
+    /// broker not to publish the client's last will.
+    pub async fn disconnect(&mut self) {
+        if let Err(e) = self.client.disconnect().await {
+            warn!("Failed to queue MQTT disconnect: {}", e);
+            return;
+        }
+
+        if let Some(event_loop) = &mut self.event_loop {
+            let flushed = tokio::time::timeout(DISCONNECT_TIMEOUT, async {
+                loop {
+                    match event_loop.poll().await {
+                        Ok(Notification::DisconnectSent) => return true,
+                        Ok(_) => {}
+                        Err(_) => return false,
+                    }
+                }
+            })
+            .await;
+
+            match flushed {
+                Ok(true) => info!("Disconnected from MQTT broker"),
+                Ok(false) => info!("MQTT connection closed before disconnect"),
+                Err(_) => warn!("Timed out waiting for MQTT disconnect"),
+            }
+        }
+    }
+
+    /// Rebuilds the client after [`Self::disconnect`] and resubscribes.
+    ///
+    /// A disconnected client refuses new requests, so reactivation needs a
+    /// fresh client and event loop.
+    pub async fn reconnect(&mut self) {
+        let (client, eventloop) = build_client(&self.config);
+        self.client = client;
+        self.event_loop = Some(eventloop);
+        self.subscriptions.reset();
+
+        for topic in &self.config.subbed_topics {
+            self.subscriptions.subscribe(&self.client, topic).await;
+        }
+    }
+
     /// Activates message processing and handles configuration updates.
     ///
     /// ## Configuration Update Strategy
@@ -303,9 +559,11 @@ impl MQTTConnection<Configured> {
     /// - **No changes**: Proceeds with existing configuration
     ///
     /// ## Server Change Handling
-    /// When server configuration changes, creates entirely new MQTT client and
-    /// event loop to ensure clean connection state. This prevents issues with
-    /// credential changes or broker switches.
+    /// When the effective connection changes (see [`MqttConfig::needs_reconnect`]),
+    /// creates entirely new MQTT client and event loop to ensure clean
+    /// connection state. Edits that connect to the same place, like
+    /// `mqtt://broker` vs. `broker:1883` or whitespace around credentials, keep
+    /// the running connection. Topic-only changes never rebuild the client.
     ///
     /// ## Topic Management
     /// Calculates topic differences and applies incremental changes:
@@ -323,42 +581,42 @@ impl MQTTConnection<Configured> {
     /// could be implemented if configuration updates become performance-critical.
     pub async fn activate(mut self) -> MQTTConnection<Processing> {
         // Get latest configuration from UI
-        let mut config = MqttConfig::default();
-        match self
+        let portal_config = match self
+            .links
             .config_portal
-            .execute_potal_action(PortalAction::GetMqttConfig)
+            .execute_portal_action_async(PortalAction::GetMqttConfig)
+            .await
         {
-            ConfigResult::MqttConfig(portal_config) => {
-                config = portal_config.clone();
+            ConfigResult::MqttConfig(portal_config) => Some(portal_config),
+            _ => {
+                warn!("Unable to get MqttConfig from ConfigPortal, keeping current");
+                None
             }
-            _ => warn!("Unable to get MqttConfig from ConfigPortal, keeping current"),
-        }
+        };
 
         let mut new_topics = Vec::new();
         let mut removed_topics = Vec::new();
 
         // Apply configuration changes if config is valid
-        if config != MqttConfig::default() {
-            // Handle server configuration changes (requires full reconnection)
-            if self.config.server != config.server {
-                info!("Server configuration changed, creating new connection");
-
-                let server_comps: Vec<&str> = config.server.url.split(':').collect();
-                let server_addr = server_comps.first().copied().unwrap_or("localhost");
-                let port = server_comps
-                    .get(1)
-                    .unwrap_or(&"1883")
-                    .parse()
-                    .unwrap_or(1883);
-
-                let mut mqtt_options = MqttOptions::new("OpenController", server_addr, port);
-                mqtt_options
-                    .set_credentials(config.server.user.clone(), config.server.pw.clone())
-                    .set_keep_alive(Duration::from_secs(5));
-
-                let (client, eventloop) = AsyncClient::new(mqtt_options, 10);
+        if let Some(mut config) = portal_config {
+            for repair in config.validate() {
+                warn!("Invalid MQTT config: {}", repair);
+            }
+            if self.config.poll_frequency != config.poll_frequency {
+                info!(
+                    "MQTT config poll frequency changed to {}Hz",
+                    config.poll_frequency
+                );
+            }
+
+            // Handle connection parameter changes (requires full reconnection)
+            if self.config.needs_reconnect(&config) {
+                info!("Connection configuration changed, creating new connection");
+
+                let (client, eventloop) = build_client(&config);
                 self.client = client;
                 self.event_loop = Some(eventloop);
+                self.subscriptions.reset();
             }
 
             // Handle topic subscription changes (incremental updates)
@@ -388,17 +646,18 @@ impl MQTTConnection<Configured> {
 
             // Execute subscription changes
             for topic in new_topics {
-                let _ = self.client.subscribe(topic, QoS::AtLeastOnce).await;
+                self.subscriptions.subscribe(&self.client, &topic).await;
             }
 
             for topic in removed_topics {
-                let _ = self.client.unsubscribe(topic).await;
+                self.subscriptions.unsubscribe(&self.client, &topic).await;
             }
         }
 
         // Trigger session persistence after configuration changes
         let (tx, mut rx) = tokio::sync::oneshot::channel::<Result<(), color_eyre::Report>>();
         let _ = self
+            .links
             .persistence_sender
             .try_send(SessionAction::SaveCurrentSession { response_tx: tx });
 
@@ -421,19 +680,25 @@ impl MQTTConnection<Processing> {
     ///
     /// **Outgoing (UI → Broker):**
     /// - Receives messages via `distribution_msg` channel
-    /// - Publishes to ALL available topics (broadcast behavior)
+    /// - Publishes to the topics selected by the configured [`PublishTarget`](crate::mqtt::config::PublishTarget)
+    /// - Honors the message's `retain` flag; an empty retained payload clears
+    ///   the retained message on the broker
+    /// - Decodes binary messages from hex; messages with invalid hex are dropped
     /// - Updates send statistics and activity timestamps
     ///
     /// **Incoming (Broker → UI):**
     /// - Polls MQTT event loop for new protocol events
     /// - Filters for Publish packets containing message data
-    /// - Converts to MQTTMessage format and forwards to UI via `received_msg` channel
+    /// - Converts to MQTTMessage format (hex for non-UTF-8 payloads) and forwards
+    ///   to UI via `received_msg` channel
     ///
     /// ## Configuration Polling Strategy
     /// Uses configurable polling frequency to balance responsiveness with CPU usage:
     /// - **High frequency**: More responsive to UI changes, higher CPU overhead
     /// - **Low frequency**: Less responsive but more efficient
-    /// - **Dynamic calculation**: `poll_interval = 1000ms / poll_frequency`
+    /// - **Dynamic calculation**: `poll_interval = 1000ms / poll_frequency`,
+    ///   see [`MqttConfig::poll_interval`]; a changed frequency applies from
+    ///   the next cycle, as [`MQTTConnection::activate`] re-reads the config
     ///
     /// ## Error Handling Philosophy
     /// Continues processing despite individual failures to maintain overall system stability:
@@ -455,8 +720,7 @@ impl MQTTConnection<Processing> {
         info!("MQTT message processing loop started");
 
         // Calculate polling interval from configuration
-        let poll_interval =
-            Duration::from_millis(1000 / self.config.poll_frequency.try_into().unwrap_or(10));
+        let poll_interval = self.config.poll_interval();
         let mut last_check = std::time::Instant::now();
 
         loop {
@@ -464,22 +728,42 @@ impl MQTTConnection<Processing> {
             match self.msg_manager.distribution_msg.try_recv() {
                 Ok(msg) => {
                     let current_client = self.client.clone();
-                    let content = msg.content.clone();
+                    let retain = msg.retain;
+                    let content = match msg.payload() {
+                        Ok(content) => content,
+                        Err(e) => {
+                            warn!("Dropping message with invalid hex payload: {}", e);
+                            self.status
+                                .error_messages
+                                .push(format!("Payload error: {}", e));
+                            continue;
+                        }
+                    };
+                    let topics = self.config.publish_topics(&msg.topic);
+                    if topics.is_empty() {
+                        warn!(
+                            "No target topic for outgoing message ({})",
+                            self.config.publish_target
+                        );
+                        self.status.error_messages.push(format!(
+                            "Publish error: no target topic ({})",
+                            self.config.publish_target
+                        ));
+                        continue;
+                    }
                     info!(
-                        "Publishing message to {} topics: {}",
-                        self.config.subbed_topics.len(),
-                        msg.topic
+                        "Publishing message to {} topic(s) ({})",
+                        topics.len(),
+                        self.config.publish_target
                     );
 
-                    // Broadcast to all subscribed topics (debugging/monitoring pattern)
-                    for topic in &self.config.subbed_topics {
-                        match current_client
-                            .publish(topic, QoS::AtLeastOnce, false, content.clone())
-                            .await
-                        {
+                    // Target topics depend on the configured PublishTarget
+                    for topic in &topics {
+                        match current_client.publish(topic, retain, content.clone()).await {
                             Ok(_) => {
                                 self.status.messages_sent += 1;
                                 self.status.last_activity = Some(chrono::Local::now());
+                                self.links.idle.record();
                             }
                             Err(e) => {
                                 warn!("Failed to publish to topic {}: {:?}", topic, e);
@@ -501,54 +785,41 @@ impl MQTTConnection<Processing> {
                 }
             }
 
+            // Resubscribe topics the UI asked to retry, reset counts
+            while let Some(request) = self.subscriptions.next_request() {
+                match request {
+                    SubscriptionRequest::Retry(topic) => {
+                        if self.config.subbed_topics.contains(&topic) {
+                            self.subscriptions.subscribe(&self.client, &topic).await;
+                        }
+                    }
+                    SubscriptionRequest::ResetCounts => self.subscriptions.reset_counts(),
+                }
+            }
+
             // Process incoming messages from broker to UI
             if let Some(event_loop) = &mut self.event_loop {
                 let notification = event_loop.poll().await;
 
                 match notification {
-                    Ok(event) => {
-                        match event {
-                            Event::Incoming(packet) => match packet {
-                                Packet::Publish(publish_packet) => {
-                                    let payload = publish_packet.payload;
-                                    let topic = publish_packet.topic;
-
-                                    // Convert binary payload to UTF-8 string
-                                    match std::str::from_utf8(&payload) {
-                                        Ok(payload_str) => {
-                                            let msg = MQTTMessage::from_topic(
-                                                topic.clone(),
-                                                payload_str.to_string(),
-                                            );
-
-                                            // Forward to UI
-                                            if let Err(e) =
-                                                self.msg_manager.received_msg.try_send(msg)
-                                            {
-                                                error!("Failed to forward message to UI: {:?}", e);
-                                            } else {
-                                                info!("Received message on topic: {}", topic);
-                                                self.status.messages_received += 1;
-                                                self.status.last_activity =
-                                                    Some(chrono::Local::now());
-                                            }
-                                        }
-                                        Err(e) => {
-                                            error!(
-                                                "Invalid UTF-8 payload on topic {}: {:?}",
-                                                topic, e
-                                            );
-                                        }
-                                    }
-                                }
-                                _ => {
-                                    // Other packet types (ping, ack, etc.) - normal protocol traffic
-                                }
-                            },
-                            Event::Outgoing(_) => {
-                                // Outgoing confirmations - normal protocol traffic
+                    Ok(notification) => {
+                        self.subscriptions.observe(&notification);
+                        if let Notification::Publish(msg) = notification {
+                            // Non-UTF-8 payloads arrive as hex binary messages
+                            let topic = msg.topic.clone();
+                            self.subscriptions.count(&topic);
+
+                            // Forward to UI
+                            if let Err(e) = self.msg_manager.received_msg.try_send(msg) {
+                                error!("Failed to forward message to UI: {:?}", e);
+                            } else {
+                                info!("Received message on topic: {}", topic);
+                                self.status.messages_received += 1;
+                                self.status.last_activity = Some(chrono::Local::now());
+                                self.links.idle.record();
                             }
                         }
+                        // Other notifications (acks, pings) are normal protocol traffic
                     }
                     Err(e) => {
                         // Network errors, broker disconnections, etc.
@@ -575,6 +846,139 @@ impl MQTTConnection<Processing> {
     }
 }
 
+/// Longest time spent flushing a DISCONNECT to the broker.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Creates a rumqttc client and event loop from the given configuration.
+///
+/// Shared by the initial connection and the reconnect path so both apply the
+/// same URL parsing, credentials, client ID, keep-alive and protocol version.
+///
+/// ## URL Parsing
+/// Uses [`MqttConfig::parse_endpoint`]; an invalid URL is logged and falls
+/// back to `localhost` on the default port so the state machine keeps running.
+///
+/// Credentials and client ID are trimmed, matching the comparison in
+/// [`MqttConfig::needs_reconnect`].
+fn build_client(config: &MqttConfig) -> (MqttClient, MqttEventLoop) {
+    let (host, port) = MqttConfig::parse_endpoint(&config.server.url).unwrap_or_else(|e| {
+        warn!("Invalid MQTT server URL '{}': {}", config.server.url, e);
+        ("localhost".to_string(), DEFAULT_MQTT_PORT)
+    });
+
+    client::connect(ConnectOptions {
+        version: config.protocol_version,
+        client_id: config.client_id.trim().to_string(),
+        host,
+        port,
+        user: config.server.user.trim().to_string(),
+        pw: config.server.pw.trim().to_string(),
+        keep_alive: config.keep_alive(),
+    })
+}
+
+/// Longest time a connection test waits for the broker's CONNACK.
+pub const CONNECTION_TEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reasons a connection test against a broker failed.
+#[derive(Debug, thiserror::Error)]
+pub enum ConnectionTestError {
+    #[error("Invalid server URL: {0}")]
+    InvalidEndpoint(#[from] EndpointError),
+
+    #[error("Broker refused the connection: {0}")]
+    Refused(String),
+
+    #[error("Connection failed: {0}")]
+    Connection(String),
+
+    #[error("No answer from the broker within {} seconds", CONNECTION_TEST_TIMEOUT.as_secs())]
+    Timeout,
+}
+
+/// Checks that a broker accepts the given server's credentials.
+///
+/// Uses its own short-lived client so the live connection of [`MQTTHandle`]
+/// is not touched. The client ID gets a `-test` suffix, since brokers drop an
+/// existing session when a second client connects with the same ID. After a
+/// successful CONNACK the test client disconnects cleanly.
+///
+/// # Errors
+/// Fails on an invalid URL, a refused CONNACK (e.g. bad credentials), a
+/// network error, or after [`CONNECTION_TEST_TIMEOUT`] without an answer.
+pub async fn test_connection(
+    server: MQTTServer,
+    client_id: String,
+    keep_alive: Duration,
+    version: MqttVersion,
+) -> Result<(), ConnectionTestError> {
+    let (host, port) = MqttConfig::parse_endpoint(&server.url)?;
+
+    let (client, mut event_loop) = client::connect(ConnectOptions {
+        version,
+        client_id: format!("{}-test", client_id.trim()),
+        host,
+        port,
+        user: server.user.trim().to_string(),
+        pw: server.pw.trim().to_string(),
+        keep_alive,
+    });
+
+    let answer = tokio::time::timeout(CONNECTION_TEST_TIMEOUT, async {
+        loop {
+            match event_loop.poll().await {
+                Ok(Notification::ConnAck { refusal: None }) => return Ok(()),
+                Ok(Notification::ConnAck {
+                    refusal: Some(code),
+                }) => return Err(ConnectionTestError::Refused(code)),
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(match e.refusal() {
+                        Some(code) => ConnectionTestError::Refused(code),
+                        None => ConnectionTestError::Connection(e.to_string()),
+                    });
+                }
+            }
+        }
+    })
+    .await
+    .unwrap_or(Err(ConnectionTestError::Timeout));
+
+    if answer.is_ok() && client.disconnect().await.is_ok() {
+        let _ = tokio::time::timeout(DISCONNECT_TIMEOUT, async {
+            while !matches!(
+                event_loop.poll().await,
+                Ok(Notification::DisconnectSent) | Err(_)
+            ) {}
+        })
+        .await;
+    }
+
+    answer
+}
+
+/// Runs [`test_connection`] in the background.
+///
+/// The result arrives on the returned channel, so the UI can poll it
+/// without blocking a frame.
+pub fn spawn_connection_test(
+    server: MQTTServer,
+    client_id: String,
+    keep_alive: Duration,
+    version: MqttVersion,
+) -> oneshot::Receiver<Result<(), ConnectionTestError>> {
+    let (result_tx, result_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let result = test_connection(server, client_id, keep_alive, version).await;
+        match &result {
+            Ok(()) => info!("MQTT connection test succeeded"),
+            Err(e) => info!("MQTT connection test failed: {}", e),
+        }
+        let _ = result_tx.send(result);
+    });
+    result_rx
+}
+
 /// High-level handle for managing the complete MQTT connection lifecycle.
 ///
 /// ## Design Rationale
@@ -600,12 +1004,18 @@ impl MQTTHandle {
     /// 3. **Processing Loop**: Handle messages and monitor for changes
     ///    - If active: Run processing and return to configure for updates
     ///    - If inactive: Sleep and wait for activation
-    /// 4. **Repeat**: Continue indefinitely for persistent MQTT functionality
+    /// 4. **Repeat**: Continue until the activation sender is dropped
     ///
     /// ## Activation Control
     /// Uses a watch channel to receive activation signals from the UI, allowing
     /// users to enable/disable MQTT functionality without restarting the application.
-    /// When inactive, the connection remains configured but doesn't process messages.
+    /// Deactivating sends a clean DISCONNECT; reactivating builds a new client
+    /// and resubscribes.
+    ///
+    /// ## Shutdown
+    /// Once the UI drops the activation sender the broker is disconnected and
+    /// the function returns, so the broker sees a normal disconnect instead of
+    /// a keep-alive timeout that would trigger the last will.
     ///
     /// ## Configuration Responsiveness
     /// The processing loop periodically returns to the Configured state, which
@@ -621,38 +1031,55 @@ impl MQTTHandle {
     /// ## Resource Management
     /// The state machine maintains ownership of expensive resources (MQTT client,
     /// event loop) across transitions, minimizing allocation overhead and
-    /// connection setup time.
+    /// connection setup time. The incoming channels are [`SharedReceiver`]s,
+    /// so after a panic the supervisor can start the handler again on them.
     ///
     /// ## Performance Considerations
     /// - **Active mode**: Full message processing with configurable polling frequency
-    /// - **Inactive mode**: 1-second sleep to minimize CPU usage while maintaining responsiveness
+    /// - **Inactive mode**: Waits on the activation channel, no polling, and
+    ///   reacts to reactivation or shutdown immediately
     /// - **State transitions**: Lightweight transitions preserve connection state when possible
     pub async fn start_connection(
         &mut self,
-        msg_in: mpsc::Receiver<MQTTMessage>,
-        msg_out: mpsc::Sender<MQTTMessage>,
-        activation_state: watch::Receiver<bool>,
-        config_portal: Arc<ConfigPortal>,
-        persistence_sender: mpsc::Sender<SessionAction>,
+        mut activation_state: watch::Receiver<bool>,
+        context: HandlerContext,
     ) {
         info!("Initializing MQTT connection state machine");
 
         // Initialize and configure the connection
-        let connection =
-            MQTTConnection::create(msg_in, msg_out, config_portal, persistence_sender).await;
+        let connection = MQTTConnection::create(context).await;
         let mut connection = connection.configure().await;
 
-        // Main lifecycle loop - runs indefinitely
+        // Main lifecycle loop - runs until the activation sender is dropped
         loop {
-            self.active = *activation_state.borrow();
+            if activation_state.has_changed().is_err() {
+                if self.active {
+                    connection.disconnect().await;
+                }
+                info!("MQTT activation channel closed, stopping MQTT handler");
+                return;
+            }
+
+            let active = *activation_state.borrow_and_update();
+            if active != self.active {
+                if active {
+                    info!("MQTT activated, reconnecting");
+                    connection.reconnect().await;
+                } else {
+                    info!("MQTT deactivated, disconnecting");
+                    connection.disconnect().await;
+                }
+                self.active = active;
+            }
 
             if self.active {
                 // Active mode: Full message processing with configuration updates
                 let processing_connection = connection.activate().await;
                 connection = processing_connection.run().await;
             } else {
-                // Inactive mode: Minimal CPU usage while waiting for activation
-                thread::sleep(Duration::from_secs(1));
+                // Inactive mode: Wait for the UI, a closed channel ends the
+                // loop at the top
+                let _ = activation_state.changed().await;
             }
         }
     }
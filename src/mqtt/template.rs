@@ -0,0 +1,188 @@
+//! # Payload Templates
+//!
+//! Placeholder substitution for payloads composed in the MQTT menu.
+//!
+//! ## Why This Module Exists
+//!
+//! Testing IoT devices often means sending the same JSON over and over with
+//! one changing value. Instead of editing the payload before every send, a
+//! template like `{"id": {{counter}}, "room": "{{room}}"}` is filled in when
+//! it is sent.
+//!
+//! ## Placeholders
+//!
+//! - `{{timestamp}}`: local time of the send as RFC 3339 with milliseconds
+//! - `{{counter}}`: number that increases by one with every send
+//! - `{{name}}`: user-defined variable, see [`TemplateVariables::vars`]
+//!
+//! Whitespace inside the braces is ignored. Unknown names and unclosed
+//! placeholders are errors, so a typo never goes out to the broker as a
+//! literal `{{...}}`.
+//!
+//! ## Opt-In
+//!
+//! Substitution only runs for messages sent in template mode. Plain payloads,
+//! which may contain `{{` themselves, are sent unchanged.
+
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Placeholder replaced by the send time.
+pub const TIMESTAMP_VAR: &str = "timestamp";
+
+/// Placeholder replaced by the send counter.
+pub const COUNTER_VAR: &str = "counter";
+
+/// Reasons a template cannot be filled in.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("Unknown template variable '{0}'")]
+    UnknownVariable(String),
+
+    #[error("Placeholder is missing its closing '}}}}'")]
+    Unclosed,
+}
+
+/// Values available to payload templates, persisted with the saved messages.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct TemplateVariables {
+    /// Value of `{{counter}}` for the next send
+    #[serde(default)]
+    pub counter: u64,
+
+    /// User-defined variables by name
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+}
+
+impl TemplateVariables {
+    /// Replaces all placeholders in `template`.
+    ///
+    /// # Errors
+    /// Returns a [`TemplateError`] for unknown variables and placeholders
+    /// without closing braces.
+    pub fn render(&self, template: &str) -> Result<String, TemplateError> {
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let placeholder = &rest[start + 2..];
+            let end = placeholder.find("}}").ok_or(TemplateError::Unclosed)?;
+
+            match placeholder[..end].trim() {
+                TIMESTAMP_VAR => rendered
+                    .push_str(&chrono::Local::now().to_rfc3339_opts(SecondsFormat::Millis, false)),
+                COUNTER_VAR => rendered.push_str(&self.counter.to_string()),
+                name => rendered.push_str(
+                    self.vars
+                        .get(name)
+                        .ok_or_else(|| TemplateError::UnknownVariable(name.to_string()))?,
+                ),
+            }
+            rest = &placeholder[end + 2..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+
+    /// Moves `{{counter}}` on to the next value, called once per sent message.
+    pub fn advance_counter(&mut self) {
+        self.counter += 1;
+    }
+
+    /// Checks whether `name` can be used for a user-defined variable.
+    ///
+    /// Names must be non-empty, consist of ASCII letters, digits, `_` and `-`,
+    /// and must not shadow a built-in placeholder.
+    pub fn validate_name(name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("Variable name empty!".to_string());
+        }
+        if name == TIMESTAMP_VAR || name == COUNTER_VAR {
+            return Err(format!("'{}' is built in", name));
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err("Use only letters, digits, '_' and '-'".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_increments_per_send() {
+        let mut vars = TemplateVariables::default();
+        let template = r#"{"id": {{counter}}}"#;
+
+        assert_eq!(vars.render(template).unwrap(), r#"{"id": 0}"#);
+        // Rendering alone, e.g. for the preview, does not advance it
+        assert_eq!(vars.render(template).unwrap(), r#"{"id": 0}"#);
+
+        vars.advance_counter();
+        assert_eq!(vars.render(template).unwrap(), r#"{"id": 1}"#);
+        vars.advance_counter();
+        assert_eq!(vars.render("{{ counter }}").unwrap(), "2");
+    }
+
+    #[test]
+    fn timestamp_is_the_send_time() {
+        let vars = TemplateVariables::default();
+        let before = chrono::Local::now();
+
+        let rendered = vars.render("at {{timestamp}}").unwrap();
+
+        let stamp = rendered.strip_prefix("at ").unwrap();
+        let parsed = chrono::DateTime::parse_from_rfc3339(stamp).unwrap();
+        assert!(parsed >= before - chrono::Duration::milliseconds(1));
+        assert!(parsed <= chrono::Local::now());
+    }
+
+    #[test]
+    fn user_variables_are_substituted() {
+        let mut vars = TemplateVariables::default();
+        vars.vars.insert("room".to_string(), "kitchen".to_string());
+        vars.vars.insert("temp".to_string(), "21.5".to_string());
+
+        assert_eq!(
+            vars.render(r#"{"room": "{{room}}", "t": {{temp}}, "n": {{counter}}}"#)
+                .unwrap(),
+            r#"{"room": "kitchen", "t": 21.5, "n": 0}"#
+        );
+        assert_eq!(
+            vars.render("{{missing}}"),
+            Err(TemplateError::UnknownVariable("missing".to_string()))
+        );
+        assert_eq!(vars.render("{{room"), Err(TemplateError::Unclosed));
+    }
+
+    #[test]
+    fn payloads_without_placeholders_are_unchanged() {
+        let vars = TemplateVariables::default();
+
+        for payload in [
+            "",
+            "plain text",
+            r#"{"a": {"b": 1}}"#,
+            "single { brace }",
+            "Grüße 🌡️",
+        ] {
+            assert_eq!(vars.render(payload).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn variable_names_cannot_shadow_builtins() {
+        assert!(TemplateVariables::validate_name("room_1").is_ok());
+        assert!(TemplateVariables::validate_name("").is_err());
+        assert!(TemplateVariables::validate_name(COUNTER_VAR).is_err());
+        assert!(TemplateVariables::validate_name(TIMESTAMP_VAR).is_err());
+        assert!(TemplateVariables::validate_name("a b").is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! # Topic Tree
+//!
+//! Hierarchy of the known topics, split on the MQTT level separator `/`.
+//!
+//! ## Why This Module Exists
+//!
+//! Smart home brokers carry many topics sharing long prefixes, like
+//! `home/livingroom/temp` and `home/kitchen/temp`. A flat list of them is hard
+//! to scan; a tree groups them by room, device and so on, and a whole branch
+//! can be subscribed with one multi-level wildcard.
+//!
+//! ## Design Rationale
+//!
+//! The tree is rebuilt from the available topics whenever it is shown instead
+//! of being stored, so it can never disagree with the topic list that is
+//! persisted in the session. Children are kept sorted by name.
+
+use std::collections::BTreeMap;
+
+/// MQTT topic level separator
+pub const LEVEL_SEPARATOR: char = '/';
+
+/// MQTT multi-level wildcard, matches a branch and everything below it
+pub const MULTI_LEVEL_WILDCARD: &str = "#";
+
+/// One topic level, the root has an empty name and path.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TopicNode {
+    /// Name of this level
+    pub name: String,
+    /// Topic up to and including this level
+    pub path: String,
+    /// Whether the path itself is one of the known topics
+    pub is_topic: bool,
+    /// Levels below this one, by name
+    pub children: BTreeMap<String, TopicNode>,
+}
+
+impl TopicNode {
+    /// Builds the tree of `topics`.
+    ///
+    /// Empty levels, e.g. from a leading `/`, are kept as levels with an empty
+    /// name, as MQTT treats them as distinct topics.
+    pub fn build<'a>(topics: impl IntoIterator<Item = &'a String>) -> Self {
+        let mut root = Self::default();
+        for topic in topics {
+            let mut node = &mut root;
+            let mut path = String::new();
+            for (depth, level) in topic.split(LEVEL_SEPARATOR).enumerate() {
+                if depth > 0 {
+                    path.push(LEVEL_SEPARATOR);
+                }
+                path.push_str(level);
+                node = node
+                    .children
+                    .entry(level.to_string())
+                    .or_insert_with(|| TopicNode {
+                        name: level.to_string(),
+                        path: path.clone(),
+                        ..Default::default()
+                    });
+            }
+            node.is_topic = true;
+        }
+        root
+    }
+
+    /// Subscription filter covering this level and everything below it.
+    pub fn branch_filter(&self) -> String {
+        format!("{}{}{}", self.path, LEVEL_SEPARATOR, MULTI_LEVEL_WILDCARD)
+    }
+
+    /// Whether this level has levels below it.
+    pub fn is_branch(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
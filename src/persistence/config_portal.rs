@@ -59,9 +59,15 @@
 //! The custom `try_lock!` macro implements sophisticated retry logic:
 //! - **Progressive retry**: 5 attempts with 10ms delays between retries
 //! - **Graceful degradation**: Returns timeout error rather than panicking
-//! - **Performance monitoring**: Logs contention for debugging and optimization
+//! - **Performance monitoring**: Logs contention for debugging and optimization,
+//!   and counts it per section for `ConfigPortal::metrics`
 //! - **Non-blocking design**: Never blocks indefinitely or spins endlessly
 //!
+//! Sync callers (UI thread, plain threads) use `execute_potal_action`, which
+//! waits between attempts on the calling thread. Async tasks use
+//! `execute_portal_action_async`, which waits with `tokio::time::sleep` and so
+//! never holds up a runtime worker.
+//!
 //! ## Error Handling Strategy
 //!
 //! Configuration errors are categorized and handled appropriately:
@@ -70,16 +76,23 @@
 //! - **Operation errors**: Invalid configuration state that needs correction
 //! - **Fallback behavior**: All operations provide safe default behavior
 
+use crate::controller;
 use crate::mapping;
 use crate::mqtt;
 use crate::try_lock;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::runtime::RuntimeFlavor;
+use tokio::sync::{RwLock, RwLockWriteGuard};
 use tracing::warn;
 
-use super::{ConnectionConfig, ControllerConfig, SavedMessages, SessionConfig, Theme, UIConfig};
+use super::{
+    AutosaveSettings, ConnectionConfig, ControllerConfig, FocusHighlight, ReceivedLog,
+    SavedMessages, SessionConfig, Theme, TimestampSettings, UIConfig,
+};
 
 /// Central hub for all application configuration data with thread-safe access.
 ///
@@ -100,6 +113,7 @@ use super::{ConnectionConfig, ControllerConfig, SavedMessages, SessionConfig, Th
 /// - **Controller Config**: Gamepad mappings for keyboard and ELRS output
 /// - **Connection Config**: MQTT broker settings and network configuration
 /// - **Message Save**: Persistent message history and saved MQTT messages
+/// - **Received Log**: Live MQTT messages received during the session
 ///
 /// ## Thread Safety Architecture
 ///
@@ -132,6 +146,15 @@ pub struct ConfigPortal {
 
     /// Persistent message history and saved content
     pub msg_save: Arc<RwLock<SavedMessages>>,
+
+    /// Live MQTT traffic log kept for post-mortem debugging
+    pub received_log: Arc<RwLock<ReceivedLog>>,
+
+    /// Set by every configuration write since the last successful save
+    dirty: AtomicBool,
+
+    /// Contention counters of every section, see [`Self::metrics`]
+    lock_stats: PortalLockStats,
 }
 
 impl ConfigPortal {
@@ -152,6 +175,7 @@ impl ConfigPortal {
         controller_config: ControllerConfig,
         connection_config: ConnectionConfig,
         msg_save: SavedMessages,
+        received_log: ReceivedLog,
     ) -> Self {
         Self {
             session: Arc::new(RwLock::new(session_config)),
@@ -159,9 +183,46 @@ impl ConfigPortal {
             controller_config: Arc::new(RwLock::new(controller_config)),
             connection_config: Arc::new(RwLock::new(connection_config)),
             msg_save: Arc::new(RwLock::new(msg_save)),
+            received_log: Arc::new(RwLock::new(received_log)),
+            dirty: AtomicBool::new(false),
+            lock_stats: PortalLockStats::default(),
         }
     }
 
+    /// Whether configuration was written since the last successful save.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Acquire)
+    }
+
+    /// Clears the dirty flag before a save, returning whether it was set.
+    ///
+    /// Clearing before rather than after writing keeps changes made during
+    /// the save marked. A failed save restores the flag with [`Self::mark_dirty`].
+    pub fn take_dirty(&self) -> bool {
+        self.dirty.swap(false, Ordering::AcqRel)
+    }
+
+    /// Marks configuration as changed since the last save.
+    pub fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Release);
+    }
+
+    /// Lock contention counted per section since startup.
+    ///
+    /// Sections are listed in lock order; every entry holds the blocked read
+    /// and write attempts and the actions that gave up with a timeout.
+    pub fn metrics(&self) -> Vec<(&'static str, LockMetrics)> {
+        let stats = &self.lock_stats;
+        vec![
+            ("Session", stats.session.snapshot()),
+            ("UI config", stats.ui_config.snapshot()),
+            ("Controller config", stats.controller_config.snapshot()),
+            ("Connection config", stats.connection_config.snapshot()),
+            ("Saved messages", stats.msg_save.snapshot()),
+            ("Received log", stats.received_log.snapshot()),
+        ]
+    }
+
     /// Updates session name with automatic retry on lock contention.
     ///
     /// ## Legacy Method Notice
@@ -209,6 +270,71 @@ impl ConfigPortal {
         }
     }
 
+    /// Replaces every configuration section with `snapshot` in one step.
+    ///
+    /// ## Atomicity
+    /// The write locks of all sections are taken before anything is written
+    /// and released together, so no reader sees one section of the new
+    /// session next to another of the old one.
+    ///
+    /// ## Lock Ordering
+    /// Locks are always taken in field order (session, UI, controller,
+    /// connection, messages, received log). Only `try_write` is used and a
+    /// busy lock releases the ones already held before the next attempt, so
+    /// a batch can neither deadlock nor starve single-section writers. Like
+    /// `try_lock!`, it gives up with [`Error::LockTimeout`] after
+    /// `max_attempts`, leaving the portal unchanged.
+    fn apply_session(
+        &self,
+        snapshot: SessionSnapshot,
+        max_attempts: usize,
+    ) -> Result<ConfigResult, Error> {
+        let stats = &self.lock_stats;
+        let mut blocked = &stats.session;
+        for attempt in 1..=max_attempts {
+            let guards = (|| {
+                Ok((
+                    try_write_counted(&self.session, &stats.session)?,
+                    try_write_counted(&self.ui_config, &stats.ui_config)?,
+                    try_write_counted(&self.controller_config, &stats.controller_config)?,
+                    try_write_counted(&self.connection_config, &stats.connection_config)?,
+                    try_write_counted(&self.msg_save, &stats.msg_save)?,
+                    try_write_counted(&self.received_log, &stats.received_log)?,
+                ))
+            })();
+
+            match guards {
+                Ok((
+                    mut session,
+                    mut ui_config,
+                    mut controller_config,
+                    mut connection_config,
+                    mut msg_save,
+                    mut received_log,
+                )) => {
+                    *session = snapshot.session;
+                    *ui_config = snapshot.ui_config;
+                    *controller_config = snapshot.controller_config;
+                    *connection_config = snapshot.connection_config;
+                    *msg_save = snapshot.msg_save;
+                    *received_log = snapshot.received_log;
+                    return Ok(ConfigResult::Success);
+                }
+                Err(counters) => blocked = counters,
+            }
+
+            warn!(
+                "Session apply blocked by a held lock (attempt {}/{})",
+                attempt, max_attempts
+            );
+            if attempt < max_attempts {
+                lock_retry_wait();
+            }
+        }
+        blocked.record_timeout();
+        Err(Error::LockTimeout)
+    }
+
     /// Executes configuration operations through the unified action dispatch pattern.
     ///
     /// ## Design Philosophy
@@ -255,60 +381,91 @@ impl ConfigPortal {
     /// // Writing configuration  
     /// portal.execute_portal_action(PortalAction::WriteMqttConfig(new_config));
     /// ```
+    ///
+    /// ## Sync or Async
+    ///
+    /// Waits between attempts by blocking the calling thread. Use it from the
+    /// UI thread, plain threads and sync helpers. Async tasks should call
+    /// [`Self::execute_portal_action_async`], which waits without blocking;
+    /// if they do call this one, see [`lock_retry_wait`].
     pub fn execute_potal_action(&self, action: PortalAction) -> ConfigResult {
+        self.dispatch(action, LOCK_ATTEMPTS)
+    }
+
+    /// Async variant of [`Self::execute_potal_action`] for tokio tasks.
+    ///
+    /// Each attempt takes the lock at most once, and the time between
+    /// attempts is spent in `tokio::time::sleep`, so a contended lock never
+    /// blocks a runtime worker. The action is cloned for every attempt but
+    /// the last; read actions carry no data, writes copy what they write.
+    pub async fn execute_portal_action_async(&self, action: PortalAction) -> ConfigResult {
+        for _ in 1..LOCK_ATTEMPTS {
+            match self.dispatch(action.clone(), 1) {
+                ConfigResult::Failed(Error::LockTimeout) => {
+                    tokio::time::sleep(LOCK_RETRY_DELAY).await;
+                }
+                result => return result,
+            }
+        }
+        self.dispatch(action, 1)
+    }
+
+    /// Runs `action`, taking its lock in at most `max_attempts` tries.
+    fn dispatch(&self, action: PortalAction, max_attempts: usize) -> ConfigResult {
+        let marks_dirty = action.marks_dirty();
         let result = match action {
             // Session configuration operations
             PortalAction::GetSession => {
-                try_lock!(@read_lock_retry, self.session.clone(), |guard: &SessionConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &SessionConfig| {
                     ConfigResult::SessionConfig(guard.clone())
                 })
             }
             PortalAction::GetSessionName => {
-                try_lock!(@read_lock_retry, self.session.clone(), |guard: &SessionConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &SessionConfig| {
                     ConfigResult::String(guard.session_name.clone())
                 })
             }
             PortalAction::GetLastSession => {
-                try_lock!(@read_lock_retry, self.session.clone(), |guard: &SessionConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &SessionConfig| {
                     ConfigResult::OptionString(guard.last_session.clone())
                 })
             }
             PortalAction::GetSessionPath => {
-                try_lock!(@read_lock_retry, self.session.clone(), |guard: &SessionConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &SessionConfig| {
                     ConfigResult::PathBuf(guard.path.clone())
                 })
             }
             PortalAction::GetAvailableSessions => {
-                try_lock!(@read_lock_retry, self.session.clone(), |guard: &SessionConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &SessionConfig| {
                     ConfigResult::AvailableSessions(guard.available_sessions.clone())
                 })
             }
             PortalAction::WriteSession(session_config) => {
-                try_lock!(@write_lock_retry, self.session.clone(), |guard: &mut SessionConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &mut SessionConfig| {
                     *guard = session_config;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteSessionName(name) => {
-                try_lock!(@write_lock_retry, self.session.clone(), |guard: &mut SessionConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &mut SessionConfig| {
                     guard.session_name = name;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteLastSession(last_session) => {
-                try_lock!(@write_lock_retry, self.session.clone(), |guard: &mut SessionConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &mut SessionConfig| {
                     guard.last_session = last_session;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteSessionPath(path) => {
-                try_lock!(@write_lock_retry, self.session.clone(), |guard: &mut SessionConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &mut SessionConfig| {
                     guard.path = path;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteAvailableSessions(sessions) => {
-                try_lock!(@write_lock_retry, self.session.clone(), |guard: &mut SessionConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.session, self.session.clone(), |guard: &mut SessionConfig| {
                     guard.available_sessions = sessions;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
@@ -316,93 +473,159 @@ impl ConfigPortal {
 
             // UI configuration operations
             PortalAction::GetUIConfig => {
-                try_lock!(@read_lock_retry, self.ui_config.clone(), |guard: &UIConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &UIConfig| {
                     ConfigResult::UIConfig(guard.clone())
                 })
             }
             PortalAction::GetTheme => {
-                try_lock!(@read_lock_retry, self.ui_config.clone(), |guard: &UIConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &UIConfig| {
                     ConfigResult::Theme(guard.theme.clone())
                 })
             }
             PortalAction::GetFps => {
-                try_lock!(@read_lock_retry, self.ui_config.clone(), |guard: &UIConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &UIConfig| {
                     ConfigResult::Fps(guard.fps)
                 })
             }
+            PortalAction::GetBrightness => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &UIConfig| {
+                    ConfigResult::Brightness(guard.brightness)
+                })
+            }
+            PortalAction::GetAutosave => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &UIConfig| {
+                    ConfigResult::Autosave(guard.autosave.clone())
+                })
+            }
+            PortalAction::GetTimestamps => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &UIConfig| {
+                    ConfigResult::Timestamps(guard.timestamps.clone())
+                })
+            }
+            PortalAction::GetFocusHighlight => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &UIConfig| {
+                    ConfigResult::FocusHighlight(guard.focus_highlight.clone())
+                })
+            }
             PortalAction::WriteUIConfig(ui_config) => {
-                try_lock!(@write_lock_retry, self.ui_config.clone(), |guard: &mut UIConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &mut UIConfig| {
                     *guard = ui_config;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteTheme(theme) => {
-                try_lock!(@write_lock_retry, self.ui_config.clone(), |guard: &mut UIConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &mut UIConfig| {
                     guard.theme = theme;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteFps(fps) => {
-                try_lock!(@write_lock_retry, self.ui_config.clone(), |guard: &mut UIConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &mut UIConfig| {
                     guard.fps = fps;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
+            PortalAction::WriteBrightness(brightness) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &mut UIConfig| {
+                    guard.brightness = Some(brightness);
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
+            PortalAction::WriteAutosave(autosave) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &mut UIConfig| {
+                    guard.autosave = autosave;
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
+            PortalAction::WriteTimestamps(timestamps) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &mut UIConfig| {
+                    guard.timestamps = timestamps;
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
+            PortalAction::WriteFocusHighlight(focus_highlight) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.ui_config, self.ui_config.clone(), |guard: &mut UIConfig| {
+                    guard.focus_highlight = focus_highlight;
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
 
             // Controller configuration operations
             PortalAction::GetElrsConfig => {
-                try_lock!(@read_lock_retry, self.controller_config.clone(), |guard: &ControllerConfig| {
-                    ConfigResult::ElrsConfig(guard.elrs_mapping.clone())
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &ControllerConfig| {
+                    ConfigResult::ElrsConfig(Box::new(guard.elrs_mapping.clone()))
                 })
             }
             PortalAction::GetKeyboardConfig => {
-                try_lock!(@read_lock_retry, self.controller_config.clone(), |guard: &ControllerConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &ControllerConfig| {
                     ConfigResult::KeyboardConfig(guard.keyboard_mapping.clone())
                 })
             }
+            PortalAction::GetCustomConfig => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &ControllerConfig| {
+                    ConfigResult::CustomConfig(guard.custom_mapping.clone())
+                })
+            }
             PortalAction::GetControllerConfig => {
-                try_lock!(@read_lock_retry, self.controller_config.clone(), |guard: &ControllerConfig| {
-                    ConfigResult::ControllerConfig(guard.clone())
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &ControllerConfig| {
+                    ConfigResult::ControllerConfig(Box::new(guard.clone()))
+                })
+            }
+            PortalAction::GetControllerSettings => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &ControllerConfig| {
+                    ConfigResult::ControllerSettings(guard.controller_settings.clone())
                 })
             }
             PortalAction::WriteElrsConfig(elrs_config) => {
-                try_lock!(@write_lock_retry, self.controller_config.clone(), |guard: &mut ControllerConfig| {
-                    guard.elrs_mapping = elrs_config;
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &mut ControllerConfig| {
+                    guard.elrs_mapping = *elrs_config;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteKeyboardConfig(keyboard_config) => {
-                try_lock!(@write_lock_retry, self.controller_config.clone(), |guard: &mut ControllerConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &mut ControllerConfig| {
                     guard.keyboard_mapping = keyboard_config;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
+            PortalAction::WriteCustomConfig(custom_config) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &mut ControllerConfig| {
+                    guard.custom_mapping = custom_config;
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
             PortalAction::WriteControllerConfig(controller_config) => {
-                try_lock!(@write_lock_retry, self.controller_config.clone(), |guard: &mut ControllerConfig| {
-                    *guard = controller_config;
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &mut ControllerConfig| {
+                    *guard = *controller_config;
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
+            PortalAction::WriteControllerSettings(controller_settings) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.controller_config, self.controller_config.clone(), |guard: &mut ControllerConfig| {
+                    guard.controller_settings = controller_settings;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
 
             // Connection configuration operations
             PortalAction::GetMqttConfig => {
-                try_lock!(@read_lock_retry, self.connection_config.clone(), |guard: &ConnectionConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.connection_config, self.connection_config.clone(), |guard: &ConnectionConfig| {
                     ConfigResult::MqttConfig(guard.mqtt_config.clone())
                 })
             }
             PortalAction::GetConnectionConfig => {
-                try_lock!(@read_lock_retry, self.connection_config.clone(), |guard: &ConnectionConfig| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.connection_config, self.connection_config.clone(), |guard: &ConnectionConfig| {
                     ConfigResult::ConnectionConfig(guard.clone())
                 })
             }
             PortalAction::WriteMqttConfig(mqtt_config) => {
-                try_lock!(@write_lock_retry, self.connection_config.clone(), |guard: &mut ConnectionConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.connection_config, self.connection_config.clone(), |guard: &mut ConnectionConfig| {
                     guard.mqtt_config = mqtt_config;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteConnectionConfig(connection_config) => {
-                try_lock!(@write_lock_retry, self.connection_config.clone(), |guard: &mut ConnectionConfig| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.connection_config, self.connection_config.clone(), |guard: &mut ConnectionConfig| {
                     *guard = connection_config;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
@@ -410,36 +633,82 @@ impl ConfigPortal {
 
             // Message persistence operations
             PortalAction::GetSavedMessagesMsg => {
-                try_lock!(@read_lock_retry, self.msg_save.clone(), |guard: &SavedMessages| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.msg_save, self.msg_save.clone(), |guard: &SavedMessages| {
                     ConfigResult::MqttMessages(guard.msg.clone())
                 })
             }
             PortalAction::GetSavedMessages => {
-                try_lock!(@read_lock_retry, self.msg_save.clone(), |guard: &SavedMessages| {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.msg_save, self.msg_save.clone(), |guard: &SavedMessages| {
                     ConfigResult::MqttHistory(guard.clone())
                 })
             }
             PortalAction::WriteSavedMessages(saved_messages) => {
-                try_lock!(@write_lock_retry, self.msg_save.clone(), |guard: &mut SavedMessages| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.msg_save, self.msg_save.clone(), |guard: &mut SavedMessages| {
                     *guard = saved_messages;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
             PortalAction::WriteSavedMessagesMsg(messages) => {
-                try_lock!(@write_lock_retry, self.msg_save.clone(), |guard: &mut SavedMessages| {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.msg_save, self.msg_save.clone(), |guard: &mut SavedMessages| {
                     guard.msg = messages;
                     Ok::<ConfigResult, Error>(ConfigResult::Success)
                 })
             }
+
+            // Received message log operations
+            PortalAction::GetReceivedLogMsg => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.received_log, self.received_log.clone(), |guard: &ReceivedLog| {
+                    ConfigResult::MqttMessages(guard.msg.clone())
+                })
+            }
+            PortalAction::GetReceivedLog => {
+                try_lock!(@read_lock_retry, max_attempts, &self.lock_stats.received_log, self.received_log.clone(), |guard: &ReceivedLog| {
+                    ConfigResult::ReceivedLog(guard.clone())
+                })
+            }
+            PortalAction::WriteReceivedLog(received_log) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.received_log, self.received_log.clone(), |guard: &mut ReceivedLog| {
+                    *guard = received_log;
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
+            PortalAction::AppendReceivedLogMsg(message) => {
+                try_lock!(@write_lock_retry, max_attempts, &self.lock_stats.received_log, self.received_log.clone(), |guard: &mut ReceivedLog| {
+                    guard.push(message);
+                    Ok::<ConfigResult, Error>(ConfigResult::Success)
+                })
+            }
+
+            // Whole-session replacement
+            PortalAction::ApplySession(snapshot) => self.apply_session(*snapshot, max_attempts),
         };
 
         match result {
-            Ok(res) => res,
+            Ok(res) => {
+                if marks_dirty {
+                    self.mark_dirty();
+                }
+                res
+            }
             Err(e) => ConfigResult::Failed(e),
         }
     }
 }
 
+/// Complete configuration of one session, as read from its files.
+///
+/// Applied to a running portal with [`PortalAction::ApplySession`], which
+/// swaps all sections at once.
+#[derive(Debug, Clone, Default)]
+pub struct SessionSnapshot {
+    pub session: SessionConfig,
+    pub ui_config: UIConfig,
+    pub controller_config: ControllerConfig,
+    pub connection_config: ConnectionConfig,
+    pub msg_save: SavedMessages,
+    pub received_log: ReceivedLog,
+}
+
 /// Enumeration of all possible configuration operations.
 ///
 /// ## Design Rationale
@@ -463,7 +732,7 @@ impl ConfigPortal {
 /// - **Read operations**: No parameters, return data through ConfigResult
 /// - **Write operations**: Take owned data to ensure thread safety
 /// - **Batch operations**: Accept complex data structures for atomic updates
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PortalAction {
     // Session configuration management
     GetSession,
@@ -481,17 +750,29 @@ pub enum PortalAction {
     GetUIConfig,
     GetTheme,
     GetFps,
+    GetBrightness,
+    GetAutosave,
+    GetTimestamps,
+    GetFocusHighlight,
     WriteUIConfig(UIConfig),
     WriteTheme(Theme),
     WriteFps(u8),
+    WriteBrightness(f32),
+    WriteAutosave(AutosaveSettings),
+    WriteTimestamps(TimestampSettings),
+    WriteFocusHighlight(FocusHighlight),
 
     // Controller and mapping configuration management
     GetElrsConfig,
     GetKeyboardConfig,
+    GetCustomConfig,
     GetControllerConfig,
-    WriteElrsConfig(mapping::elrs::ELRSConfig),
+    GetControllerSettings,
+    WriteElrsConfig(Box<mapping::elrs::ELRSConfig>),
     WriteKeyboardConfig(mapping::keyboard::KeyboardConfig),
-    WriteControllerConfig(ControllerConfig),
+    WriteCustomConfig(mapping::custom::CustomConfig),
+    WriteControllerConfig(Box<ControllerConfig>),
+    WriteControllerSettings(controller::controller_handle::ControllerSettings),
 
     // Network and communication configuration management
     GetMqttConfig,
@@ -504,6 +785,57 @@ pub enum PortalAction {
     GetSavedMessages,
     WriteSavedMessages(SavedMessages),
     WriteSavedMessagesMsg(Vec<mqtt::message_manager::MQTTMessage>),
+
+    // Received message log management
+    GetReceivedLogMsg,
+    GetReceivedLog,
+    WriteReceivedLog(ReceivedLog),
+    AppendReceivedLogMsg(mqtt::message_manager::MQTTMessage),
+
+    // Whole-session replacement
+    ApplySession(Box<SessionSnapshot>),
+}
+
+impl PortalAction {
+    /// Whether the action changes configuration that needs to be saved.
+    ///
+    /// The session registry is excluded: it is bookkeeping rebuilt from the
+    /// config directory and rewritten by every save itself. So is an applied
+    /// session, which matches the files it was loaded from, and the received
+    /// log, which is runtime MQTT traffic rather than a user edit. The log is
+    /// still written with the next save.
+    fn marks_dirty(&self) -> bool {
+        !matches!(
+            self,
+            PortalAction::GetSession
+                | PortalAction::GetSessionName
+                | PortalAction::GetLastSession
+                | PortalAction::GetSessionPath
+                | PortalAction::GetAvailableSessions
+                | PortalAction::WriteAvailableSessions(_)
+                | PortalAction::GetUIConfig
+                | PortalAction::GetTheme
+                | PortalAction::GetFps
+                | PortalAction::GetBrightness
+                | PortalAction::GetAutosave
+                | PortalAction::GetTimestamps
+                | PortalAction::GetFocusHighlight
+                | PortalAction::GetElrsConfig
+                | PortalAction::GetKeyboardConfig
+                | PortalAction::GetCustomConfig
+                | PortalAction::GetControllerConfig
+                | PortalAction::GetControllerSettings
+                | PortalAction::GetMqttConfig
+                | PortalAction::GetConnectionConfig
+                | PortalAction::GetSavedMessagesMsg
+                | PortalAction::GetSavedMessages
+                | PortalAction::GetReceivedLogMsg
+                | PortalAction::GetReceivedLog
+                | PortalAction::WriteReceivedLog(_)
+                | PortalAction::AppendReceivedLogMsg(_)
+                | PortalAction::ApplySession(_)
+        )
+    }
 }
 
 /// Type-safe return values for configuration operations.
@@ -536,16 +868,115 @@ pub enum ConfigResult {
     UIConfig(UIConfig),
     Theme(Theme),
     Fps(u8),
-    ControllerConfig(ControllerConfig),
-    ElrsConfig(mapping::elrs::ELRSConfig),
+    Brightness(Option<f32>),
+    Autosave(AutosaveSettings),
+    Timestamps(TimestampSettings),
+    FocusHighlight(FocusHighlight),
+    ControllerConfig(Box<ControllerConfig>),
+    ControllerSettings(controller::controller_handle::ControllerSettings),
+    ElrsConfig(Box<mapping::elrs::ELRSConfig>),
     KeyboardConfig(mapping::keyboard::KeyboardConfig),
+    CustomConfig(mapping::custom::CustomConfig),
     ConnectionConfig(ConnectionConfig),
     MqttConfig(mqtt::config::MqttConfig),
     MqttMessages(Vec<mqtt::message_manager::MQTTMessage>),
     MqttHistory(SavedMessages),
+    ReceivedLog(ReceivedLog),
     Failed(Error),
 }
 
+/// Contention counters of one configuration section.
+///
+/// Plain relaxed atomics, cheap enough to stay on in every build. They count
+/// events only; ordering between sections does not matter for diagnostics.
+#[derive(Debug, Default)]
+pub struct LockCounters {
+    read_waits: AtomicU64,
+    write_waits: AtomicU64,
+    timeouts: AtomicU64,
+}
+
+impl LockCounters {
+    /// Counts a read attempt that found the lock taken.
+    pub fn record_read_wait(&self) {
+        self.read_waits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a write attempt that found the lock taken.
+    pub fn record_write_wait(&self) {
+        self.write_waits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts an action that gave up with [`Error::LockTimeout`].
+    pub fn record_timeout(&self) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LockMetrics {
+        LockMetrics {
+            read_waits: self.read_waits.load(Ordering::Relaxed),
+            write_waits: self.write_waits.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Takes `lock` for writing without waiting, counting a failure in `counters`.
+///
+/// Returns the counters of the busy section on failure.
+fn try_write_counted<'a, T>(
+    lock: &'a RwLock<T>,
+    counters: &'a LockCounters,
+) -> Result<RwLockWriteGuard<'a, T>, &'a LockCounters> {
+    lock.try_write().map_err(|_| {
+        counters.record_write_wait();
+        counters
+    })
+}
+
+/// Counters of every section, in lock order.
+#[derive(Debug, Default)]
+struct PortalLockStats {
+    session: LockCounters,
+    ui_config: LockCounters,
+    controller_config: LockCounters,
+    connection_config: LockCounters,
+    msg_save: LockCounters,
+    received_log: LockCounters,
+}
+
+/// Contention of one section as returned by [`ConfigPortal::metrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LockMetrics {
+    /// Read attempts that found the lock taken by a writer
+    pub read_waits: u64,
+    /// Write attempts that found the lock taken
+    pub write_waits: u64,
+    /// Actions that gave up after all attempts
+    pub timeouts: u64,
+}
+
+/// Attempts of a blocking portal action before it fails with [`Error::LockTimeout`]
+pub const LOCK_ATTEMPTS: usize = 5;
+
+/// Time between two attempts to take a contended section lock
+pub const LOCK_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Waits [`LOCK_RETRY_DELAY`] between two blocking lock attempts.
+///
+/// On a worker of the multi-threaded tokio runtime the wait runs inside
+/// `block_in_place`, so the worker's other tasks move to another thread instead
+/// of stalling behind it. Elsewhere, e.g. on the UI thread, it simply sleeps.
+pub fn lock_retry_wait() {
+    let multi_threaded = tokio::runtime::Handle::try_current()
+        .is_ok_and(|handle| handle.runtime_flavor() == RuntimeFlavor::MultiThread);
+    if multi_threaded {
+        tokio::task::block_in_place(|| std::thread::sleep(LOCK_RETRY_DELAY));
+    } else {
+        std::thread::sleep(LOCK_RETRY_DELAY);
+    }
+}
+
 /// Sophisticated lock retry macro with timeout and logging.
 ///
 /// ## Design Motivation
@@ -559,14 +990,18 @@ pub enum ConfigResult {
 ///
 /// ## Retry Strategy
 ///
-/// - **Maximum attempts**: 5 retries prevent infinite loops under high contention
-/// - **Retry delay**: 10ms delays allow contention to resolve without excessive waiting
+/// - **Maximum attempts**: Given by the caller, [`LOCK_ATTEMPTS`] for blocking
+///   dispatch and 1 per attempt of the async dispatch
+/// - **Retry delay**: [`LOCK_RETRY_DELAY`] through [`lock_retry_wait`], so
+///   contention can resolve without excessive waiting
 /// - **Progressive logging**: Each retry attempt is logged for debugging
 /// - **Timeout behavior**: Returns LockTimeout error rather than panicking or hanging
 ///
 /// ## Macro Design
 ///
 /// Uses internal pattern matching (@read_lock_retry, @write_lock_retry) to provide:
+/// - **Contention metrics**: Every blocked attempt and timeout is counted in
+///   the section's [`LockCounters`]
 /// - **Type safety**: Read and write operations have different signatures
 /// - **Code reuse**: Common retry logic shared between read and write operations
 /// - **Flexibility**: Operations can provide custom logic within the retry framework
@@ -576,7 +1011,8 @@ pub enum ConfigResult {
 /// - **Success case**: Minimal overhead (single try_lock attempt)
 /// - **Contention case**: Maximum 50ms delay (5 attempts × 10ms) before timeout
 /// - **Memory usage**: No allocations, stack-based retry logic only
-/// - **Thread impact**: Sleep calls yield to other threads during contention
+/// - **Thread impact**: Waits hand a tokio worker's tasks to other workers,
+///   see [`lock_retry_wait`]
 ///
 /// ## Usage Context
 ///
@@ -586,39 +1022,40 @@ pub enum ConfigResult {
 #[macro_export]
 macro_rules! try_lock {
     // Write lock retry with timeout and error handling
-    (@write_lock_retry, $accessor:expr, $operation:expr) => {{
+    (@write_lock_retry, $max_attempts:expr, $stats:expr, $accessor:expr, $operation:expr) => {{
         let mut attempts = 0;
-        const MAX_ATTEMPTS: usize = 5;
+        let max_attempts: usize = $max_attempts;
 
         loop {
             match $accessor.try_write() {
                 Ok(mut guard) => {
-                    $operation(&mut *guard);
-                    break Ok($crate::persistence::config_portal::ConfigResult::Success);
+                    break $operation(&mut *guard);
                 }
                 Err(e) => {
                     attempts += 1;
+                    $stats.record_write_wait();
                     tracing::warn!(
                         "Write lock blocked: {} (attempt {}/{})",
                         e,
                         attempts,
-                        MAX_ATTEMPTS
+                        max_attempts
                     );
 
-                    if attempts >= MAX_ATTEMPTS {
+                    if attempts >= max_attempts {
+                        $stats.record_timeout();
                         break Err($crate::persistence::config_portal::Error::LockTimeout);
                     }
 
-                    std::thread::sleep(std::time::Duration::from_millis(10));
+                    $crate::persistence::config_portal::lock_retry_wait();
                 }
             }
         }
     }};
 
     // Read lock retry with timeout and error handling
-    (@read_lock_retry, $accessor:expr, $operation:expr) => {{
+    (@read_lock_retry, $max_attempts:expr, $stats:expr, $accessor:expr, $operation:expr) => {{
         let mut attempts = 0;
-        const MAX_ATTEMPTS: usize = 5;
+        let max_attempts: usize = $max_attempts;
 
         loop {
             match $accessor.try_read() {
@@ -628,18 +1065,20 @@ macro_rules! try_lock {
                 }
                 Err(e) => {
                     attempts += 1;
+                    $stats.record_read_wait();
                     tracing::warn!(
                         "Read lock blocked: {} (attempt {}/{})",
                         e,
                         attempts,
-                        MAX_ATTEMPTS
+                        max_attempts
                     );
 
-                    if attempts >= MAX_ATTEMPTS {
+                    if attempts >= max_attempts {
+                        $stats.record_timeout();
                         break Err($crate::persistence::config_portal::Error::LockTimeout);
                     }
 
-                    std::thread::sleep(std::time::Duration::from_millis(10));
+                    $crate::persistence::config_portal::lock_retry_wait();
                 }
             }
         }
@@ -671,3 +1110,43 @@ pub enum Error {
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn received_messages_do_not_mark_dirty() {
+        let portal = ConfigPortal::default();
+        let message = mqtt::message_manager::MQTTMessage::from_topic(
+            "sensors/temperature".to_string(),
+            "23.5".to_string(),
+        );
+
+        portal.execute_potal_action(PortalAction::AppendReceivedLogMsg(message));
+        portal.execute_potal_action(PortalAction::WriteReceivedLog(ReceivedLog::default()));
+
+        assert!(!portal.is_dirty());
+    }
+
+    #[test]
+    fn reads_do_not_mark_dirty() {
+        let portal = ConfigPortal::default();
+
+        portal.execute_potal_action(PortalAction::GetUIConfig);
+        portal.execute_potal_action(PortalAction::GetReceivedLog);
+
+        assert!(!portal.is_dirty());
+    }
+
+    #[test]
+    fn config_edit_marks_dirty_until_taken() {
+        let portal = ConfigPortal::default();
+
+        portal.execute_potal_action(PortalAction::WriteFps(30));
+
+        assert!(portal.is_dirty());
+        assert!(portal.take_dirty());
+        assert!(!portal.is_dirty());
+    }
+}
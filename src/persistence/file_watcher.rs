@@ -0,0 +1,261 @@
+//! # Session File Watcher
+//!
+//! ## Why This Module Exists
+//! Session files are plain TOML and meant to be edited by hand or synced from
+//! another machine. Without a watcher such changes only take effect after a
+//! restart. The watcher notices changed files of the active session and loads
+//! them into the [`ConfigPortal`], so the running application picks them up
+//! the same way it picks up edits made in the UI.
+//!
+//! ## Design Rationale
+//!
+//! ### Whole Config Directory
+//! The config directory is watched recursively and events are filtered by the
+//! active session's directory, so switching sessions needs no re-registration.
+//!
+//! ### Debouncing
+//! Editors and sync tools often write a file in several steps (truncate,
+//! write, rename). Changes are collected until no event arrived for
+//! [`DEBOUNCE`], then every affected file is reloaded once.
+//!
+//! ### Feedback Loop Guard
+//! Saves and autosaves write the same files the watcher observes. Every write
+//! through the session client records a hash of the written content; a file
+//! whose content matches its last recorded write is skipped. Content is
+//! compared instead of timestamps, so a slow disk cannot turn an own write
+//! into a reload.
+//!
+//! ### Opt-In
+//! The watcher follows [`AutosaveSettings::reload_on_change`] and is stopped
+//! while it is off. Files that fail to parse are logged and the running
+//! configuration is kept.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex as StdMutex};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use super::config_portal::{ConfigResult, PortalAction};
+use super::session_client::{self, SessionClient};
+use super::{
+    AutosaveSettings, ConnectionConfig, ControllerConfig, ReceivedLog, SavedMessages, UIConfig,
+};
+
+/// Quiet time after the last change before files are reloaded.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Content hashes of the files last written by the application itself.
+static OWN_WRITES: LazyLock<StdMutex<HashMap<PathBuf, u64>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+/// Records content the application wrote to `path`, so the watcher skips it.
+pub(super) fn record_own_write(path: &Path, content: &[u8]) {
+    if let Ok(mut writes) = OWN_WRITES.lock() {
+        writes.insert(path.to_path_buf(), content_hash(content));
+    }
+}
+
+/// Whether `content` is exactly what the application last wrote to `path`.
+fn is_own_write(path: &Path, content: &[u8]) -> bool {
+    OWN_WRITES
+        .lock()
+        .map(|writes| writes.get(path) == Some(&content_hash(content)))
+        .unwrap_or(false)
+}
+
+fn content_hash(content: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Starts the background task that reloads externally changed session files.
+///
+/// The task runs for the lifetime of the application and starts or stops the
+/// filesystem watcher whenever `reload_on_change` changes in `settings_rx`.
+/// A reloaded UI config also republishes its autosave settings through
+/// `settings_tx`, so hand-edited autosave values apply immediately.
+pub fn start_file_watch_task(
+    session_client: Arc<Mutex<SessionClient>>,
+    mut settings_rx: watch::Receiver<AutosaveSettings>,
+    settings_tx: watch::Sender<AutosaveSettings>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let enabled = settings_rx.borrow_and_update().reload_on_change;
+            if !enabled {
+                debug!("Session file watcher off");
+                if settings_rx.changed().await.is_err() {
+                    return;
+                }
+                continue;
+            }
+
+            let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+            let _watcher = match watch_config_dir(event_tx) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    error!("Failed to watch session files: {}", e);
+                    if settings_rx.changed().await.is_err() {
+                        return;
+                    }
+                    continue;
+                }
+            };
+            info!("Watching session files for external changes");
+
+            loop {
+                tokio::select! {
+                    path = event_rx.recv() => {
+                        let Some(path) = path else {
+                            warn!("Session file watcher stopped");
+                            break;
+                        };
+                        let mut changed = HashSet::from([path]);
+                        while let Ok(Some(path)) =
+                            tokio::time::timeout(DEBOUNCE, event_rx.recv()).await
+                        {
+                            changed.insert(path);
+                        }
+                        reload_files(&session_client, &settings_tx, changed).await;
+                    }
+                    result = settings_rx.changed() => {
+                        if result.is_err() {
+                            return;
+                        }
+                        if !settings_rx.borrow().reload_on_change {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Starts a recursive watcher on the config directory.
+///
+/// Paths of created or modified files are sent to `event_tx`.
+fn watch_config_dir(
+    event_tx: mpsc::UnboundedSender<PathBuf>,
+) -> notify::Result<RecommendedWatcher> {
+    let mut watcher =
+        notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result {
+            Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                for path in event.paths {
+                    let _ = event_tx.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Session file watch error: {}", e),
+        })?;
+    watcher.watch(&session_client::config_dir(), RecursiveMode::Recursive)?;
+    Ok(watcher)
+}
+
+/// Loads every changed file of the active session into its config portal.
+async fn reload_files(
+    session_client: &Arc<Mutex<SessionClient>>,
+    settings_tx: &watch::Sender<AutosaveSettings>,
+    changed: HashSet<PathBuf>,
+) {
+    let client = session_client.lock().await;
+    let session_dir = client.current_session_dir();
+
+    for path in changed {
+        if path.parent() != Some(session_dir.as_path()) {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        let content = match tokio::fs::read(&path).await {
+            Ok(content) => content,
+            Err(e) => {
+                // Usually a temporary file renamed away before we got to it
+                debug!("Skipping {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        if is_own_write(&path, &content) {
+            continue;
+        }
+
+        match reload_file(&client, file_name, &path).await {
+            Ok(true) => {
+                info!("Reloaded {} after external change", path.display());
+                record_own_write(&path, &content);
+                if file_name == session_client::UI_CONFIG_FILE {
+                    if let ConfigResult::Autosave(settings) = client
+                        .get_portal_ref()
+                        .execute_portal_action_async(PortalAction::GetAutosave)
+                        .await
+                    {
+                        settings_tx.send_replace(settings);
+                    }
+                }
+            }
+            Ok(false) => {}
+            Err(e) => warn!("Keeping current configuration, {}", e),
+        }
+    }
+}
+
+/// Parses one session file and writes it into the portal.
+///
+/// Returns `false` for files that are not reloaded, e.g. the session
+/// metadata, which only the session client itself may change.
+async fn reload_file(
+    client: &SessionClient,
+    file_name: &str,
+    path: &Path,
+) -> color_eyre::Result<bool> {
+    let action = match file_name {
+        session_client::UI_CONFIG_FILE => {
+            session_client::read_config_file::<UIConfig>(path, "UI config")
+                .await?
+                .map(PortalAction::WriteUIConfig)
+        }
+        session_client::CONNECTION_CONFIG_FILE => {
+            session_client::read_config_file::<ConnectionConfig>(path, "Connection config")
+                .await?
+                .map(PortalAction::WriteConnectionConfig)
+        }
+        session_client::CONTROLLER_CONFIG_FILE => {
+            session_client::read_config_file::<ControllerConfig>(path, "Controller config")
+                .await?
+                .map(|config| PortalAction::WriteControllerConfig(Box::new(config)))
+        }
+        session_client::MESSAGES_FILE => {
+            session_client::read_config_file::<SavedMessages>(path, "Messages file")
+                .await?
+                .map(PortalAction::WriteSavedMessages)
+        }
+        session_client::RECEIVED_LOG_FILE => {
+            session_client::read_config_file::<ReceivedLog>(path, "Received log")
+                .await?
+                .map(PortalAction::WriteReceivedLog)
+        }
+        _ => None,
+    };
+
+    let Some(action) = action else {
+        return Ok(false);
+    };
+    match client
+        .get_portal_ref()
+        .execute_portal_action_async(action)
+        .await
+    {
+        ConfigResult::Failed(e) => Err(color_eyre::eyre::eyre!("{}", e)),
+        _ => Ok(true),
+    }
+}
@@ -0,0 +1,233 @@
+//! # Startup Config Integrity Check
+//!
+//! ## Why This Module Exists
+//! Corrupt config files used to be replaced by defaults while loading, and the
+//! next save overwrote them, so a broken file turned into lost settings
+//! without the user noticing. The check parses every config file before the
+//! persistence layer starts and lets the user decide what happens to broken
+//! ones.
+//!
+//! ## Held Saves
+//! While corrupt files are unresolved, [`saves_held`] is true and
+//! [`SessionClient::save_session`](super::session_client::SessionClient::save_session)
+//! writes nothing. The application runs on defaults for the broken sections,
+//! but the files on disk stay as they are until the user picks one of:
+//! - **Use defaults**: broken files are renamed to `<file>.corrupt` for later
+//!   inspection, saving resumes and writes the defaults
+//! - **Restore backup**: broken files with a readable `<file>.bak` are
+//!   replaced by it, the others are handled like "use defaults"
+//! - **Quit**: nothing is changed on disk
+//!
+//! ## What Counts as Corrupt
+//! A file is parsed exactly like the loader does, including the repair of
+//! missing fields, so outdated but repairable files are not reported.
+
+use super::session_client::{
+    config_dir, parse_config, with_suffix, SessionClient, BACKUP_SUFFIX, CONNECTION_CONFIG_FILE,
+    CONTROLLER_CONFIG_FILE, MAIN_CONFIG_FILE, MESSAGES_FILE, RECEIVED_LOG_FILE,
+    SESSION_CONFIG_FILE, SESSION_FILES, TEMP_SUFFIX, UI_CONFIG_FILE,
+};
+use super::{
+    ConnectionConfig, ControllerConfig, ReceivedLog, SavedMessages, SessionConfig, UIConfig,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{error, info, warn};
+
+/// Suffix a corrupt file is renamed to when the user picks the defaults
+pub const CORRUPT_SUFFIX: &str = ".corrupt";
+
+/// Whether session saves wait for the user's recovery decision
+static SAVES_HELD: AtomicBool = AtomicBool::new(false);
+
+/// A config file that failed to parse at startup.
+#[derive(Debug, Clone)]
+pub struct CorruptFile {
+    /// Path of the broken file
+    pub path: PathBuf,
+    /// Parse error, first line only
+    pub error: String,
+    /// Backup that parses and can replace the file, if any
+    pub backup: Option<PathBuf>,
+}
+
+/// Parses every config file and returns the broken ones.
+///
+/// Covers the main config and the files of every session directory. If any
+/// file is broken, saves are held until [`use_defaults`] or [`restore_backups`]
+/// resolves them.
+pub fn check_config_files() -> Vec<CorruptFile> {
+    let dir = config_dir();
+    let mut corrupt = Vec::new();
+
+    check_file(&dir.join(MAIN_CONFIG_FILE), MAIN_CONFIG_FILE, &mut corrupt);
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let session_dir = entry.path();
+            if !session_dir.is_dir() {
+                continue;
+            }
+            for file_name in SESSION_FILES {
+                check_file(&session_dir.join(file_name), file_name, &mut corrupt);
+            }
+        }
+    }
+
+    if !corrupt.is_empty() {
+        for file in &corrupt {
+            error!(
+                "Corrupt config file {}: {}",
+                file.path.display(),
+                file.error
+            );
+        }
+        SAVES_HELD.store(true, Ordering::SeqCst);
+    }
+    corrupt
+}
+
+/// Whether saves wait for a recovery decision, see the module docs.
+pub fn saves_held() -> bool {
+    SAVES_HELD.load(Ordering::SeqCst)
+}
+
+/// Moves the corrupt files aside and resumes saving, so the defaults in use
+/// are written on the next save.
+///
+/// # Errors
+/// Returns the files that could not be renamed; saving resumes anyway.
+pub fn use_defaults(files: &[CorruptFile]) -> Result<(), String> {
+    let failed: Vec<String> = files
+        .iter()
+        .filter_map(|file| move_aside(&file.path).err())
+        .collect();
+    SAVES_HELD.store(false, Ordering::SeqCst);
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed.join("\n"))
+    }
+}
+
+/// Replaces corrupt files by their backups and resumes saving.
+///
+/// Files without a backup are moved aside like in [`use_defaults`]. The
+/// restored files only take effect once the session is loaded again.
+///
+/// # Errors
+/// Returns the files that could not be restored or moved; saving resumes anyway.
+pub fn restore_backups(files: &[CorruptFile]) -> Result<(), String> {
+    let failed: Vec<String> = files
+        .iter()
+        .filter_map(|file| {
+            let result = match &file.backup {
+                Some(backup) => restore(&file.path, backup),
+                None => move_aside(&file.path),
+            };
+            result.err()
+        })
+        .collect();
+    SAVES_HELD.store(false, Ordering::SeqCst);
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed.join("\n"))
+    }
+}
+
+/// Session named in the main config on disk, to reload after a restore.
+pub fn stored_session_name() -> Option<String> {
+    let content = fs::read_to_string(config_dir().join(MAIN_CONFIG_FILE)).ok()?;
+    toml::from_str::<SessionClient>(&content)
+        .ok()
+        .map(|client| client.current_session().to_string())
+}
+
+/// Records `path` as corrupt if it exists and fails to parse.
+fn check_file(path: &Path, file_name: &str, corrupt: &mut Vec<CorruptFile>) {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            corrupt.push(CorruptFile {
+                path: path.to_path_buf(),
+                error: format!("Unreadable: {}", e),
+                backup: None,
+            });
+            return;
+        }
+    };
+
+    let Err(error) = parse_file(file_name, &content) else {
+        return;
+    };
+    let backup = with_suffix(path, BACKUP_SUFFIX);
+    let backup = fs::read_to_string(&backup)
+        .ok()
+        .filter(|content| parse_file(file_name, content).is_ok())
+        .map(|_| backup);
+    corrupt.push(CorruptFile {
+        path: path.to_path_buf(),
+        error: error.lines().next().unwrap_or_default().to_string(),
+        backup,
+    });
+}
+
+/// Parses `content` as the config type stored in `file_name`.
+fn parse_file(file_name: &str, content: &str) -> Result<(), String> {
+    fn check<T>(content: &str, description: &str) -> Result<(), String>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + Default,
+    {
+        parse_config::<T>(content, description)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    match file_name {
+        MAIN_CONFIG_FILE => toml::from_str::<SessionClient>(content)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        UI_CONFIG_FILE => check::<UIConfig>(content, "UI config"),
+        CONNECTION_CONFIG_FILE => check::<ConnectionConfig>(content, "Connection config"),
+        CONTROLLER_CONFIG_FILE => check::<ControllerConfig>(content, "Controller config"),
+        MESSAGES_FILE => check::<SavedMessages>(content, "Messages file"),
+        RECEIVED_LOG_FILE => check::<ReceivedLog>(content, "Received log"),
+        SESSION_CONFIG_FILE => check::<SessionConfig>(content, "Session config"),
+        _ => Ok(()),
+    }
+}
+
+/// Renames a corrupt file to its [`CORRUPT_SUFFIX`] name.
+fn move_aside(path: &Path) -> Result<(), String> {
+    let target = with_suffix(path, CORRUPT_SUFFIX);
+    match fs::rename(path, &target) {
+        Ok(()) => {
+            info!("Moved corrupt {} to {}", path.display(), target.display());
+            Ok(())
+        }
+        Err(e) => {
+            warn!("Failed to move corrupt {} aside: {}", path.display(), e);
+            Err(format!("{}: {}", path.display(), e))
+        }
+    }
+}
+
+/// Replaces `path` by `backup` through a temporary copy, so the file is
+/// never half restored.
+fn restore(path: &Path, backup: &Path) -> Result<(), String> {
+    let temp = with_suffix(path, TEMP_SUFFIX);
+    let result = fs::copy(backup, &temp).and_then(|_| fs::rename(&temp, path));
+    match result {
+        Ok(()) => {
+            info!("Restored {} from {}", path.display(), backup.display());
+            Ok(())
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            warn!("Failed to restore {}: {}", path.display(), e);
+            Err(format!("{}: {}", path.display(), e))
+        }
+    }
+}
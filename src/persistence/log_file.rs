@@ -0,0 +1,283 @@
+//! # Log File
+//!
+//! ## Why This Module Exists
+//! Logs go to stdout, which is gone after a crash on a headless device or when
+//! the application was started from a desktop entry. For post-incident
+//! analysis the same records can be written to a file in [`log_dir`], next to
+//! the config directory. File logging is off by default and enabled with the
+//! [`LOG_FILE_ENV`] environment variable; stdout logging stays active.
+//!
+//! ## Rotation and Retention
+//! - One file per day, `opencontroller.YYYY-MM-DD.log`, switched on the first
+//!   record after midnight (local time)
+//! - At most [`MAX_LOG_FILES`] files are kept, older ones are deleted when a
+//!   new file is opened
+//! - A file stops growing at [`MAX_FILE_SIZE`]; the rest of the day's records
+//!   are dropped after a marker line
+//!
+//! Together this caps the log directory at `MAX_LOG_FILES * MAX_FILE_SIZE`.
+//!
+//! ## Design Rationale
+//! The writer runs inside the tracing subscriber, so it cannot log its own
+//! failures. Opening the first file is the only error reported (by
+//! [`from_env`]); later failures drop records instead of disturbing the
+//! application.
+
+use super::session_client::config_dir;
+use chrono::{Local, NaiveDate};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Environment variable enabling file logging, any value except `0`,
+/// `false` or an empty string enables it
+pub const LOG_FILE_ENV: &str = "OPENCONTROLLER_LOG_FILE";
+
+/// Number of daily log files kept, including today's
+pub const MAX_LOG_FILES: usize = 7;
+
+/// Size at which a log file stops growing
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+const LOG_DIR: &str = "logs";
+const FILE_PREFIX: &str = "opencontroller.";
+const FILE_SUFFIX: &str = ".log";
+
+/// Directory of the log files, a sibling of the config directory.
+pub fn log_dir() -> PathBuf {
+    let config = config_dir();
+    config
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or(config)
+        .join(LOG_DIR)
+}
+
+/// Whether [`LOG_FILE_ENV`] enables file logging.
+pub fn enabled() -> bool {
+    std::env::var(LOG_FILE_ENV)
+        .map(|value| {
+            let value = value.trim().to_lowercase();
+            !(value.is_empty() || value == "0" || value == "false")
+        })
+        .unwrap_or(false)
+}
+
+/// Opens today's log file if file logging is enabled.
+///
+/// `None` when it is disabled, an error when the log directory or file
+/// cannot be created.
+pub fn from_env() -> Option<io::Result<RotatingLogFile>> {
+    enabled().then(|| RotatingLogFile::open(log_dir()))
+}
+
+/// Daily rotating log file with retention and size limit
+#[derive(Debug)]
+pub struct RotatingLogFile {
+    dir: PathBuf,
+    date: NaiveDate,
+    file: Option<File>,
+    written: u64,
+    truncated: bool,
+}
+
+impl RotatingLogFile {
+    /// Creates `dir` if needed and opens today's file in it.
+    pub fn open(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let mut log_file = Self {
+            dir,
+            date: Local::now().date_naive(),
+            file: None,
+            written: 0,
+            truncated: false,
+        };
+        log_file.rotate(log_file.date)?;
+        Ok(log_file)
+    }
+
+    /// Path of the file currently written.
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(file_name(self.date))
+    }
+
+    /// Switches to the file of `date`, appending if it exists, and deletes
+    /// the oldest files beyond [`MAX_LOG_FILES`].
+    fn rotate(&mut self, date: NaiveDate) -> io::Result<()> {
+        self.date = date;
+        self.file = None;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path())?;
+        self.written = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+        self.truncated = self.written >= MAX_FILE_SIZE;
+        self.file = Some(file);
+        self.prune();
+        Ok(())
+    }
+
+    /// Deletes log files beyond [`MAX_LOG_FILES`], oldest first.
+    ///
+    /// The date in the name sorts lexicographically, so no metadata is read.
+    fn prune(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+                    })
+            })
+            .collect();
+        files.sort();
+        let excess = files.len().saturating_sub(MAX_LOG_FILES);
+        for path in files.into_iter().take(excess) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl Write for RotatingLogFile {
+    /// Writes one formatted record; records beyond the size limit are
+    /// reported as written and dropped.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_on(Local::now().date_naive(), buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl RotatingLogFile {
+    /// Writes a record on `today`, rotating first if the day changed.
+    fn write_on(&mut self, today: NaiveDate, buf: &[u8]) -> io::Result<usize> {
+        if today != self.date {
+            // A failed rotation leaves no file, the day's records are dropped
+            let _ = self.rotate(today);
+        }
+        let Some(file) = self.file.as_mut() else {
+            return Ok(buf.len());
+        };
+
+        if self.truncated {
+            return Ok(buf.len());
+        }
+        if self.written + buf.len() as u64 > MAX_FILE_SIZE {
+            self.truncated = true;
+            file.write_all(
+                b"--- log file size limit reached, dropping records until tomorrow ---\n",
+            )?;
+            return Ok(buf.len());
+        }
+
+        file.write_all(buf)?;
+        self.written += buf.len() as u64;
+        Ok(buf.len())
+    }
+}
+
+fn file_name(date: NaiveDate) -> String {
+    format!("{}{}{}", FILE_PREFIX, date.format("%Y-%m-%d"), FILE_SUFFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh log directory under the system temp dir, unique per test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "opencontroller-log-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn date_change_switches_to_a_new_file() {
+        let dir = test_dir("rotate");
+        let mut log = RotatingLogFile::open(dir.clone()).unwrap();
+
+        log.write_on(day(1), b"first day\n").unwrap();
+        log.write_on(day(1), b"still first\n").unwrap();
+        log.write_on(day(2), b"second day\n").unwrap();
+        log.flush().unwrap();
+
+        assert_eq!(log.path(), dir.join("opencontroller.2026-03-02.log"));
+        assert_eq!(
+            fs::read_to_string(dir.join(file_name(day(1)))).unwrap(),
+            "first day\nstill first\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join(file_name(day(2)))).unwrap(),
+            "second day\n"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn size_cap_drops_records_until_the_next_day() {
+        let dir = test_dir("size-cap");
+        let mut log = RotatingLogFile::open(dir.clone()).unwrap();
+        let almost_full = vec![b'x'; MAX_FILE_SIZE as usize - 4];
+
+        log.write_on(day(1), &almost_full).unwrap();
+        // Dropped records are still reported as written
+        assert_eq!(log.write_on(day(1), b"too long\n").unwrap(), 9);
+        assert_eq!(log.write_on(day(1), b"dropped\n").unwrap(), 8);
+        log.write_on(day(2), b"next day\n").unwrap();
+        log.flush().unwrap();
+
+        let capped = fs::read(dir.join(file_name(day(1)))).unwrap();
+        assert!(capped.starts_with(&almost_full));
+        assert!(String::from_utf8_lossy(&capped[almost_full.len()..])
+            .starts_with("--- log file size limit reached"));
+        assert_eq!(
+            fs::read_to_string(dir.join(file_name(day(2)))).unwrap(),
+            "next day\n"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_files() {
+        let dir = test_dir("prune");
+        let mut log = RotatingLogFile::open(dir.clone()).unwrap();
+        // Opening created today's file, which counts as well
+        let opened = file_name(log.date);
+        fs::write(dir.join("notes.txt"), "not a log").unwrap();
+
+        for date in 1..=10 {
+            log.write_on(day(date), b"record\n").unwrap();
+        }
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        let mut logs: Vec<String> = (1..=10).map(|date| file_name(day(date))).collect();
+        logs.push(opened);
+        logs.sort();
+        let mut expected = logs.split_off(logs.len() - MAX_LOG_FILES);
+        expected.push("notes.txt".to_string());
+        expected.sort();
+        assert_eq!(names, expected);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
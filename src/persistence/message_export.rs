@@ -0,0 +1,115 @@
+//! # Message Log Export
+//!
+//! ## Why This Module Exists
+//! The live log of received MQTT messages is kept with the session, but only
+//! in the session's own TOML file. After a debugging session the messages are
+//! needed in a spreadsheet or a script, so this module writes the in-memory log
+//! to a file chosen by the user. It is independent of session saving: nothing
+//! is read from or written to the session directory.
+//!
+//! ## Formats
+//! - **CSV**: header `timestamp,topic,payload`, fields quoted per RFC 4180
+//!   when they contain commas, quotes or line breaks
+//! - **JSON Lines**: one object with `timestamp`, `topic` and `payload` per line
+//!
+//! Binary payloads are exported as the hex bytes shown in the log.
+
+use crate::mqtt::message_manager::MQTTMessage;
+use color_eyre::eyre::{eyre, Result};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+
+/// Timestamp format of exported messages, sortable and with milliseconds
+pub const EXPORT_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+/// File format of an exported message log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Csv,
+    JsonLines,
+}
+
+impl LogFormat {
+    pub const ALL: [LogFormat; 2] = [LogFormat::Csv, LogFormat::JsonLines];
+
+    /// File extension used when the export path names no file
+    pub fn extension(self) -> &'static str {
+        match self {
+            LogFormat::Csv => "csv",
+            LogFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Csv => write!(f, "CSV"),
+            LogFormat::JsonLines => write!(f, "JSON Lines"),
+        }
+    }
+}
+
+/// One exported message, field order is the column order of the CSV
+#[derive(Serialize)]
+struct ExportedMessage<'a> {
+    timestamp: String,
+    topic: &'a str,
+    payload: &'a str,
+}
+
+impl<'a> From<&'a MQTTMessage> for ExportedMessage<'a> {
+    fn from(msg: &'a MQTTMessage) -> Self {
+        Self {
+            timestamp: msg.timestamp.format(EXPORT_TIMESTAMP_FORMAT).to_string(),
+            topic: &msg.topic,
+            payload: &msg.content,
+        }
+    }
+}
+
+/// Writes `messages` to `path` in the given format, oldest first.
+///
+/// An existing file is overwritten.
+pub fn export_messages(messages: &[MQTTMessage], path: &Path, format: LogFormat) -> Result<()> {
+    let content = match format {
+        LogFormat::Csv => to_csv(messages),
+        LogFormat::JsonLines => to_json_lines(messages)?,
+    };
+    std::fs::write(path, content).map_err(|e| eyre!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Renders messages as CSV with a header line.
+fn to_csv(messages: &[MQTTMessage]) -> String {
+    let mut csv = String::from("timestamp,topic,payload\n");
+    for msg in messages.iter().map(ExportedMessage::from) {
+        let fields = [msg.timestamp.as_str(), msg.topic, msg.payload];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Renders messages as JSON Lines, one object per message.
+fn to_json_lines(messages: &[MQTTMessage]) -> Result<String> {
+    let mut lines = String::new();
+    for msg in messages.iter().map(ExportedMessage::from) {
+        let line = serde_json::to_string(&msg)
+            .map_err(|e| eyre!("Failed to serialize message on {}: {}", msg.topic, e))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+/// Quotes a CSV field if needed, doubling embedded quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
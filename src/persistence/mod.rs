@@ -25,14 +25,25 @@
 //! usable even in degraded scenarios.
 
 pub mod config_portal;
+pub mod file_watcher;
+pub mod integrity;
+pub mod log_file;
+pub mod message_export;
 pub mod persistence_worker;
 pub mod session_client;
 
-use crate::mapping::{elrs::ELRSConfig, keyboard::KeyboardConfig};
-use crate::mqtt::{config::MqttConfig, message_manager::MQTTMessage};
+use crate::controller::controller_handle::ControllerSettings;
+use crate::mapping::{custom::CustomConfig, elrs::ELRSConfig, keyboard::KeyboardConfig};
+use crate::mqtt::{config::MqttConfig, message_manager::MQTTMessage, template::TemplateVariables};
+use crate::ui::common::{UiColors, UiPalette};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
 use color_eyre::eyre::{eyre, Result};
+use eframe::egui;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 
 /// Defines color scheme and visual styling for the application UI.
@@ -46,7 +57,18 @@ use std::path::{Path, PathBuf};
 /// Applied by the UI system to customize the visual appearance. Supports the
 /// project's goal of providing a configurable interface that can be adapted
 /// to different environments and user preferences.
-#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+///
+/// ## Defaults
+/// The default theme is the built-in dark palette. Sessions saved before themes
+/// were applied store all colors as black; such themes count as unset and fall
+/// back to the default when applied.
+///
+/// ## Repair
+/// Hand-edited or migrated themes can make the UI unreadable, e.g. black text
+/// on a black background, leaving no way to reach the theme editor. The UI
+/// runs [`Theme::validate_and_repair`] on every loaded theme before applying
+/// it; the stored theme itself is left as it is.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub struct Theme {
     /// Border color for UI elements (frames, separators)
     border_color: (u8, u8, u8),
@@ -68,6 +90,289 @@ pub struct Theme {
     frame_color_two: (u8, u8, u8),
 }
 
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border_color: (60, 60, 60),
+            background_color_one: (30, 30, 30),
+            background_color_two: (25, 25, 25),
+            background_color_three: (20, 20, 20),
+            text_color: (180, 180, 180),
+            highlight_color: (50, 200, 20),
+            highlight_color_two: (200, 50, 20),
+            frame_color: (60, 60, 60),
+            frame_color_two: (70, 70, 70),
+        }
+    }
+}
+
+/// Smallest contrast ratio between text and any background it is drawn on.
+///
+/// The WCAG minimum for large text and UI components.
+pub const MIN_TEXT_CONTRAST: f32 = 3.0;
+
+/// Smallest contrast ratio between a highlight color and the main background.
+pub const MIN_HIGHLIGHT_CONTRAST: f32 = 1.5;
+
+/// Relative luminance of an sRGB color as defined by WCAG, 0.0 to 1.0.
+fn luminance((r, g, b): (u8, u8, u8)) -> f32 {
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// WCAG contrast ratio of two colors, 1.0 (identical) to 21.0 (black/white).
+fn contrast(a: (u8, u8, u8), b: (u8, u8, u8)) -> f32 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+impl Theme {
+    /// Replaces colors that would make the UI unreadable with safe ones.
+    ///
+    /// Returns a description of every replaced color, empty if the theme was
+    /// fine. An unset theme becomes the default without being reported.
+    ///
+    /// ## Checks, in order
+    /// 1. Text must reach [`MIN_TEXT_CONTRAST`] on background one. Otherwise
+    ///    the default text color is used, or white or black if that fails too.
+    /// 2. The other backgrounds and both frame colors must reach the same
+    ///    contrast with the (repaired) text. Failing ones take their default,
+    ///    or background one if the default fails as well.
+    /// 3. Highlight colors must reach [`MIN_HIGHLIGHT_CONTRAST`] on background
+    ///    one, so active and error states stay visible. Failing ones take their
+    ///    default, or the text color.
+    pub fn validate_and_repair(&mut self) -> Vec<String> {
+        if self.is_unset() {
+            *self = Theme::default();
+            return Vec::new();
+        }
+
+        let defaults = Theme::default();
+        let mut repairs = Vec::new();
+        let main_bg = self.background_color_one;
+
+        if contrast(self.text_color, main_bg) < MIN_TEXT_CONTRAST {
+            let replacement = [defaults.text_color, (255, 255, 255), (0, 0, 0)]
+                .into_iter()
+                .find(|candidate| contrast(*candidate, main_bg) >= MIN_TEXT_CONTRAST)
+                .unwrap_or((255, 255, 255));
+            repairs.push(format!(
+                "Text color {:?} is unreadable on background {:?}, using {:?}",
+                self.text_color, main_bg, replacement
+            ));
+            self.text_color = replacement;
+        }
+
+        let text = self.text_color;
+        for (name, color, default) in [
+            (
+                "Background 2",
+                &mut self.background_color_two,
+                defaults.background_color_two,
+            ),
+            (
+                "Background 3",
+                &mut self.background_color_three,
+                defaults.background_color_three,
+            ),
+            ("Frame 1", &mut self.frame_color, defaults.frame_color),
+            (
+                "Frame 2",
+                &mut self.frame_color_two,
+                defaults.frame_color_two,
+            ),
+        ] {
+            if contrast(*color, text) >= MIN_TEXT_CONTRAST {
+                continue;
+            }
+            let replacement = if contrast(default, text) >= MIN_TEXT_CONTRAST {
+                default
+            } else {
+                main_bg
+            };
+            repairs.push(format!(
+                "{} color {:?} makes text {:?} unreadable, using {:?}",
+                name, color, text, replacement
+            ));
+            *color = replacement;
+        }
+
+        for (name, color, default) in [
+            (
+                "Highlight 1",
+                &mut self.highlight_color,
+                defaults.highlight_color,
+            ),
+            (
+                "Highlight 2",
+                &mut self.highlight_color_two,
+                defaults.highlight_color_two,
+            ),
+        ] {
+            if contrast(*color, main_bg) >= MIN_HIGHLIGHT_CONTRAST {
+                continue;
+            }
+            let replacement = if contrast(default, main_bg) >= MIN_HIGHLIGHT_CONTRAST {
+                default
+            } else {
+                text
+            };
+            repairs.push(format!(
+                "{} color {:?} is invisible on background {:?}, using {:?}",
+                name, color, main_bg, replacement
+            ));
+            *color = replacement;
+        }
+
+        repairs
+    }
+
+    /// Whether all colors are zero, as stored by sessions without a theme
+    pub fn is_unset(&self) -> bool {
+        [
+            self.border_color,
+            self.background_color_one,
+            self.background_color_two,
+            self.background_color_three,
+            self.text_color,
+            self.highlight_color,
+            self.highlight_color_two,
+            self.frame_color,
+            self.frame_color_two,
+        ]
+        .iter()
+        .all(|color| *color == (0, 0, 0))
+    }
+
+    /// Every color with a display label, for editing in the UI
+    pub fn colors_mut(&mut self) -> [(&'static str, &mut (u8, u8, u8)); 9] {
+        [
+            ("Border", &mut self.border_color),
+            ("Background 1", &mut self.background_color_one),
+            ("Background 2", &mut self.background_color_two),
+            ("Background 3", &mut self.background_color_three),
+            ("Text", &mut self.text_color),
+            ("Highlight 1", &mut self.highlight_color),
+            ("Highlight 2", &mut self.highlight_color_two),
+            ("Frame 1", &mut self.frame_color),
+            ("Frame 2", &mut self.frame_color_two),
+        ]
+    }
+
+    /// Applies the theme to egui's visuals and the shared UI palette.
+    ///
+    /// ## Color Mapping
+    /// - **Backgrounds**: one → panels and windows, two → faint/striped
+    ///   backgrounds, three → text edits and other extreme backgrounds
+    /// - **Border**: separators, window and non-interactive widget strokes
+    /// - **Text**: non-interactive and inactive widget text
+    /// - **Frames**: one → widget fills, two → hovered widget fills
+    /// - **Highlights**: one → selection and active status, two → errors and
+    ///   inactive status
+    ///
+    /// The focus highlight is drawn on top in highlight color one, see
+    /// [`FocusHighlight`]. An unset theme applies the default. Colors are
+    /// applied as they are; run [`Self::validate_and_repair`] first.
+    pub fn apply(&self, ctx: &egui::Context, focus: &FocusHighlight) {
+        let default_theme = Theme::default();
+        let theme = if self.is_unset() {
+            &default_theme
+        } else {
+            self
+        };
+
+        let rgb = |(r, g, b): (u8, u8, u8)| egui::Color32::from_rgb(r, g, b);
+        let border = rgb(theme.border_color);
+        let text = rgb(theme.text_color);
+
+        let mut visuals = egui::Visuals::dark();
+        visuals.panel_fill = rgb(theme.background_color_one);
+        visuals.window_fill = rgb(theme.background_color_one);
+        visuals.faint_bg_color = rgb(theme.background_color_two);
+        visuals.extreme_bg_color = rgb(theme.background_color_three);
+        visuals.window_stroke.color = border;
+        visuals.widgets.noninteractive.bg_stroke.color = border;
+        visuals.widgets.noninteractive.fg_stroke.color = text;
+        visuals.widgets.inactive.fg_stroke.color = text;
+        visuals.widgets.inactive.bg_fill = rgb(theme.frame_color);
+        visuals.widgets.inactive.weak_bg_fill = rgb(theme.frame_color);
+        visuals.widgets.hovered.bg_fill = rgb(theme.frame_color_two);
+        visuals.widgets.hovered.weak_bg_fill = rgb(theme.frame_color_two);
+        visuals.selection.stroke.color = rgb(theme.highlight_color);
+        visuals.error_fg_color = rgb(theme.highlight_color_two);
+        focus.apply_to(&mut visuals, rgb(theme.highlight_color));
+        ctx.set_visuals(visuals);
+
+        UiColors::set_palette(UiPalette {
+            main_bg: rgb(theme.background_color_one),
+            inner_bg: rgb(theme.background_color_two),
+            extreme_bg: rgb(theme.background_color_three),
+            border,
+            active: rgb(theme.highlight_color),
+            inactive: rgb(theme.highlight_color_two),
+        });
+    }
+}
+
+/// Outline widths the focus highlight accepts
+pub const FOCUS_WIDTH_RANGE: RangeInclusive<f32> = 1.0..=6.0;
+
+/// Outline width used when a session has no focus highlight stored
+pub const DEFAULT_FOCUS_WIDTH: f32 = 2.0;
+
+/// Marks the widget that has keyboard focus, i.e. the one gamepad input acts on.
+///
+/// egui draws focused widgets with its "active" visuals, which differ only
+/// slightly from the idle ones. On a small screen driven by a gamepad that is
+/// not enough to tell where the cursor is, so the highlight gives the focused
+/// widget an outline and fill in the theme's highlight color.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct FocusHighlight {
+    /// Whether focused widgets are highlighted, egui's defaults are used otherwise
+    pub enabled: bool,
+    /// Outline width in points, scaled up for small or distant displays
+    pub width: f32,
+}
+
+impl Default for FocusHighlight {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            width: DEFAULT_FOCUS_WIDTH,
+        }
+    }
+}
+
+impl FocusHighlight {
+    /// Adds the highlight in `accent` color to `visuals`.
+    ///
+    /// Also thickens the frame egui draws around a focused text edit. The
+    /// active visuals are shared with pressed widgets, which get the same look.
+    pub fn apply_to(&self, visuals: &mut egui::Visuals, accent: egui::Color32) {
+        if !self.enabled {
+            return;
+        }
+        let width = self
+            .width
+            .clamp(*FOCUS_WIDTH_RANGE.start(), *FOCUS_WIDTH_RANGE.end());
+        let stroke = egui::Stroke::new(width, accent);
+
+        let active = &mut visuals.widgets.active;
+        active.bg_stroke = stroke;
+        active.bg_fill = accent.gamma_multiply(0.35);
+        active.weak_bg_fill = accent.gamma_multiply(0.35);
+        active.expansion = width / 2.0;
+        visuals.selection.stroke.width = width;
+    }
+}
+
 /// Contains UI-specific configuration including theming and performance settings.
 ///
 /// ## Design Rationale
@@ -76,15 +381,147 @@ pub struct Theme {
 /// different hardware capabilities, particularly important for Raspberry Pi deployment.
 ///
 /// ## Usage Context
-/// Loaded by the UI system during initialization and applied to the egui context.
-/// Changes require UI restart to take effect, which is handled by the session
-/// switching mechanism.
+/// Read by the UI system and applied to the egui context while running, so
+/// theme and frame rate changes take effect without a restart.
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct UIConfig {
     /// Visual styling configuration
     theme: Theme,
     /// Target frames per second for UI rendering
     fps: u8,
+    /// Display backlight level (0.0 to 1.0), `None` leaves the system level
+    #[serde(default)]
+    brightness: Option<f32>,
+    /// Periodic saving of the current session
+    #[serde(default)]
+    autosave: AutosaveSettings,
+    /// Format and time zone of message timestamps
+    #[serde(default)]
+    timestamps: TimestampSettings,
+    /// Highlighting of the widget with gamepad focus
+    #[serde(default)]
+    focus_highlight: FocusHighlight,
+}
+
+/// Allowed autosave intervals in seconds
+pub const AUTOSAVE_INTERVAL_RANGE: RangeInclusive<u64> = 10..=600;
+
+/// Autosave interval used when a session has none stored
+pub const DEFAULT_AUTOSAVE_INTERVAL_SECS: u64 = 60;
+
+/// Allowed number of kept session backups, 0 turns backups off
+pub const BACKUP_COUNT_RANGE: RangeInclusive<usize> = 0..=20;
+
+/// Number of session backups kept when a session has none stored
+pub const DEFAULT_BACKUP_COUNT: usize = 3;
+
+/// Settings of the background tasks that keep the session and its files in sync.
+///
+/// Published to the running tasks through a watch channel, so changes apply
+/// without a restart. With autosave off only explicit saves persist.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct AutosaveSettings {
+    /// Whether the session is saved periodically
+    pub enabled: bool,
+    /// Seconds between two autosaves
+    pub interval_secs: u64,
+    /// Whether session files changed outside the application are reloaded
+    #[serde(default)]
+    pub reload_on_change: bool,
+    /// Previous versions of the session files kept before each save
+    #[serde(default = "default_backup_count")]
+    pub backup_count: usize,
+}
+
+fn default_backup_count() -> usize {
+    DEFAULT_BACKUP_COUNT
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: DEFAULT_AUTOSAVE_INTERVAL_SECS,
+            reload_on_change: false,
+            backup_count: DEFAULT_BACKUP_COUNT,
+        }
+    }
+}
+
+/// Timestamp format used when a session has none or an invalid one stored
+pub const DEFAULT_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// How message timestamps are shown in the MQTT menu.
+///
+/// Messages keep their timestamp in local time; the zone is only applied when
+/// formatting, so switching to UTC also affects already received messages.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct TimestampSettings {
+    /// chrono `strftime` format, see [`chrono::format::strftime`]
+    pub format: String,
+    /// Whether timestamps are shown in UTC instead of the system's local zone
+    pub utc: bool,
+}
+
+impl Default for TimestampSettings {
+    fn default() -> Self {
+        Self {
+            format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            utc: false,
+        }
+    }
+}
+
+impl TimestampSettings {
+    /// Whether `format` is a non-empty format string chrono can parse.
+    pub fn is_valid_format(&self) -> bool {
+        !self.format.is_empty()
+            && !StrftimeItems::new(&self.format).any(|item| matches!(item, Item::Error))
+    }
+
+    /// Formats a local `timestamp` with these settings.
+    ///
+    /// Falls back to [`DEFAULT_TIMESTAMP_FORMAT`] if the format is invalid or
+    /// cannot be applied, so a bad format never breaks rendering.
+    pub fn format(&self, timestamp: NaiveDateTime) -> String {
+        let local = Local
+            .from_local_datetime(&timestamp)
+            .earliest()
+            // Skipped by a DST switch, only possible for hand-edited sessions
+            .unwrap_or_else(|| Local.from_utc_datetime(&timestamp));
+        let time = if self.utc {
+            local.with_timezone(&Utc).fixed_offset()
+        } else {
+            local.fixed_offset()
+        };
+
+        let mut out = String::new();
+        if self.is_valid_format() && write!(out, "{}", time.format(&self.format)).is_ok() {
+            return out;
+        }
+        time.format(DEFAULT_TIMESTAMP_FORMAT).to_string()
+    }
+}
+
+/// Layout version of the session files written by this build.
+///
+/// Bumped whenever a config struct changes in a way older files cannot be
+/// parsed with. Sessions with a lower version are upgraded when loaded.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Frame rates the UI accepts; lower rates save CPU between interactions
+pub const FPS_RANGE: RangeInclusive<u8> = 10..=60;
+
+/// Frame rate used when a session has none stored
+pub const DEFAULT_FPS: u8 = 30;
+
+/// Clamps a stored frame rate into [`FPS_RANGE`], treating 0 as unset.
+pub fn effective_fps(fps: u8) -> u8 {
+    if fps == 0 {
+        DEFAULT_FPS
+    } else {
+        fps.clamp(*FPS_RANGE.start(), *FPS_RANGE.end())
+    }
 }
 
 /// Network configuration for wireless connectivity management.
@@ -146,7 +583,8 @@ pub struct ConnectionConfig {
 /// ## Usage Context
 /// Applied by the mapping engine system to determine how controller inputs
 /// are translated to output events. Changes require mapping engine restart,
-/// which is handled through the session switching mechanism.
+/// which is handled through the session switching mechanism. The controller
+/// timing and deadzone settings are the exception; they are applied live.
 ///
 /// ## Extension Points
 /// Future mapping types (433MHz, LoRa, etc.) can be added as additional fields
@@ -157,6 +595,12 @@ pub struct ControllerConfig {
     pub keyboard_mapping: KeyboardConfig,
     /// ELRS (ExpressLRS) drone control mapping configuration
     pub elrs_mapping: ELRSConfig,
+    /// Chord-to-payload mapping for custom protocols such as 433MHz remotes
+    #[serde(default)]
+    pub custom_mapping: CustomConfig,
+    /// Input timing and deadzone used by the controller subsystem
+    #[serde(default)]
+    pub controller_settings: ControllerSettings,
 }
 
 /// Container for user-saved MQTT messages for reuse and debugging.
@@ -174,6 +618,47 @@ pub struct ControllerConfig {
 pub struct SavedMessages {
     /// Collection of saved MQTT messages
     pub msg: Vec<MQTTMessage>,
+
+    /// Payload templates, stored with their placeholders unfilled
+    #[serde(default)]
+    pub templates: Vec<MQTTMessage>,
+
+    /// Counter and user-defined variables of the payload templates
+    #[serde(default)]
+    pub variables: TemplateVariables,
+}
+
+/// Maximum number of live messages kept in a session's received log.
+///
+/// Bounds the size of `received_log.toml` on busy brokers; the oldest
+/// entries are dropped first.
+pub const RECEIVED_LOG_LIMIT: usize = 1000;
+
+/// Live MQTT messages received during a session, kept for post-mortem debugging.
+///
+/// ## Design Rationale
+/// Mirrors [`SavedMessages`] but holds broker traffic instead of user
+/// templates, so the two can be managed and cleared independently. Stored in
+/// its own file to keep the template file small and hand-editable.
+///
+/// ## Usage Context
+/// Filled by the MQTT UI as messages arrive and restored into the live log
+/// when a session is loaded.
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct ReceivedLog {
+    /// Received MQTT messages in arrival order
+    pub msg: Vec<MQTTMessage>,
+}
+
+impl ReceivedLog {
+    /// Appends a message, dropping the oldest entries beyond [`RECEIVED_LOG_LIMIT`].
+    pub fn push(&mut self, msg: MQTTMessage) {
+        self.msg.push(msg);
+        if self.msg.len() > RECEIVED_LOG_LIMIT {
+            let overflow = self.msg.len() - RECEIVED_LOG_LIMIT;
+            self.msg.drain(..overflow);
+        }
+    }
 }
 
 /// Metadata and state information for a configuration session.
@@ -203,4 +688,89 @@ pub struct SessionConfig {
     pub path: PathBuf,
     /// Registry of all known sessions and their paths
     pub available_sessions: HashMap<String, PathBuf>,
+    /// Layout version the session files were written with, 0 before versioning
+    #[serde(default)]
+    pub version: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every color reaches its required contrast.
+    fn assert_readable(theme: &Theme) {
+        let main_bg = theme.background_color_one;
+        assert!(contrast(theme.text_color, main_bg) >= MIN_TEXT_CONTRAST);
+        for color in [
+            theme.background_color_two,
+            theme.background_color_three,
+            theme.frame_color,
+            theme.frame_color_two,
+        ] {
+            assert!(contrast(color, theme.text_color) >= MIN_TEXT_CONTRAST);
+        }
+        for color in [theme.highlight_color, theme.highlight_color_two] {
+            assert!(contrast(color, main_bg) >= MIN_HIGHLIGHT_CONTRAST);
+        }
+    }
+
+    #[test]
+    fn black_text_on_black_background_is_repaired() {
+        let mut theme = Theme {
+            background_color_one: (0, 0, 0),
+            background_color_two: (0, 0, 0),
+            background_color_three: (0, 0, 0),
+            text_color: (0, 0, 0),
+            ..Theme::default()
+        };
+
+        let repairs = theme.validate_and_repair();
+
+        assert_eq!(theme.background_color_one, (0, 0, 0));
+        assert_eq!(theme.text_color, Theme::default().text_color);
+        assert!(repairs[0].starts_with("Text color (0, 0, 0)"));
+        assert_readable(&theme);
+    }
+
+    #[test]
+    fn all_black_theme_with_border_is_repaired() {
+        // Borders are not checked, only set so the theme does not count as unset
+        let mut theme = Theme {
+            border_color: (1, 1, 1),
+            background_color_one: (0, 0, 0),
+            background_color_two: (0, 0, 0),
+            background_color_three: (0, 0, 0),
+            text_color: (0, 0, 0),
+            highlight_color: (0, 0, 0),
+            highlight_color_two: (0, 0, 0),
+            frame_color: (0, 0, 0),
+            frame_color_two: (0, 0, 0),
+        };
+
+        let repairs = theme.validate_and_repair();
+
+        assert!(!repairs.is_empty());
+        assert_readable(&theme);
+    }
+
+    #[test]
+    fn readable_and_unset_themes_report_no_repairs() {
+        let mut theme = Theme::default();
+        assert!(theme.validate_and_repair().is_empty());
+        assert_eq!(theme, Theme::default());
+
+        let mut unset = Theme {
+            border_color: (0, 0, 0),
+            background_color_one: (0, 0, 0),
+            background_color_two: (0, 0, 0),
+            background_color_three: (0, 0, 0),
+            text_color: (0, 0, 0),
+            highlight_color: (0, 0, 0),
+            highlight_color_two: (0, 0, 0),
+            frame_color: (0, 0, 0),
+            frame_color_two: (0, 0, 0),
+        };
+        assert!(unset.validate_and_repair().is_empty());
+        assert_eq!(unset, Theme::default());
+    }
 }
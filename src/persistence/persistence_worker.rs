@@ -26,14 +26,16 @@ use crate::mqtt::mqtt_handler::Configured;
 
 use super::{
     config_portal::{ConfigPortal, ConfigResult, PortalAction},
+    file_watcher,
     session_client::SessionClient,
+    AutosaveSettings,
 };
 use color_eyre::Result;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
-use tokio::sync::Mutex;
+use tokio::sync::{watch, Mutex};
 use tracing::{debug, error, info, warn};
 
 /// Convenience macro for handling session action responses.
@@ -72,8 +74,12 @@ pub struct PersistenceManager {
     worker_handle: tokio::task::JoinHandle<()>,
     /// Handle to the autosave task for independent cleanup
     autosave_handle: tokio::task::JoinHandle<()>,
+    /// Handle to the task reloading externally changed session files, aborted on drop
+    file_watch_handle: tokio::task::JoinHandle<()>,
     /// Shared access to the current session client for direct portal access
     session_client: Arc<Mutex<SessionClient>>,
+    /// Publishes autosave settings to the running autosave task
+    autosave_tx: watch::Sender<AutosaveSettings>,
 }
 
 impl PersistenceManager {
@@ -83,9 +89,12 @@ impl PersistenceManager {
     /// subsystem including worker task spawning and autosave configuration.
     ///
     /// ## Design Rationale
-    /// Spawns two independent tasks:
+    /// Spawns three independent tasks:
     /// - **Worker Task**: Processes session operations sequentially to prevent race conditions
-    /// - **Autosave Task**: Provides automatic backup every 60 seconds for crash recovery
+    /// - **Autosave Task**: Provides automatic backup for crash recovery, every 60
+    ///   seconds by default and configurable per session
+    /// - **File Watch Task**: Reloads session files edited outside the
+    ///   application, if enabled for the session
     ///
     /// The worker pattern ensures that all session operations are atomic and consistent,
     /// while the autosave provides a safety net against data loss.
@@ -106,24 +115,33 @@ impl PersistenceManager {
     ///
     /// ## Performance Notes
     /// The channel buffer size (32) is chosen to handle burst operations like rapid
-    /// session switching without blocking the sender. The autosave interval (60s)
-    /// balances crash recovery with disk I/O overhead.
+    /// session switching without blocking the sender. The default autosave interval
+    /// (60s) balances crash recovery with disk I/O overhead.
     pub async fn new() -> Self {
         let session_client = Arc::new(Mutex::new(SessionClient::load_last_session().await));
         let session_cpy = session_client.clone();
         let (tx, mut rx) = channel::<SessionAction>(32);
 
+        let initial_autosave = autosave_settings(&session_client.lock().await.get_portal_ref());
+        let (autosave_tx, autosave_rx) = watch::channel(initial_autosave);
+        let worker_autosave_tx = autosave_tx.clone();
+
         let handle = tokio::spawn(async move {
             while let Some(action) = rx.recv().await {
                 match action {
                     SessionAction::CreateSession { name, response_tx } => {
                         handle_action!(session_client.lock().await.save_session(name), response_tx);
                     }
-                    SessionAction::LoadSession { name, response_tx } => {
-                        handle_action!(
-                            session_client.lock().await.change_session(&name),
-                            response_tx
-                        );
+                    SessionAction::LoadSession {
+                        name,
+                        save_current,
+                        response_tx,
+                    } => {
+                        let mut client = session_client.lock().await;
+                        handle_action!(client.change_session(&name, save_current), response_tx);
+                        // The loaded session brings its own autosave settings
+                        worker_autosave_tx
+                            .send_replace(autosave_settings(&client.get_portal_ref()));
                     }
                     SessionAction::SaveCurrentSession { response_tx } => {
                         handle_action!(
@@ -137,23 +155,94 @@ impl PersistenceManager {
                             response_tx
                         );
                     }
+                    SessionAction::RenameSession {
+                        name,
+                        new_name,
+                        response_tx,
+                    } => {
+                        handle_action!(
+                            session_client.lock().await.rename_session(&name, &new_name),
+                            response_tx
+                        );
+                    }
+                    SessionAction::DuplicateSession {
+                        name,
+                        new_name,
+                        response_tx,
+                    } => {
+                        handle_action!(
+                            session_client
+                                .lock()
+                                .await
+                                .duplicate_session(&name, &new_name),
+                            response_tx
+                        );
+                    }
+                    SessionAction::ExportSession {
+                        name,
+                        dest,
+                        response_tx,
+                    } => {
+                        handle_action!(
+                            session_client.lock().await.export_session(&name, &dest),
+                            response_tx
+                        );
+                    }
+                    SessionAction::ImportSession { src, response_tx } => {
+                        handle_action!(
+                            session_client.lock().await.import_session(&src),
+                            response_tx
+                        );
+                    }
                     SessionAction::ListSessions { response_tx } => {
                         handle_action!(SessionClient::scan_available_sessions(), response_tx);
                     }
+                    SessionAction::RefreshSessions { response_tx } => {
+                        handle_action!(session_client.lock().await.refresh_sessions(), response_tx);
+                    }
+                    SessionAction::ListBackups { name, response_tx } => {
+                        handle_action!(SessionClient::list_backups(&name), response_tx);
+                    }
+                    SessionAction::RestoreBackup {
+                        name,
+                        backup,
+                        response_tx,
+                    } => {
+                        let mut client = session_client.lock().await;
+                        handle_action!(client.restore_backup(&name, &backup), response_tx);
+                        // A restored active session may bring other autosave settings
+                        worker_autosave_tx
+                            .send_replace(autosave_settings(&client.get_portal_ref()));
+                    }
                 }
             }
         });
 
-        let autosave = SessionClient::start_autosave_task(session_cpy.clone(), 60).await;
+        let file_watch = file_watcher::start_file_watch_task(
+            session_cpy.clone(),
+            autosave_tx.subscribe(),
+            autosave_tx.clone(),
+        );
+        let autosave = SessionClient::start_autosave_task(session_cpy.clone(), autosave_rx).await;
 
         Self {
             tx,
             autosave_handle: autosave,
+            file_watch_handle: file_watch,
             worker_handle: handle,
             session_client: session_cpy.clone(),
+            autosave_tx,
         }
     }
 
+    /// Returns the sender for changing autosave settings at runtime.
+    ///
+    /// The autosave task picks up new settings immediately. Callers persist
+    /// them in the session's UI config themselves.
+    pub fn autosave_sender(&self) -> watch::Sender<AutosaveSettings> {
+        self.autosave_tx.clone()
+    }
+
     /// Returns a sender channel for submitting session operations to the worker.
     ///
     /// Used by other modules to perform session operations asynchronously.
@@ -178,6 +267,22 @@ impl PersistenceManager {
     }
 }
 
+impl Drop for PersistenceManager {
+    /// Stops reloading externally changed files once the manager is gone, so
+    /// no reload races the final session writes at shutdown.
+    fn drop(&mut self) {
+        self.file_watch_handle.abort();
+    }
+}
+
+/// Reads the autosave settings of a session, falling back to the defaults.
+fn autosave_settings(config_portal: &ConfigPortal) -> AutosaveSettings {
+    match config_portal.execute_potal_action(PortalAction::GetAutosave) {
+        ConfigResult::Autosave(settings) => settings,
+        _ => AutosaveSettings::default(),
+    }
+}
+
 /// Represents the various session operations that can be performed by the worker.
 ///
 /// ## Design Rationale
@@ -198,6 +303,9 @@ pub enum SessionAction {
     /// Switches to an existing session, loading its configuration
     LoadSession {
         name: String,
+        /// Save the current session before switching, otherwise its unsaved
+        /// changes are discarded
+        save_current: bool,
         response_tx: tokio::sync::oneshot::Sender<Result<()>>,
     },
     /// Saves the current session state to persistent storage
@@ -209,10 +317,48 @@ pub enum SessionAction {
         name: String,
         response_tx: tokio::sync::oneshot::Sender<Result<()>>,
     },
+    /// Renames a session, moving its directory
+    RenameSession {
+        name: String,
+        new_name: String,
+        response_tx: tokio::sync::oneshot::Sender<Result<()>>,
+    },
+    /// Copies a session under a new name
+    DuplicateSession {
+        name: String,
+        new_name: String,
+        response_tx: tokio::sync::oneshot::Sender<Result<()>>,
+    },
+    /// Writes a session into a portable archive, returning the archive path
+    ExportSession {
+        name: String,
+        dest: PathBuf,
+        response_tx: tokio::sync::oneshot::Sender<Result<PathBuf>>,
+    },
+    /// Registers a session from an archive, returning the new session's name
+    ImportSession {
+        src: PathBuf,
+        response_tx: tokio::sync::oneshot::Sender<Result<String>>,
+    },
     /// Lists all available sessions for UI display
     ListSessions {
         response_tx: tokio::sync::oneshot::Sender<Result<HashMap<String, PathBuf>>>,
     },
+    /// Rescans the available sessions and updates the session registry
+    RefreshSessions {
+        response_tx: tokio::sync::oneshot::Sender<Result<HashMap<String, PathBuf>>>,
+    },
+    /// Lists the backups of a session, newest first
+    ListBackups {
+        name: String,
+        response_tx: tokio::sync::oneshot::Sender<Result<Vec<String>>>,
+    },
+    /// Replaces the files of a session by one of its backups
+    RestoreBackup {
+        name: String,
+        backup: String,
+        response_tx: tokio::sync::oneshot::Sender<Result<()>>,
+    },
 }
 
 /// Provides ergonomic macros for common session operations with built-in error handling.
@@ -243,8 +389,9 @@ pub enum SessionAction {
 /// // Create a new session
 /// session_action!(@create, session_sender, "my_new_session")?;
 ///
-/// // Load an existing session
+/// // Load an existing session, saving or discarding the current one's changes
 /// session_action!(@load, session_sender, "production_config")?;
+/// session_action!(@load_discard, session_sender, "production_config")?;
 ///
 /// // Save current session
 /// session_action!(@save, session_sender)?;
@@ -252,8 +399,15 @@ pub enum SessionAction {
 /// // Delete a session
 /// session_action!(@delete, session_sender, "old_session")?;
 ///
+/// // Rename or copy a session
+/// session_action!(@rename, session_sender, "old_session", "new_session")?;
+/// session_action!(@duplicate, session_sender, "production_config", "experiment")?;
+///
 /// // List all sessions
 /// let sessions = session_action!(@list, session_sender)?;
+///
+/// // Rescan sessions and update the session registry
+/// let sessions = session_action!(@refresh, session_sender)?;
 /// ```
 #[macro_export]
 macro_rules! session_action {
@@ -275,6 +429,20 @@ macro_rules! session_action {
 
         let action = $crate::persistence::persistence_worker::SessionAction::LoadSession {
             name: $session_name.to_string(),
+            save_current: true,
+            response_tx
+        };
+
+        session_action!(@send_and_receive, $session_sender, action, response_rx)
+    }};
+
+    (@load_discard, $session_sender:expr, $session_name:expr) => {{
+        let (response_tx, mut response_rx) =
+            tokio::sync::oneshot::channel::<color_eyre::Result<()>>();
+
+        let action = $crate::persistence::persistence_worker::SessionAction::LoadSession {
+            name: $session_name.to_string(),
+            save_current: false,
             response_tx
         };
 
@@ -302,6 +470,32 @@ macro_rules! session_action {
         session_action!(@send_and_receive, $session_sender, action, response_rx)
     }};
 
+    (@rename, $session_sender:expr, $session_name:expr, $new_name:expr) => {{
+        let (response_tx, mut response_rx) =
+            tokio::sync::oneshot::channel::<color_eyre::Result<()>>();
+
+        let action = $crate::persistence::persistence_worker::SessionAction::RenameSession {
+            name: $session_name.to_string(),
+            new_name: $new_name.to_string(),
+            response_tx
+        };
+
+        session_action!(@send_and_receive, $session_sender, action, response_rx)
+    }};
+
+    (@duplicate, $session_sender:expr, $session_name:expr, $new_name:expr) => {{
+        let (response_tx, mut response_rx) =
+            tokio::sync::oneshot::channel::<color_eyre::Result<()>>();
+
+        let action = $crate::persistence::persistence_worker::SessionAction::DuplicateSession {
+            name: $session_name.to_string(),
+            new_name: $new_name.to_string(),
+            response_tx
+        };
+
+        session_action!(@send_and_receive, $session_sender, action, response_rx)
+    }};
+
     (@list, $session_sender:expr) => {{
         let (response_tx, mut response_rx) =
             tokio::sync::oneshot::channel::<color_eyre::Result<std::collections::HashMap<String, std::path::PathBuf>>>();
@@ -311,6 +505,15 @@ macro_rules! session_action {
         session_action!(@send_and_receive, $session_sender, action, response_rx)
     }};
 
+    (@refresh, $session_sender:expr) => {{
+        let (response_tx, mut response_rx) =
+            tokio::sync::oneshot::channel::<color_eyre::Result<std::collections::HashMap<String, std::path::PathBuf>>>();
+
+        let action = $crate::persistence::persistence_worker::SessionAction::RefreshSessions { response_tx };
+
+        session_action!(@send_and_receive, $session_sender, action, response_rx)
+    }};
+
     (@send_and_receive, $session_sender:expr, $action:expr, $response_rx:expr) => {{
         if let Err(e) = $session_sender.try_send($action) {
             Err(color_eyre::Report::msg(format!("Failed to send action: {}", e)))
@@ -18,31 +18,80 @@
 //! where detailed error information is crucial for debugging. Errors bubble up through the persistence
 //! layer to the UI for user notification.
 //!
+//! ## Crash Safety
+//! Config files are never written in place. Each write goes to a `.tmp` file in
+//! the same directory, is synced and then renamed over the target, so pulling
+//! the power on a Pi mid-save cannot corrupt a session. Leftover `.tmp` files
+//! are ignored on load. The replaced version is kept as a `.bak` file next to
+//! it, so a file broken by hand or by a failing SD card can be restored, see
+//! [`super::integrity`].
+//!
+//! ## Session Backups
+//! Before a save overwrites a session, its files are copied into
+//! `backups/<timestamp>/` inside the session directory. Only the newest
+//! [`AutosaveSettings::backup_count`] backups are kept, and a save whose files
+//! match a kept backup adds none, so repeated saves of an unchanged session do
+//! not push older versions out. [`SessionClient::restore_backup`] copies a
+//! backup back, keeping the replaced files as a backup of their own.
+//!
+//! ## Config Versioning
+//! `session.toml` records the layout version ([`CONFIG_VERSION`]) its session was
+//! written with. Older sessions are upgraded on load: files that no longer parse
+//! get their missing fields filled in from the defaults instead of the whole
+//! section being reset, and the upgraded files are written back.
+//!
 //! ## Async Patterns Used
 //! All file operations are async to prevent blocking the UI thread during potentially slow disk I/O.
 //! Uses tokio's filesystem operations for consistency with the rest of the application's async runtime.
 
-use super::config_portal::{ConfigPortal, ConfigResult, PortalAction};
-use super::{ConnectionConfig, ControllerConfig, SavedMessages, SessionConfig, UIConfig};
+use super::config_portal::{ConfigPortal, ConfigResult, PortalAction, SessionSnapshot};
+use super::file_watcher;
+use super::integrity;
+use super::{
+    AutosaveSettings, ConnectionConfig, ControllerConfig, ReceivedLog, SavedMessages,
+    SessionConfig, UIConfig, CONFIG_VERSION,
+};
+use crate::mqtt::config::MqttConfig;
 use color_eyre::{eyre::eyre, Report, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs::{
-    create_dir_all, metadata, read_dir, read_to_string, remove_dir_all, try_exists, write,
+    copy, create_dir_all, metadata, read_dir, read_to_string, remove_dir_all, remove_file, rename,
+    try_exists, File,
 };
-use tokio::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{watch, Mutex};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 const CONFIG_DIR: &str = ".config/opencontroller/config";
-const MAIN_CONFIG_FILE: &str = "main_config.toml";
-const UI_CONFIG_FILE: &str = "ui_config.toml";
-const CONNECTION_CONFIG_FILE: &str = "connection_config.toml";
-const CONTROLLER_CONFIG_FILE: &str = "controller_config.toml";
-const MESSAGES_FILE: &str = "saved_messages.toml";
-const SESSION_CONFIG_FILE: &str = "session.toml";
+pub(super) const MAIN_CONFIG_FILE: &str = "main_config.toml";
+pub(super) const UI_CONFIG_FILE: &str = "ui_config.toml";
+pub(super) const CONNECTION_CONFIG_FILE: &str = "connection_config.toml";
+pub(super) const CONTROLLER_CONFIG_FILE: &str = "controller_config.toml";
+pub(super) const MESSAGES_FILE: &str = "saved_messages.toml";
+pub(super) const RECEIVED_LOG_FILE: &str = "received_log.toml";
+pub(super) const SESSION_CONFIG_FILE: &str = "session.toml";
+
+/// Suffix of the temporary file a config is written to before replacing it
+pub(super) const TEMP_SUFFIX: &str = ".tmp";
+
+/// Suffix of the previous version kept when a config file is replaced
+pub(super) const BACKUP_SUFFIX: &str = ".bak";
+
+/// Directory inside a session directory that holds its backups
+const BACKUPS_DIR: &str = "backups";
+
+/// Name format of backup directories, sorts chronologically
+const BACKUP_NAME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S%.3f";
+
+/// Prefix of the directory an archive is unpacked into before it becomes a session
+const IMPORT_STAGING_PREFIX: &str = ".import-";
+
+/// File extension of exported session archives
+pub const SESSION_ARCHIVE_EXTENSION: &str = "ocsession";
 
 /// Manages application sessions and their persistent storage.
 ///
@@ -84,6 +133,8 @@ impl SessionClient {
     /// ## Error Handling
     /// Gracefully degrades to default configuration rather than failing, ensuring the
     /// application can always start even with corrupted configuration files.
+    /// Corrupt files are reported to the user by [`integrity::check_config_files`]
+    /// before this runs.
     ///
     /// # Errors
     ///
@@ -104,6 +155,7 @@ impl SessionClient {
     pub async fn load_last_session() -> Self {
         let mut path = Self::get_home_dir();
         path.push(CONFIG_DIR);
+        Self::remove_import_leftovers_in(&path).await;
         path.push(MAIN_CONFIG_FILE);
 
         let client_string = read_to_string(path).await.unwrap_or_default();
@@ -113,13 +165,25 @@ impl SessionClient {
             let default = Self::ensure_default();
             return default;
         } else {
-            let client_res = toml::from_str::<SessionClient>(&client_string);
-            let client = client_res.unwrap_or(Self::ensure_default());
+            let client = toml::from_str::<SessionClient>(&client_string).unwrap_or_else(|e| {
+                error!("Main config is corrupt, loading default session: {}", e);
+                Self::ensure_default()
+            });
 
-            Self::load_session(&client.current_session).await.unwrap()
+            Self::load_session(&client.current_session)
+                .await
+                .unwrap_or_else(|e| {
+                    error!("Failed to load last session, using defaults: {}", e);
+                    Self::ensure_default()
+                })
         }
     }
 
+    /// Name of the active session.
+    pub fn current_session(&self) -> &str {
+        &self.current_session
+    }
+
     /// Returns a reference to the configuration portal for runtime access.
     pub fn get_portal_ref(&self) -> Arc<ConfigPortal> {
         self.config_portal.clone()
@@ -156,10 +220,23 @@ impl SessionClient {
     /// - Makes manual configuration editing more manageable
     /// - Allows for type-specific backup and recovery strategies
     ///
+    /// ## Change Tracking
+    /// Clears the portal's dirty flag, so autosave skips the next tick unless
+    /// configuration is written again. A failed save keeps the flag set.
+    ///
+    /// ## Backups
+    /// The files about to be replaced are kept as a backup first, see the
+    /// module docs. A failed backup is logged but does not stop the save.
+    ///
+    /// ## Held Saves
+    /// While the user has not decided how to recover corrupt config files,
+    /// nothing is written and the dirty flag is kept, see
+    /// [`integrity::saves_held`].
+    ///
     /// ## Error Handling
-    /// File operations are atomic where possible - either the entire session is saved
-    /// successfully or the previous state is preserved. Uses detailed error context
-    /// to help diagnose configuration persistence issues.
+    /// Every file is replaced atomically through a synced temporary file, so a
+    /// power loss mid-save leaves each file either old or new, never truncated.
+    /// Uses detailed error context to help diagnose configuration persistence issues.
     ///
     /// # Errors
     ///
@@ -180,9 +257,26 @@ impl SessionClient {
     /// - Performs multiple file system operations (create directories, write files)
     /// - Allows other tasks to run during potentially slow disk I/O
     ///
-    /// **Cancellation**: Partial writes may occur if cancelled mid-operation
+    /// **Cancellation**: Some files may be updated and others not if cancelled mid-operation
     /// **Concurrency**: Safe to call concurrently for different session names
     pub async fn save_session(&self, name: String) -> Result<()> {
+        if integrity::saves_held() {
+            debug!(
+                "Not saving session {} until corrupt files are resolved",
+                name
+            );
+            return Ok(());
+        }
+        let was_dirty = self.config_portal.take_dirty();
+        let result = self.write_session_files(name).await;
+        if result.is_err() && was_dirty {
+            self.config_portal.mark_dirty();
+        }
+        result
+    }
+
+    /// Writes all session files, see [`Self::save_session`].
+    async fn write_session_files(&self, name: String) -> Result<()> {
         let mut base_path: PathBuf = Self::get_home_dir();
         base_path.push(CONFIG_DIR);
 
@@ -199,6 +293,10 @@ impl SessionClient {
                 .map_err(|e| eyre!("Failed to create session directory: {}", e))?;
         }
 
+        if let Err(e) = backup_session(&base_path, self.backup_count().await).await {
+            warn!("Failed to back up session {}: {}", name, e);
+        }
+
         let mut ui_path = base_path.clone();
         ui_path.push(UI_CONFIG_FILE);
 
@@ -214,9 +312,13 @@ impl SessionClient {
         let mut messages_path = base_path.clone();
         messages_path.push(MESSAGES_FILE);
 
+        let mut received_log_path = base_path.clone();
+        received_log_path.push(RECEIVED_LOG_FILE);
+
         let ui_config = self
             .config_portal
-            .execute_potal_action(PortalAction::GetUIConfig);
+            .execute_portal_action_async(PortalAction::GetUIConfig)
+            .await;
         let ui_config = if let ConfigResult::UIConfig(ui_c) = ui_config {
             ui_c
         } else {
@@ -226,9 +328,10 @@ impl SessionClient {
 
         let controller_config = self
             .config_portal
-            .execute_potal_action(PortalAction::GetControllerConfig);
+            .execute_portal_action_async(PortalAction::GetControllerConfig)
+            .await;
         let controller_config = if let ConfigResult::ControllerConfig(result) = controller_config {
-            result
+            *result
         } else {
             warn!("Could not retriev valid UiConfig");
             ControllerConfig::default()
@@ -236,7 +339,8 @@ impl SessionClient {
 
         let connection_config = self
             .config_portal
-            .execute_potal_action(PortalAction::GetConnectionConfig);
+            .execute_portal_action_async(PortalAction::GetConnectionConfig)
+            .await;
         let connection_config = if let ConfigResult::ConnectionConfig(result) = connection_config {
             result
         } else {
@@ -246,7 +350,8 @@ impl SessionClient {
 
         let saved_msg = self
             .config_portal
-            .execute_potal_action(PortalAction::GetSavedMessages);
+            .execute_portal_action_async(PortalAction::GetSavedMessages)
+            .await;
         let saved_msg = if let ConfigResult::MqttHistory(result) = saved_msg {
             result
         } else {
@@ -254,43 +359,62 @@ impl SessionClient {
             SavedMessages::default()
         };
 
+        let received_log = self
+            .config_portal
+            .execute_portal_action_async(PortalAction::GetReceivedLog)
+            .await;
+        let received_log = if let ConfigResult::ReceivedLog(result) = received_log {
+            result
+        } else {
+            warn!("Could not retrieve valid received message log");
+            ReceivedLog::default()
+        };
+
         let ui_content = toml::to_string_pretty(&ui_config)
             .map_err(|e| eyre!("Failed to serialize UI config: {}", e))?;
 
-        write(&ui_path, ui_content)
+        write_atomic(&ui_path, ui_content)
             .await
             .map_err(|e| eyre!("Failed to write UI config file: {}", e))?;
 
         let connection_content = toml::to_string_pretty(&connection_config)
             .map_err(|e| eyre!("Failed to serialize connection config: {}", e))?;
 
-        write(&connection_path, connection_content)
+        write_atomic(&connection_path, connection_content)
             .await
             .map_err(|e| eyre!("Failed to write connection config file: {}", e))?;
 
         let controller_content = toml::to_string_pretty(&controller_config)
             .map_err(|e| eyre!("Failed to serialize controller config: {}", e))?;
 
-        write(&controller_path, controller_content)
+        write_atomic(&controller_path, controller_content)
             .await
             .map_err(|e| eyre!("Failed to write controller config file: {}", e))?;
 
         let messages_content = toml::to_string_pretty(&saved_msg)
             .map_err(|e| eyre!("Failed to serialize messages: {}", e))?;
 
-        write(&messages_path, messages_content)
+        write_atomic(&messages_path, messages_content)
             .await
             .map_err(|e| eyre!("Failed to write messages file: {}", e))?;
 
+        let received_log_content = toml::to_string_pretty(&received_log)
+            .map_err(|e| eyre!("Failed to serialize received log: {}", e))?;
+
+        write_atomic(&received_log_path, received_log_content)
+            .await
+            .map_err(|e| eyre!("Failed to write received log file: {}", e))?;
+
         let client_content = toml::to_string_pretty(&self)
             .map_err(|e| eyre!("Failed to parse main config file: {}", e))?;
-        write(&main_config, client_content)
+        write_atomic(&main_config, client_content)
             .await
             .map_err(|e| eyre!("Failed to write main config file: {}", e))?;
 
         let mut current_sessions = match self
             .config_portal
-            .execute_potal_action(PortalAction::GetAvailableSessions)
+            .execute_portal_action_async(PortalAction::GetAvailableSessions)
+            .await
         {
             ConfigResult::AvailableSessions(sessions) => sessions,
             _ => HashMap::new(),
@@ -298,21 +422,28 @@ impl SessionClient {
 
         current_sessions.insert(name.to_string(), base_path.clone());
         self.config_portal
-            .execute_potal_action(PortalAction::WriteAvailableSessions(current_sessions));
+            .execute_portal_action_async(PortalAction::WriteAvailableSessions(current_sessions))
+            .await;
 
-        let session = if let ConfigResult::SessionConfig(session) = self
+        let mut session = if let ConfigResult::SessionConfig(session) = self
             .config_portal
-            .execute_potal_action(PortalAction::GetSession)
+            .execute_portal_action_async(PortalAction::GetSession)
+            .await
         {
             session
         } else {
             warn!("Could not read current Session from Configportal");
             SessionConfig::default()
         };
+        // Saving under another name creates that session, it must not keep
+        // the identity of the one it was copied from
+        session.session_name = name.clone();
+        session.path = base_path.clone();
+        session.version = CONFIG_VERSION;
 
         let session_content = toml::to_string_pretty(&session)
             .map_err(|e| eyre!("Failed to parse session config file: {}", e))?;
-        write(&session_path, session_content)
+        write_atomic(&session_path, session_content)
             .await
             .map_err(|e| eyre!("Failed to write session config file: {}", e))?;
 
@@ -332,9 +463,9 @@ impl SessionClient {
     /// even if some configuration files are missing or corrupted.
     ///
     /// ## Error Handling
-    /// Uses graceful degradation - missing configuration files are replaced with
-    /// defaults and warnings are logged. Only fails if the session directory
-    /// doesn't exist at all.
+    /// Uses graceful degradation - missing or corrupt configuration files are
+    /// replaced with defaults and warnings are logged. Only fails if the session
+    /// directory doesn't exist at all.
     ///
     /// # Errors
     ///
@@ -343,8 +474,8 @@ impl SessionClient {
     ///   - *Recovery*: Check available sessions or create a new session with that name
     /// - **File system access**: Unable to read session directory or files
     ///   - *Recovery*: Check file permissions and disk health
-    /// - **Configuration parsing**: TOML files are corrupted and cannot be parsed
-    ///   - *Recovery*: Individual files fall back to defaults with warnings
+    /// - **Configuration upgrade**: An outdated session config cannot be written back
+    ///   - *Recovery*: Check file permissions and disk space
     ///
     /// ## Async Behavior
     ///
@@ -355,9 +486,29 @@ impl SessionClient {
     /// **Cancellation**: Safe to cancel - no state modifications occur until success
     /// **Concurrency**: Safe to call concurrently for different session names
     pub async fn load_session(session_name: &str) -> Result<Self> {
-        let mut base_path = Self::get_home_dir();
-        base_path.push(CONFIG_DIR);
-        base_path.push(session_name);
+        let snapshot = Self::read_session(session_name).await?;
+        let last_session = snapshot.session.last_session.clone();
+
+        Ok(Self {
+            current_session: session_name.to_string(),
+            last_session,
+            config_portal: Arc::new(ConfigPortal::new(
+                snapshot.session,
+                snapshot.ui_config,
+                snapshot.controller_config,
+                snapshot.connection_config,
+                snapshot.msg_save,
+                snapshot.received_log,
+            )),
+        })
+    }
+
+    /// Reads all configuration files of a session, see [`Self::load_session`].
+    ///
+    /// Outdated session files are upgraded on disk; nothing else is changed.
+    /// Files that cannot be parsed are logged and replaced by their defaults.
+    async fn read_session(session_name: &str) -> Result<SessionSnapshot> {
+        let base_path = Self::session_dir(session_name);
 
         if !try_exists(&base_path)
             .await
@@ -366,121 +517,122 @@ impl SessionClient {
             return Err(eyre!("Session directory does not exist: {}", session_name));
         }
 
-        let mut ui_path = base_path.clone();
+        Self::read_session_dir(&base_path, session_name).await
+    }
+
+    /// Reads the configuration files in `base_path`, see [`Self::read_session`].
+    async fn read_session_dir(base_path: &Path, session_name: &str) -> Result<SessionSnapshot> {
+        let mut ui_path = base_path.to_path_buf();
         ui_path.push(UI_CONFIG_FILE);
 
-        let mut session_path = base_path.clone();
+        let mut session_path = base_path.to_path_buf();
         session_path.push(SESSION_CONFIG_FILE);
 
-        let mut connection_path = base_path.clone();
+        let mut connection_path = base_path.to_path_buf();
         connection_path.push(CONNECTION_CONFIG_FILE);
 
-        let mut controller_path = base_path.clone();
+        let mut controller_path = base_path.to_path_buf();
         controller_path.push(CONTROLLER_CONFIG_FILE);
 
-        let mut messages_path = base_path.clone();
+        let mut messages_path = base_path.to_path_buf();
         messages_path.push(MESSAGES_FILE);
 
-        let session_config = if try_exists(&session_path)
-            .await
-            .map_err(|e| eyre!("Failed to check if Session config file exists: {}", e))?
-        {
-            let content = read_to_string(&session_path)
-                .await
-                .map_err(|e| eyre!("Failed to read Session config file: {}", e))?;
-            toml::from_str(&content)
-                .map_err(|e| eyre!("Failed to parse Session config file: {}", e))?
-        } else {
-            warn!(
-                "Session config file does not exist for session {}, using default",
-                session_name
-            );
-            SessionConfig::default()
-        };
+        let mut received_log_path = base_path.to_path_buf();
+        received_log_path.push(RECEIVED_LOG_FILE);
 
-        let ui_config = if try_exists(&ui_path)
+        let mut session_config = read_config_file::<SessionConfig>(&session_path, "Session config")
             .await
-            .map_err(|e| eyre!("Failed to check if UI config file exists: {}", e))?
-        {
-            let content = read_to_string(&ui_path)
-                .await
-                .map_err(|e| eyre!("Failed to read UI config file: {}", e))?;
-            toml::from_str(&content).map_err(|e| eyre!("Failed to parse UI config file: {}", e))?
-        } else {
-            warn!(
-                "UI config file does not exist for session {}, using default",
-                session_name
+            .unwrap_or_else(corrupt_as_missing)
+            .unwrap_or_else(|| {
+                warn!(
+                    "Session config file does not exist for session {}, using default",
+                    session_name
+                );
+                SessionConfig::default()
+            });
+
+        if session_config.version < CONFIG_VERSION {
+            info!(
+                "Upgrading session {} from config version {} to {}",
+                session_name, session_config.version, CONFIG_VERSION
             );
-            UIConfig::default()
-        };
-
-        let connection_config = if try_exists(&connection_path)
-            .await
-            .map_err(|e| eyre!("Failed to check if connection config file exists: {}", e))?
-        {
-            let content = read_to_string(&connection_path)
+            session_config.version = CONFIG_VERSION;
+            let session_content = toml::to_string_pretty(&session_config)
+                .map_err(|e| eyre!("Failed to parse session config file: {}", e))?;
+            write_atomic(&session_path, session_content)
                 .await
-                .map_err(|e| eyre!("Failed to read connection config file: {}", e))?;
-
-            toml::from_str(&content)
-                .map_err(|e| eyre!("Failed to parse connection config file: {}", e))?
-        } else {
+                .map_err(|e| eyre!("Failed to write session config file: {}", e))?;
+        } else if session_config.version > CONFIG_VERSION {
             warn!(
-                "Connection config file does not exist for session {}, using default",
-                session_name
+                "Session {} was written with newer config version {}, some settings may be ignored",
+                session_name, session_config.version
             );
-            ConnectionConfig::default()
-        };
+        }
 
-        let controller_config = if try_exists(&controller_path)
+        let ui_config = read_config_file::<UIConfig>(&ui_path, "UI config")
             .await
-            .map_err(|e| eyre!("Failed to check if controller config file exists: {}", e))?
-        {
-            let content = read_to_string(&controller_path)
+            .unwrap_or_else(corrupt_as_missing)
+            .unwrap_or_else(|| {
+                warn!(
+                    "UI config file does not exist for session {}, using default",
+                    session_name
+                );
+                UIConfig::default()
+            });
+
+        let connection_config =
+            read_config_file::<ConnectionConfig>(&connection_path, "Connection config")
                 .await
-                .map_err(|e| eyre!("Failed to read controller config file: {}", e))?;
-
-            toml::from_str(&content)
-                .map_err(|e| eyre!("Failed to parse controller config file: {}", e))?
-        } else {
-            warn!(
-                "Controller config file does not exist for session {}, using default",
-                session_name
-            );
-            ControllerConfig::default()
-        };
-
-        let saved_msg = if try_exists(&messages_path)
-            .await
-            .map_err(|e| eyre!("Failed to check if messages file exists: {}", e))?
-        {
-            let content = read_to_string(&messages_path)
+                .unwrap_or_else(corrupt_as_missing)
+                .unwrap_or_else(|| {
+                    warn!(
+                        "Connection config file does not exist for session {}, using default",
+                        session_name
+                    );
+                    ConnectionConfig::default()
+                });
+
+        let controller_config =
+            read_config_file::<ControllerConfig>(&controller_path, "Controller config")
                 .await
-                .map_err(|e| eyre!("Failed to read messages file: {}", e))?;
-
-            toml::from_str(&content).map_err(|e| eyre!("Failed to parse messages file: {}", e))?
-        } else {
-            warn!(
-                "Messages file does not exist for session {}, using default",
-                session_name
-            );
-            SavedMessages::default()
-        };
-
-        let portal = ConfigPortal::new(
-            session_config.clone(),
+                .unwrap_or_else(corrupt_as_missing)
+                .unwrap_or_else(|| {
+                    warn!(
+                        "Controller config file does not exist for session {}, using default",
+                        session_name
+                    );
+                    ControllerConfig::default()
+                });
+
+        let saved_msg = read_config_file::<SavedMessages>(&messages_path, "Messages file")
+            .await
+            .unwrap_or_else(corrupt_as_missing)
+            .unwrap_or_else(|| {
+                warn!(
+                    "Messages file does not exist for session {}, using default",
+                    session_name
+                );
+                SavedMessages::default()
+            });
+
+        let received_log = read_config_file::<ReceivedLog>(&received_log_path, "Received log")
+            .await
+            .unwrap_or_else(corrupt_as_missing)
+            .unwrap_or_else(|| {
+                debug!(
+                    "Received log file does not exist for session {}, starting empty",
+                    session_name
+                );
+                ReceivedLog::default()
+            });
+
+        Ok(SessionSnapshot {
+            session: session_config,
             ui_config,
             controller_config,
             connection_config,
-            saved_msg,
-        );
-
-        let last_session = session_config.last_session.clone();
-
-        Ok(Self {
-            current_session: session_name.to_string(),
-            last_session,
-            config_portal: Arc::new(portal),
+            msg_save: saved_msg,
+            received_log,
         })
     }
 
@@ -493,15 +645,24 @@ impl SessionClient {
         }
     }
 
-    /// Switches to a different session, saving the current state first.
+    /// Switches to a different session, saving the current state first unless
+    /// `save_current` is false.
+    ///
+    /// Without saving, changes since the last save are discarded; the session
+    /// keeps what is stored on disk.
     ///
-    /// This operation performs an atomic session switch by first saving the current
-    /// session, then loading the target session. If the target session fails to load,
-    /// the system falls back to the default configuration.
+    /// The session being left becomes the last session, which deleting the
+    /// active session falls back to.
+    ///
+    /// The target session is read from disk and applied to the running
+    /// [`ConfigPortal`] with [`PortalAction::ApplySession`]. All sections change
+    /// in one step, so no component sees a half-loaded session, and every
+    /// holder of the portal sees the new session without re-fetching it.
     ///
     /// ## Error Handling
-    /// Uses a multi-stage fallback strategy to ensure the application remains usable
-    /// even if session switching fails partially.
+    /// If the target session cannot be read, the default configuration is
+    /// applied instead, so the application remains usable. If the portal cannot
+    /// be locked, nothing is changed and the current session stays active.
     ///
     /// # Errors
     ///
@@ -510,53 +671,94 @@ impl SessionClient {
     ///   - *Recovery*: Continues with switch but current changes may be lost
     /// - **Target session load fails**: Target session cannot be loaded
     ///   - *Recovery*: Falls back to default configuration
-    /// - **State update fails**: Unable to update session registry
-    ///   - *Recovery*: Falls back to default configuration with error logging
-    pub async fn change_session(&mut self, name: &str) -> Result<()> {
-        self.save_current_session().await;
-        let new_session = SessionClient::load_session(name)
-            .await
-            .unwrap_or(SessionClient::ensure_default());
+    /// - **Portal update fails**: The configuration sections stay locked
+    ///   - *Recovery*: The current session stays active, the switch can be retried
+    pub async fn change_session(&mut self, name: &str, save_current: bool) -> Result<()> {
+        if !save_current {
+            info!(
+                "Discarding unsaved changes of session {}",
+                self.current_session
+            );
+        } else if let Err(e) = self.save_current_session().await {
+            warn!(
+                "Failed to save session {} before switching: {}",
+                self.current_session, e
+            );
+        }
 
-        let available_sessions = SessionClient::scan_available_sessions()
+        let (session_name, mut snapshot) = match Self::read_session(name).await {
+            Ok(snapshot) => (name.to_string(), snapshot),
+            Err(e) => {
+                error!(
+                    "Failed to load session {}, using default configuration: {}",
+                    name, e
+                );
+                ("default".to_string(), SessionSnapshot::default())
+            }
+        };
+        snapshot.session.available_sessions = SessionClient::scan_available_sessions()
             .await
             .unwrap_or_default();
+        let previous = self.current_session.clone();
 
-        let result = new_session
+        match self
             .config_portal
-            .execute_potal_action(PortalAction::WriteAvailableSessions(available_sessions));
-
-        match result {
+            .execute_portal_action_async(PortalAction::ApplySession(Box::new(snapshot)))
+            .await
+        {
             ConfigResult::Success => {
-                new_session
-                    .save_current_session()
+                // Reloading the active session keeps the session it came from
+                if previous != session_name {
+                    self.last_session = Some(previous);
+                }
+                if let ConfigResult::Failed(e) = self
+                    .config_portal
+                    .execute_portal_action_async(PortalAction::WriteLastSession(
+                        self.last_session.clone(),
+                    ))
                     .await
-                    .map_err(|_e| eyre!("Failed to save loaded session"));
-                *self = new_session;
+                {
+                    warn!("Failed to store last session: {}", e);
+                }
+                self.current_session = session_name;
+                if let Err(e) = self.save_current_session().await {
+                    warn!("Failed to save loaded session: {}", e);
+                }
                 Ok(())
             }
-            _ => {
-                error!("Fallback to default configuration as an error while changing_sessions occured. For details refer to logs.");
-                *self = SessionClient::ensure_default();
-                Err(Report::msg("Fallback to default"))
+            ConfigResult::Failed(e) => {
+                error!(
+                    "Keeping session {}, switching failed: {}",
+                    self.current_session, e
+                );
+                Err(eyre!("Failed to apply session {}: {}", name, e))
             }
+            _ => Err(Report::msg("Unexpected result while applying session")),
         }
     }
 
     /// Scans the configuration directory for available sessions.
     ///
-    /// Discovers all valid session directories by attempting to load each one.
+    /// A directory counts as a session if it holds at least one of the
+    /// session's config files. Only directory entries are checked, no file is
+    /// read or parsed, so listing stays fast with many sessions and lists
+    /// sessions with broken config files too; those fail when loaded instead.
     /// Used by the UI to populate session selection menus.
     ///
     /// # Errors
     ///
     /// Returns [`color_eyre::Report`] when unable to access the configuration directory.
-    /// Individual session loading errors are logged but don't fail the entire operation.
+    /// Unreadable session directories are logged but don't fail the entire operation.
     pub async fn scan_available_sessions() -> Result<HashMap<String, PathBuf>> {
         let mut base_path = Self::get_home_dir();
         base_path.push(CONFIG_DIR);
+        Self::scan_sessions_in(&base_path).await
+    }
 
-        if !try_exists(&base_path)
+    /// Lists the session directories below `base_path`, see
+    /// [`Self::scan_available_sessions`].
+    async fn scan_sessions_in(base_path: &Path) -> Result<HashMap<String, PathBuf>> {
+        if !try_exists(base_path)
             .await
             .map_err(|e| eyre!("Failed to check if config directory exists: {}", e))?
         {
@@ -566,7 +768,7 @@ impl SessionClient {
 
         let mut available_sessions = HashMap::new();
 
-        let mut read_dir = read_dir(&base_path)
+        let mut read_dir = read_dir(base_path)
             .await
             .map_err(|e| eyre!("Failed to read config directory: {}", e))?;
 
@@ -577,19 +779,27 @@ impl SessionClient {
         {
             let path = entry.path();
 
+            // Hidden directories, such as import staging, are never sessions
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+
             if metadata(&path)
                 .await
                 .map_err(|e| eyre!("Failed to get metadata for {}: {}", path.display(), e))?
                 .is_dir()
             {
                 if let Some(session_name) = path.file_name().and_then(|n| n.to_str()) {
-                    match Self::load_session(session_name).await {
-                        Ok(_config) => {
+                    match Self::has_session_files(&path).await {
+                        Ok(true) => {
                             debug!("Found session: {}", session_name);
                             available_sessions.insert(session_name.to_string(), path);
                         }
+                        Ok(false) => {
+                            debug!("Skipping {}, it holds no session files", session_name);
+                        }
                         Err(e) => {
-                            warn!("Failed to load session {}: {}", session_name, e);
+                            warn!("Failed to check session {}: {}", session_name, e);
                         }
                     }
                 }
@@ -599,41 +809,562 @@ impl SessionClient {
         Ok(available_sessions)
     }
 
+    /// Removes staging directories left below `base_path` by an import that
+    /// was interrupted, e.g. by a crash.
+    async fn remove_import_leftovers_in(base_path: &Path) {
+        let Ok(mut read_dir) = read_dir(base_path).await else {
+            return;
+        };
+        while let Ok(Some(entry)) = read_dir.next_entry().await {
+            if !entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(IMPORT_STAGING_PREFIX)
+            {
+                continue;
+            }
+            let path = entry.path();
+            match remove_dir_all(&path).await {
+                Ok(()) => info!("Removed leftover import directory {}", path.display()),
+                Err(e) => warn!("Failed to remove {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    /// Whether `dir` contains any of the [`SESSION_FILES`].
+    async fn has_session_files(dir: &Path) -> std::io::Result<bool> {
+        for file in SESSION_FILES {
+            if try_exists(dir.join(file)).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Rescans the available sessions and stores them in the ConfigPortal.
+    ///
+    /// Uses the cheap [`Self::scan_available_sessions`], so it can run every
+    /// time the session list is shown.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`color_eyre::Report`] when the scan fails or the ConfigPortal
+    /// rejects the update.
+    pub async fn refresh_sessions(&self) -> Result<HashMap<String, PathBuf>> {
+        let sessions = Self::scan_available_sessions().await?;
+        match self
+            .config_portal
+            .execute_portal_action_async(PortalAction::WriteAvailableSessions(sessions.clone()))
+            .await
+        {
+            ConfigResult::Failed(e) => Err(eyre!("Failed to store available sessions: {}", e)),
+            _ => Ok(sessions),
+        }
+    }
+
     /// Deletes a session from persistent storage.
     ///
     /// If the session being deleted is currently active, automatically switches
-    /// to the last used session or default session as a fallback.
+    /// to the last used session or default session as a fallback. The session
+    /// is removed from the registry afterwards.
     ///
     /// # Errors
     ///
-    /// Returns [`color_eyre::Report`] when unable to delete the session directory
-    /// or when the session directory doesn't exist.
+    /// Returns [`color_eyre::Report`] when unable to delete the session directory,
+    /// when the session directory doesn't exist, or when the active session is
+    /// the only one left.
     pub async fn delete_session(&mut self, session_name: &str) -> Result<()> {
+        let base_path = Self::session_dir(session_name);
+
+        if !try_exists(&base_path)
+            .await
+            .map_err(|e| eyre!("Failed to check if session directory exists: {}", e))?
+        {
+            return Err(eyre!("Session directory does not exist: {}", session_name));
+        }
+
         if session_name == self.current_session.as_str() {
-            let last_session = self.last_session.clone().unwrap_or("default".to_string());
-            self.clone().change_session(&last_session).await;
+            let fallback = self.fallback_session(session_name).await.ok_or_else(|| {
+                eyre!(
+                    "Cannot delete {}, there is no other session to switch to",
+                    session_name
+                )
+            })?;
+            info!(
+                "Deleting active session {}, switching to {}",
+                session_name, fallback
+            );
+            self.change_session(&fallback, true).await?;
         }
 
-        let mut base_path = Self::get_home_dir();
-        base_path.push(CONFIG_DIR);
-        base_path.push(session_name);
+        remove_dir_all(&base_path)
+            .await
+            .map_err(|e| eyre!("Failed to delete session directory: {}", e))?;
+
+        self.update_registry(|sessions| {
+            sessions.remove(session_name);
+        });
 
-        if try_exists(&base_path)
+        info!("Session {} deleted successfully", session_name);
+        Ok(())
+    }
+
+    /// Picks the session to switch to when the active `session_name` is deleted.
+    ///
+    /// Prefers the last session if it still exists, then `default`, then any
+    /// other stored session. `None` if `session_name` is the only session.
+    async fn fallback_session(&self, session_name: &str) -> Option<String> {
+        if let Some(last) = &self.last_session {
+            if last != session_name && try_exists(Self::session_dir(last)).await.unwrap_or(false) {
+                return Some(last.clone());
+            }
+        }
+        if session_name != "default" {
+            return Some("default".to_string());
+        }
+        Self::scan_available_sessions()
+            .await
+            .unwrap_or_default()
+            .into_keys()
+            .filter(|name| name != session_name)
+            .min()
+    }
+
+    /// Renames a session, moving its directory under the config directory.
+    ///
+    /// The directory is moved with a single rename, so the session exists under
+    /// exactly one name at any time. The active session is saved first, so no
+    /// unsaved changes are lost, and keeps running under its new name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`color_eyre::Report`] when:
+    /// - **Invalid name**: The new name is empty, contains path separators or
+    ///   is already used by another session
+    /// - **Session not found**: The session directory does not exist
+    /// - **File system errors**: The directory cannot be moved or updated
+    pub async fn rename_session(&mut self, session_name: &str, new_name: &str) -> Result<()> {
+        Self::validate_new_session_name(new_name).await?;
+
+        let old_path = Self::session_dir(session_name);
+        if !try_exists(&old_path)
             .await
             .map_err(|e| eyre!("Failed to check if session directory exists: {}", e))?
         {
-            remove_dir_all(&base_path)
-                .await
-                .map_err(|e| eyre!("Failed to delete session directory: {}", e))?;
+            return Err(eyre!("Session directory does not exist: {}", session_name));
+        }
 
-            info!("Session {} deleted successfully", session_name);
-            Ok(())
-        } else {
+        let is_active = session_name == self.current_session.as_str();
+        if is_active {
+            self.save_current_session().await?;
+        }
+
+        let new_path = Self::session_dir(new_name);
+        rename(&old_path, &new_path)
+            .await
+            .map_err(|e| eyre!("Failed to move session directory: {}", e))?;
+        Self::write_session_identity(new_name, &new_path).await?;
+
+        if is_active {
+            self.current_session = new_name.to_string();
+            self.config_portal
+                .execute_portal_action_async(PortalAction::WriteSessionName(new_name.to_string()))
+                .await;
+            self.config_portal
+                .execute_portal_action_async(PortalAction::WriteSessionPath(new_path.clone()))
+                .await;
+            self.save_current_session().await?;
+        }
+
+        self.update_registry(|sessions| {
+            sessions.remove(session_name);
+            sessions.insert(new_name.to_string(), new_path);
+        });
+
+        info!("Session {} renamed to {}", session_name, new_name);
+        Ok(())
+    }
+
+    /// Copies a session to a new name.
+    ///
+    /// Duplicating the active session saves it first, so the copy contains
+    /// the current state. The active session stays unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`color_eyre::Report`] when:
+    /// - **Invalid name**: The new name is empty, contains path separators or
+    ///   is already used by another session
+    /// - **Session not found**: The session directory does not exist
+    /// - **File system errors**: Files cannot be copied
+    pub async fn duplicate_session(&mut self, session_name: &str, new_name: &str) -> Result<()> {
+        Self::validate_new_session_name(new_name).await?;
+
+        if session_name == self.current_session.as_str() {
+            self.save_current_session().await?;
+        }
+
+        let source = Self::session_dir(session_name);
+        if !try_exists(&source)
+            .await
+            .map_err(|e| eyre!("Failed to check if session directory exists: {}", e))?
+        {
+            return Err(eyre!("Session directory does not exist: {}", session_name));
+        }
+
+        let target = Self::session_dir(new_name);
+        create_dir_all(&target)
+            .await
+            .map_err(|e| eyre!("Failed to create session directory: {}", e))?;
+
+        let mut entries = read_dir(&source)
+            .await
+            .map_err(|e| eyre!("Failed to read session directory: {}", e))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| eyre!("Failed to read directory entry: {}", e))?
+        {
+            let path = entry.path();
+            let leftover = entry.file_name().to_string_lossy().ends_with(TEMP_SUFFIX);
+            if path.is_file() && !leftover {
+                copy(&path, target.join(entry.file_name()))
+                    .await
+                    .map_err(|e| eyre!("Failed to copy {}: {}", path.display(), e))?;
+            }
+        }
+        Self::write_session_identity(new_name, &target).await?;
+
+        self.update_registry(|sessions| {
+            sessions.insert(new_name.to_string(), target);
+        });
+
+        info!("Session {} duplicated as {}", session_name, new_name);
+        Ok(())
+    }
+
+    /// Exports a session into a single `.ocsession` archive.
+    ///
+    /// The archive is a zip file with the session's TOML files at its root. If
+    /// `dest` is a directory the archive is named after the session, otherwise
+    /// `dest` is used as file name with the archive extension. The active
+    /// session is saved first, so the export contains the current state.
+    ///
+    /// Returns the path of the written archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`color_eyre::Report`] when:
+    /// - **Session not found**: The session directory does not exist
+    /// - **File system errors**: Session files cannot be read or the archive
+    ///   cannot be written
+    pub async fn export_session(&self, session_name: &str, dest: &Path) -> Result<PathBuf> {
+        if session_name == self.current_session.as_str() {
+            self.save_current_session().await?;
+        }
+
+        let source = Self::session_dir(session_name);
+        if !try_exists(&source)
+            .await
+            .map_err(|e| eyre!("Failed to check if session directory exists: {}", e))?
+        {
             return Err(eyre!("Session directory does not exist: {}", session_name));
         }
+
+        let mut files = Vec::new();
+        for file_name in SESSION_FILES {
+            let path = source.join(file_name);
+            if try_exists(&path).await.unwrap_or(false) {
+                let content = read_to_string(&path)
+                    .await
+                    .map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+                files.push((file_name, content));
+            }
+        }
+
+        let archive_path = if dest.is_dir() {
+            dest.join(session_name)
+                .with_extension(SESSION_ARCHIVE_EXTENSION)
+        } else {
+            dest.with_extension(SESSION_ARCHIVE_EXTENSION)
+        };
+
+        let target = archive_path.clone();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let file = std::fs::File::create(&target)
+                .map_err(|e| eyre!("Failed to create archive {}: {}", target.display(), e))?;
+            let mut archive = zip::ZipWriter::new(file);
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+
+            for (file_name, content) in files {
+                archive
+                    .start_file(file_name, options)
+                    .map_err(|e| eyre!("Failed to add {} to archive: {}", file_name, e))?;
+                std::io::Write::write_all(&mut archive, content.as_bytes())
+                    .map_err(|e| eyre!("Failed to write {} to archive: {}", file_name, e))?;
+            }
+            archive
+                .finish()
+                .map_err(|e| eyre!("Failed to finish archive: {}", e))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| eyre!("Archive task failed: {}", e))??;
+
+        info!(
+            "Session {} exported to {}",
+            session_name,
+            archive_path.display()
+        );
+        Ok(archive_path)
+    }
+
+    /// Imports a session from an `.ocsession` archive.
+    ///
+    /// Every contained config file is parsed into its config struct before
+    /// anything is written, so a corrupt or incompatible archive is rejected
+    /// without touching existing sessions. The session is named after the
+    /// archive's session file, or the archive file name, with a numbered
+    /// suffix if that name is already taken. Files are unpacked into a staging
+    /// directory that is moved into place only once complete. The staging
+    /// directory is hidden from session scans, and one left by a crash is
+    /// removed at the next start.
+    ///
+    /// Returns the name of the imported session.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`color_eyre::Report`] when:
+    /// - **Unreadable archive**: The file is missing or not a zip archive
+    /// - **Invalid content**: The archive holds no session files, or a config
+    ///   file does not match its config struct
+    /// - **File system errors**: The session directory cannot be written
+    pub async fn import_session(&mut self, src: &Path) -> Result<String> {
+        let archive_path = src.to_path_buf();
+        let files = tokio::task::spawn_blocking(move || read_session_archive(&archive_path))
+            .await
+            .map_err(|e| eyre!("Archive task failed: {}", e))??;
+
+        let mut files = files;
+        validate_session_files(&files)?;
+        dedup_archive_servers(&mut files)?;
+
+        let stored_name = files
+            .get(SESSION_CONFIG_FILE)
+            .and_then(|content| toml::from_str::<SessionConfig>(content).ok())
+            .map(|config| config.session_name)
+            .filter(|name| !name.trim().is_empty());
+        let base_name = match stored_name {
+            Some(name) => name,
+            None => src
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(str::to_string)
+                .ok_or_else(|| eyre!("Cannot derive a session name from {}", src.display()))?,
+        };
+
+        let mut session_name = base_name.clone();
+        let mut suffix = 1;
+        while Self::validate_new_session_name(&session_name)
+            .await
+            .is_err()
+        {
+            suffix += 1;
+            session_name = format!("{} {}", base_name, suffix);
+            if suffix > 100 {
+                return Err(eyre!("No free session name for {}", base_name));
+            }
+        }
+
+        let staging = Self::session_dir(&format!("{}{}", IMPORT_STAGING_PREFIX, session_name));
+        let unpacked = async {
+            create_dir_all(&staging)
+                .await
+                .map_err(|e| eyre!("Failed to create session directory: {}", e))?;
+            for (file_name, content) in &files {
+                write_atomic(staging.join(file_name), content)
+                    .await
+                    .map_err(|e| eyre!("Failed to write {}: {}", file_name, e))?;
+            }
+            let target = Self::session_dir(&session_name);
+            rename(&staging, &target)
+                .await
+                .map_err(|e| eyre!("Failed to move imported session into place: {}", e))?;
+            Self::write_session_identity(&session_name, &target).await?;
+            Ok::<PathBuf, Report>(target)
+        }
+        .await;
+
+        let target = match unpacked {
+            Ok(target) => target,
+            Err(e) => {
+                let _ = remove_dir_all(&staging).await;
+                return Err(e);
+            }
+        };
+
+        self.update_registry(|sessions| {
+            sessions.insert(session_name.clone(), target);
+        });
+
+        info!("Session {} imported from {}", session_name, src.display());
+        Ok(session_name)
+    }
+
+    /// Lists the backups of a session, newest first.
+    ///
+    /// Backups are named after the time they were taken.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`color_eyre::Report`] when the backup directory exists but
+    /// cannot be read.
+    pub async fn list_backups(session_name: &str) -> Result<Vec<String>> {
+        list_backup_names(&Self::session_dir(session_name).join(BACKUPS_DIR)).await
+    }
+
+    /// Replaces the files of a session by one of its backups.
+    ///
+    /// The replaced files are backed up first, so a restore can be undone by
+    /// restoring that backup. Files missing from the backup are removed.
+    /// Restoring the active session loads it again and discards its unsaved
+    /// changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`color_eyre::Report`] when:
+    /// - **Backup not found**: The session has no backup of that name
+    /// - **File system errors**: The backup cannot be read or the session
+    ///   files cannot be written
+    /// - **Reload fails**: The restored active session cannot be applied
+    pub async fn restore_backup(&mut self, session_name: &str, backup: &str) -> Result<()> {
+        if !Self::list_backups(session_name)
+            .await?
+            .iter()
+            .any(|name| name == backup)
+        {
+            return Err(eyre!("Session {} has no backup {}", session_name, backup));
+        }
+
+        let session_dir = Self::session_dir(session_name);
+        let files = read_session_files(&session_dir.join(BACKUPS_DIR).join(backup)).await?;
+        if files.is_empty() {
+            return Err(eyre!(
+                "Backup {} of session {} is empty",
+                backup,
+                session_name
+            ));
+        }
+        if let Err(e) = backup_session(&session_dir, self.backup_count().await).await {
+            warn!("Failed to back up session {}: {}", session_name, e);
+        }
+
+        for file_name in SESSION_FILES {
+            let path = session_dir.join(file_name);
+            match files.iter().find(|(name, _)| *name == file_name) {
+                Some((_, content)) => write_atomic(&path, content)
+                    .await
+                    .map_err(|e| eyre!("Failed to restore {}: {}", path.display(), e))?,
+                None => {
+                    if try_exists(&path).await.unwrap_or(false) {
+                        remove_file(&path)
+                            .await
+                            .map_err(|e| eyre!("Failed to remove {}: {}", path.display(), e))?;
+                    }
+                }
+            }
+        }
+        // The backup may predate a rename of the session
+        Self::write_session_identity(session_name, &session_dir).await?;
+        info!("Session {} restored from backup {}", session_name, backup);
+
+        if session_name == self.current_session.as_str() {
+            self.change_session(session_name, false).await?;
+        }
+        Ok(())
+    }
+
+    /// Number of backups to keep, from the active session's autosave settings.
+    async fn backup_count(&self) -> usize {
+        match self
+            .config_portal
+            .execute_portal_action_async(PortalAction::GetAutosave)
+            .await
+        {
+            ConfigResult::Autosave(settings) => settings.backup_count,
+            _ => AutosaveSettings::default().backup_count,
+        }
+    }
+
+    /// Returns the directory of a session.
+    fn session_dir(session_name: &str) -> PathBuf {
+        let mut path = config_dir();
+        path.push(session_name);
+        path
+    }
+
+    /// Directory holding the files of the active session.
+    pub(super) fn current_session_dir(&self) -> PathBuf {
+        Self::session_dir(&self.current_session)
+    }
+
+    /// Checks that a name can be used for a new session directory.
+    async fn validate_new_session_name(new_name: &str) -> Result<()> {
+        if new_name.trim().is_empty() {
+            return Err(eyre!("Session name cannot be empty"));
+        }
+        if new_name.contains(['/', '\\']) || new_name == "." || new_name == ".." {
+            return Err(eyre!("Invalid session name: {}", new_name));
+        }
+        if try_exists(Self::session_dir(new_name))
+            .await
+            .map_err(|e| eyre!("Failed to check if session directory exists: {}", e))?
+        {
+            return Err(eyre!("Session {} already exists", new_name));
+        }
+        Ok(())
+    }
+
+    /// Updates name and path stored in a session directory's session file.
+    async fn write_session_identity(session_name: &str, base_path: &Path) -> Result<()> {
+        let session_path = base_path.join(SESSION_CONFIG_FILE);
+        let mut session_config: SessionConfig = match read_to_string(&session_path).await {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| eyre!("Failed to parse Session config file: {}", e))?,
+            Err(_) => SessionConfig::default(),
+        };
+        session_config.session_name = session_name.to_string();
+        session_config.path = base_path.to_path_buf();
+
+        let session_content = toml::to_string_pretty(&session_config)
+            .map_err(|e| eyre!("Failed to parse session config file: {}", e))?;
+        write_atomic(&session_path, session_content)
+            .await
+            .map_err(|e| eyre!("Failed to write session config file: {}", e))
+    }
+
+    /// Applies a change to the session registry in the config portal.
+    fn update_registry(&self, apply: impl FnOnce(&mut HashMap<String, PathBuf>)) {
+        let mut sessions = match self
+            .config_portal
+            .execute_potal_action(PortalAction::GetAvailableSessions)
+        {
+            ConfigResult::AvailableSessions(sessions) => sessions,
+            _ => HashMap::new(),
+        };
+        apply(&mut sessions);
+
+        if let ConfigResult::Failed(e) = self
+            .config_portal
+            .execute_potal_action(PortalAction::WriteAvailableSessions(sessions))
+        {
+            error!("Failed to update session registry: {}", e);
+        }
     }
 
     /// Returns the user's home directory or current directory as fallback.
+    #[cfg(not(test))]
     fn get_home_dir() -> PathBuf {
         dirs::home_dir().unwrap_or_else(|| {
             warn!("Could not determine home directory, using current directory");
@@ -641,6 +1372,12 @@ impl SessionClient {
         })
     }
 
+    /// Tests get a home directory of their own, see [`tests::test_home`].
+    #[cfg(test)]
+    fn get_home_dir() -> PathBuf {
+        tests::test_home()
+    }
+
     /// Ensures the default configuration directory structure exists.
     pub async fn ensure_default_config() -> Result<()> {
         let mut base_path = SessionClient::get_home_dir();
@@ -665,31 +1402,722 @@ impl SessionClient {
     /// Provides automatic backup functionality to prevent configuration loss
     /// in case of application crashes or unexpected shutdowns.
     ///
+    /// ## Live Settings
+    /// Interval and on/off state come from `settings_rx` and apply as soon as
+    /// they change. While paused the task only waits for new settings.
+    ///
+    /// ## Change Detection
+    /// A tick only writes if the config portal was changed since the last
+    /// save, sparing the SD card needless writes.
+    ///
     /// ## Runtime Requirements
     ///
     /// Requires tokio runtime because it spawns a long-running background task
     /// that uses async intervals for timing.
     pub async fn start_autosave_task(
         portal: Arc<Mutex<SessionClient>>,
-        interval_seconds: u64,
+        mut settings_rx: watch::Receiver<AutosaveSettings>,
     ) -> JoinHandle<()> {
-        info!(
-            "Starting autosave task with interval: {}s",
-            interval_seconds
-        );
-
         tokio::spawn(async move {
-            let mut interval =
-                tokio::time::interval(tokio::time::Duration::from_secs(interval_seconds));
-
             loop {
-                interval.tick().await;
-                if let Err(e) = portal.lock().await.save_current_session().await {
-                    error!("Failed to autosave configuration: {}", e);
-                } else {
-                    debug!("Configuration autosaved successfully");
+                let settings = settings_rx.borrow_and_update().clone();
+                if !settings.enabled {
+                    info!("Autosave paused");
+                    if settings_rx.changed().await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+
+                info!(
+                    "Autosave running with interval: {}s",
+                    settings.interval_secs
+                );
+                let period = tokio::time::Duration::from_secs(settings.interval_secs.max(1));
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            let client = portal.lock().await;
+                            if !client.config_portal.is_dirty() {
+                                debug!("Autosave skipped, nothing changed");
+                                continue;
+                            }
+                            match client.save_current_session().await {
+                                Ok(()) => info!("Autosaved session {}", client.current_session),
+                                Err(e) => error!("Failed to autosave configuration: {}", e),
+                            }
+                        }
+                        changed = settings_rx.changed() => {
+                            if changed.is_err() {
+                                return;
+                            }
+                            break;
+                        }
+                    }
                 }
             }
         })
     }
 }
+
+/// Directory holding the main config and one subdirectory per session.
+pub(super) fn config_dir() -> PathBuf {
+    let mut path = SessionClient::get_home_dir();
+    path.push(CONFIG_DIR);
+    path
+}
+
+/// Replaces a file atomically, so an interrupted write keeps the old content.
+///
+/// The content goes to a temporary file next to the target, is synced to disk
+/// and then renamed over the target, which is atomic on the same filesystem.
+/// The directory is synced after the rename, so the new entry survives a
+/// power loss as well. Leftover temporary files from interrupted writes are never read; they are
+/// overwritten by the next write. The content is recorded as an own write, so
+/// the session file watcher does not reload it.
+///
+/// The previous content is copied to the [`BACKUP_SUFFIX`] file first. A
+/// failed backup is logged but does not stop the write.
+async fn write_atomic(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let temp_path = with_suffix(path, TEMP_SUFFIX);
+
+    if try_exists(path).await.unwrap_or(false) {
+        if let Err(e) = copy(path, with_suffix(path, BACKUP_SUFFIX)).await {
+            warn!("Failed to back up {}: {}", path.display(), e);
+        }
+    }
+
+    file_watcher::record_own_write(path, content.as_ref());
+    let result = async {
+        let mut file = File::create(&temp_path).await?;
+        file.write_all(content.as_ref()).await?;
+        file.sync_all().await?;
+        rename(&temp_path, path).await?;
+        sync_dir(path).await
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = remove_file(&temp_path).await;
+    }
+    result
+}
+
+/// Syncs the directory containing `path`, persisting renames inside it.
+async fn sync_dir(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => File::open(dir).await?.sync_all().await,
+        _ => Ok(()),
+    }
+}
+
+/// Path of `path` with `suffix` appended to its file name.
+pub(super) fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Treats a config file that cannot be read like a missing one.
+///
+/// Used while loading a session, so one corrupt file only resets its own
+/// section.
+fn corrupt_as_missing<T>(error: Report) -> Option<T> {
+    error!("{}, using defaults", error);
+    None
+}
+
+/// Reads a config file, repairing older layouts instead of discarding them.
+///
+/// Returns `None` if the file does not exist. Missing fields are normally
+/// filled by the struct's serde defaults. If the file still fails to parse,
+/// every field missing from it is taken from the struct's default, one field
+/// at a time, so the user's values are kept. Each added field is logged and
+/// the repaired file is written back.
+///
+/// # Errors
+///
+/// Returns [`color_eyre::Report`] when the file cannot be read, is not valid
+/// TOML, or cannot be parsed even after adding the missing fields.
+pub(super) async fn read_config_file<T>(path: &Path, description: &str) -> Result<Option<T>>
+where
+    T: Serialize + serde::de::DeserializeOwned + Default,
+{
+    if !try_exists(path)
+        .await
+        .map_err(|e| eyre!("Failed to check if {} file exists: {}", description, e))?
+    {
+        return Ok(None);
+    }
+
+    let content = read_to_string(path)
+        .await
+        .map_err(|e| eyre!("Failed to read {} file: {}", description, e))?;
+    let (config, added) = parse_config::<T>(&content, description)?;
+    if added.is_empty() {
+        return Ok(Some(config));
+    }
+
+    for field in &added {
+        info!("Migrated {}: added missing field {}", path.display(), field);
+    }
+
+    let upgraded = toml::to_string_pretty(&config)
+        .map_err(|e| eyre!("Failed to serialize migrated {}: {}", description, e))?;
+    write_atomic(path, upgraded)
+        .await
+        .map_err(|e| eyre!("Failed to write migrated {} file: {}", description, e))?;
+
+    Ok(Some(config))
+}
+
+/// Parses a config file's content, see [`read_config_file`].
+///
+/// Returns the config and the fields taken from the defaults; nothing is
+/// written.
+///
+/// # Errors
+///
+/// Returns [`color_eyre::Report`] when the content is not valid TOML or cannot
+/// be parsed even after adding the missing fields.
+pub(super) fn parse_config<T>(content: &str, description: &str) -> Result<(T, Vec<String>)>
+where
+    T: Serialize + serde::de::DeserializeOwned + Default,
+{
+    let mut value: toml::Value = toml::from_str(content)
+        .map_err(|e| eyre!("Failed to parse {} file: {}", description, e))?;
+
+    let parse_error = match value.clone().try_into::<T>() {
+        Ok(config) => return Ok((config, Vec::new())),
+        Err(e) => e,
+    };
+
+    let defaults = toml::Value::try_from(T::default())
+        .map_err(|e| eyre!("Failed to serialize default {}: {}", description, e))?;
+    let mut added = Vec::new();
+    add_missing_fields(&mut value, &defaults, "", &mut added);
+    if added.is_empty() {
+        return Err(eyre!(
+            "Failed to parse {} file: {}",
+            description,
+            parse_error
+        ));
+    }
+
+    let config: T = value
+        .try_into()
+        .map_err(|e| eyre!("Failed to migrate {} file: {}", description, e))?;
+    Ok((config, added))
+}
+
+/// Copies every table entry of `defaults` that `value` lacks, recursing into
+/// tables both sides have. Paths of added fields are collected in `added`.
+fn add_missing_fields(
+    value: &mut toml::Value,
+    defaults: &toml::Value,
+    prefix: &str,
+    added: &mut Vec<String>,
+) {
+    let (Some(table), Some(default_table)) = (value.as_table_mut(), defaults.as_table()) else {
+        return;
+    };
+
+    for (key, default) in default_table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match table.get_mut(key) {
+            Some(existing) => add_missing_fields(existing, default, &path, added),
+            None => {
+                table.insert(key.clone(), default.clone());
+                added.push(path);
+            }
+        }
+    }
+}
+
+/// Config files that make up a session directory
+pub(super) const SESSION_FILES: [&str; 6] = [
+    UI_CONFIG_FILE,
+    CONNECTION_CONFIG_FILE,
+    CONTROLLER_CONFIG_FILE,
+    MESSAGES_FILE,
+    RECEIVED_LOG_FILE,
+    SESSION_CONFIG_FILE,
+];
+
+/// Reads the session files present in `dir`.
+async fn read_session_files(dir: &Path) -> Result<Vec<(&'static str, String)>> {
+    let mut files = Vec::new();
+    for file_name in SESSION_FILES {
+        let path = dir.join(file_name);
+        match read_to_string(&path).await {
+            Ok(content) => files.push((file_name, content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(eyre!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+    Ok(files)
+}
+
+/// Copies the files of `session_dir` into a new backup, then prunes the
+/// backups to the newest `keep`.
+///
+/// No backup is taken if the session has no files yet or a kept backup
+/// already holds the same files. The backup is written to a temporary
+/// directory and renamed into place, so an interrupted backup is never listed.
+async fn backup_session(session_dir: &Path, keep: usize) -> Result<()> {
+    let backups_dir = session_dir.join(BACKUPS_DIR);
+    let files = read_session_files(session_dir).await?;
+
+    if keep > 0 && !files.is_empty() {
+        let mut known = false;
+        for name in list_backup_names(&backups_dir).await? {
+            if read_session_files(&backups_dir.join(&name)).await? == files {
+                known = true;
+                break;
+            }
+        }
+
+        if !known {
+            let name = chrono::Local::now().format(BACKUP_NAME_FORMAT).to_string();
+            let target = backups_dir.join(&name);
+            let staging = with_suffix(&target, TEMP_SUFFIX);
+            let result = async {
+                create_dir_all(&staging).await?;
+                for (file_name, content) in &files {
+                    tokio::fs::write(staging.join(file_name), content).await?;
+                }
+                rename(&staging, &target).await
+            }
+            .await;
+            if let Err(e) = result {
+                let _ = remove_dir_all(&staging).await;
+                return Err(eyre!("Failed to write backup {}: {}", target.display(), e));
+            }
+            debug!("Backed up {} as {}", session_dir.display(), name);
+        }
+    }
+
+    for name in list_backup_names(&backups_dir).await?.iter().skip(keep) {
+        let path = backups_dir.join(name);
+        remove_dir_all(&path)
+            .await
+            .map_err(|e| eyre!("Failed to remove old backup {}: {}", path.display(), e))?;
+        debug!("Removed old backup {}", path.display());
+    }
+    Ok(())
+}
+
+/// Names of the backups in `backups_dir`, newest first.
+///
+/// A missing directory has no backups. Leftovers of interrupted backups are
+/// skipped.
+async fn list_backup_names(backups_dir: &Path) -> Result<Vec<String>> {
+    let mut entries = match read_dir(backups_dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(eyre!("Failed to read {}: {}", backups_dir.display(), e)),
+    };
+
+    let mut names = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| eyre!("Failed to read directory entry: {}", e))?
+    {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.path().is_dir() && !name.ends_with(TEMP_SUFFIX) {
+            names.push(name);
+        }
+    }
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(names)
+}
+
+/// Reads the session files of an archive, keyed by file name.
+///
+/// Entries other than known session files are skipped.
+fn read_session_archive(path: &Path) -> Result<HashMap<String, String>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| eyre!("Failed to open archive {}: {}", path.display(), e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| eyre!("Not a session archive: {}", e))?;
+
+    let mut files = HashMap::new();
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| eyre!("Failed to read archive entry: {}", e))?;
+        let name = entry.name().to_string();
+        if !entry.is_file() || !SESSION_FILES.contains(&name.as_str()) {
+            warn!("Skipping unexpected archive entry {}", name);
+            continue;
+        }
+
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut entry, &mut content)
+            .map_err(|e| eyre!("Failed to read {} from archive: {}", name, e))?;
+        files.insert(name, content);
+    }
+
+    if files.is_empty() {
+        return Err(eyre!("Archive contains no session files"));
+    }
+    Ok(files)
+}
+
+/// Drops duplicate saved MQTT servers from an archived connection config.
+fn dedup_archive_servers(files: &mut HashMap<String, String>) -> Result<()> {
+    let Some(content) = files.get(CONNECTION_CONFIG_FILE) else {
+        return Ok(());
+    };
+    let mut config: ConnectionConfig = toml::from_str(content)
+        .map_err(|e| eyre!("Invalid {} in archive: {}", CONNECTION_CONFIG_FILE, e))?;
+
+    let removed = MqttConfig::dedup_servers(&mut config.mqtt_config.available_servers);
+    if removed > 0 {
+        info!("Dropped {} duplicate MQTT server(s) from archive", removed);
+        let content = toml::to_string(&config)
+            .map_err(|e| eyre!("Failed to serialize {}: {}", CONNECTION_CONFIG_FILE, e))?;
+        files.insert(CONNECTION_CONFIG_FILE.to_string(), content);
+    }
+    Ok(())
+}
+
+/// Parses every session file into its config struct.
+fn validate_session_files(files: &HashMap<String, String>) -> Result<()> {
+    fn check<T: serde::de::DeserializeOwned>(name: &str, content: &str) -> Result<()> {
+        toml::from_str::<T>(content)
+            .map(|_| ())
+            .map_err(|e| eyre!("Invalid {} in archive: {}", name, e))
+    }
+
+    for (name, content) in files {
+        match name.as_str() {
+            UI_CONFIG_FILE => check::<UIConfig>(name, content)?,
+            CONNECTION_CONFIG_FILE => check::<ConnectionConfig>(name, content)?,
+            CONTROLLER_CONFIG_FILE => check::<ControllerConfig>(name, content)?,
+            MESSAGES_FILE => check::<SavedMessages>(name, content)?,
+            RECEIVED_LOG_FILE => check::<ReceivedLog>(name, content)?,
+            SESSION_CONFIG_FILE => check::<SessionConfig>(name, content)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::Theme;
+
+    /// Home directory of the running test, so sessions never touch the real one.
+    ///
+    /// Test threads are named after their test, which keeps tests apart.
+    pub(super) fn test_home() -> PathBuf {
+        let test = std::thread::current()
+            .name()
+            .unwrap_or("main")
+            .replace("::", "-");
+        std::env::temp_dir().join(format!(
+            "opencontroller-home-{}-{}",
+            std::process::id(),
+            test
+        ))
+    }
+
+    /// Client of the session `name` with a default portal.
+    fn client(name: &str) -> SessionClient {
+        SessionClient {
+            current_session: name.to_string(),
+            last_session: None,
+            config_portal: Arc::new(ConfigPortal::default()),
+        }
+    }
+
+    /// Fresh directory under the system temp dir, unique per test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("opencontroller-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn write_replaces_content_and_keeps_backup() {
+        let dir = test_dir("write-atomic");
+        let path = dir.join("ui_config.toml");
+
+        write_atomic(&path, "fps = 30").await.unwrap();
+        write_atomic(&path, "fps = 60").await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fps = 60");
+        assert_eq!(
+            std::fs::read_to_string(with_suffix(&path, BACKUP_SUFFIX)).unwrap(),
+            "fps = 30"
+        );
+        assert!(!with_suffix(&path, TEMP_SUFFIX).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn interrupted_write_keeps_original_and_backup() {
+        let dir = test_dir("write-interrupted");
+        let path = dir.join("ui_config.toml");
+        write_atomic(&path, "fps = 30").await.unwrap();
+        // A directory in place of the temporary file makes the write fail
+        // before anything is renamed over the target.
+        std::fs::create_dir(with_suffix(&path, TEMP_SUFFIX)).unwrap();
+
+        assert!(write_atomic(&path, "fps = 60").await.is_err());
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fps = 30");
+        assert_eq!(
+            std::fs::read_to_string(with_suffix(&path, BACKUP_SUFFIX)).unwrap(),
+            "fps = 30"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Session files as written before config versioning.
+    const V0_SESSION: [(&str, &str); 3] = [
+        (
+            SESSION_CONFIG_FILE,
+            include_str!("fixtures/session_v0/session.toml"),
+        ),
+        (
+            UI_CONFIG_FILE,
+            include_str!("fixtures/session_v0/ui_config.toml"),
+        ),
+        (
+            CONNECTION_CONFIG_FILE,
+            include_str!("fixtures/session_v0/connection_config.toml"),
+        ),
+    ];
+
+    #[tokio::test]
+    async fn v0_session_is_migrated_keeping_user_values() {
+        let dir = test_dir("session-v0");
+        for (file, content) in V0_SESSION {
+            std::fs::write(dir.join(file), content).unwrap();
+        }
+
+        let snapshot = SessionClient::read_session_dir(&dir, "workshop")
+            .await
+            .unwrap();
+
+        assert_eq!(snapshot.session.version, CONFIG_VERSION);
+        assert_eq!(snapshot.session.session_name, "workshop");
+        assert_eq!(snapshot.session.last_session.as_deref(), Some("default"));
+        assert_eq!(snapshot.session.available_sessions.len(), 2);
+
+        assert_eq!(snapshot.ui_config.fps, 30);
+        assert_eq!(snapshot.ui_config.theme.border_color, (10, 20, 30));
+        assert_eq!(snapshot.ui_config.theme.text_color, (220, 220, 220));
+        assert_eq!(
+            snapshot.ui_config.theme.frame_color_two,
+            Theme::default().frame_color_two
+        );
+
+        let mqtt = &snapshot.connection_config.mqtt_config;
+        assert_eq!(mqtt.subbed_topics, ["sensors/temperature"]);
+        assert_eq!(mqtt.available_topics.len(), 2);
+        assert_eq!(mqtt.server.url, "broker.local:1883");
+        assert_eq!(mqtt.server.user, "pi");
+        assert_eq!(mqtt.poll_frequency, 20);
+
+        // Upgraded files are written back
+        let session = std::fs::read_to_string(dir.join(SESSION_CONFIG_FILE)).unwrap();
+        assert!(session.contains(&format!("version = {}", CONFIG_VERSION)));
+        let ui = std::fs::read_to_string(dir.join(UI_CONFIG_FILE)).unwrap();
+        assert!(ui.contains("frame_color_two"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn imported_archive_drops_duplicate_servers() {
+        let mut config = ConnectionConfig::default();
+        let server = crate::ui::MQTTServer {
+            url: "broker.local:1883".to_string(),
+            user: "pi".to_string(),
+            pw: "secret".to_string(),
+            connected: false,
+        };
+        config.mqtt_config.available_servers = vec![server.clone(), server];
+        let mut files = HashMap::from([(
+            CONNECTION_CONFIG_FILE.to_string(),
+            toml::to_string(&config).unwrap(),
+        )]);
+
+        dedup_archive_servers(&mut files).unwrap();
+
+        let imported: ConnectionConfig = toml::from_str(&files[CONNECTION_CONFIG_FILE]).unwrap();
+        assert_eq!(imported.mqtt_config.available_servers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn enumeration_does_not_parse_session_files() {
+        let dir = test_dir("scan-sessions");
+        let valid = dir.join("valid");
+        let broken = dir.join("broken");
+        std::fs::create_dir_all(&valid).unwrap();
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::create_dir_all(dir.join("empty")).unwrap();
+        std::fs::write(valid.join(SESSION_CONFIG_FILE), "session_name = \"valid\"").unwrap();
+        std::fs::write(broken.join(UI_CONFIG_FILE), "fps = [not toml").unwrap();
+        std::fs::write(broken.join(CONNECTION_CONFIG_FILE), [0xff, 0xfe, 0x00]).unwrap();
+        std::fs::write(dir.join("stray.toml"), "").unwrap();
+
+        let sessions = SessionClient::scan_sessions_in(&dir).await.unwrap();
+
+        let mut names: Vec<_> = sessions.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, ["broken", "valid"]);
+        assert_eq!(sessions["broken"], broken);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn interrupted_import_is_not_a_session_and_is_removed() {
+        let dir = test_dir("scan-staging");
+        let session = dir.join("session");
+        let staging = dir.join(format!("{}session", IMPORT_STAGING_PREFIX));
+        for path in [&session, &staging] {
+            std::fs::create_dir_all(path).unwrap();
+            std::fs::write(path.join(SESSION_CONFIG_FILE), "session_name = \"session\"").unwrap();
+        }
+
+        let sessions = SessionClient::scan_sessions_in(&dir).await.unwrap();
+        assert_eq!(sessions.keys().collect::<Vec<_>>(), ["session"]);
+
+        SessionClient::remove_import_leftovers_in(&dir).await;
+        assert!(!staging.exists());
+        assert!(session.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn enumeration_of_missing_config_dir_is_empty() {
+        let dir = test_dir("scan-missing").join("missing");
+
+        assert!(SessionClient::scan_sessions_in(&dir)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn pruning_keeps_exactly_the_newest_backups() {
+        let dir = test_dir("backup-prune");
+        let path = dir.join(UI_CONFIG_FILE);
+
+        for fps in 1..=5 {
+            std::fs::write(&path, format!("fps = {}", fps)).unwrap();
+            backup_session(&dir, 3).await.unwrap();
+            // Backups are named by the millisecond
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+
+        let backups_dir = dir.join(BACKUPS_DIR);
+        let names = list_backup_names(&backups_dir).await.unwrap();
+        assert_eq!(names.len(), 3);
+        let kept: Vec<_> = names
+            .iter()
+            .map(|name| {
+                std::fs::read_to_string(backups_dir.join(name).join(UI_CONFIG_FILE)).unwrap()
+            })
+            .collect();
+        assert_eq!(kept, ["fps = 5", "fps = 4", "fps = 3"]);
+
+        // Unchanged files add no backup, a smaller limit prunes right away
+        backup_session(&dir, 3).await.unwrap();
+        assert_eq!(list_backup_names(&backups_dir).await.unwrap(), names);
+        backup_session(&dir, 1).await.unwrap();
+        assert_eq!(list_backup_names(&backups_dir).await.unwrap(), names[..1]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn created_session_gets_its_own_name() {
+        let _ = std::fs::remove_dir_all(test_home());
+        client("first").save_current_session().await.unwrap();
+        let loaded = SessionClient::load_session("first").await.unwrap();
+
+        // CreateSession saves the loaded state under the new name
+        loaded.save_session("second".to_string()).await.unwrap();
+
+        let created = SessionClient::read_session("second").await.unwrap();
+        assert_eq!(created.session.session_name, "second");
+        assert_eq!(created.session.path, SessionClient::session_dir("second"));
+        let original = SessionClient::read_session("first").await.unwrap();
+        assert_eq!(original.session.session_name, "first");
+        let _ = std::fs::remove_dir_all(test_home());
+    }
+
+    #[tokio::test]
+    async fn switching_sessions_records_the_session_left() {
+        let _ = std::fs::remove_dir_all(test_home());
+        client("a").save_current_session().await.unwrap();
+        client("b").save_current_session().await.unwrap();
+        let mut active = SessionClient::load_session("a").await.unwrap();
+
+        active.change_session("b", true).await.unwrap();
+
+        assert_eq!(active.current_session(), "b");
+        assert_eq!(active.last_session.as_deref(), Some("a"));
+        assert!(matches!(
+            active
+                .get_portal_ref()
+                .execute_potal_action(PortalAction::GetLastSession),
+            ConfigResult::OptionString(Some(last)) if last == "a"
+        ));
+        let stored = SessionClient::read_session("b").await.unwrap();
+        assert_eq!(stored.session.last_session.as_deref(), Some("a"));
+
+        // Reloading the active session keeps the previous one
+        active.change_session("b", true).await.unwrap();
+        assert_eq!(active.last_session.as_deref(), Some("a"));
+        let _ = std::fs::remove_dir_all(test_home());
+    }
+
+    #[tokio::test]
+    async fn deleting_active_session_switches_to_last_session() {
+        let _ = std::fs::remove_dir_all(test_home());
+        client("a").save_current_session().await.unwrap();
+        client("b").save_current_session().await.unwrap();
+        let mut active = SessionClient::load_session("a").await.unwrap();
+        active.change_session("b", true).await.unwrap();
+
+        active.delete_session("b").await.unwrap();
+
+        assert_eq!(active.current_session(), "a");
+        assert!(!SessionClient::session_dir("b").exists());
+        assert!(SessionClient::session_dir("a").exists());
+        let _ = std::fs::remove_dir_all(test_home());
+    }
+
+    #[tokio::test]
+    async fn deleting_active_default_session_needs_another_session() {
+        let _ = std::fs::remove_dir_all(test_home());
+        let mut active = client("default");
+        active.save_current_session().await.unwrap();
+
+        assert!(active.delete_session("default").await.is_err());
+        assert_eq!(active.current_session(), "default");
+        assert!(SessionClient::session_dir("default").exists());
+
+        client("other").save_current_session().await.unwrap();
+        active.delete_session("default").await.unwrap();
+        assert_eq!(active.current_session(), "other");
+        assert!(!SessionClient::session_dir("default").exists());
+        let _ = std::fs::remove_dir_all(test_home());
+    }
+}
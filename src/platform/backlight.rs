@@ -0,0 +1,87 @@
+//! Display backlight control through sysfs
+//!
+//! Backlight devices (e.g. the Raspberry Pi touchscreen's `rpi_backlight`)
+//! appear under `/sys/class/backlight/<device>/` with a `brightness` file and
+//! the matching `max_brightness`. Levels are handled as 0.0 to 1.0 and scaled
+//! to the device range when written.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info};
+
+const BACKLIGHT_CLASS: &str = "/sys/class/backlight";
+
+/// Errors while reading or writing the backlight
+#[derive(Debug, thiserror::Error)]
+pub enum BacklightError {
+    #[error("Backlight access failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid backlight value: {0}")]
+    InvalidValue(String),
+}
+
+/// A detected backlight device
+#[derive(Debug, Clone)]
+pub struct Backlight {
+    /// Device directory below [`BACKLIGHT_CLASS`]
+    path: PathBuf,
+    /// Raw value of full brightness
+    max_brightness: u32,
+}
+
+impl Backlight {
+    /// Finds the first backlight device with a usable `max_brightness`.
+    ///
+    /// Returns `None` on systems without a backlight or without sysfs.
+    pub fn detect() -> Option<Self> {
+        let devices = fs::read_dir(BACKLIGHT_CLASS).ok()?;
+
+        let backlight = devices.flatten().find_map(|device| {
+            let path = device.path();
+            let max_brightness = read_value(&path.join("max_brightness")).ok()?;
+            (max_brightness > 0).then_some(Backlight {
+                path,
+                max_brightness,
+            })
+        });
+
+        match &backlight {
+            Some(backlight) => info!(
+                "Found backlight {} (max brightness {})",
+                backlight.path.display(),
+                backlight.max_brightness
+            ),
+            None => debug!("No backlight device found"),
+        }
+        backlight
+    }
+
+    /// Reads the current level as 0.0 to 1.0.
+    pub fn level(&self) -> Result<f32, BacklightError> {
+        let raw = read_value(&self.path.join("brightness"))?;
+        Ok((raw as f32 / self.max_brightness as f32).clamp(0.0, 1.0))
+    }
+
+    /// Sets the level, scaling 0.0 to 1.0 onto the device range.
+    ///
+    /// The raw value never goes below 1, so the lowest level still leaves the
+    /// display readable instead of switching the backlight off.
+    pub fn set_level(&self, level: f32) -> Result<(), BacklightError> {
+        let raw = (level.clamp(0.0, 1.0) * self.max_brightness as f32).round() as u32;
+        let raw = raw.max(1);
+        fs::write(self.path.join("brightness"), raw.to_string())?;
+        debug!("Set backlight to {}/{}", raw, self.max_brightness);
+        Ok(())
+    }
+}
+
+/// Reads a numeric sysfs attribute
+fn read_value(path: &Path) -> Result<u32, BacklightError> {
+    let content = fs::read_to_string(path)?;
+    content
+        .trim()
+        .parse()
+        .map_err(|_| BacklightError::InvalidValue(content.trim().to_string()))
+}
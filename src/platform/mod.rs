@@ -0,0 +1,10 @@
+//! # Platform Integration
+//!
+//! Access to hardware of the device OpenController runs on, such as the
+//! display backlight of a Raspberry Pi touchscreen.
+//!
+//! Everything here is optional: on platforms without the hardware the
+//! detection returns `None` and the UI disables the related controls instead
+//! of reporting errors.
+
+pub mod backlight;
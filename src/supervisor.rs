@@ -0,0 +1,235 @@
+//! # Background Task Supervision
+//!
+//! Restarts long-running background tasks (MQTT handler, mapping manager)
+//! when they panic or fail, and reports their health to the UI.
+//!
+//! ## Why This Module Exists
+//!
+//! The tasks spawned in `main` used to be fire-and-forget. A panic in one of
+//! them left the application running without MQTT or without mapping, and
+//! nothing on screen said so. [`Supervisor::spawn`] runs every attempt of a
+//! task in its own tokio task, so a panic surfaces as a join error instead of
+//! taking anything else down, and starts the next attempt after a backoff.
+//!
+//! ## Design Rationale
+//!
+//! ### Restart Factory
+//! A task is given as a closure creating one attempt. Everything an attempt
+//! needs must be clonable or shared, since whatever the failed attempt owned
+//! is dropped with it. Channel receivers that must survive a restart are
+//! wrapped in a [`SharedReceiver`], long-lived state like the mapping manager
+//! sits behind an async mutex.
+//!
+//! ### Backoff
+//! Restarts wait [`INITIAL_BACKOFF`], doubling up to [`MAX_BACKOFF`], so a
+//! task failing right away does not spin. A run that lasted
+//! [`STABLE_RUN_TIME`] resets the backoff.
+//!
+//! ### Ending a Task
+//! An attempt returning `Ok(())` ends the task for good, e.g. the MQTT handler
+//! after its activation channel was closed on shutdown. Errors and panics
+//! always restart it.
+
+use std::any::Any;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use tokio::sync::mpsc::{self, error::TryRecvError};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+/// Wait before the first restart of a failed task.
+pub const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest wait between two restarts.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Run time after which a task counts as recovered and the backoff resets.
+pub const STABLE_RUN_TIME: Duration = Duration::from_secs(60);
+
+/// Lifecycle state of a supervised task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    Running,
+    /// Failed and waiting for the next attempt
+    Restarting,
+    /// Ended normally and will not be restarted
+    Finished,
+}
+
+impl std::fmt::Display for TaskState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TaskState::Running => "Running",
+            TaskState::Restarting => "Restarting",
+            TaskState::Finished => "Finished",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Health of one supervised task, as shown in the UI
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskHealth {
+    /// Display name, e.g. `MQTT`
+    pub name: &'static str,
+    pub state: TaskState,
+    /// Restarts since startup
+    pub restarts: u32,
+    /// Error or panic message of the last failure
+    pub last_error: Option<String>,
+}
+
+/// Spawns background tasks and restarts them when they fail
+#[derive(Debug, Clone)]
+pub struct Supervisor {
+    /// Health of all supervised tasks, in spawn order
+    health_tx: watch::Sender<Vec<TaskHealth>>,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self {
+            health_tx: watch::Sender::new(Vec::new()),
+        }
+    }
+}
+
+impl Supervisor {
+    /// Returns a receiver for the health of all supervised tasks
+    pub fn health_receiver(&self) -> watch::Receiver<Vec<TaskHealth>> {
+        self.health_tx.subscribe()
+    }
+
+    /// Runs attempts created by `task` until one returns `Ok(())`.
+    ///
+    /// Every attempt runs in its own tokio task. An error or panic is logged
+    /// and recorded in the task's health, then the next attempt starts after
+    /// the backoff described in the module documentation. The returned handle
+    /// completes once the task has finished.
+    pub fn spawn<F, Fut, E>(&self, name: &'static str, mut task: F) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display + Send + 'static,
+    {
+        self.health_tx.send_modify(|tasks| {
+            tasks.push(TaskHealth {
+                name,
+                state: TaskState::Running,
+                restarts: 0,
+                last_error: None,
+            })
+        });
+
+        let health_tx = self.health_tx.clone();
+        tokio::spawn(async move {
+            let mut backoff = INITIAL_BACKOFF;
+            loop {
+                let started = Instant::now();
+                let failure = match tokio::spawn(task()).await {
+                    Ok(Ok(())) => {
+                        info!("{} task finished", name);
+                        update(&health_tx, name, |health| {
+                            health.state = TaskState::Finished
+                        });
+                        return;
+                    }
+                    Ok(Err(e)) => e.to_string(),
+                    Err(e) if e.is_panic() => {
+                        format!("panicked: {}", panic_message(e.into_panic()))
+                    }
+                    Err(e) => {
+                        warn!("{} task was cancelled: {}", name, e);
+                        update(&health_tx, name, |health| {
+                            health.state = TaskState::Finished
+                        });
+                        return;
+                    }
+                };
+
+                if started.elapsed() >= STABLE_RUN_TIME {
+                    backoff = INITIAL_BACKOFF;
+                }
+                error!(
+                    "{} task failed, restarting in {:?}: {}",
+                    name, backoff, failure
+                );
+                update(&health_tx, name, |health| {
+                    health.state = TaskState::Restarting;
+                    health.restarts += 1;
+                    health.last_error = Some(failure);
+                });
+
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                update(&health_tx, name, |health| health.state = TaskState::Running);
+            }
+        })
+    }
+}
+
+/// Applies `change` to the health entry of the task `name`.
+fn update(
+    health_tx: &watch::Sender<Vec<TaskHealth>>,
+    name: &str,
+    change: impl FnOnce(&mut TaskHealth),
+) {
+    health_tx.send_modify(|tasks| {
+        if let Some(health) = tasks.iter_mut().find(|health| health.name == name) {
+            change(health);
+        }
+    });
+}
+
+/// Extracts the message of a panic payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Channel receiver shared across the attempts of a supervised task.
+///
+/// A plain receiver would be dropped with a panicking attempt, closing the
+/// channel for its senders. Clones of a `SharedReceiver` read from the same
+/// channel, so the next attempt continues where the last one stopped.
+#[derive(Debug)]
+pub struct SharedReceiver<T> {
+    inner: Arc<Mutex<mpsc::Receiver<T>>>,
+}
+
+impl<T> Clone for SharedReceiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> SharedReceiver<T> {
+    pub fn new(receiver: mpsc::Receiver<T>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(receiver)),
+        }
+    }
+
+    /// Like [`mpsc::Receiver::try_recv`].
+    ///
+    /// A lock poisoned by a panicking attempt is taken over, the channel
+    /// itself is unaffected by the panic.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .try_recv()
+    }
+}
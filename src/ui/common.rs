@@ -24,9 +24,15 @@
 //! to ensure consistent data representation across different UI components and
 //! backend integration points.
 //!
+//! ### Gamepad Selection
+//! [`GamepadSelect`] replaces egui's `ComboBox` for picking one value from a
+//! list. It is driven by the injected arrow, Enter and Escape keys, which a
+//! `ComboBox` handles poorly.
+//!
 //! ### Theme and Styling System
-//! The [`UiColors`] struct provides a centralized dark theme color palette,
-//! ensuring visual consistency and making theme changes manageable from a single location.
+//! The [`UiColors`] struct provides the color palette of the active theme,
+//! ensuring visual consistency. It follows the session's theme, so custom
+//! colors apply to all components at once.
 //!
 //! ## Design Rationale
 //!
@@ -34,7 +40,7 @@
 //! reconstructed each frame. The shared utilities here support this pattern by:
 //! - Providing stateless utility functions for consistent styling
 //! - Defining lightweight data structures that can be easily cloned
-//! - Using compile-time constants for colors to avoid runtime allocation
+//! - Keeping the active color palette in one cheap-to-copy struct
 //!
 //! ## Integration with ConfigPortal
 //!
@@ -42,9 +48,15 @@
 //! the application's ConfigPortal persistence system, supporting serialization
 //! and the session management architecture.
 
-use eframe::egui::{self, vec2, Color32, Frame, Stroke, Vec2};
+use crate::mapping::navigation::NavigationAction;
+use crate::mqtt::config::MqttConfig;
+use eframe::egui::{
+    self, vec2, Color32, EventFilter, Frame, Id, Key, Modal, Modifiers, PopupCloseBehavior,
+    Response, Stroke,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::RwLock;
 
 /// Represents the current active menu screen in the UI navigation system.
 ///
@@ -73,6 +85,31 @@ pub enum MenuState {
     Settings,
 }
 
+impl MenuState {
+    /// Menus in top panel order
+    pub const ALL: [MenuState; 4] = [
+        MenuState::Main,
+        MenuState::MQTT,
+        MenuState::ELRS,
+        MenuState::Settings,
+    ];
+
+    /// Menu shown after a navigation action, wrapping around at both ends
+    pub fn navigate(self, action: NavigationAction) -> MenuState {
+        let index = Self::ALL.iter().position(|menu| *menu == self).unwrap_or(0);
+        match action {
+            NavigationAction::NextMenu => Self::ALL[(index + 1) % Self::ALL.len()],
+            NavigationAction::PreviousMenu => {
+                Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+            }
+            NavigationAction::MainMenu => MenuState::Main,
+            NavigationAction::Mqtt => MenuState::MQTT,
+            NavigationAction::Elrs => MenuState::ELRS,
+            NavigationAction::Settings => MenuState::Settings,
+        }
+    }
+}
+
 /// Configuration for MQTT server connections used across UI and backend modules.
 ///
 /// This structure represents MQTT broker connection parameters and is shared
@@ -109,6 +146,35 @@ impl fmt::Display for MQTTServer {
     }
 }
 
+impl MQTTServer {
+    /// Checks whether both entries describe the same broker account.
+    ///
+    /// Servers are keyed on `(url, user)`. URLs are compared by their parsed
+    /// endpoint so `mqtt://broker` and `broker:1883` count as the same broker;
+    /// unparseable URLs fall back to a trimmed string comparison.
+    pub fn same_account(&self, other: &MQTTServer) -> bool {
+        self.user == other.user && self.endpoint_key() == other.endpoint_key()
+    }
+
+    /// Checks whether connecting to `other` would open the same connection.
+    ///
+    /// Compares the parsed endpoint and the credentials without surrounding
+    /// whitespace, which the connection handler strips before connecting.
+    /// The `connected` flag is UI state and ignored.
+    pub fn same_connection(&self, other: &MQTTServer) -> bool {
+        self.endpoint_key() == other.endpoint_key()
+            && self.user.trim() == other.user.trim()
+            && self.pw.trim() == other.pw.trim()
+    }
+
+    fn endpoint_key(&self) -> String {
+        match MqttConfig::parse_endpoint(&self.url) {
+            Ok((host, port)) => format!("{}:{}", host, port),
+            Err(_) => self.url.trim().to_string(),
+        }
+    }
+}
+
 /// WiFi network configuration for device connectivity settings.
 ///
 /// Used in the Settings menu to manage WiFi connections, particularly important
@@ -174,59 +240,377 @@ pub fn create_frame(ui: &mut egui::Ui, bg_color: Color32, border_color: Color32)
         .inner_margin(4)
         .outer_margin(2)
 }
-/// Centralized color palette for the OpenController dark theme.
+
+/// Shows a modal asking the user to confirm an operation.
+///
+/// Calls `on_confirm` or `on_cancel` with `state` in the frame the user
+/// decides; closing the modal with Escape or a click outside counts as
+/// cancel. The caller keeps showing the modal every frame until then.
+///
+/// # Parameters
+/// - `ctx`: egui context the modal is shown in
+/// - `id`: Unique id of this dialog
+/// - `message`: Question shown to the user
+/// - `confirm_label`: Text of the confirm button, e.g. "Delete"
+/// - `state`: Passed to whichever callback runs, usually the calling menu
+pub fn confirmation_modal<T>(
+    ctx: &egui::Context,
+    id: Id,
+    message: &str,
+    confirm_label: &str,
+    state: &mut T,
+    on_confirm: impl FnOnce(&mut T),
+    on_cancel: impl FnOnce(&mut T),
+) {
+    match choice_modal(ctx, id, message, &[confirm_label, "Cancel"]) {
+        Some(0) => on_confirm(state),
+        Some(_) => on_cancel(state),
+        None => {}
+    }
+}
+
+/// Shows a modal with one button per choice and returns the index picked.
+///
+/// The last choice is the safe one, e.g. "Cancel": closing the modal with
+/// Escape or a click outside picks it as well. Returns `None` while the user
+/// has not decided; the caller keeps showing the modal every frame until then.
+///
+/// ## Gamepad Use
+/// Focus is kept on the buttons and starts on the last one, so an accidental
+/// press never confirms. The arrow keys move between them and Enter or
+/// Space activates the focused one.
+pub fn choice_modal(ctx: &egui::Context, id: Id, message: &str, choices: &[&str]) -> Option<usize> {
+    let mut picked = None;
+    let modal = Modal::new(id).show(ctx, |ui| {
+        ui.set_width(250.0);
+        ui.label(message);
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            choices
+                .iter()
+                .enumerate()
+                .map(|(index, choice)| {
+                    let button = ui.button(*choice);
+                    if button.clicked() {
+                        picked = Some(index);
+                    }
+                    button
+                })
+                .collect::<Vec<_>>()
+        })
+        .inner
+    });
+
+    let should_close = modal.should_close();
+    let buttons = modal.inner;
+    let focused = ctx.memory(|memory| memory.focused());
+    if !buttons.iter().any(|button| Some(button.id) == focused) {
+        if let Some(safe) = buttons.last() {
+            safe.request_focus();
+        }
+    }
+
+    if picked.is_none() && should_close {
+        picked = choices.len().checked_sub(1);
+    }
+    picked
+}
+
+/// Dropdown for picking one value, operable with a gamepad.
+///
+/// Replaces egui's `ComboBox` where a value is picked from a list: the
+/// button opens the list, Up/Down move the highlight, Enter or Space picks
+/// the highlighted entry and Escape or Backspace closes the list unchanged.
+/// With the default keyboard mapping these are the D-pad, B or A, and X or
+/// the right stick. Mouse clicks work as before.
+///
+/// ## Focus
+/// The button keeps keyboard focus while the list is open and claims the
+/// arrow keys, so they move the highlight instead of the focus. After a pick
+/// or cancel the focus stays on the button, ready for the next widget.
+///
+/// ## Usage
+/// ```ignore
+/// let response = GamepadSelect::new("deadzone_mode")
+///     .selected_text(mode.to_string())
+///     .show(ui, &mut mode, DeadzoneMode::ALL.map(|m| (m, m.to_string())));
+/// if response.changed() { /* value was picked */ }
+/// ```
+pub struct GamepadSelect {
+    id_salt: Id,
+    selected_text: String,
+    width: Option<f32>,
+    max_height: f32,
+}
+
+/// Highlighted entry of an open [`GamepadSelect`], kept in egui memory
+#[derive(Clone, Copy, Debug, Default)]
+struct SelectHighlight(usize);
+
+impl GamepadSelect {
+    /// Creates a selector, `id_salt` must be unique within the parent UI.
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            selected_text: String::new(),
+            width: None,
+            max_height: 200.0,
+        }
+    }
+
+    /// Text on the button, usually the current value.
+    pub fn selected_text(mut self, text: impl Into<String>) -> Self {
+        self.selected_text = text.into();
+        self
+    }
+
+    /// Minimum width of the button and the list.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Height of the list before it scrolls.
+    pub fn max_height(mut self, height: f32) -> Self {
+        self.max_height = height;
+        self
+    }
+
+    /// Shows the button and, while open, the list of `options`.
+    ///
+    /// Picking an entry stores its value in `current`; the returned button
+    /// response is marked changed if that differs from the previous value,
+    /// like `selectable_value` in a `ComboBox`.
+    pub fn show<T: PartialEq + Clone>(
+        self,
+        ui: &mut egui::Ui,
+        current: &mut T,
+        options: impl IntoIterator<Item = (T, String)>,
+    ) -> Response {
+        let options: Vec<(T, String)> = options.into_iter().collect();
+        let popup_id = ui.make_persistent_id(self.id_salt);
+        let was_open = ui.memory(|memory| memory.is_popup_open(popup_id));
+        let mut highlight = ui
+            .data(|data| data.get_temp::<SelectHighlight>(popup_id))
+            .unwrap_or_default()
+            .0
+            .min(options.len().saturating_sub(1));
+
+        // Keys are consumed before the button is added, so Enter does not
+        // also count as a click on it
+        let mut picked = None;
+        let mut cancelled = false;
+        let mut moved = false;
+        if was_open {
+            ui.input_mut(|input| {
+                if input.consume_key(Modifiers::NONE, Key::ArrowUp) {
+                    highlight = highlight.saturating_sub(1);
+                    moved = true;
+                }
+                if input.consume_key(Modifiers::NONE, Key::ArrowDown) {
+                    highlight = (highlight + 1).min(options.len().saturating_sub(1));
+                    moved = true;
+                }
+                if input.consume_key(Modifiers::NONE, Key::Enter)
+                    || input.consume_key(Modifiers::NONE, Key::Space)
+                {
+                    picked = Some(highlight);
+                }
+                if input.consume_key(Modifiers::NONE, Key::Escape)
+                    || input.consume_key(Modifiers::NONE, Key::Backspace)
+                {
+                    cancelled = true;
+                }
+            });
+        }
+
+        let mut button = egui::Button::new(format!("{} \u{23F7}", self.selected_text));
+        if let Some(width) = self.width {
+            button = button.min_size(vec2(width, 0.0));
+        }
+        let mut response = ui.add(button);
+
+        if response.clicked() {
+            if was_open {
+                ui.memory_mut(|memory| memory.close_popup());
+            } else {
+                highlight = options
+                    .iter()
+                    .position(|(value, _)| value == current)
+                    .unwrap_or(0);
+                moved = true;
+                ui.memory_mut(|memory| memory.open_popup(popup_id));
+                response.request_focus();
+            }
+        }
+
+        let open = ui.memory(|memory| memory.is_popup_open(popup_id));
+        if open {
+            ui.memory_mut(|memory| {
+                memory.set_focus_lock_filter(
+                    response.id,
+                    EventFilter {
+                        tab: false,
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        escape: true,
+                    },
+                )
+            });
+            ui.painter().rect_stroke(
+                response.rect,
+                2.0,
+                Stroke::new(2.0, UiColors::active()),
+                egui::StrokeKind::Outside,
+            );
+        }
+
+        let max_height = self.max_height;
+        egui::popup_below_widget(
+            ui,
+            popup_id,
+            &response,
+            PopupCloseBehavior::CloseOnClickOutside,
+            |ui| {
+                if let Some(width) = self.width {
+                    ui.set_min_width(width);
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        for (index, (value, text)) in options.iter().enumerate() {
+                            let mut entry = ui.selectable_label(value == current, text);
+                            if index == highlight {
+                                entry = entry.highlight();
+                                ui.painter().rect_stroke(
+                                    entry.rect,
+                                    2.0,
+                                    Stroke::new(2.0, UiColors::active()),
+                                    egui::StrokeKind::Inside,
+                                );
+                                if moved {
+                                    entry.scroll_to_me(None);
+                                }
+                            }
+                            if entry.clicked() {
+                                picked = Some(index);
+                            }
+                        }
+                    });
+            },
+        );
+
+        if let Some((value, _)) = picked.and_then(|index| options.get(index)) {
+            if value != current {
+                *current = value.clone();
+                response.mark_changed();
+            }
+        }
+        if open && (picked.is_some() || cancelled) {
+            ui.memory_mut(|memory| memory.close_popup());
+            response.request_focus();
+        }
+        ui.data_mut(|data| data.insert_temp(popup_id, SelectHighlight(highlight)));
+
+        response
+    }
+}
+
+/// Colors shared by all custom-drawn UI components.
 ///
-/// Provides compile-time color constants for consistent theming across
-/// all UI components. The dark theme is optimized for low-light usage
+/// Set from the session's [`Theme`](crate::persistence::Theme) by
+/// [`Theme::apply`](crate::persistence::Theme::apply); [`UiPalette::DEFAULT`]
+/// is the built-in dark theme used until a theme is applied.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiPalette {
+    pub main_bg: Color32,
+    pub inner_bg: Color32,
+    pub extreme_bg: Color32,
+    pub border: Color32,
+    pub active: Color32,
+    pub inactive: Color32,
+}
+
+impl UiPalette {
+    /// Built-in dark theme
+    pub const DEFAULT: UiPalette = UiPalette {
+        main_bg: Color32::from_rgb(30, 30, 30),
+        inner_bg: Color32::from_rgb(25, 25, 25),
+        extreme_bg: Color32::from_rgb(20, 20, 20),
+        border: Color32::from_rgb(60, 60, 60),
+        active: Color32::from_rgb(50, 200, 20),
+        inactive: Color32::from_rgb(200, 50, 20),
+    };
+}
+
+/// Palette read by [`UiColors`], replaced whenever a theme is applied
+static ACTIVE_PALETTE: RwLock<UiPalette> = RwLock::new(UiPalette::DEFAULT);
+
+/// Centralized color palette for the OpenController UI.
+///
+/// Provides the colors of the active theme for consistent theming across
+/// all UI components. The default dark theme is optimized for low-light usage
 /// scenarios common in workshop and control environments.
 ///
 /// ## Design Rationale
-/// Uses associated constants rather than a color struct to avoid runtime
-/// allocation and enable compile-time optimizations. The color values are
-/// carefully chosen to provide sufficient contrast while being comfortable
-/// for extended use.
-///
-/// ## Future Migration Path
-/// **Note**: This static color system will likely be replaced by the configurable
-/// \[`Theme`\] system from the ConfigPortal in future versions. The existing
-/// \[`Theme`\] struct in the persistence layer already defines a more flexible
-/// color system with:
-/// - Serializable color definitions (RGB tuples)
-/// - Multiple background color levels
-/// - Configurable highlight and frame colors
-/// - Integration with the session management system
-///
-/// The migration would allow users to customize themes through the UI while
-/// maintaining the current dark theme as the default configuration.
+/// Components draw many frames by hand rather than through egui's visuals, so
+/// they read their colors here instead of from the egui style. The palette is
+/// a small `Copy` struct behind a lock that is only written on theme changes,
+/// which keeps per-frame lookups cheap.
 ///
 /// ## Color Hierarchy
 /// Colors are organized from darkest to lightest background colors, with
 /// semantic colors for status indication:
-/// - **Background Colors**: EXTREME_BG → INNER_BG → MAIN_BG (darkest to lightest)
-/// - **Status Colors**: ACTIVE (green) for connected/enabled states, INACTIVE (red) for disconnected/disabled states
-/// - **Structural Colors**: BORDER for component separation
-///
-/// ## Known Limitations
-/// Currently hardcoded and cannot be changed without recompilation. This
-/// limitation will be addressed when migrating to the configurable Theme system.
+/// - **Background Colors**: extreme_bg → inner_bg → main_bg (darkest to lightest)
+/// - **Status Colors**: active (green) for connected/enabled states, inactive (red) for disconnected/disabled states
+/// - **Structural Colors**: border for component separation
 pub struct UiColors;
 
 impl UiColors {
-    /// Primary background color for main content areas (RGB: 30, 30, 30)
-    pub const MAIN_BG: Color32 = Color32::from_rgb(30, 30, 30);
+    /// Replaces the active palette, used by [`Theme::apply`](crate::persistence::Theme::apply)
+    pub fn set_palette(palette: UiPalette) {
+        match ACTIVE_PALETTE.write() {
+            Ok(mut guard) => *guard = palette,
+            Err(poisoned) => *poisoned.into_inner() = palette,
+        }
+    }
 
-    /// Secondary background color for nested components (RGB: 25, 25, 25)  
-    pub const INNER_BG: Color32 = Color32::from_rgb(25, 25, 25);
+    /// Returns the active palette
+    pub fn palette() -> UiPalette {
+        match ACTIVE_PALETTE.read() {
+            Ok(guard) => *guard,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
 
-    /// Deepest background color for emphasized content areas (RGB: 20, 20, 20)
-    pub const EXTREME_BG: Color32 = Color32::from_rgb(20, 20, 20);
+    /// Primary background color for main content areas
+    pub fn main_bg() -> Color32 {
+        Self::palette().main_bg
+    }
 
-    /// Border color for component separation (RGB: 60, 60, 60)
-    pub const BORDER: Color32 = Color32::from_rgb(60, 60, 60);
+    /// Secondary background color for nested components
+    pub fn inner_bg() -> Color32 {
+        Self::palette().inner_bg
+    }
 
-    /// Active/connected status indicator color (RGB: 50, 200, 20) - Green
-    pub const ACTIVE: Color32 = Color32::from_rgb(50, 200, 20);
+    /// Deepest background color for emphasized content areas
+    pub fn extreme_bg() -> Color32 {
+        Self::palette().extreme_bg
+    }
 
-    /// Inactive/disconnected status indicator color (RGB: 200, 50, 20) - Red  
-    pub const INACTIVE: Color32 = Color32::from_rgb(200, 50, 20);
+    /// Border color for component separation
+    pub fn border() -> Color32 {
+        Self::palette().border
+    }
+
+    /// Active/connected status indicator color, green by default
+    pub fn active() -> Color32 {
+        Self::palette().active
+    }
+
+    /// Inactive/disconnected status indicator color, red by default
+    pub fn inactive() -> Color32 {
+        Self::palette().inactive
+    }
 }
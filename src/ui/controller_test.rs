@@ -0,0 +1,218 @@
+//! # Controller Test
+//!
+//! Live view of everything the controller subsystem reports, for checking a
+//! gamepad before relying on a mapping.
+//!
+//! ## Why This Module Exists
+//!
+//! New users cannot tell whether their pad is detected, which physical button
+//! is which [`ButtonType`], or whether the deadzone swallows small stick
+//! movements. The test panel shows every button with its keyboard assignment,
+//! both sticks with the section the keyboard mapping would see, and both
+//! triggers, all updating as the pad is used.
+//!
+//! ## Design Rationale
+//!
+//! ### Read-Only Tap
+//! The panel reads the latest [`ControllerOutput`] published by the mapping
+//! manager, the same tap the keyboard overlay uses. It never consumes input,
+//! and it sees navigation buttons before the manager takes them out.
+//!
+//! ### Short Presses
+//! The UI samples the tap once per frame, so a tap shorter than a frame could
+//! be missed. A button is lit while the latest output reports it held, and
+//! for [`PRESS_DISPLAY_TIME`] after any output last reported it.
+//!
+//! ### Sections
+//! Sections are detected with the region tuning currently set in the keyboard
+//! settings, including hysteresis, so slider changes show up immediately.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, Color32, Frame, ProgressBar, RichText, Ui, Vec2};
+use tokio::sync::watch;
+
+use super::common::UiColors;
+use crate::controller::controller_handle::{
+    ButtonEventState, ButtonType, ControllerOutput, ControllerStatus,
+};
+use crate::mapping::keyboard::{ButtonAssignment, RegionSet, Section};
+
+/// How long a reported button stays lit after it was last seen.
+const PRESS_DISPLAY_TIME: Duration = Duration::from_millis(200);
+
+/// Live state of the connected gamepad.
+pub struct ControllerTest {
+    /// Latest controller input from the mapping manager
+    state_rx: watch::Receiver<Option<ControllerOutput>>,
+
+    /// Connection status reported by the collector
+    status_rx: watch::Receiver<ControllerStatus>,
+
+    /// When each button was last reported
+    last_pressed: HashMap<ButtonType, Instant>,
+
+    /// When each chord was last reported, by name
+    last_chords: HashMap<String, Instant>,
+
+    /// Sections detected in the previous frame (left, right)
+    last_sections: (Section, Section),
+}
+
+impl ControllerTest {
+    pub fn new(
+        state_rx: watch::Receiver<Option<ControllerOutput>>,
+        status_rx: watch::Receiver<ControllerStatus>,
+    ) -> Self {
+        Self {
+            state_rx,
+            status_rx,
+            last_pressed: HashMap::new(),
+            last_chords: HashMap::new(),
+            last_sections: (Section::Center, Section::Center),
+        }
+    }
+
+    /// Renders the connection state, sticks, triggers and buttons.
+    ///
+    /// `regions` is the current keyboard region tuning, `assignments` the
+    /// keyboard assignment shown next to each button.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        regions: &RegionSet,
+        assignments: &[(ButtonType, ButtonAssignment)],
+    ) {
+        if *self.status_rx.borrow() == ControllerStatus::Disconnected {
+            ui.label(
+                RichText::new("No controller detected")
+                    .strong()
+                    .color(UiColors::inactive()),
+            );
+            return;
+        }
+
+        let Some(state) = self.latest_state() else {
+            ui.label("Waiting for controller input, move a stick or press a button");
+            return;
+        };
+
+        ui.label(format!("Gamepad {}", state.gamepad_id));
+        self.render_sticks(ui, &state, regions);
+        render_triggers(ui, &state);
+        self.render_buttons(ui, &state, assignments);
+    }
+
+    /// Returns the latest input, recording which buttons and chords it reports.
+    fn latest_state(&mut self) -> Option<ControllerOutput> {
+        let changed = self.state_rx.has_changed().unwrap_or(false);
+        let state = self.state_rx.borrow_and_update().clone()?;
+        if changed {
+            let now = Instant::now();
+            for event in &state.button_events {
+                self.last_pressed.insert(event.button.clone(), now);
+            }
+            for chord in &state.chord_events {
+                self.last_chords.insert(chord.name.clone(), now);
+            }
+        }
+        Some(state)
+    }
+
+    fn render_sticks(&mut self, ui: &mut Ui, state: &ControllerOutput, regions: &RegionSet) {
+        let (prev_left, prev_right) = self.last_sections;
+        let left = regions
+            .region_from_pos(state.left_stick.x, state.left_stick.y, Some(prev_left))
+            .section;
+        let right = regions
+            .region_from_pos(state.right_stick.x, state.right_stick.y, Some(prev_right))
+            .section;
+        self.last_sections = (left, right);
+
+        egui::Grid::new("controller_test_sticks")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["Stick", "X", "Y", "Section"] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for (name, stick, section) in [
+                    ("Left", &state.left_stick, left),
+                    ("Right", &state.right_stick, right),
+                ] {
+                    ui.label(name);
+                    ui.label(format!("{:+.2}", stick.x));
+                    ui.label(format!("{:+.2}", stick.y));
+                    ui.label(format!("{:?}", section));
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Draws every button, lit while pressed, plus the active chords.
+    fn render_buttons(
+        &self,
+        ui: &mut Ui,
+        state: &ControllerOutput,
+        assignments: &[(ButtonType, ButtonAssignment)],
+    ) {
+        let lit = |last: Option<&Instant>| last.is_some_and(|at| at.elapsed() < PRESS_DISPLAY_TIME);
+
+        ui.horizontal_wrapped(|ui| {
+            for button in ButtonType::ALL {
+                let assignment = assignments
+                    .iter()
+                    .find(|(assigned, _)| *assigned == button)
+                    .map(|(_, assignment)| *assignment)
+                    .unwrap_or(ButtonAssignment::Unmapped);
+                let held = state
+                    .button_events
+                    .iter()
+                    .any(|event| event.button == button && event.state == ButtonEventState::Held);
+                let pressed = held || lit(self.last_pressed.get(&button));
+                let (fill, text_color) = if pressed {
+                    (UiColors::active(), Color32::BLACK)
+                } else {
+                    (UiColors::inner_bg(), Color32::LIGHT_GRAY)
+                };
+
+                Frame::new().fill(fill).inner_margin(4.0).show(ui, |ui| {
+                    ui.set_min_size(Vec2::new(90.0, 20.0));
+                    ui.label(
+                        RichText::new(format!("{:?}: {}", button, assignment)).color(text_color),
+                    );
+                });
+            }
+        });
+
+        let mut chords: Vec<&str> = self
+            .last_chords
+            .iter()
+            .filter(|(_, last)| lit(Some(last)))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        chords.sort_unstable();
+        if !chords.is_empty() {
+            ui.label(format!("Chords: {}", chords.join(", ")));
+        }
+    }
+}
+
+fn render_triggers(ui: &mut Ui, state: &ControllerOutput) {
+    for (name, trigger) in [
+        ("Left trigger", &state.left_trigger),
+        ("Right trigger", &state.right_trigger),
+    ] {
+        ui.horizontal(|ui| {
+            ui.label(name);
+            ui.add(
+                ProgressBar::new(trigger.value.clamp(0.0, 1.0))
+                    .desired_width(150.0)
+                    .text(format!("{:.2}", trigger.value)),
+            );
+        });
+    }
+}
@@ -15,9 +15,9 @@
 //!
 //! ## Current Implementation Status
 //!
-//! **⚠️ IMPORTANT**: This is currently a **dummy UI implementation** that only provides
-//! the visual layout and structure. No actual ELRS communication or telemetry processing
-//! is implemented yet.
+//! **⚠️ IMPORTANT**: Port discovery and the serial link to the transmitter are
+//! implemented through [`ELRSTransmitter`]. Battery, link statistics and GPS
+//! telemetry are decoded on the link thread and shown in the telemetry panel.
 //!
 //! ## Key Abstractions
 //!
@@ -39,68 +39,381 @@
 //! ## Future Development Path
 //!
 //! Planned implementations include:
-//! - Real telemetry data parsing and display
+//! - Telemetry history and graphs
 //! - Transmitter connection management
-//! - RC channel monitoring and configuration
+//! - RC channel monitoring
 //! - Safety features for RC control
 //! - Integration with the controller mapping system
 
-use eframe::egui::{self, Color32, ComboBox, Frame, Layout, Stroke, Ui, Vec2};
+use std::sync::Arc;
 
-use super::common::UiColors;
+use eframe::egui::{self, DragValue, Frame, Grid, Layout, RichText, Stroke, Ui};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use super::common::{GamepadSelect, UiColors};
+use crate::mapping::elrs::{
+    ELRSChannel, ELRSConfig, ELRSOutput, ELRSTransmitter, InputAxis, TelemetryState,
+    CHANNEL_US_LIMITS, CRSF_DEFAULT_BAUD, DEFAULT_PACKET_RATE_HZ,
+};
+use crate::mapping::{MappingConfig, MappingType};
+use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
+
+/// Maximum number of serial errors kept for display in the telemetry panel.
+const MAX_SERIAL_ERRORS: usize = 20;
+
+/// Largest trim offered by the channel editor, in microseconds.
+const MAX_TRIM_US: i16 = 200;
 
 /// Main data structure for the ELRS menu interface.
 ///
 /// This structure manages the state and configuration for ExpressLRS RC vehicle
-/// control interface. Currently serves as a placeholder for future ELRS integration.
+/// control interface, including the serial link to the transmitter module.
 ///
 /// ## Design Rationale
-/// Structured to anticipate future integration with the ELRS backend mapping engine.
-/// Fields are designed to mirror the actual data that will be provided by the
-/// ELRS communication layer and ConfigPortal persistence.
+/// Owns the [`ELRSTransmitter`] directly so the link lifetime matches the
+/// "Live Connect" toggle. Serial errors are collected instead of propagated
+/// so a flaky or unplugged module never takes down the UI.
 ///
-/// ## Current Limitations
-/// All data is currently mock/dummy data. Real implementation will require:
-/// - Integration with CRSF protocol handlers
-/// - Connection to the controller mapping system
-/// - Telemetry data structure definitions
-/// - Safety and error handling for RC control
+/// ## Channel Flow
+/// Channel maps from the ELRS mapping engine arrive on `elrs_rx` and are
+/// handed to the transmitter link, which encodes and streams them as CRSF
+/// frames at `packet_rate_hz`. The arm state reported with each map drives
+/// the ARMED/DISARMED banner; the strategy already substitutes failsafe
+/// values while disarmed.
+///
+/// ## Channel Mapping
+/// The channel editor works on a copy of the stored [`ELRSConfig`]. Each
+/// settled change is validated, stored through the ConfigPortal and the
+/// running ELRS engine is rebuilt, which disarms it. Rejected changes are
+/// reported and the editor returns to the stored mapping.
 ///
 /// ## Future Extension Points
-/// - Telemetry data structures (RSSI, voltage, GPS, etc.)
-/// - RC channel configuration and monitoring
+/// - RC channel monitoring
 /// - Transmitter profiles and device management
 /// - Safety features (failsafe, range checking)
 #[derive(Default)]
 pub struct ELRSMenuData {
-    /// Current transmitter port identifier (placeholder)
+    /// Name of the port the transmitter is connected on
     transmitter_port: String,
 
     /// Connection status with ELRS transmitter
     transmitter_connection: bool,
 
-    /// Currently selected connection from available options
+    /// Currently selected port from available options
     connection: String,
 
-    /// List of available ELRS connections/devices
+    /// Serial ports found by the last scan
     available_connections: Vec<String>,
 
     /// Live connection status for real-time control
     live_connect: bool,
+
+    /// Baud rate used when opening the transmitter port
+    baud_rate: u32,
+
+    /// RC frame rate used when opening the transmitter port (Hz)
+    packet_rate_hz: u16,
+
+    /// Channel maps produced by the ELRS mapping engine
+    elrs_rx: Option<mpsc::Receiver<ELRSOutput>>,
+
+    /// Arm state reported with the last channel map
+    armed: bool,
+
+    /// Open serial link to the transmitter, if any
+    transmitter: Option<ELRSTransmitter>,
+
+    /// Total bytes received from the transmitter on the current link
+    bytes_received: usize,
+
+    /// Latest telemetry decoded on the current link
+    telemetry: TelemetryState,
+
+    /// Recent serial errors surfaced in the telemetry panel
+    serial_errors: Vec<String>,
+
+    /// Session storage of the ELRS mapping config
+    config_portal: Arc<ConfigPortal>,
+
+    /// Requests a rebuild of the ELRS engine after a mapping change
+    mapping_reload_tx: Option<mpsc::Sender<MappingType>>,
+
+    /// Mapping shown in the channel editor
+    channel_config: ELRSConfig,
+
+    /// Reason the last channel mapping change was rejected
+    channel_status: Option<String>,
 }
 
 impl ELRSMenuData {
-    /// Creates mock data for UI development and testing.
+    /// Creates the ELRS menu with no transmitter connected.
+    ///
+    /// Performs an initial port scan so the connection list is populated
+    /// on first display. Uses the CRSF default baud and packet rate.
+    ///
+    /// # Parameters
+    /// - `elrs_rx`: Channel maps from the ELRS mapping engine
+    /// - `config_portal`: Storage of the ELRS mapping config
+    /// - `mapping_reload_tx`: Rebuilds the ELRS engine after mapping changes
+    pub fn new(
+        elrs_rx: mpsc::Receiver<ELRSOutput>,
+        config_portal: Arc<ConfigPortal>,
+        mapping_reload_tx: mpsc::Sender<MappingType>,
+    ) -> Self {
+        let mut data = Self {
+            baud_rate: CRSF_DEFAULT_BAUD,
+            packet_rate_hz: DEFAULT_PACKET_RATE_HZ,
+            elrs_rx: Some(elrs_rx),
+            channel_config: load_elrs_config(&config_portal),
+            config_portal,
+            mapping_reload_tx: Some(mapping_reload_tx),
+            ..Default::default()
+        };
+        data.scan_ports();
+        data
+    }
+
+    /// Refreshes the list of available serial ports.
+    ///
+    /// Keeps the current selection if it is still present, otherwise selects
+    /// the first port found.
+    fn scan_ports(&mut self) {
+        match ELRSTransmitter::available_ports() {
+            Ok(ports) => {
+                if !ports.contains(&self.connection) {
+                    self.connection = ports.first().cloned().unwrap_or_default();
+                }
+                self.available_connections = ports;
+            }
+            Err(e) => self.push_error(e.to_string()),
+        }
+    }
+
+    /// Opens or closes the transmitter link depending on the current state.
+    fn toggle_live_connect(&mut self) {
+        if self.transmitter.take().is_some() {
+            self.live_connect = false;
+            self.transmitter_connection = false;
+            return;
+        }
+
+        if self.connection.is_empty() {
+            self.push_error("No serial port selected".to_string());
+            return;
+        }
+
+        match ELRSTransmitter::open(&self.connection, self.baud_rate, self.packet_rate_hz) {
+            Ok(transmitter) => {
+                self.transmitter_port = transmitter.port_name().to_string();
+                self.transmitter = Some(transmitter);
+                self.transmitter_connection = true;
+                self.live_connect = true;
+                self.bytes_received = 0;
+                self.telemetry = TelemetryState::default();
+            }
+            Err(e) => self.push_error(e.to_string()),
+        }
+    }
+
+    /// Forwards the latest mapped channels and drains telemetry from the link.
+    ///
+    /// A read error closes the link, since it usually means the module was
+    /// unplugged.
+    fn poll_transmitter(&mut self) {
+        // Only the most recent channel map matters, older ones are superseded
+        let mut latest = None;
+        if let Some(rx) = self.elrs_rx.as_mut() {
+            while let Ok(output) = rx.try_recv() {
+                latest = Some(output);
+            }
+        }
+
+        if let Some(output) = &latest {
+            self.armed = output.armed;
+        }
+
+        let Some(transmitter) = self.transmitter.as_mut() else {
+            return;
+        };
+
+        if let Some(output) = latest {
+            transmitter.update_channels(output.channels);
+        }
+
+        match transmitter.poll() {
+            Ok(update) => {
+                self.bytes_received += update.bytes_received;
+                for frame in update.telemetry {
+                    self.telemetry.apply(frame);
+                }
+            }
+            Err(e) => {
+                self.transmitter = None;
+                self.transmitter_connection = false;
+                self.live_connect = false;
+                self.push_error(e.to_string());
+            }
+        }
+    }
+
+    /// Records a serial error for display, dropping the oldest beyond the limit.
+    fn push_error(&mut self, error: String) {
+        warn!("ELRS serial: {}", error);
+        self.serial_errors.push(error);
+        if self.serial_errors.len() > MAX_SERIAL_ERRORS {
+            self.serial_errors.remove(0);
+        }
+    }
+
+    /// Renders the decoded telemetry values as a two-column table.
     ///
-    /// Provides placeholder data that represents the structure of real ELRS
-    /// data without requiring actual hardware connections.
-    pub fn mock_data() -> Self {
-        ELRSMenuData {
-            transmitter_port: "Port Test 1".to_string(),
-            transmitter_connection: true,
-            connection: "TestCon".to_string(),
-            available_connections: vec!["Test1".to_string(), "Test2".to_string()],
-            live_connect: false,
+    /// Values that have not been reported on this link yet show "-".
+    fn render_telemetry_table(&self, ui: &mut Ui) {
+        let telemetry = &self.telemetry;
+        let missing = || "-".to_string();
+
+        Grid::new("elrs_telemetry")
+            .num_columns(2)
+            .spacing([24.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Voltage");
+                ui.label(
+                    telemetry
+                        .battery
+                        .map_or_else(missing, |b| format!("{:.1} V", b.voltage)),
+                );
+                ui.end_row();
+
+                ui.label("Current");
+                ui.label(
+                    telemetry
+                        .battery
+                        .map_or_else(missing, |b| format!("{:.1} A", b.current)),
+                );
+                ui.end_row();
+
+                ui.label("RSSI");
+                ui.label(
+                    telemetry
+                        .link
+                        .map_or_else(missing, |l| format!("{} dBm", l.uplink_rssi_dbm)),
+                );
+                ui.end_row();
+
+                ui.label("Link Quality");
+                ui.label(
+                    telemetry
+                        .link
+                        .map_or_else(missing, |l| format!("{} %", l.uplink_link_quality)),
+                );
+                ui.end_row();
+
+                ui.label("GPS");
+                ui.label(telemetry.gps.map_or_else(missing, |g| {
+                    format!(
+                        "{:.6}, {:.6} ({} sats, {} m)",
+                        g.latitude, g.longitude, g.satellites, g.altitude_m
+                    )
+                }));
+                ui.end_row();
+            });
+    }
+
+    /// Renders the axis-to-channel editor with range, trim and reverse.
+    ///
+    /// Only mapped axes are listed; sticks are mapped as X/Y pairs in the
+    /// config and cannot gain a single axis here.
+    fn render_channel_mapping(&mut self, ui: &mut Ui) {
+        let mut settled = false;
+
+        Grid::new("elrs_channels")
+            .num_columns(6)
+            .spacing([12.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for heading in ["Axis", "Channel", "Min", "Max", "Trim", "Reverse"] {
+                    ui.strong(heading);
+                }
+                ui.end_row();
+
+                for axis in InputAxis::ALL {
+                    let Some(current) = self.channel_config.axis_channel(axis) else {
+                        continue;
+                    };
+                    ui.label(axis.to_string());
+
+                    let mut channel = current;
+                    GamepadSelect::new(("elrs_axis_channel", axis.to_string()))
+                        .selected_text(format!("{:?}", channel))
+                        .show(
+                            ui,
+                            &mut channel,
+                            ELRSChannel::ALL.map(|option| (option, format!("{:?}", option))),
+                        );
+                    if channel != current {
+                        self.channel_config.set_axis_channel(axis, channel);
+                        settled = true;
+                    }
+
+                    let mut range = self.channel_config.channel_range(channel);
+                    let responses = [
+                        ui.add(DragValue::new(&mut range.min).range(CHANNEL_US_LIMITS)),
+                        ui.add(DragValue::new(&mut range.max).range(CHANNEL_US_LIMITS)),
+                        ui.add(DragValue::new(&mut range.trim).range(-MAX_TRIM_US..=MAX_TRIM_US)),
+                    ];
+                    if range != self.channel_config.channel_range(channel) {
+                        self.channel_config.channel_ranges.insert(channel, range);
+                    }
+                    settled |= responses
+                        .iter()
+                        .any(|r| r.drag_stopped() || (r.changed() && !r.dragged()));
+
+                    let mut inverted = self.channel_config.is_inverted(channel);
+                    if ui.checkbox(&mut inverted, "").changed() {
+                        self.channel_config.set_inverted(channel, inverted);
+                        settled = true;
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if settled {
+            self.store_channel_config();
+        }
+        if let Some(status) = &self.channel_status {
+            ui.colored_label(UiColors::inactive(), status);
+        }
+    }
+
+    /// Stores the edited channel mapping and rebuilds the ELRS engine.
+    ///
+    /// An invalid mapping is not written; the editor returns to the stored one.
+    fn store_channel_config(&mut self) {
+        if let Err(e) = self.channel_config.validate() {
+            warn!("Rejected ELRS channel mapping: {}", e);
+            self.channel_status = Some(e.to_string());
+            self.channel_config = load_elrs_config(&self.config_portal);
+            return;
+        }
+        if let ConfigResult::Failed(e) =
+            self.config_portal
+                .execute_potal_action(PortalAction::WriteElrsConfig(Box::new(
+                    self.channel_config.clone(),
+                )))
+        {
+            error!("Failed to store ELRS channel mapping: {}", e);
+            self.channel_status = Some("Failed to store the channel mapping".to_string());
+            return;
+        }
+
+        info!("Stored ELRS channel mapping");
+        self.channel_status = None;
+        if let Some(reload_tx) = &self.mapping_reload_tx {
+            if let Err(e) = reload_tx.try_send(MappingType::ELRS) {
+                error!("Failed to request ELRS mapping reload: {}", e);
+            }
         }
     }
 
@@ -117,11 +430,9 @@ impl ELRSMenuData {
     ///
     /// ## Current Implementation
     ///
-    /// This is a **visual prototype only**. The layout and styling are finalized,
-    /// but all functionality placeholders need implementation:
-    /// - Connection scanning and device discovery
-    /// - Real telemetry data parsing and display
-    /// - Live connection management with safety features
+    /// Port scanning and live connection management are functional. The
+    /// telemetry panel shows decoded vehicle telemetry, link statistics and
+    /// serial errors.
     ///
     /// ## Performance Considerations
     ///
@@ -130,19 +441,34 @@ impl ELRSMenuData {
     /// - Using consistent styling from UiColors to leverage egui's caching
     /// - Structuring UI hierarchy to minimize unnecessary redraws
     pub fn render(&mut self, ui: &mut Ui) {
+        self.poll_transmitter();
+
         // Header section with connection status
         ui.horizontal(|ui| {
             ui.heading("ELRS");
             if self.transmitter_connection {
                 ui.label("Transmitter Connected");
-                ui.label("Port Test"); // TODO: Function to read actual port
+                ui.label(&self.transmitter_port);
             } else {
                 ui.label("No Transmitter found");
             }
+
+            // Arm state banner, red while the vehicle reacts to input
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.armed {
+                    ui.label(RichText::new("ARMED").heading().color(UiColors::inactive()));
+                } else {
+                    ui.label(
+                        RichText::new("DISARMED")
+                            .heading()
+                            .color(UiColors::active()),
+                    );
+                }
+            });
         });
 
         let available_size = ui.available_size();
-        let border_color = UiColors::BORDER;
+        let border_color = UiColors::border();
         let background_color = ui.visuals().extreme_bg_color;
 
         // Layout calculations for responsive design
@@ -169,9 +495,30 @@ impl ELRSMenuData {
                             ui.set_min_width(left_width);
                             ui.set_min_height(panel_height - 30.0); // Height minus heading
 
-                            // TODO: Replace with real telemetry data display
-                            // Future: RSSI graphs, voltage monitoring, GPS data, etc.
-                            ui.label("Hier kommt Telemetrie");
+                            self.render_telemetry_table(ui);
+                            ui.add_space(8.0);
+
+                            if let Some(transmitter) = &self.transmitter {
+                                ui.label(format!(
+                                    "Link: {} @ {} baud, {} Hz",
+                                    transmitter.port_name(),
+                                    transmitter.baud_rate(),
+                                    transmitter.packet_rate_hz()
+                                ));
+                                ui.label(format!("Bytes received: {}", self.bytes_received));
+                            } else {
+                                ui.label("No live link");
+                            }
+
+                            // Serial errors are shown here instead of panicking
+                            for error in &self.serial_errors {
+                                ui.colored_label(UiColors::inactive(), error);
+                            }
+
+                            ui.add_space(8.0);
+                            egui::CollapsingHeader::new("Channel mapping")
+                                .id_salt("elrs_channel_mapping")
+                                .show(ui, |ui| self.render_channel_mapping(ui));
                         });
                 });
             });
@@ -183,7 +530,7 @@ impl ELRSMenuData {
                 // Connection management controls
                 Frame::new()
                     .stroke(Stroke::new(1.0, border_color))
-                    .fill(UiColors::INNER_BG)
+                    .fill(UiColors::inner_bg())
                     .corner_radius(2)
                     .inner_margin(6.0)
                     .outer_margin(0.0)
@@ -193,22 +540,35 @@ impl ELRSMenuData {
                             // Device scanning and selection
                             ui.horizontal(|ui| {
                                 if ui.button("Scan").clicked() {
-                                    // TODO: Implement device discovery
-                                    // Future: Scan for available ELRS transmitters
+                                    self.scan_ports();
                                 }
 
-                                ComboBox::from_id_salt("Connections")
+                                GamepadSelect::new("Connections")
                                     .selected_text(&self.connection)
                                     .width(right_width - 70.0)
-                                    .show_ui(ui, |ui| {
-                                        for con in &mut self.available_connections {
-                                            ui.selectable_value(
-                                                &mut self.connection,
-                                                con.to_string(),
-                                                con.to_string(),
-                                            );
-                                        }
-                                    });
+                                    .show(
+                                        ui,
+                                        &mut self.connection,
+                                        self.available_connections
+                                            .iter()
+                                            .map(|con| (con.to_string(), con.to_string())),
+                                    );
+                            });
+
+                            ui.add_space(4.0);
+
+                            // Link parameters, only editable while disconnected
+                            ui.horizontal(|ui| {
+                                ui.label("Baud");
+                                ui.add_enabled(
+                                    self.transmitter.is_none(),
+                                    DragValue::new(&mut self.baud_rate).range(9600..=5_250_000),
+                                );
+                                ui.label("Rate (Hz)");
+                                ui.add_enabled(
+                                    self.transmitter.is_none(),
+                                    DragValue::new(&mut self.packet_rate_hz).range(1..=1000),
+                                );
                             });
 
                             ui.add_space(4.0);
@@ -216,9 +576,7 @@ impl ELRSMenuData {
                             // Live connection toggle
                             ui.horizontal(|ui| {
                                 if ui.button("Live Connect").clicked() {
-                                    // TODO: Implement live connection management
-                                    // Future: Establish real-time CRSF communication
-                                    self.live_connect = !self.live_connect;
+                                    self.toggle_live_connect();
                                 }
 
                                 let status = if self.live_connect {
@@ -235,3 +593,13 @@ impl ELRSMenuData {
         });
     }
 }
+
+/// Loads the stored ELRS config, showing the default for sessions without one.
+///
+/// Matches the mapping manager, which falls back to the same default.
+fn load_elrs_config(config_portal: &ConfigPortal) -> ELRSConfig {
+    match config_portal.execute_potal_action(PortalAction::GetElrsConfig) {
+        ConfigResult::ElrsConfig(config) if !config.joystick_mapping.is_empty() => *config,
+        _ => ELRSConfig::default_config(),
+    }
+}
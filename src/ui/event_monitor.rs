@@ -0,0 +1,165 @@
+//! # Mapping Event Monitor
+//!
+//! Live view of the events the mapping engines inject into the UI.
+//!
+//! ## Why This Module Exists
+//!
+//! Tuning a keyboard layout is hard when the only way to see its output is a
+//! focused text field or the debug log. The monitor records every event that
+//! reaches the UI together with the stick sections detected at that moment,
+//! so a layout can be checked combination by combination.
+//!
+//! ## Design Rationale
+//!
+//! ### Passive Tap
+//! Events are recorded where they enter egui's input, after they have been
+//! received from the mapping engine. The monitor never consumes or alters
+//! them, so input handling behaves the same with the monitor open or closed.
+//!
+//! ### Bounded History
+//! Only the last [`EVENT_MONITOR_LIMIT`] entries are kept. Pausing stops
+//! recording without affecting the events themselves.
+
+use std::collections::VecDeque;
+
+use chrono::{Local, NaiveTime};
+use eframe::egui::{self, Event, ModifierNames, ScrollArea, Ui};
+
+use crate::mapping::keyboard::Section;
+
+/// Number of events kept in the monitor.
+pub const EVENT_MONITOR_LIMIT: usize = 50;
+
+/// One recorded event.
+#[derive(Debug, Clone)]
+pub struct MonitorEntry {
+    /// Local time the event reached the UI
+    pub time: NaiveTime,
+    /// Event kind, e.g. "Key down" or "Text"
+    pub kind: String,
+    /// Key name, empty for events without a key
+    pub key: String,
+    /// Held modifiers, empty if none
+    pub modifiers: String,
+    /// Typed text, empty for events without text
+    pub text: String,
+    /// Left and right stick sections when the event arrived
+    pub sections: (Section, Section),
+}
+
+impl MonitorEntry {
+    /// Describes an egui event for display.
+    fn new(event: &Event, sections: (Section, Section)) -> Self {
+        let (kind, key, modifiers, text) = match event {
+            Event::Key {
+                key,
+                pressed,
+                repeat,
+                modifiers,
+                ..
+            } => {
+                let kind = match (pressed, repeat) {
+                    (true, true) => "Key repeat",
+                    (true, false) => "Key down",
+                    (false, _) => "Key up",
+                };
+                (
+                    kind.to_string(),
+                    key.name().to_string(),
+                    ModifierNames::NAMES.format(modifiers, false),
+                    String::new(),
+                )
+            }
+            Event::Text(text) => (
+                "Text".to_string(),
+                String::new(),
+                String::new(),
+                text.clone(),
+            ),
+            other => (
+                format!("{:?}", other),
+                String::new(),
+                String::new(),
+                String::new(),
+            ),
+        };
+
+        Self {
+            time: Local::now().time(),
+            kind,
+            key,
+            modifiers,
+            text,
+            sections,
+        }
+    }
+}
+
+/// Recorder and view for mapped events.
+#[derive(Debug, Default)]
+pub struct EventMonitor {
+    /// Recorded events, oldest first
+    entries: VecDeque<MonitorEntry>,
+    /// Whether recording is paused
+    paused: bool,
+}
+
+impl EventMonitor {
+    /// Records a batch of events that arrived with the given stick sections.
+    ///
+    /// Does nothing while paused.
+    pub fn record(&mut self, events: &[Event], sections: (Section, Section)) {
+        if self.paused {
+            return;
+        }
+        for event in events {
+            self.entries.push_back(MonitorEntry::new(event, sections));
+        }
+        while self.entries.len() > EVENT_MONITOR_LIMIT {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Renders pause/clear controls and the recorded events, newest first.
+    pub fn render(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let pause_text = if self.paused { "Resume" } else { "Pause" };
+            if ui.button(pause_text).clicked() {
+                self.paused = !self.paused;
+            }
+            if ui.button("Clear").clicked() {
+                self.entries.clear();
+            }
+            ui.label(format!(
+                "{} / {} events",
+                self.entries.len(),
+                EVENT_MONITOR_LIMIT
+            ));
+        });
+
+        ScrollArea::vertical()
+            .id_salt("event_monitor_scroll")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                egui::Grid::new("event_monitor_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["Time", "Event", "Key", "Modifiers", "Text", "Sticks"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for entry in self.entries.iter().rev() {
+                            ui.label(entry.time.format("%H:%M:%S%.3f").to_string());
+                            ui.label(&entry.kind);
+                            ui.label(&entry.key);
+                            ui.label(&entry.modifiers);
+                            ui.label(&entry.text);
+                            ui.label(format!("{:?} / {:?}", entry.sections.0, entry.sections.1));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
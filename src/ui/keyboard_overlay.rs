@@ -0,0 +1,232 @@
+//! # On-Screen Keyboard Overlay
+//!
+//! Visual feedback for the dual-joystick typing scheme of the keyboard mapping.
+//!
+//! ## Why This Module Exists
+//!
+//! The keyboard mapping spreads the alphabet over combinations of two 9-region
+//! joysticks. Without feedback, learning which combination produces which letter
+//! means trial and error. The overlay shows both joysticks as 3x3 grids, highlights
+//! the region each stick is currently in and previews the letter the current
+//! combination would type.
+//!
+//! ## Design Rationale
+//!
+//! ### Read-Only Mirror of the Mapping
+//! The overlay does not take part in input processing. It samples the latest
+//! controller state published by the mapping manager and runs the same region
+//! detection (including hysteresis) as the keyboard strategy, so what is shown
+//! matches what will be typed.
+//!
+//! ### Grid Labels
+//! Each cell of one grid shows the letter it would produce together with the
+//! other stick's current region. Moving one stick therefore relabels the other
+//! grid, which makes the two-step combinations easy to follow.
+//!
+//! ### Configuration Refresh
+//! The keyboard config is re-read from the ConfigPortal periodically, so layout
+//! switches and session changes show up without extra plumbing.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, Align2, Color32, Context, Frame, RichText, Stroke, Ui, Vec2};
+use tokio::sync::watch;
+
+use super::common::UiColors;
+use crate::controller::controller_handle::ControllerOutput;
+use crate::mapping::keyboard::{KeyboardConfig, RegionSet, Section};
+use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
+
+/// How often the keyboard config is re-read while the overlay is shown.
+const CONFIG_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Grid cell order, row by row as the regions appear on the stick.
+const GRID: [[Section; 3]; 3] = [
+    [Section::NorthWest, Section::North, Section::NorthEast],
+    [Section::West, Section::Center, Section::East],
+    [Section::SouthWest, Section::South, Section::SouthEast],
+];
+
+/// Side length of one grid cell in points.
+const CELL_SIZE: f32 = 28.0;
+
+/// State for the keyboard overlay.
+///
+/// Keeps its own copy of the keyboard config and the last detected sections
+/// for hysteresis, mirroring the keyboard strategy.
+pub struct KeyboardOverlay {
+    /// Latest controller input from the mapping manager
+    state_rx: watch::Receiver<Option<ControllerOutput>>,
+
+    /// Source of the active keyboard config
+    config_portal: Arc<ConfigPortal>,
+
+    /// Keyboard config used for letter lookup
+    config: KeyboardConfig,
+
+    /// Regions built from `config`, as the keyboard strategy does
+    regions: RegionSet,
+
+    /// When `config` was last loaded, `None` before the first load
+    config_loaded: Option<Instant>,
+
+    /// Sections detected in the previous frame (left, right)
+    last_sections: (Section, Section),
+}
+
+impl KeyboardOverlay {
+    /// Creates the overlay; the config is loaded on first render.
+    pub fn new(
+        state_rx: watch::Receiver<Option<ControllerOutput>>,
+        config_portal: Arc<ConfigPortal>,
+    ) -> Self {
+        Self {
+            state_rx,
+            config_portal,
+            config: KeyboardConfig::default_config(),
+            regions: RegionSet::default(),
+            config_loaded: None,
+            last_sections: (Section::Center, Section::Center),
+        }
+    }
+
+    /// Draws the overlay in the bottom-right corner of the screen.
+    ///
+    /// The overlay is non-interactable so it never blocks the menus below it.
+    pub fn render(&mut self, ctx: &Context) {
+        let (left, right) = self.current_sections();
+        let symbol_mode = self
+            .state_rx
+            .borrow()
+            .as_ref()
+            .is_some_and(|state| self.config.is_symbol_mode(state));
+        let current = self.config.label_for(left, right, symbol_mode);
+
+        egui::Area::new(egui::Id::new("keyboard_overlay"))
+            .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-10.0, -40.0))
+            .order(egui::Order::Foreground)
+            .interactable(false)
+            .show(ctx, |ui| {
+                Frame::new()
+                    .stroke(Stroke::new(1.0, UiColors::border()))
+                    .fill(UiColors::extreme_bg())
+                    .inner_margin(8.0)
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            self.render_grid(ui, "Left", left, |cell| {
+                                self.config.label_for(cell, right, symbol_mode)
+                            });
+                            ui.add_space(10.0);
+                            self.render_grid(ui, "Right", right, |cell| {
+                                self.config.label_for(left, cell, symbol_mode)
+                            });
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(if symbol_mode { "Symbols:" } else { "Letter:" });
+                            ui.label(
+                                RichText::new(current.unwrap_or("-"))
+                                    .heading()
+                                    .color(UiColors::active()),
+                            );
+                        });
+                    });
+            });
+    }
+
+    /// Returns the sections both sticks are currently in (left, right).
+    ///
+    /// Uses the same hysteresis as the keyboard strategy and is `Center` for
+    /// both sticks before the first controller state arrives.
+    pub fn current_sections(&mut self) -> (Section, Section) {
+        self.refresh_config();
+        let state = self.state_rx.borrow().clone();
+        match &state {
+            Some(state) => self.detect_sections(state),
+            None => (Section::Center, Section::Center),
+        }
+    }
+
+    /// Draws one stick as a 3x3 grid, highlighting the active section.
+    ///
+    /// `label` returns the text for a cell given the other stick's position.
+    fn render_grid<'a>(
+        &'a self,
+        ui: &mut Ui,
+        title: &str,
+        active: Section,
+        label: impl Fn(Section) -> Option<&'a str>,
+    ) {
+        ui.vertical(|ui| {
+            ui.label(title);
+            egui::Grid::new(("keyboard_overlay_grid", title))
+                .spacing(Vec2::splat(2.0))
+                .show(ui, |ui| {
+                    for row in GRID {
+                        for section in row {
+                            let fill = if section == active {
+                                UiColors::active()
+                            } else {
+                                UiColors::inner_bg()
+                            };
+                            let text_color = if section == active {
+                                Color32::BLACK
+                            } else {
+                                Color32::LIGHT_GRAY
+                            };
+                            Frame::new().fill(fill).show(ui, |ui| {
+                                ui.set_min_size(Vec2::splat(CELL_SIZE));
+                                ui.centered_and_justified(|ui| {
+                                    ui.label(
+                                        RichText::new(label(section).unwrap_or(""))
+                                            .color(text_color)
+                                            .strong(),
+                                    );
+                                });
+                            });
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    /// Runs region detection with the same hysteresis as the keyboard strategy.
+    fn detect_sections(&mut self, state: &ControllerOutput) -> (Section, Section) {
+        let (prev_left, prev_right) = self.last_sections;
+        let left = self
+            .regions
+            .region_from_pos(state.left_stick.x, state.left_stick.y, Some(prev_left))
+            .section;
+        let right = self
+            .regions
+            .region_from_pos(state.right_stick.x, state.right_stick.y, Some(prev_right))
+            .section;
+        self.last_sections = (left, right);
+        (left, right)
+    }
+
+    /// Re-reads the keyboard config when the refresh interval has passed.
+    fn refresh_config(&mut self) {
+        let due = self
+            .config_loaded
+            .is_none_or(|loaded| loaded.elapsed() >= CONFIG_REFRESH_INTERVAL);
+        if !due {
+            return;
+        }
+
+        if let ConfigResult::KeyboardConfig(config) = self
+            .config_portal
+            .execute_potal_action(PortalAction::GetKeyboardConfig)
+        {
+            // Empty configs are replaced by the default in the mapping manager too
+            self.config = if config.button_mapping.is_empty() {
+                KeyboardConfig::default_config()
+            } else {
+                config
+            };
+            self.regions = self.config.regions();
+        }
+        self.config_loaded = Some(Instant::now());
+    }
+}
@@ -0,0 +1,316 @@
+//! # In-UI Log Viewer
+//!
+//! Recent log records, captured from `tracing` and shown in the settings
+//! diagnostics.
+//!
+//! ## Why This Module Exists
+//!
+//! OpenController often runs on a Raspberry Pi without a terminal attached,
+//! where stdout is out of reach. The same records that go to stdout are
+//! kept in memory so they can be read on the device itself.
+//!
+//! ## Design Rationale
+//!
+//! ### Capture Layer
+//! [`LogCapture`] is a `tracing_subscriber` layer installed next to the
+//! formatting layer in `setup_logging_env`. Records go into the static
+//! [`LOG_BUFFER`], which lives as long as the subscriber and needs no wiring
+//! through the UI.
+//!
+//! ### Own Level Filter
+//! Stdout and the log file stay at INFO, while the capture layer has its own
+//! reloadable filter. Picking DEBUG or TRACE in the viewer raises only the
+//! capture level, so the extra records show up on the device without
+//! flooding the terminal. Records below the previous level are not captured
+//! retroactively.
+//!
+//! ### Bounded History
+//! Only the last [`LOG_BUFFER_LIMIT`] records are kept. The viewer copies
+//! them only when new ones arrived, and pausing freezes its copy while
+//! capturing continues. Level filtering is also applied when rendering, so
+//! lowering it takes effect immediately.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{Local, NaiveTime};
+use eframe::egui::{self, ComboBox, RichText, ScrollArea, Ui, Visuals};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::Filtered;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::{reload, Registry};
+
+/// Number of log records kept in memory.
+pub const LOG_BUFFER_LIMIT: usize = 500;
+
+/// Levels offered by the viewer's filter, least verbose first.
+const FILTER_LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
+/// Records captured by [`LogCapture`].
+pub static LOG_BUFFER: LogBuffer = LogBuffer::new();
+
+/// Handle to the capture layer's level filter, set by [`LogCapture::layer`].
+static CAPTURE_FILTER: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+/// One captured log record.
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    /// Local time the record was emitted
+    pub time: NaiveTime,
+    pub level: Level,
+    /// Module path the record came from
+    pub target: String,
+    /// Message followed by any further fields as `name=value`
+    pub message: String,
+}
+
+/// Bounded store of the most recent log records.
+#[derive(Debug)]
+pub struct LogBuffer {
+    /// Records, oldest first
+    records: Mutex<VecDeque<LogRecord>>,
+    /// Number of records pushed or cleared so far, to detect changes
+    generation: AtomicU64,
+}
+
+impl LogBuffer {
+    const fn new() -> Self {
+        Self {
+            records: Mutex::new(VecDeque::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Appends a record, dropping the oldest beyond [`LOG_BUFFER_LIMIT`].
+    fn push(&self, record: LogRecord) {
+        if let Ok(mut records) = self.records.lock() {
+            records.push_back(record);
+            while records.len() > LOG_BUFFER_LIMIT {
+                records.pop_front();
+            }
+            self.generation.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Removes all records.
+    pub fn clear(&self) {
+        if let Ok(mut records) = self.records.lock() {
+            records.clear();
+            self.generation.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Changes whenever records are added or cleared.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Copies the current records, oldest first.
+    pub fn snapshot(&self) -> Vec<LogRecord> {
+        self.records
+            .lock()
+            .map(|records| records.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// `tracing` layer feeding [`LOG_BUFFER`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogCapture;
+
+impl LogCapture {
+    /// Capture layer behind its own reloadable filter, starting at INFO.
+    ///
+    /// Must be the first layer added to the registry.
+    pub fn layer() -> Filtered<Self, reload::Layer<LevelFilter, Registry>, Registry> {
+        let (filter, handle) = reload::Layer::new(LevelFilter::INFO);
+        let _ = CAPTURE_FILTER.set(handle);
+        Self.with_filter(filter)
+    }
+}
+
+/// Sets the most verbose level captured into [`LOG_BUFFER`].
+fn set_capture_level(level: Level) {
+    let Some(handle) = CAPTURE_FILTER.get() else {
+        return;
+    };
+    if let Err(e) = handle.reload(LevelFilter::from_level(level)) {
+        tracing::warn!("Failed to change the log viewer level: {}", e);
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogCapture {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        LOG_BUFFER.push(LogRecord {
+            time: Local::now().time(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message + &visitor.fields,
+        });
+    }
+}
+
+/// Collects the message and remaining fields of an event.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// View of the captured log with level filter and pause/clear controls.
+#[derive(Debug)]
+pub struct LogViewer {
+    /// Records shown, refreshed from [`LOG_BUFFER`] unless paused
+    records: Vec<LogRecord>,
+    /// Buffer generation `records` was copied at
+    generation: Option<u64>,
+    /// Most verbose level shown
+    max_level: Level,
+    /// Whether `records` is frozen
+    paused: bool,
+}
+
+impl Default for LogViewer {
+    fn default() -> Self {
+        Self {
+            records: Vec::new(),
+            generation: None,
+            max_level: Level::INFO,
+            paused: false,
+        }
+    }
+}
+
+impl LogViewer {
+    /// Renders the controls and the matching records, newest first.
+    pub fn render(&mut self, ui: &mut Ui) {
+        let generation = LOG_BUFFER.generation();
+        if !self.paused && self.generation != Some(generation) {
+            self.records = LOG_BUFFER.snapshot();
+            self.generation = Some(generation);
+        }
+
+        ui.horizontal(|ui| {
+            let previous_level = self.max_level;
+            ComboBox::from_id_salt("log_viewer_level")
+                .selected_text(self.max_level.as_str())
+                .show_ui(ui, |ui| {
+                    for level in FILTER_LEVELS {
+                        ui.selectable_value(&mut self.max_level, level, level.as_str());
+                    }
+                });
+            if self.max_level != previous_level {
+                set_capture_level(self.max_level);
+            }
+
+            let pause_text = if self.paused { "Resume" } else { "Pause" };
+            if ui.button(pause_text).clicked() {
+                self.paused = !self.paused;
+            }
+            if ui.button("Clear").clicked() {
+                LOG_BUFFER.clear();
+                self.records.clear();
+                self.generation = None;
+            }
+            ui.label(format!(
+                "{} / {} records",
+                self.records.len(),
+                LOG_BUFFER_LIMIT
+            ));
+        });
+
+        ScrollArea::vertical()
+            .id_salt("log_viewer_scroll")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("log_viewer_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["Time", "Level", "Target", "Message"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for record in self
+                            .records
+                            .iter()
+                            .rev()
+                            .filter(|record| record.level <= self.max_level)
+                        {
+                            ui.label(record.time.format("%H:%M:%S%.3f").to_string());
+                            ui.label(level_text(ui.visuals(), record.level));
+                            ui.label(&record.target);
+                            ui.label(&record.message);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+/// Level label, colored for warnings and errors.
+fn level_text(visuals: &Visuals, level: Level) -> RichText {
+    let text = RichText::new(level.as_str());
+    match level {
+        Level::ERROR => text.color(visuals.error_fg_color),
+        Level::WARN => text.color(visuals.warn_fg_color),
+        _ => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn capture_level_is_raised_independently() {
+        let subscriber = tracing_subscriber::registry()
+            .with(LogCapture::layer())
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_test_writer()
+                    .with_filter(LevelFilter::INFO),
+            );
+
+        tracing::subscriber::with_default(subscriber, || {
+            LOG_BUFFER.clear();
+            tracing::debug!("hidden");
+            tracing::info!("shown");
+            set_capture_level(Level::DEBUG);
+            tracing::debug!("now shown");
+        });
+
+        let messages: Vec<_> = LOG_BUFFER
+            .snapshot()
+            .into_iter()
+            .map(|record| record.message)
+            .collect();
+        assert_eq!(messages, ["shown", "now shown"]);
+    }
+}
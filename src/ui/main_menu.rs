@@ -30,7 +30,8 @@
 //!
 //! This module serves as the primary integration point between:
 //! - **ConfigPortal**: Direct access to current session configuration
-//! - **PersistenceManager**: Async session operations (create, load, delete)
+//! - **PersistenceManager**: Async session operations (create, load, rename,
+//!   duplicate, delete)
 //! - **Session Autosave System**: Background persistence protection
 //! - **Thread Architecture**: Non-blocking communication with storage layer
 //!
@@ -46,22 +47,68 @@
 //! ### List-Based Session Browser
 //! The scrollable session list design mirrors other parts of the UI (like message logs)
 //! to maintain visual consistency while providing efficient navigation through
-//! potentially many saved configurations.
+//! potentially many saved configurations. Each entry can be renamed inline,
+//! duplicated, exported, or deleted after a confirmation.
+//!
+//! ### Confirmations
+//! On a gamepad a wrong button press is easy. Operations that lose data ask
+//! first, through the shared confirmation modal: deleting a session, saving
+//! or renaming over an existing one. Switching away from unsaved changes
+//! offers to save or discard them.
+//!
+//! ### Session Backups
+//! The persistence system keeps the last versions of each session before a
+//! save overwrites it. The backups of a session are listed in a modal, and
+//! restoring one asks for confirmation like other operations that replace
+//! data.
+//!
+//! ### Session Transfer
+//! Sessions are exported into single `.ocsession` archives and imported from
+//! them, for moving a setup between devices. Both run in the persistence
+//! worker; their results are polled each frame, so a failed import is shown
+//! to the user even if it finishes after the click.
 
 use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
 use crate::persistence::persistence_worker::SessionAction;
 use crate::persistence::session_client::SessionClient;
 use crate::persistence::SessionConfig;
-use eframe::egui::{self, vec2, Color32, Frame, Label, ScrollArea, Stroke, TextEdit, Ui};
+use eframe::egui::{self, vec2, Frame, Id, Label, Modal, ScrollArea, Stroke, TextEdit, Ui};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{ops::Deref, str::FromStr};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
-use super::common::{SessionData, UiColors};
+use super::common::{choice_modal, confirmation_modal, UiColors};
 use crate::session_action;
 
+/// Session operation waiting for the user's confirmation
+#[derive(Debug, Clone)]
+enum SessionConfirmation {
+    /// Delete the session
+    Delete(String),
+    /// Save the current state over another existing session
+    Overwrite(String),
+    /// Rename `name` to `new_name`, replacing the session stored there
+    RenameOver { name: String, new_name: String },
+    /// Switch to the session although the current one has unsaved changes
+    Switch(String),
+    /// Replace the files of session `name` by its backup `backup`
+    RestoreBackup { name: String, backup: String },
+}
+
+/// Backups of one session shown in the backup modal
+#[derive(Debug)]
+struct BackupBrowser {
+    /// Session whose backups are listed
+    session: String,
+    /// Backup names, newest first, `None` while they are being listed
+    backups: Option<Vec<String>>,
+    /// Running listing of the backups
+    pending: Option<oneshot::Receiver<color_eyre::Result<Vec<String>>>>,
+}
+
 /// Main data structure for the session management interface.
 ///
 /// This structure manages the state and operations for OpenController's session
@@ -106,6 +153,30 @@ pub struct MainMenuData {
 
     /// List of available sessions for navigation
     available_sessions: Vec<String>,
+
+    /// Session being renamed and the edited name
+    renaming: Option<(String, String)>,
+
+    /// Operation waiting for confirmation in a modal
+    pending_confirmation: Option<SessionConfirmation>,
+
+    /// Archive file to import, or directory/file to export to
+    archive_path: String,
+
+    /// Running export, resolves to the written archive
+    pending_export: Option<oneshot::Receiver<color_eyre::Result<PathBuf>>>,
+
+    /// Running import, resolves to the imported session's name
+    pending_import: Option<oneshot::Receiver<color_eyre::Result<String>>>,
+
+    /// Running backup restore, resolves once the files are replaced
+    pending_restore: Option<oneshot::Receiver<color_eyre::Result<()>>>,
+
+    /// Backups listed in the backup modal, if it is open
+    backup_browser: Option<BackupBrowser>,
+
+    /// Result of the last finished export, import or restore
+    transfer_status: Option<String>,
 }
 
 impl MainMenuData {
@@ -150,6 +221,14 @@ impl MainMenuData {
                 .into_iter()
                 .collect(),
             session_load_error: None,
+            renaming: None,
+            pending_confirmation: None,
+            archive_path: String::new(),
+            pending_export: None,
+            pending_import: None,
+            pending_restore: None,
+            backup_browser: None,
+            transfer_status: None,
         }
     }
 
@@ -183,7 +262,8 @@ impl MainMenuData {
     /// - Clones session list only when necessary to minimize allocations
     pub fn render(&mut self, ui: &mut Ui) {
         let available_size = ui.available_size();
-        let border_color = UiColors::BORDER;
+        let border_color = UiColors::border();
+        self.poll_transfers();
 
         ui.vertical(|ui| {
             // Header section: current session info and creation controls
@@ -197,17 +277,48 @@ impl MainMenuData {
                     self.create_session();
                 }
                 if ui.button("Load").clicked() {
-                    self.list_sessions();
+                    self.refresh_sessions();
+                }
+            });
+
+            // Session transfer: archive path plus import
+            ui.horizontal(|ui| {
+                ui.label("Archive:");
+                ui.add(
+                    TextEdit::singleline(&mut self.archive_path)
+                        .hint_text("path to .ocsession file or export folder"),
+                );
+                let busy = self.pending_import.is_some() || self.pending_export.is_some();
+                if ui.add_enabled(!busy, egui::Button::new("Import")).clicked() {
+                    self.import_session();
+                }
+                if let Some(status) = &self.transfer_status {
+                    ui.label(status);
                 }
             });
 
+            // Validation errors are shown as modal, like the MQTT server dialog
+            if let Some(error) = self.session_load_error.clone() {
+                let mut confirmed = false;
+                let response = Modal::new(Id::new("SessionValidationErr")).show(ui.ctx(), |pop| {
+                    pop.set_width(200.0);
+                    pop.label(error);
+                    confirmed = pop.button("OK").clicked();
+                });
+                if confirmed || response.should_close() {
+                    self.session_load_error = None;
+                }
+            }
+            self.backup_prompt(ui.ctx());
+            self.confirmation_prompt(ui.ctx());
+
             // Session browser: scrollable list of available sessions
             Frame::new()
                 .fill(ui.visuals().extreme_bg_color)
                 .inner_margin(6)
                 .stroke(Stroke::new(1.0, ui.visuals().widgets.active.bg_fill))
                 .show(ui, |ui| {
-                    let list_height = available_size.y - 40.0; // Height minus header
+                    let list_height = available_size.y - 70.0; // Height minus header rows
                     ui.set_min_size(vec2(available_size.x, list_height));
 
                     ScrollArea::vertical().show(ui, |ui| {
@@ -217,20 +328,10 @@ impl MainMenuData {
                                     .stroke(Stroke::new(1.0, border_color))
                                     .inner_margin(2)
                                     .outer_margin(2)
-                                    .fill(UiColors::EXTREME_BG)
+                                    .fill(UiColors::extreme_bg())
                                     .show(ui, |ui| {
-                                        if ui
-                                            .add_sized(
-                                                vec2(available_size.x - 20.0, list_height / 6.0),
-                                                Label::new(format!("Session: {}", session))
-                                                    .selectable(true)
-                                                    .sense(egui::Sense::click()),
-                                            )
-                                            .clicked()
-                                        {
-                                            debug!("Loading Session");
-                                            self.change_session(session.clone());
-                                        }
+                                        ui.set_min_height(list_height / 6.0);
+                                        self.render_session_row(ui, &session);
                                     });
                             }
 
@@ -244,37 +345,385 @@ impl MainMenuData {
         });
     }
 
+    /// Renders one session entry with load, rename, duplicate and delete controls.
+    ///
+    /// Clicking the name loads the session. Rename turns the name into a text
+    /// field, delete asks for confirmation first.
+    fn render_session_row(&mut self, ui: &mut Ui, session: &str) {
+        ui.horizontal_centered(|ui| {
+            if let Some((renamed, new_name)) = &mut self.renaming {
+                if renamed == session {
+                    ui.add(TextEdit::singleline(new_name));
+                    let new_name = new_name.clone();
+                    if ui.button("OK").clicked() {
+                        self.rename_session(session.to_string(), new_name);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.renaming = None;
+                    }
+                    return;
+                }
+            }
+
+            let marker = if session == self.current_session_name {
+                " (active)"
+            } else {
+                ""
+            };
+            if ui
+                .add(
+                    Label::new(format!("Session: {}{}", session, marker))
+                        .selectable(true)
+                        .sense(egui::Sense::click()),
+                )
+                .clicked()
+            {
+                debug!("Loading Session");
+                self.request_session_change(session.to_string());
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Delete").clicked() {
+                    self.pending_confirmation =
+                        Some(SessionConfirmation::Delete(session.to_string()));
+                }
+                if ui
+                    .add_enabled(self.pending_export.is_none(), egui::Button::new("Export"))
+                    .clicked()
+                {
+                    self.export_session(session.to_string());
+                }
+                if ui.button("Duplicate").clicked() {
+                    self.duplicate_session(session.to_string());
+                }
+                if ui
+                    .add_enabled(self.pending_restore.is_none(), egui::Button::new("Backups"))
+                    .clicked()
+                {
+                    self.list_backups(session.to_string());
+                }
+                if ui.button("Rename").clicked() {
+                    self.renaming = Some((session.to_string(), session.to_string()));
+                }
+            });
+        });
+    }
+
     /// Creates a new session with the specified name.
     ///
     /// Validates input and initiates async session creation through the
-    /// persistence system. Updates the session list upon completion.
+    /// persistence system. The new session is listed right away and the input
+    /// field is cleared.
     ///
     /// ## Error Handling
-    /// - Validates session name is not empty
-    /// - Sets local error state for immediate user feedback
+    /// - Rejects empty names
+    /// - Asks for confirmation before saving over another existing session
+    /// - Shows validation errors in a modal for immediate user feedback
     /// - Logs async operation failures without blocking UI
     ///
     /// ## Async Behavior
     /// Uses the session_action! macro to send creation requests to the
     /// persistence manager, ensuring non-blocking operation.
     fn create_session(&mut self) {
-        let session_name = self.new_session_name.clone();
+        let session_name = self.new_session_name.trim().to_string();
 
-        if session_name.is_empty() {
-            self.session_load_error = Some("Session name cannot be empty".to_string());
+        if let Err(e) = validate_session_name(&session_name) {
+            self.session_load_error = Some(e);
+            return;
+        }
+        if session_name != self.current_session_name
+            && self.available_sessions.contains(&session_name)
+        {
+            self.pending_confirmation = Some(SessionConfirmation::Overwrite(session_name));
             return;
         }
 
-        let result = session_action!(@create, self.session_sender, session_name);
-        self.list_sessions();
+        self.save_session_as(session_name);
+    }
+
+    /// Saves the current state under `session_name`, replacing what is stored there.
+    fn save_session_as(&mut self, session_name: String) {
+        if let Err(e) = session_action!(@create, self.session_sender, session_name) {
+            debug!("Session creation not confirmed yet: {}", e);
+        }
+        info!("Saved session {}", session_name);
+        if !self.available_sessions.contains(&session_name) {
+            self.available_sessions.push(session_name);
+        }
+        self.new_session_name.clear();
+        self.refresh_sessions();
+    }
+
+    /// Shows the modal of the operation waiting for confirmation, if any.
+    fn confirmation_prompt(&mut self, ctx: &egui::Context) {
+        let Some(pending) = self.pending_confirmation.clone() else {
+            return;
+        };
+
+        if let SessionConfirmation::Switch(name) = &pending {
+            self.unsaved_changes_prompt(ctx, name.clone());
+            return;
+        }
+
+        let (message, confirm_label) = match &pending {
+            SessionConfirmation::Delete(name) => (
+                format!("Delete session {}? This cannot be undone.", name),
+                "Delete",
+            ),
+            SessionConfirmation::Overwrite(name) => (
+                format!(
+                    "Session {} already exists. Replace it with the current state?",
+                    name
+                ),
+                "Replace",
+            ),
+            SessionConfirmation::RenameOver { name, new_name } => (
+                format!(
+                    "Session {} already exists. Replace it with {}?",
+                    new_name, name
+                ),
+                "Replace",
+            ),
+            SessionConfirmation::RestoreBackup { name, backup } => {
+                let mut message = format!(
+                    "Restore session {} from the backup of {}? The current files are kept as a backup.",
+                    name, backup
+                );
+                if *name == self.current_session_name && self.has_unsaved_changes() {
+                    message.push_str(" Unsaved changes are discarded.");
+                }
+                (message, "Restore")
+            }
+            SessionConfirmation::Switch(_) => return,
+        };
+
+        confirmation_modal(
+            ctx,
+            Id::new("SessionConfirmation"),
+            &message,
+            confirm_label,
+            self,
+            |menu| {
+                menu.pending_confirmation = None;
+                match pending {
+                    SessionConfirmation::Delete(name) => menu.delet_session(name),
+                    SessionConfirmation::Overwrite(name) => menu.save_session_as(name),
+                    SessionConfirmation::RenameOver { name, new_name } => {
+                        menu.replace_session(name, new_name)
+                    }
+                    SessionConfirmation::RestoreBackup { name, backup } => {
+                        menu.restore_backup(name, backup)
+                    }
+                    SessionConfirmation::Switch(name) => menu.change_session(name, true),
+                }
+            },
+            |menu| menu.pending_confirmation = None,
+        );
+    }
+
+    /// Asks whether unsaved changes are saved or discarded before switching.
+    fn unsaved_changes_prompt(&mut self, ctx: &egui::Context, name: String) {
+        let message = format!(
+            "{} has unsaved changes. Save them before switching to {}?",
+            self.current_session_name, name
+        );
+        let choice = choice_modal(
+            ctx,
+            Id::new("UnsavedChangesPrompt"),
+            &message,
+            &["Save", "Discard", "Cancel"],
+        );
+
+        match choice {
+            Some(0) => self.change_session(name, true),
+            Some(1) => self.change_session(name, false),
+            _ => {}
+        }
+        if choice.is_some() {
+            self.pending_confirmation = None;
+        }
+    }
+
+    /// Starts exporting a session into an archive.
+    ///
+    /// Exports to the archive path, or the home directory if it is empty.
+    ///
+    /// # Parameters
+    /// - `name`: Session to export
+    fn export_session(&mut self, name: String) {
+        let dest = match self.archive_path.trim() {
+            "" => dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")),
+            path => PathBuf::from(path),
+        };
+        let (response_tx, response_rx) = oneshot::channel();
+        let action = SessionAction::ExportSession {
+            name,
+            dest,
+            response_tx,
+        };
+
+        match self.session_sender.try_send(action) {
+            Ok(()) => self.pending_export = Some(response_rx),
+            Err(e) => self.session_load_error = Some(format!("Export failed: {}", e)),
+        }
+    }
+
+    /// Starts importing the session archive at the archive path.
+    fn import_session(&mut self) {
+        let src = self.archive_path.trim();
+        if src.is_empty() {
+            self.session_load_error = Some("Enter the path of a session archive".to_string());
+            return;
+        }
+        let (response_tx, response_rx) = oneshot::channel();
+        let action = SessionAction::ImportSession {
+            src: PathBuf::from(src),
+            response_tx,
+        };
+
+        match self.session_sender.try_send(action) {
+            Ok(()) => self.pending_import = Some(response_rx),
+            Err(e) => self.session_load_error = Some(format!("Import failed: {}", e)),
+        }
+    }
+
+    /// Starts listing the backups of a session and opens the backup modal.
+    fn list_backups(&mut self, name: String) {
+        let (response_tx, response_rx) = oneshot::channel();
+        let action = SessionAction::ListBackups {
+            name: name.clone(),
+            response_tx,
+        };
+
+        match self.session_sender.try_send(action) {
+            Ok(()) => {
+                self.backup_browser = Some(BackupBrowser {
+                    session: name,
+                    backups: None,
+                    pending: Some(response_rx),
+                })
+            }
+            Err(e) => self.session_load_error = Some(format!("Listing backups failed: {}", e)),
+        }
+    }
+
+    /// Shows the backups of a session with a restore button for each.
+    ///
+    /// Restoring closes the modal and asks for confirmation first.
+    fn backup_prompt(&mut self, ctx: &egui::Context) {
+        let Some(browser) = &mut self.backup_browser else {
+            return;
+        };
+        if let Some(result) = poll_response(&mut browser.pending) {
+            match result {
+                Ok(backups) => browser.backups = Some(backups),
+                Err(e) => {
+                    self.session_load_error = Some(format!("Listing backups failed: {}", e));
+                    self.backup_browser = None;
+                    return;
+                }
+            }
+        }
+
+        let mut restore = None;
+        let mut closed = false;
+        let response = Modal::new(Id::new("SessionBackups")).show(ctx, |pop| {
+            pop.set_width(300.0);
+            pop.heading(format!("Backups of {}", browser.session));
+            match &browser.backups {
+                None => {
+                    pop.label("Loading...");
+                }
+                Some(backups) if backups.is_empty() => {
+                    pop.label("No backups yet, they are taken when the session is saved");
+                }
+                Some(backups) => {
+                    ScrollArea::vertical().max_height(300.0).show(pop, |pop| {
+                        for backup in backups {
+                            pop.horizontal(|pop| {
+                                pop.label(backup);
+                                if pop.button("Restore").clicked() {
+                                    restore = Some(backup.clone());
+                                }
+                            });
+                        }
+                    });
+                }
+            }
+            pop.separator();
+            closed = pop.button("Close").clicked();
+        });
+
+        if let Some(backup) = restore {
+            self.pending_confirmation = Some(SessionConfirmation::RestoreBackup {
+                name: browser.session.clone(),
+                backup,
+            });
+            self.backup_browser = None;
+        } else if closed || response.should_close() {
+            self.backup_browser = None;
+        }
+    }
+
+    /// Starts replacing the files of a session by one of its backups.
+    ///
+    /// # Parameters
+    /// - `name`: Session to restore
+    /// - `backup`: Backup name as listed by the persistence system
+    fn restore_backup(&mut self, name: String, backup: String) {
+        let (response_tx, response_rx) = oneshot::channel();
+        let action = SessionAction::RestoreBackup {
+            name,
+            backup,
+            response_tx,
+        };
+
+        match self.session_sender.try_send(action) {
+            Ok(()) => self.pending_restore = Some(response_rx),
+            Err(e) => self.session_load_error = Some(format!("Restore failed: {}", e)),
+        }
+    }
+
+    /// Picks up finished exports, imports and restores.
+    ///
+    /// Failures are shown in the error modal, successes next to the archive path.
+    fn poll_transfers(&mut self) {
+        if let Some(result) = poll_response(&mut self.pending_export) {
+            match result {
+                Ok(path) => self.transfer_status = Some(format!("Exported to {}", path.display())),
+                Err(e) => self.session_load_error = Some(format!("Export failed: {}", e)),
+            }
+        }
+
+        if let Some(result) = poll_response(&mut self.pending_import) {
+            match result {
+                Ok(name) => {
+                    self.transfer_status = Some(format!("Imported session {}", name));
+                    self.archive_path.clear();
+                    if !self.available_sessions.contains(&name) {
+                        self.available_sessions.push(name);
+                    }
+                }
+                Err(e) => self.session_load_error = Some(format!("Import failed: {}", e)),
+            }
+        }
+
+        if let Some(result) = poll_response(&mut self.pending_restore) {
+            match result {
+                Ok(()) => self.transfer_status = Some("Backup restored".to_string()),
+                Err(e) => self.session_load_error = Some(format!("Restore failed: {}", e)),
+            }
+        }
     }
 
     /// Refreshes the available sessions list from the persistence system.
     ///
-    /// Initiates async communication to retrieve current session list,
-    /// updating the UI state for immediate display.
-    fn list_sessions(&mut self) {
-        let result = session_action!(@list, self.session_sender);
+    /// Initiates async communication to rescan the session directories,
+    /// updating the UI state and the session registry. The scan only looks at
+    /// directory entries, so it is cheap enough to run whenever the main menu
+    /// is opened.
+    pub fn refresh_sessions(&mut self) {
+        let result = session_action!(@refresh, self.session_sender);
 
         match result {
             Ok(sessions) => self.available_sessions = sessions.keys().cloned().collect(),
@@ -282,6 +731,26 @@ impl MainMenuData {
         }
     }
 
+    /// Switches sessions, asking first if the current one has unsaved changes.
+    ///
+    /// The user decides whether those changes are saved into the current
+    /// session or discarded, e.g. after experimenting.
+    fn request_session_change(&mut self, name: String) {
+        if name != self.current_session_name && self.has_unsaved_changes() {
+            self.pending_confirmation = Some(SessionConfirmation::Switch(name));
+            return;
+        }
+        self.change_session(name, true);
+    }
+
+    /// Whether the current session changed since it was last saved.
+    ///
+    /// Follows the dirty flag of the [`ConfigPortal`], which saves and
+    /// autosaves clear.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.config_portal.is_dirty()
+    }
+
     /// Switches to a different session configuration.
     ///
     /// Saves current session as previous (for potential fallback) and
@@ -293,26 +762,143 @@ impl MainMenuData {
     ///
     /// # Parameters
     /// - `name`: Session name to load
-    fn change_session(&mut self, name: String) {
+    /// - `save_current`: Save the current session first, otherwise its unsaved
+    ///   changes are discarded
+    fn change_session(&mut self, name: String, save_current: bool) {
         self.previous_session = Some(self.current_session_name.clone());
         self.current_session_name = name.clone();
 
-        let result = session_action!(@load, self.session_sender, name);
-        self.list_sessions();
+        let result = if save_current {
+            session_action!(@load, self.session_sender, name)
+        } else {
+            session_action!(@load_discard, self.session_sender, name)
+        };
+        if let Err(e) = result {
+            debug!("Session load not confirmed yet: {}", e);
+        }
+        self.refresh_sessions();
     }
 
     /// Deletes a session from the persistence system.
     ///
     /// Initiates async session deletion and refreshes the available sessions list.
+    /// Deleting the active session makes the persistence system fall back to the
+    /// last session or the default.
     ///
     /// ## Safety Considerations
-    /// Currently no confirmation dialog - future enhancement should add
-    /// user confirmation for destructive operations.
+    /// Only called after the user confirmed the deletion in the session list.
     ///
     /// # Parameters
     /// - `name`: Session name to delete
     fn delet_session(&mut self, name: String) {
-        let result = session_action!(@delete, self.session_sender, name);
-        self.list_sessions();
+        if let Err(e) = session_action!(@delete, self.session_sender, name) {
+            debug!("Session deletion not confirmed yet: {}", e);
+        }
+        if name == self.current_session_name {
+            // Mirrors the fallback of the persistence system
+            self.current_session_name = match self.previous_session.take() {
+                Some(last) if last != name => last,
+                _ => "default".to_string(),
+            };
+        }
+        self.refresh_sessions();
     }
+
+    /// Renames a session and follows the rename if it is the active one.
+    ///
+    /// # Parameters
+    /// - `name`: Session to rename
+    /// - `new_name`: Name it is stored under afterwards
+    fn rename_session(&mut self, name: String, new_name: String) {
+        let new_name = new_name.trim().to_string();
+        if new_name == name {
+            self.renaming = None;
+            return;
+        }
+        if let Err(e) = validate_session_name(&new_name) {
+            self.session_load_error = Some(e);
+            return;
+        }
+        if self.available_sessions.contains(&new_name) {
+            if new_name == self.current_session_name {
+                self.session_load_error = Some(format!(
+                    "Session {} is active and cannot be replaced",
+                    new_name
+                ));
+            } else {
+                self.pending_confirmation =
+                    Some(SessionConfirmation::RenameOver { name, new_name });
+            }
+            return;
+        }
+
+        if let Err(e) = session_action!(@rename, self.session_sender, name, new_name) {
+            debug!("Session rename not confirmed yet: {}", e);
+        }
+        if name == self.current_session_name {
+            self.current_session_name = new_name;
+        }
+        self.renaming = None;
+        self.session_load_error = None;
+        self.refresh_sessions();
+    }
+
+    /// Renames a session over an existing one, deleting that one first.
+    ///
+    /// Only called after the user confirmed; the replaced session is never
+    /// the active one.
+    fn replace_session(&mut self, name: String, new_name: String) {
+        if let Err(e) = session_action!(@delete, self.session_sender, new_name.clone()) {
+            debug!("Session deletion not confirmed yet: {}", e);
+        }
+        self.available_sessions
+            .retain(|session| *session != new_name);
+        self.rename_session(name, new_name);
+    }
+
+    /// Copies a session under the first free name of the form `<name> copy`.
+    ///
+    /// # Parameters
+    /// - `name`: Session to duplicate
+    fn duplicate_session(&mut self, name: String) {
+        let new_name = (1..)
+            .map(|n| match n {
+                1 => format!("{} copy", name),
+                n => format!("{} copy {}", name, n),
+            })
+            .find(|candidate| !self.available_sessions.contains(candidate))
+            .unwrap_or_default();
+
+        if let Err(e) = session_action!(@duplicate, self.session_sender, name, new_name) {
+            debug!("Session duplication not confirmed yet: {}", e);
+        }
+        self.refresh_sessions();
+    }
+}
+
+/// Checks a name for a new session, existing names are handled by the caller.
+fn validate_session_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Session name cannot be empty".to_string());
+    }
+    if name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(format!("Invalid session name: {}", name));
+    }
+    Ok(())
+}
+
+/// Takes the result of a pending persistence response once it arrived.
+fn poll_response<T>(
+    pending: &mut Option<oneshot::Receiver<color_eyre::Result<T>>>,
+) -> Option<color_eyre::Result<T>> {
+    let response_rx = pending.as_mut()?;
+    let result = match response_rx.try_recv() {
+        Ok(result) => result,
+        Err(oneshot::error::TryRecvError::Empty) => return None,
+        Err(oneshot::error::TryRecvError::Closed) => Err(color_eyre::Report::msg(
+            "Persistence worker dropped the request",
+        )),
+    };
+    *pending = None;
+    Some(result)
 }
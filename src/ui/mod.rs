@@ -82,12 +82,16 @@
 //! ## Performance Considerations
 //!
 //! ### Frame Rate Management
-//! Requests 30fps refresh rate (`Duration::from_millis(33)`) to balance:
+//! Repaints at the session's configured frame rate (30fps by default, 10–60fps
+//! allowed) to balance:
 //! - Responsive controller input handling
 //! - Smooth visual feedback for user interactions
 //! - Reasonable resource usage on embedded hardware
 //! - Compatibility with typical display refresh rates
 //!
+//! While the application is idle (see [`crate::idle`]) the UI drops to
+//! [`IDLE_FPS`] and returns to the configured rate on the next input.
+//!
 //! ### Memory Efficiency
 //! Uses pre-calculated layout dimensions and efficient widget patterns to:
 //! - Minimize per-frame allocations
@@ -96,31 +100,87 @@
 //! - Support extended operation on resource-constrained hardware
 
 pub mod common;
+pub mod controller_test;
 pub mod elrs_menu;
+pub mod event_monitor;
+pub mod keyboard_overlay;
+pub mod log_viewer;
 pub mod main_menu;
 pub mod mqtt_menu;
+pub mod recovery_prompt;
 pub mod settings_menu;
+pub mod virtual_cursor;
 
-use eframe::egui::{self, Button, Color32, Context, Event, Layout, Vec2};
+use eframe::egui::{self, Button, Color32, Context, Event, Frame, RichText, Ui, Vec2};
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, error, info, warn};
 
+use crate::controller::controller_handle::{ControllerHandle, ControllerOutput, ControllerStatus};
+use crate::idle::{IdleTracker, IDLE_FPS};
+use crate::latency;
+use crate::mapping::elrs::ELRSOutput;
+use crate::mapping::navigation::NavigationAction;
+use crate::mapping::{DroppedEvents, KeyboardBatch, MappingCommand, MappingType};
 use crate::mqtt::config::MqttConfig;
 use crate::mqtt::message_manager::MQTTMessage;
-use crate::persistence::config_portal::{ConfigPortal, ConfigResult};
+use crate::mqtt::mqtt_handler::{SubscriptionRequest, SubscriptionStates};
+use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
+use crate::persistence::integrity::CorruptFile;
 use crate::persistence::persistence_worker::SessionAction;
 use crate::persistence::session_client::SessionClient;
+use crate::persistence::{effective_fps, AutosaveSettings, FocusHighlight, Theme, DEFAULT_FPS};
+use crate::supervisor::{TaskHealth, TaskState};
 
-use self::common::MenuState;
+use self::common::{MenuState, UiColors};
 use self::elrs_menu::ELRSMenuData;
+use self::keyboard_overlay::KeyboardOverlay;
 use self::main_menu::MainMenuData;
 use self::mqtt_menu::MQTTMenuData;
-use self::settings_menu::SettingsMenuData;
+use self::recovery_prompt::RecoveryPrompt;
+use self::settings_menu::{SettingsChannels, SettingsMenuData};
+use self::virtual_cursor::VirtualCursor;
 
 pub use common::MQTTServer;
 
+/// Backend channels and shared state the UI is connected to
+pub struct UiChannels {
+    /// Processed controller events from the keyboard mapping
+    pub event_receiver: mpsc::Receiver<KeyboardBatch>,
+    /// Menu changes from gamepad buttons bound to navigation
+    pub navigation_rx: mpsc::Receiver<NavigationAction>,
+    /// ELRS channel maps from the mapping system
+    pub elrs_rx: mpsc::Receiver<ELRSOutput>,
+    /// Incoming MQTT messages
+    pub received_msg: mpsc::Receiver<MQTTMessage>,
+    /// Outgoing MQTT messages
+    pub msg_sender: mpsc::Sender<MQTTMessage>,
+    pub subscription_rx: watch::Receiver<SubscriptionStates>,
+    pub subscription_request_tx: mpsc::Sender<SubscriptionRequest>,
+    /// Activation of the MQTT handler, used for a clean disconnect on exit
+    pub mqtt_active_tx: watch::Sender<bool>,
+    pub config_portal: Arc<ConfigPortal>,
+    pub session_sender: mpsc::Sender<SessionAction>,
+    /// Live autosave settings of the persistence system
+    pub autosave_tx: watch::Sender<AutosaveSettings>,
+    /// Rebuilds mapping engines after config changes
+    pub mapping_reload_tx: mpsc::Sender<MappingType>,
+    /// Latest controller input, used by the keyboard overlay and controller test
+    pub controller_state_rx: watch::Receiver<Option<ControllerOutput>>,
+    /// Switches or adds mappings at runtime
+    pub mapping_command_tx: mpsc::Sender<MappingCommand>,
+    /// Currently running mapping engines
+    pub active_mappings_rx: watch::Receiver<Vec<(MappingType, bool)>>,
+    /// Whether the kill switch disabled all mapping outputs
+    pub kill_switch_rx: watch::Receiver<bool>,
+    /// Mapped events lost to full output channels, for diagnostics
+    pub dropped_events: Arc<DroppedEvents>,
+    /// Health of the supervised MQTT and mapping tasks
+    pub task_health_rx: watch::Receiver<Vec<TaskHealth>>,
+}
+
 /// Central UI orchestration component implementing OpenController's "diamond" architecture.
 ///
 /// This structure serves as the primary coordination point between user interaction,
@@ -163,13 +223,19 @@ pub struct OpencontrollerUI {
     /// Current active menu screen for navigation state machine
     menu_state: MenuState,
 
+    /// Menu rendered in the previous frame, to detect a menu being opened
+    shown_menu: Option<MenuState>,
+
     /// Receiver for processed controller events from mapping system
-    event_receiver: mpsc::Receiver<Vec<egui::Event>>,
+    event_receiver: mpsc::Receiver<KeyboardBatch>,
+
+    /// Menu changes requested by navigation buttons of the gamepad
+    navigation_rx: mpsc::Receiver<NavigationAction>,
 
     /// Session management and configuration interface
     main_menu_data: MainMenuData,
 
-    /// ELRS RC vehicle control interface (currently mock implementation)
+    /// ELRS RC vehicle control interface and transmitter link
     elrs_menu_data: ELRSMenuData,
 
     /// MQTT debugging and message management interface
@@ -178,17 +244,62 @@ pub struct OpencontrollerUI {
     /// System settings and configuration interface
     settings_menu_data: SettingsMenuData,
 
-    /// Controller battery level for status display
-    bat_controller: usize,
+    /// Joystick region feedback for keyboard typing, toggled in settings
+    keyboard_overlay: KeyboardOverlay,
+
+    /// Pointer driven by the mouse mode of the keyboard mapping
+    virtual_cursor: VirtualCursor,
+
+    /// Decision about config files that failed the startup integrity check
+    recovery_prompt: RecoveryPrompt,
+
+    /// Controller battery level for status display, `None` if unavailable
+    bat_controller: Option<u8>,
 
-    /// PC/System battery level for status display
-    bat_pc: usize,
+    /// Battery level updates from the controller subsystem
+    bat_controller_rx: watch::Receiver<Option<u8>>,
+
+    /// PC/System battery level for status display, `None` if unavailable
+    bat_pc: Option<u8>,
+
+    /// When the system battery was last read
+    bat_pc_checked: Option<Instant>,
+
+    /// Whether any gamepad is connected, drives the "no controller" overlay
+    controller_status_rx: watch::Receiver<ControllerStatus>,
 
     /// Direct access to configuration portal for immediate reads
     config_portal: Arc<ConfigPortal>,
 
     /// Channel for session management operations
     session_sender: mpsc::Sender<SessionAction>,
+
+    /// Runtime control of the mapping engines
+    mapping_command_tx: mpsc::Sender<MappingCommand>,
+
+    /// Running mapping engines and whether they are enabled, shown in the status bar
+    active_mappings_rx: watch::Receiver<Vec<(MappingType, bool)>>,
+
+    /// Whether the kill switch disabled all mapping outputs
+    kill_switch_rx: watch::Receiver<bool>,
+
+    /// Health of the supervised background tasks, failures shown in the status bar
+    task_health_rx: watch::Receiver<Vec<TaskHealth>>,
+
+    /// Theme and focus highlight currently applied to the egui context
+    applied_theme: Option<(Theme, FocusHighlight)>,
+
+    /// When the session theme was last read
+    theme_checked: Option<Instant>,
+
+    /// Frame rate used for repaints
+    fps: u8,
+
+    /// Activity of the application, lowers the frame rate while idle
+    idle: Arc<IdleTracker>,
+
+    /// Activation of the MQTT handler, switched off on shutdown
+    mqtt_active_tx: watch::Sender<bool>,
 }
 
 impl OpencontrollerUI {
@@ -201,15 +312,15 @@ impl OpencontrollerUI {
     /// ## Initialization Strategy
     ///
     /// ### Theme Configuration
-    /// Sets dark theme immediately to provide optimal visibility in workshop
-    /// environments and reduce eye strain during extended debugging sessions.
+    /// Starts from the dark theme for visibility in workshop environments and
+    /// applies the session theme on top, so colors are right from the first frame.
     ///
     /// ### Component Architecture
     /// Each menu component is initialized with appropriate backend integration:
     /// - **Main Menu**: Direct ConfigPortal and session management integration
     /// - **MQTT Menu**: Full bidirectional MQTT communication setup
-    /// - **ELRS Menu**: Mock data for development (pending backend implementation)
-    /// - **Settings Menu**: Mock data with planned system integration
+    /// - **ELRS Menu**: Serial transmitter link fed by the ELRS mapping channel
+    /// - **Settings Menu**: Live controller and keyboard settings, mock network/display data
     ///
     /// ### Channel Distribution
     /// Distributes communication channels to appropriate components while maintaining
@@ -217,11 +328,10 @@ impl OpencontrollerUI {
     ///
     /// # Parameters
     /// - `cc`: eframe creation context for egui initialization
-    /// - `event_receiver`: Channel for receiving processed controller events
-    /// - `received_msg`: Channel for incoming MQTT messages
-    /// - `msg_sender`: Channel for outgoing MQTT messages  
-    /// - `config_portal`: Shared access to configuration system
-    /// - `session_sender`: Channel for session management operations
+    /// - `channels`: Backend channels, see [`UiChannels`]
+    /// - `idle`: Activity of the application, lowers the frame rate while idle
+    /// - `controller`: Controller subsystem, source of battery and connection status
+    /// - `corrupt_files`: Config files that failed the startup integrity check
     ///
     /// # Design Rationale
     /// Takes all necessary communication channels at initialization to ensure
@@ -229,29 +339,358 @@ impl OpencontrollerUI {
     /// channel management or connection establishment.
     pub fn new(
         cc: &eframe::CreationContext<'_>,
-        event_receiver: mpsc::Receiver<Vec<egui::Event>>,
-        received_msg: mpsc::Receiver<MQTTMessage>,
-        msg_sender: mpsc::Sender<MQTTMessage>,
-        config_portal: Arc<ConfigPortal>,
-        session_sender: mpsc::Sender<SessionAction>,
+        channels: UiChannels,
+        idle: Arc<IdleTracker>,
+        controller: &ControllerHandle,
+        corrupt_files: Vec<CorruptFile>,
     ) -> Self {
+        let UiChannels {
+            event_receiver,
+            navigation_rx,
+            elrs_rx,
+            received_msg,
+            msg_sender,
+            subscription_rx,
+            subscription_request_tx,
+            mqtt_active_tx,
+            config_portal,
+            session_sender,
+            autosave_tx,
+            mapping_reload_tx,
+            controller_state_rx,
+            mapping_command_tx,
+            active_mappings_rx,
+            kill_switch_rx,
+            dropped_events,
+            task_health_rx,
+        } = channels;
         cc.egui_ctx.set_theme(egui::Theme::Dark);
-        OpencontrollerUI {
+        idle.set_ui_context(cc.egui_ctx.clone());
+        let mut ui = OpencontrollerUI {
             menu_state: MenuState::Main,
+            shown_menu: None,
             event_receiver,
+            navigation_rx,
             main_menu_data: MainMenuData::new(config_portal.clone(), session_sender.clone()),
-            elrs_menu_data: ELRSMenuData::mock_data(),
+            elrs_menu_data: ELRSMenuData::new(
+                elrs_rx,
+                config_portal.clone(),
+                mapping_reload_tx.clone(),
+            ),
             mqtt_menu_data: MQTTMenuData::new(
                 received_msg,
                 msg_sender,
+                mqtt_active_tx.clone(),
+                subscription_rx,
+                subscription_request_tx,
                 config_portal.clone(),
                 session_sender.clone(),
             ),
             config_portal: config_portal.clone(),
             session_sender: session_sender.clone(),
-            settings_menu_data: SettingsMenuData::mock_data(),
-            bat_controller: 0,
-            bat_pc: 0,
+            settings_menu_data: SettingsMenuData::new(SettingsChannels {
+                controller_settings_tx: controller.settings_sender(),
+                config_portal: config_portal.clone(),
+                mapping_reload_tx,
+                session_sender: session_sender.clone(),
+                autosave_tx,
+                dropped_events,
+                task_health_rx: task_health_rx.clone(),
+                controller_state_rx: controller_state_rx.clone(),
+                controller_status_rx: controller.status_receiver(),
+            }),
+            keyboard_overlay: KeyboardOverlay::new(controller_state_rx, config_portal.clone()),
+            virtual_cursor: VirtualCursor::default(),
+            recovery_prompt: RecoveryPrompt::new(corrupt_files, session_sender.clone()),
+            bat_controller: None,
+            bat_controller_rx: controller.battery_receiver(),
+            bat_pc: None,
+            bat_pc_checked: None,
+            controller_status_rx: controller.status_receiver(),
+            mapping_command_tx,
+            active_mappings_rx,
+            kill_switch_rx,
+            task_health_rx,
+            applied_theme: None,
+            theme_checked: None,
+            fps: DEFAULT_FPS,
+            idle,
+            mqtt_active_tx,
+        };
+        ui.update_theme(&cc.egui_ctx);
+        ui
+    }
+
+    /// Time until the next repaint at the configured frame rate.
+    ///
+    /// Keeps the last known rate if the config is briefly unavailable. While
+    /// idle the rate drops to [`IDLE_FPS`], unless configured even lower.
+    fn repaint_interval(&mut self) -> Duration {
+        if let ConfigResult::Fps(fps) = self
+            .config_portal
+            .execute_potal_action(PortalAction::GetFps)
+        {
+            self.fps = effective_fps(fps);
+        }
+        let fps = if self.idle.is_idle() {
+            self.fps.min(IDLE_FPS)
+        } else {
+            self.fps
+        };
+        Duration::from_millis(1000 / u64::from(fps))
+    }
+
+    /// Applies the session theme and focus highlight when they changed.
+    ///
+    /// Both are re-read at most every [`THEME_POLL_INTERVAL`], so loading
+    /// another session or saving a theme changes the colors without a restart.
+    /// Unreadable colors are repaired before applying, see
+    /// [`Theme::validate_and_repair`].
+    fn update_theme(&mut self, ctx: &egui::Context) {
+        let due = self
+            .theme_checked
+            .is_none_or(|checked| checked.elapsed() >= THEME_POLL_INTERVAL);
+        if !due {
+            return;
+        }
+        self.theme_checked = Some(Instant::now());
+
+        let theme = match self
+            .config_portal
+            .execute_potal_action(PortalAction::GetTheme)
+        {
+            ConfigResult::Theme(theme) => theme,
+            ConfigResult::Failed(e) => {
+                warn!("Failed to read theme: {}", e);
+                return;
+            }
+            _ => {
+                warn!("Unexpected result while reading theme");
+                return;
+            }
+        };
+        let focus = match self
+            .config_portal
+            .execute_potal_action(PortalAction::GetFocusHighlight)
+        {
+            ConfigResult::FocusHighlight(focus) => focus,
+            _ => FocusHighlight::default(),
+        };
+
+        // Compared unrepaired, so repairs are logged once per theme change
+        let current = (theme, focus);
+        if self.applied_theme.as_ref() != Some(&current) {
+            debug!("Applying session theme");
+            let mut theme = current.0.clone();
+            for repair in theme.validate_and_repair() {
+                warn!("Repaired theme: {}", repair);
+            }
+            theme.apply(ctx, &current.1);
+            self.applied_theme = Some(current);
+        }
+    }
+
+    /// Renders the running mappings with controls for changing them.
+    ///
+    /// Each mapping can run next to the others via its checkbox, or be made the
+    /// only active one with "Only". The shown state follows the mapping manager,
+    /// so a failed change is visible right away.
+    fn render_mapping_selector(&self, ui: &mut egui::Ui) {
+        let active = self.active_mappings_rx.borrow().clone();
+        let active_text = if active.is_empty() {
+            "None".to_string()
+        } else {
+            active
+                .iter()
+                .map(|(mapping_type, enabled)| {
+                    if *enabled {
+                        mapping_type.to_string()
+                    } else {
+                        format!("{} (paused)", mapping_type)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" + ")
+        };
+
+        ui.label("Mapping:");
+        egui::ComboBox::from_id_salt("active_mapping")
+            .selected_text(active_text)
+            .show_ui(ui, |ui| {
+                for mapping_type in SWITCHABLE_MAPPINGS {
+                    let running = active.iter().any(|(t, _)| *t == mapping_type);
+                    ui.horizontal(|ui| {
+                        let mut checked = running;
+                        if ui
+                            .checkbox(&mut checked, mapping_type.to_string())
+                            .changed()
+                        {
+                            self.send_mapping_command(if checked {
+                                MappingCommand::Add(mapping_type)
+                            } else {
+                                MappingCommand::Remove(mapping_type)
+                            });
+                        }
+                        if ui.small_button("Only").clicked() {
+                            self.send_mapping_command(MappingCommand::Switch(mapping_type));
+                        }
+                    });
+                }
+            });
+    }
+
+    /// Shows background tasks that are down in the status bar.
+    ///
+    /// Nothing is shown while all tasks run; restart counts and errors are in
+    /// the settings diagnostics.
+    fn render_task_health(&self, ui: &mut Ui) {
+        for task in self.task_health_rx.borrow().iter() {
+            if task.state != TaskState::Restarting {
+                continue;
+            }
+            let label = ui.label(
+                RichText::new(format!("⚠ {} down, restarting", task.name))
+                    .color(ui.visuals().warn_fg_color),
+            );
+            if let Some(error) = &task.last_error {
+                label.on_hover_text(error);
+            }
+        }
+    }
+
+    /// Forwards a mapping change to the mapping manager.
+    fn send_mapping_command(&self, command: MappingCommand) {
+        info!("Requesting mapping change: {:?}", command);
+        if let Err(e) = self.mapping_command_tx.try_send(command) {
+            error!("Failed to request mapping change: {}", e);
+        }
+    }
+
+    /// Shows a banner while the kill switch keeps all mapping outputs disabled.
+    ///
+    /// Releasing the combo does not resume anything; outputs stay off until
+    /// "Resume outputs" is pressed. Keyboard output is off as well, so the
+    /// button has to be reached by touch or mouse.
+    fn render_kill_switch_banner(&self, ui: &mut Ui) {
+        if !*self.kill_switch_rx.borrow() {
+            return;
+        }
+
+        egui::TopBottomPanel::top("kill_switch_panel")
+            .frame(
+                Frame::new()
+                    .fill(ui.visuals().error_fg_color)
+                    .inner_margin(6.0),
+            )
+            .show_inside(ui, |ui| {
+                ui.horizontal_centered(|ui| {
+                    ui.label(
+                        RichText::new("ALL OUTPUTS DISABLED - kill switch engaged")
+                            .heading()
+                            .strong()
+                            .color(Color32::BLACK),
+                    );
+                    if ui.button("Resume outputs").clicked() {
+                        self.send_mapping_command(MappingCommand::ClearKillSwitch);
+                    }
+                });
+            });
+    }
+
+    /// Covers the screen with a notice while no gamepad is connected.
+    ///
+    /// The whole interface is gamepad-driven, so without a pad the user would
+    /// otherwise be left with an unresponsive screen. The overlay disappears on
+    /// its own once a pad reconnects.
+    fn render_controller_overlay(&self, ctx: &Context) {
+        if *self.controller_status_rx.borrow() == ControllerStatus::Connected {
+            return;
+        }
+
+        let screen = ctx.screen_rect();
+        egui::Area::new(egui::Id::new("controller_overlay"))
+            .fixed_pos(screen.min)
+            .order(egui::Order::Foreground)
+            // Keep touch/mouse input reaching the UI underneath
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.painter()
+                    .rect_filled(screen, 0.0, Color32::from_black_alpha(220));
+                ui.set_min_size(screen.size());
+                ui.vertical_centered(|ui| {
+                    ui.add_space(screen.height() / 2.0 - 30.0);
+                    ui.label(
+                        RichText::new("No controller connected")
+                            .heading()
+                            .color(UiColors::inactive()),
+                    );
+                    ui.label("Connect a gamepad to continue");
+                });
+            });
+    }
+
+    /// Refreshes the battery levels shown in the bottom panel.
+    ///
+    /// The controller level is pushed by the collector; the system battery is
+    /// read from sysfs at most every [`PC_BATTERY_POLL_INTERVAL`].
+    fn update_battery_status(&mut self) {
+        self.bat_controller = *self.bat_controller_rx.borrow();
+
+        let due = self
+            .bat_pc_checked
+            .is_none_or(|checked| checked.elapsed() >= PC_BATTERY_POLL_INTERVAL);
+        if due {
+            self.bat_pc = read_system_battery();
+            self.bat_pc_checked = Some(Instant::now());
+        }
+    }
+
+    /// Saves the current session and waits up to `timeout` for the result.
+    ///
+    /// Used on shutdown, where the UI thread may block. The response is polled
+    /// because the UI thread runs inside the tokio runtime and cannot use
+    /// blocking receives.
+    fn save_session_blocking(&self, timeout: Duration) {
+        let (response_tx, mut response_rx) = tokio::sync::oneshot::channel();
+        if let Err(e) = self
+            .session_sender
+            .try_send(SessionAction::SaveCurrentSession { response_tx })
+        {
+            error!("Failed to request final session save: {}", e);
+            return;
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match response_rx.try_recv() {
+                Ok(Ok(())) => {
+                    info!("Session saved on shutdown");
+                    return;
+                }
+                Ok(Err(e)) => {
+                    error!("Final session save failed: {}", e);
+                    return;
+                }
+                Err(tokio::sync::oneshot::error::TryRecvError::Empty)
+                    if Instant::now() < deadline =>
+                {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(e) => {
+                    warn!("No response to final session save: {}", e);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Switches menus as requested by gamepad navigation buttons.
+    ///
+    /// Actions arriving within one frame are applied in order, so two quick
+    /// "next menu" presses skip a menu as expected.
+    fn apply_navigation(&mut self) {
+        while let Ok(action) = self.navigation_rx.try_recv() {
+            self.menu_state = self.menu_state.navigate(action);
         }
     }
 
@@ -266,8 +705,8 @@ impl OpencontrollerUI {
     fn log_controller_state(&mut self) {
         let controller_events = self.event_receiver.try_recv();
 
-        if let Ok(events) = controller_events {
-            for element in events {
+        if let Ok(batch) = controller_events {
+            for element in batch.events {
                 info!(
                     "This event got succesfully transfered into UI:\n{:?}",
                     element
@@ -296,13 +735,51 @@ impl eframe::App for OpencontrollerUI {
     /// controller events are available. Event processing occurs within
     /// the existing egui frame processing, maintaining consistent timing.
     ///
+    /// ## Event Monitor
+    /// While the event monitor in settings is open, each batch is also
+    /// recorded there with the stick sections detected at that moment.
+    ///
+    /// ## Focus Entry
+    /// egui moves focus with the arrow keys only away from a focused widget,
+    /// so after a menu switch or Escape the arrows would do nothing. While no
+    /// widget has focus, arrow presses are turned into Tab (Down/Right) or
+    /// Shift+Tab (Up/Left), which focus the first or last widget of the
+    /// screen, starting at the top panel.
+    ///
+    /// ## Input Latency
+    /// While measuring is enabled in the diagnostics, the age of each stamped
+    /// batch is recorded on arrival, see [`crate::latency`].
+    ///
+    /// ## Virtual Mouse
+    /// Cursor steps and clicks from the mouse mode are turned into pointer
+    /// events at the [`VirtualCursor`] position, clamped to the screen.
+    ///
     /// # Parameters
-    /// - `_ctx`: egui context (unused in current implementation)
+    /// - `ctx`: egui context, queried for the currently focused widget
     /// - `raw_input`: Mutable reference to egui's input state for event injection
-    fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
-        if let Ok(events) = self.event_receiver.try_recv() {
+    fn raw_input_hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        if let Ok(KeyboardBatch {
+            events,
+            collected_at,
+        }) = self.event_receiver.try_recv()
+        {
+            if let Some(collected_at) = collected_at {
+                latency::record_since(collected_at);
+            }
+            if self.settings_menu_data.is_monitoring_events() {
+                let sections = self.keyboard_overlay.current_sections();
+                self.settings_menu_data.record_events(&events, sections);
+            }
+            let unfocused = ctx.memory(|memory| memory.focused().is_none());
+            let screen = raw_input.screen_rect.unwrap_or_else(|| ctx.screen_rect());
             for event in events {
-                raw_input.events.push(event);
+                for event in self.virtual_cursor.translate(event, screen) {
+                    if unfocused {
+                        raw_input.events.push(focus_entry_event(event));
+                    } else {
+                        raw_input.events.push(event);
+                    }
+                }
             }
         }
     }
@@ -316,9 +793,10 @@ impl eframe::App for OpencontrollerUI {
     /// ## Frame Processing Strategy
     ///
     /// ### Refresh Rate Management
-    /// Requests 30fps refresh (`33ms`) to balance responsiveness with resource usage.
-    /// This rate provides smooth interaction feedback while supporting extended
-    /// operation on embedded hardware.
+    /// Requests the next repaint after `1000 / fps` milliseconds, with the frame
+    /// rate read from the session every frame so a changed setting applies on the
+    /// next frame. Lower rates reduce CPU usage on embedded hardware. Pointer and
+    /// keyboard input counts as activity for the idle mode.
     ///
     /// ### Layout Architecture
     /// Implements consistent three-panel layout:
@@ -350,17 +828,32 @@ impl eframe::App for OpencontrollerUI {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         // Uncomment for controller event debugging
         // self.log_controller_state();
+        if ctx.input(|input| !input.events.is_empty() || input.pointer.is_moving()) {
+            self.idle.record();
+        }
+        self.update_battery_status();
+        self.update_theme(ctx);
+        self.apply_navigation();
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.ctx().request_repaint_after(Duration::from_millis(33));
+            ui.ctx().request_repaint_after(self.repaint_interval());
             let width = ui.available_width() - 60.0;
 
+            self.render_kill_switch_banner(ui);
+
             // Top navigation panel with application area buttons
             egui::TopBottomPanel::top("top_panel")
                 .show_separator_line(false)
                 .show_inside(ui, |ui| {
                     ui.horizontal_centered(|ui| {
-                        let main_button = Button::new("MainMenu").min_size(Vec2 {
+                        // Subtle marker while the session has unsaved changes
+                        let unsaved_changes = self.main_menu_data.has_unsaved_changes();
+                        let main_label = if unsaved_changes {
+                            "● MainMenu"
+                        } else {
+                            "MainMenu"
+                        };
+                        let main_button = Button::new(main_label).min_size(Vec2 {
                             x: width / 4.0,
                             y: 20.0,
                         });
@@ -377,7 +870,12 @@ impl eframe::App for OpencontrollerUI {
                             y: 20.0,
                         });
 
-                        if ui.add(main_button).clicked() {
+                        let mut main_response = ui.add(main_button);
+                        if unsaved_changes {
+                            main_response =
+                                main_response.on_hover_text("The session has unsaved changes");
+                        }
+                        if main_response.clicked() {
                             self.menu_state = MenuState::Main;
                         };
                         if ui.add(mqtt_button).clicked() {
@@ -392,6 +890,12 @@ impl eframe::App for OpencontrollerUI {
                     });
                 });
 
+            // Session list may be stale after sessions changed on disk
+            if self.menu_state == MenuState::Main && self.shown_menu != Some(MenuState::Main) {
+                self.main_menu_data.refresh_sessions();
+            }
+            self.shown_menu = Some(self.menu_state);
+
             // Central content panel with menu-specific content
             egui::CentralPanel::default().show_inside(ui, |ui| match self.menu_state {
                 MenuState::Main => self.main_menu_data.render(ui),
@@ -415,10 +919,99 @@ impl eframe::App for OpencontrollerUI {
                             self.settings_menu_data.get_network_name(),
                             connection_status
                         ));
-                        ui.label(format!("CBat: {}%", self.bat_controller));
-                        ui.label(format!("PCBat: {}%", self.bat_pc));
+                        ui.label(format!("CBat: {}", format_battery(self.bat_controller)));
+                        ui.label(format!("PCBat: {}", format_battery(self.bat_pc)));
+                        self.render_task_health(ui);
+                        self.render_mapping_selector(ui);
                     });
                 });
         });
+
+        if self.settings_menu_data.show_keyboard_overlay() {
+            self.keyboard_overlay.render(ctx);
+        }
+        self.recovery_prompt.render(ctx);
+        self.virtual_cursor.paint(ctx);
+        self.render_controller_overlay(ctx);
+    }
+
+    /// Shuts down backend services when the window closes.
+    ///
+    /// Switches the MQTT handler off so it sends a clean DISCONNECT, then saves
+    /// the current session, waiting at most [`SHUTDOWN_SAVE_TIMEOUT`]. The MQTT
+    /// handler finishes once the UI, and with it the activation sender, is
+    /// dropped; `main` waits for that.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        info!("Shutting down");
+        let _ = self.mqtt_active_tx.send(false);
+        self.save_session_blocking(SHUTDOWN_SAVE_TIMEOUT);
     }
 }
+
+/// Mappings offered in the status bar selector.
+const SWITCHABLE_MAPPINGS: [MappingType; 3] = [
+    MappingType::Keyboard,
+    MappingType::ELRS,
+    MappingType::Custom,
+];
+
+/// How often the system battery is read from sysfs.
+const PC_BATTERY_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest time the final session save may take on shutdown.
+const SHUTDOWN_SAVE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How often the session theme is checked for changes
+const THEME_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Turns an arrow key into Tab or Shift+Tab, leaving other events unchanged.
+///
+/// See the focus entry notes on `raw_input_hook`.
+fn focus_entry_event(event: Event) -> Event {
+    match event {
+        Event::Key {
+            key:
+                key @ (egui::Key::ArrowUp
+                | egui::Key::ArrowDown
+                | egui::Key::ArrowLeft
+                | egui::Key::ArrowRight),
+            physical_key,
+            pressed,
+            repeat,
+            mut modifiers,
+        } => {
+            modifiers.shift = matches!(key, egui::Key::ArrowUp | egui::Key::ArrowLeft);
+            Event::Key {
+                key: egui::Key::Tab,
+                physical_key,
+                pressed,
+                repeat,
+                modifiers,
+            }
+        }
+        event => event,
+    }
+}
+
+/// Formats a battery level for the status bar, "N/A" if unavailable.
+fn format_battery(level: Option<u8>) -> String {
+    level.map_or_else(|| "N/A".to_string(), |level| format!("{}%", level))
+}
+
+/// Reads the system battery level from `/sys/class/power_supply`.
+///
+/// Uses the first supply of type "Battery". Returns `None` on systems without
+/// a battery or without sysfs (e.g. a Raspberry Pi on mains power).
+fn read_system_battery() -> Option<u8> {
+    let supplies = std::fs::read_dir(Path::new("/sys/class/power_supply")).ok()?;
+
+    supplies.flatten().find_map(|supply| {
+        let path = supply.path();
+        let supply_type = std::fs::read_to_string(path.join("type")).ok()?;
+        if supply_type.trim() != "Battery" {
+            return None;
+        }
+        let capacity = std::fs::read_to_string(path.join("capacity")).ok()?;
+        capacity.trim().parse::<u8>().ok().map(|c| c.min(100))
+    })
+}
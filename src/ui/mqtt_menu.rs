@@ -19,10 +19,13 @@
 //!
 //! ### Real-Time MQTT Debugging Workflow
 //! The interface implements a complete MQTT debugging cycle:
-//! - **Connection Management**: Multiple server profiles with credentials
-//! - **Topic Subscription**: Dynamic topic management with visual subscription state
+//! - **Connection Management**: Multiple server profiles with credentials, and
+//!   named connection profiles switching server and subscriptions at once
+//! - **Topic Subscription**: Dynamic topic management with visual subscription state,
+//!   plus a quick-bar of pinned topics for one-click (un)subscribing
 //! - **Message Monitoring**: Live message log with real-time updates
-//! - **Message Composition**: Built-in editor for testing message publishing
+//! - **Message Composition**: Built-in editor for testing message publishing,
+//!   exportable as a `mosquitto_pub` command line for sharing repro steps
 //! - **History Management**: Persistent message templates and debugging sessions
 //!
 //! ### Three-Panel Layout Architecture
@@ -30,6 +33,11 @@
 //! - **Left Panel (70%)**: Live message log for monitoring incoming traffic
 //! - **Right Panel (30%)**: Split into message composition and history access
 //! - **Header Controls**: Server/topic selection with visual connection status
+//!   and a Connect/Disconnect toggle
+//! - **Topic Tree**: The available topics grouped by level in collapsible
+//!   branches; whole branches are subscribed through their `#` wildcard
+//! - **Log Export**: Writes the live log to a CSV or JSON Lines file, see
+//!   [`crate::persistence::message_export`]
 //!
 //! ## Design Rationale
 //!
@@ -73,21 +81,40 @@
 //! - Configuration read failures fall back to default values
 //! - Network connectivity issues are indicated through visual status
 //! - Modal validation prevents invalid configurations from being saved
+//! - New servers can be test-connected from the modal without touching the live connection
 
-use super::common::{MQTTServer, UiColors};
-use crate::mqtt::config::MqttConfig;
-use crate::mqtt::message_manager::MQTTMessage;
+use super::common::{GamepadSelect, MQTTServer, UiColors};
+use crate::mqtt::config::{
+    MqttConfig, MqttProfile, MqttVersion, PublishTarget, MIN_KEEP_ALIVE_SECS, POLL_FREQUENCY_RANGE,
+};
+use crate::mqtt::message_manager::{parse_hex, MQTTMessage, PayloadFormat, TopicRates};
+use crate::mqtt::mqtt_handler::{
+    self, ConnectionTestError, SubscriptionRequest, SubscriptionState, SubscriptionStates,
+};
+use crate::mqtt::template::{TemplateVariables, COUNTER_VAR, TIMESTAMP_VAR};
+use crate::mqtt::topic_tree::{TopicNode, MULTI_LEVEL_WILDCARD};
 use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
+use crate::persistence::message_export::{self, LogFormat};
 use crate::persistence::persistence_worker::SessionAction;
+use crate::persistence::{SavedMessages, TimestampSettings, RECEIVED_LOG_LIMIT};
 use crate::session_action;
 use eframe::egui::{
-    self, vec2, Color32, ComboBox, Frame, Id, Label, Modal, ScrollArea, Stroke, TextEdit, Ui, Vec2,
+    self, vec2, CollapsingHeader, Color32, ComboBox, DragValue, Frame, Id, Label, Modal,
+    ScrollArea, Slider, Stroke, TextEdit, Ui, Vec2,
 };
 use std::cell::Cell;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::{mpsc, watch};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
 use tracing::{debug, error, info, warn};
 
+/// Content characters shown per entry of the message history dropdown.
+const HISTORY_PREVIEW_CHARS: usize = 40;
+
+/// Content characters shown per entry of the live message log.
+const LOG_PREVIEW_CHARS: usize = 120;
+
 /// Main data structure for the MQTT debugging and management interface.
 ///
 /// This structure manages the complete MQTT debugging workflow, from server
@@ -125,6 +152,15 @@ pub struct MQTTMenuData {
     /// Sender for outgoing MQTT messages to backend
     msg_sender: mpsc::Sender<MQTTMessage>,
 
+    /// Activation of the MQTT handler, switched by the Connect/Disconnect button
+    mqtt_active_tx: watch::Sender<bool>,
+
+    /// Per-topic subscription results reported by the backend
+    subscription_rx: watch::Receiver<SubscriptionStates>,
+
+    /// Sender for subscription retries and count resets
+    subscription_request_tx: mpsc::Sender<SubscriptionRequest>,
+
     /// Currently active MQTT server configuration
     active_server: MQTTServer,
 
@@ -140,6 +176,12 @@ pub struct MQTTMenuData {
     /// List of all available/known topics
     available_topics: Vec<String>,
 
+    /// Topics shown in the quick-bar for one-click subscription
+    pinned_topics: Vec<String>,
+
+    /// Saved server and subscription bundles
+    profiles: Vec<MqttProfile>,
+
     /// Persistent message history for debugging templates
     message_history: Vec<MQTTMessage>,
 
@@ -149,9 +191,78 @@ pub struct MQTTMenuData {
     /// Current message being composed in the editor
     current_message: String,
 
+    /// Where sent messages are published
+    publish_target: PublishTarget,
+
+    /// Target topic of [`PublishTarget::SelectedTopic`]
+    publish_topic: String,
+
+    /// Saved payload templates, placeholders unfilled
+    templates: Vec<MQTTMessage>,
+
+    /// Counter and user variables substituted into templates
+    template_vars: TemplateVariables,
+
+    /// Whether the editor content is a template filled in at send time
+    template_mode: bool,
+
+    /// Modal state for the template and variable dialog
+    editing_templates: bool,
+
+    /// Modal state for the topic tree
+    browsing_topic_tree: bool,
+
+    /// Modal state for the log export dialog
+    exporting_log: bool,
+
+    /// File format of the log export
+    export_format: LogFormat,
+
+    /// Target file or directory of the log export, home directory if empty
+    export_path: String,
+
+    /// Outcome of the last log export, shown in the export dialog
+    export_status: Option<String>,
+
+    /// New variable name input field
+    new_var_name: String,
+
+    /// New variable value input field
+    new_var_value: String,
+
+    /// Outcome of the last variable change, shown in the template dialog
+    template_status: Option<String>,
+
+    /// Whether the composed message is published with the retain flag
+    retain_message: bool,
+
+    /// Whether the editor content is hex bytes instead of text
+    hex_mode: bool,
+
+    /// Whether the copied `mosquitto_pub` command includes the password
+    export_password: bool,
+
     /// Live messages received during this session
     received_messages: Vec<MQTTMessage>,
 
+    /// MQTT config as last read from or written to the ConfigPortal
+    synced_config: MqttConfig,
+
+    /// Payload formatting used in the message log and history previews
+    payload_format: PayloadFormat,
+
+    /// Timestamp format and zone of the message log and history
+    timestamps: TimestampSettings,
+
+    /// Sliding-window arrival counts for the per-topic rate readout
+    topic_rates: TopicRates,
+
+    /// History message awaiting confirmation to re-subscribe its topic
+    pending_resend: Option<MQTTMessage>,
+
+    /// Session whose received log is currently shown, used to detect session loads
+    log_session: String,
+
     /// Modal state for server configuration dialog
     adding_server: Cell<bool>,
 
@@ -164,9 +275,36 @@ pub struct MQTTMenuData {
     /// New server password input field
     new_pw: String,
 
+    /// MQTT client ID presented to the broker
+    client_id: String,
+
+    /// MQTT keep-alive interval in seconds
+    keep_alive_secs: u16,
+
+    /// MQTT protocol version used for the connection
+    protocol_version: MqttVersion,
+
+    /// How often the connection handler checks for config changes, in Hz
+    poll_frequency: usize,
+
+    /// Running connection test of the server-add form
+    connection_test: Option<oneshot::Receiver<Result<(), ConnectionTestError>>>,
+
+    /// Outcome of the last connection test, shown in the server-add form
+    connection_test_status: Option<Result<(), String>>,
+
     /// Modal state for topic configuration dialog
     adding_topic: Cell<bool>,
 
+    /// Modal state for the profile management dialog
+    managing_profiles: bool,
+
+    /// Name input for saving the current setup as a profile
+    new_profile_name: String,
+
+    /// Outcome of the last profile change, shown in the profile dialog
+    profile_status: Option<String>,
+
     /// New topic input field
     new_topic: String,
 
@@ -189,11 +327,16 @@ impl MQTTMenuData {
     /// ## Channel Architecture
     /// - `received_msg`: Incoming MQTT messages for live display
     /// - `msg_sender`: Outgoing messages for publishing
+    /// - `mqtt_active_tx`: Activation of the connection, toggled by the user
+    /// - `subscription_rx`/`subscription_request_tx`: Subscription results and retry requests
     /// - `session_sender`: Session management for persistent storage
     ///
     /// # Parameters
     /// - `received_msg`: Channel receiver for incoming MQTT messages
     /// - `msg_sender`: Channel sender for outgoing MQTT messages  
+    /// - `mqtt_active_tx`: Watch sender switching the MQTT handler on and off
+    /// - `subscription_rx`: Watch receiver for per-topic subscription results
+    /// - `subscription_request_tx`: Channel sender for subscription retries and count resets
     /// - `config_portal`: Shared access to configuration system
     /// - `session_sender`: Channel for session management operations
     ///
@@ -203,11 +346,14 @@ impl MQTTMenuData {
     pub fn new(
         received_msg: mpsc::Receiver<MQTTMessage>,
         msg_sender: mpsc::Sender<MQTTMessage>,
+        mqtt_active_tx: watch::Sender<bool>,
+        subscription_rx: watch::Receiver<SubscriptionStates>,
+        subscription_request_tx: mpsc::Sender<SubscriptionRequest>,
         config_portal: Arc<ConfigPortal>,
         session_sender: mpsc::Sender<SessionAction>,
     ) -> Self {
         let config_res = config_portal.execute_potal_action(PortalAction::GetMqttConfig);
-        let msg_res = config_portal.execute_potal_action(PortalAction::GetSavedMessagesMsg);
+        let msg_res = config_portal.execute_potal_action(PortalAction::GetSavedMessages);
 
         let config = if let ConfigResult::MqttConfig(config) = config_res {
             config
@@ -216,27 +362,62 @@ impl MQTTMenuData {
             MqttConfig::default()
         };
 
-        let msg_history = if let ConfigResult::MqttMessages(msg) = msg_res {
-            msg
+        let saved = if let ConfigResult::MqttHistory(saved) = msg_res {
+            saved
         } else {
             warn!("Could not load MQTT Message history");
-            Vec::new()
+            SavedMessages::default()
         };
+        let msg_history = saved.msg;
+
+        let log_session = Self::session_name(&config_portal);
+        let received_messages = Self::load_received_log(&config_portal);
+        let timestamps = Self::load_timestamps(&config_portal);
 
         MQTTMenuData {
             config_portal,
             session_sender,
             received_msg,
             msg_sender,
+            mqtt_active_tx,
+            subscription_rx,
+            subscription_request_tx,
             active_server: config.server.clone(),
             saved_servers: config.available_servers.clone(),
             subscribed_topics: config.subbed_topics.clone(),
             available_topics: config.available_topics.clone(),
+            pinned_topics: config.pinned_topics.clone(),
+            profiles: config.profiles.clone(),
             message_history: msg_history.clone(),
             current_message: String::new(),
-            received_messages: vec![],
+            publish_target: config.publish_target,
+            publish_topic: config.publish_topic.clone(),
+            templates: saved.templates,
+            template_vars: saved.variables,
+            template_mode: false,
+            editing_templates: false,
+            browsing_topic_tree: false,
+            exporting_log: false,
+            export_format: LogFormat::default(),
+            export_path: String::new(),
+            export_status: None,
+            new_var_name: String::new(),
+            new_var_value: String::new(),
+            template_status: None,
+            retain_message: false,
+            hex_mode: false,
+            export_password: false,
+            received_messages,
+            payload_format: PayloadFormat::default(),
+            timestamps,
+            topic_rates: TopicRates::default(),
+            pending_resend: None,
+            log_session,
             adding_server: Cell::new(false),
             adding_topic: Cell::new(false),
+            managing_profiles: false,
+            new_profile_name: String::new(),
+            profile_status: None,
             selected_topic: String::new(),
             active_message: msg_history
                 .first()
@@ -245,8 +426,15 @@ impl MQTTMenuData {
             new_pw: String::new(),
             new_server_url: String::new(),
             new_user: String::new(),
+            client_id: config.client_id.clone(),
+            keep_alive_secs: config.keep_alive_secs,
+            protocol_version: config.protocol_version,
+            poll_frequency: config.poll_frequency,
+            connection_test: None,
+            connection_test_status: None,
             new_topic: String::new(),
             response_trigger: false,
+            synced_config: config,
         }
     }
 
@@ -280,7 +468,8 @@ impl MQTTMenuData {
     /// Implements pre/post update pattern:
     /// - **Pre-Update**: Loads latest configuration from ConfigPortal
     /// - **Render Phase**: UI operations with synchronized state
-    /// - **Post-Update**: Persists user changes back to ConfigPortal
+    /// - **Post-Update**: Persists user changes back to ConfigPortal, skipped
+    ///   when nothing changed
     ///
     /// This ensures UI reflects current configuration while capturing user changes
     /// for immediate persistence and backend notification.
@@ -295,22 +484,37 @@ impl MQTTMenuData {
         // Header section: server, topic controls, and connection status
         ui.horizontal(|ui| {
             ui.heading("MQTT");
+            self.profile_selection(ui);
             self.server_selection(ui);
             self.topic_selection(ui);
 
-            let status_color = if self.active_server.connected {
-                UiColors::ACTIVE
-            } else {
-                UiColors::INACTIVE
-            };
-            ui.colored_label(status_color, "\u{2B24}");
+            self.connection_toggle(ui);
+
+            if ui.button("Export log").clicked() {
+                self.exporting_log = true;
+                self.export_status = None;
+            }
+
+            let mut pretty = self.payload_format == PayloadFormat::PrettyJson;
+            if ui.checkbox(&mut pretty, "Pretty JSON").changed() {
+                self.payload_format = if pretty {
+                    PayloadFormat::PrettyJson
+                } else {
+                    PayloadFormat::Raw
+                };
+            }
         });
 
+        self.pinned_topic_bar(ui);
+        self.template_dialog(ui);
+        self.topic_tree_dialog(ui);
+        self.export_dialog(ui);
+
         let available_size = ui.available_size();
 
         Frame::new()
-            .stroke(Stroke::new(1.0, UiColors::BORDER))
-            .fill(UiColors::MAIN_BG)
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
             .inner_margin(4)
             .outer_margin(2)
             .show(ui, |ui| {
@@ -324,15 +528,20 @@ impl MQTTMenuData {
                 let message_history_height = 25.0;
                 let spacing_height = 10.0; // Total spacing allocation
 
+                let rate_area_height = 16.0 * self.subscribed_topics.len() as f32;
+
                 let panel_height = available_size.y - 50.0;
-                let editor_height =
-                    panel_height - button_area_height - message_history_height - spacing_height;
+                let editor_height = panel_height
+                    - button_area_height
+                    - message_history_height
+                    - rate_area_height
+                    - spacing_height;
 
                 ui.horizontal(|ui| {
                     // Left Panel: Real-time message log
                     Frame::new()
-                        .stroke(Stroke::new(1.0, UiColors::BORDER))
-                        .fill(UiColors::INNER_BG)
+                        .stroke(Stroke::new(1.0, UiColors::border()))
+                        .fill(UiColors::inner_bg())
                         .show(ui, |ui| {
                             ui.set_max_width(log_width);
                             ui.set_min_height(panel_height);
@@ -340,7 +549,7 @@ impl MQTTMenuData {
                             self.message_log(
                                 ui,
                                 Vec2::new(log_width, panel_height),
-                                UiColors::BORDER,
+                                UiColors::border(),
                             );
                         });
 
@@ -356,12 +565,18 @@ impl MQTTMenuData {
                             self.message_history(ui);
                         });
 
+                        // Per-topic message rates
+                        ui.vertical(|ui| {
+                            ui.set_min_height(rate_area_height);
+                            self.topic_rate_list(ui);
+                        });
+
                         ui.add_space(2.0);
 
                         // Message editor
                         Frame::new()
-                            .stroke(Stroke::new(1.0, UiColors::BORDER))
-                            .fill(UiColors::INNER_BG)
+                            .stroke(Stroke::new(1.0, UiColors::border()))
+                            .fill(UiColors::inner_bg())
                             .show(ui, |ui| {
                                 let editor_size = Vec2::new(right_width - 4.0, editor_height);
                                 self.msg_editor(ui, editor_size);
@@ -377,21 +592,72 @@ impl MQTTMenuData {
                                 ui.with_layout(
                                     egui::Layout::right_to_left(egui::Align::Center),
                                     |ui| {
-                                        if ui.button("Save").clicked() {
-                                            let msg = MQTTMessage::from_topic(
-                                                "OpenController".to_string(),
-                                                self.current_message.clone(),
-                                            );
-                                            self.save_msg(msg);
+                                        let payload = self.outgoing_payload();
+                                        let payload_error = payload.as_ref().err().cloned();
+                                        let valid = payload_error.is_none();
+                                        let save_label = if self.template_mode {
+                                            "Save template"
+                                        } else {
+                                            "Save"
+                                        };
+                                        if ui
+                                            .add_enabled(valid, egui::Button::new(save_label))
+                                            .clicked()
+                                        {
+                                            let msg =
+                                                self.composed_message(self.current_message.clone());
+                                            if self.template_mode {
+                                                self.save_template(msg);
+                                            } else {
+                                                self.save_msg(msg);
+                                            }
                                         }
                                         ui.add_space(2.0);
-                                        if ui.button("Send").clicked() {
-                                            let msg = MQTTMessage::from_topic(
-                                                "OpenController".to_string(),
-                                                self.current_message.clone(),
-                                            );
-                                            self.save_msg(msg.clone());
-                                            let _ = self.msg_sender.try_send(msg);
+                                        if ui
+                                            .add_enabled(
+                                                valid && self.is_connection_active(),
+                                                egui::Button::new("Send"),
+                                            )
+                                            .on_disabled_hover_text(
+                                                "Connect to the broker to send",
+                                            )
+                                            .clicked()
+                                        {
+                                            if let Ok(content) = payload {
+                                                let msg = self
+                                                    .composed_message(content)
+                                                    .with_retain(self.retain_message);
+                                                self.save_msg(msg.clone());
+                                                let _ = self.msg_sender.try_send(msg);
+                                                if self.template_mode {
+                                                    self.template_vars.advance_counter();
+                                                    self.store_templates();
+                                                }
+                                            }
+                                        }
+                                        self.publish_target_selection(ui);
+                                        ui.add_space(2.0);
+                                        if ui
+                                            .add_enabled(valid, egui::Button::new("Copy command"))
+                                            .on_hover_text("Copy as mosquitto_pub command line")
+                                            .clicked()
+                                        {
+                                            self.copy_publish_command(ui.ctx());
+                                        }
+                                        ui.checkbox(&mut self.export_password, "With password");
+                                        // Empty payload + retain clears the retained topic
+                                        ui.checkbox(&mut self.retain_message, "Retain");
+                                        ui.checkbox(&mut self.hex_mode, "Hex");
+                                        ui.checkbox(&mut self.template_mode, "Template")
+                                            .on_hover_text(format!(
+                                                "Fill in {{{{{}}}}}, {{{{{}}}}} and variables when sending",
+                                                TIMESTAMP_VAR, COUNTER_VAR
+                                            ));
+                                        if ui.button("Templates").clicked() {
+                                            self.editing_templates = true;
+                                        }
+                                        if let Some(e) = payload_error {
+                                            ui.colored_label(UiColors::inactive(), e);
                                         }
                                     },
                                 );
@@ -422,7 +688,7 @@ impl MQTTMenuData {
             .execute_potal_action(PortalAction::GetMqttConfig);
         let msg_res = self
             .config_portal
-            .execute_potal_action(PortalAction::GetSavedMessagesMsg);
+            .execute_potal_action(PortalAction::GetSavedMessages);
 
         let config = if let ConfigResult::MqttConfig(config) = config_res {
             config
@@ -431,61 +697,325 @@ impl MQTTMenuData {
             MqttConfig::default()
         };
 
-        let msg_history = if let ConfigResult::MqttMessages(msg) = msg_res {
-            msg
+        let saved = if let ConfigResult::MqttHistory(saved) = msg_res {
+            saved
         } else {
             warn!("Could not load MQTT Message history");
-            Vec::new()
+            SavedMessages::default()
         };
 
-        self.active_server = config.server;
-        self.available_topics = config.available_topics;
-        self.saved_servers = config.available_servers;
-        self.subscribed_topics = config.subbed_topics;
-        self.message_history = msg_history;
+        self.active_server = config.server.clone();
+        self.available_topics = config.available_topics.clone();
+        self.pinned_topics = config.pinned_topics.clone();
+        self.profiles = config.profiles.clone();
+        self.saved_servers = config.available_servers.clone();
+        self.subscribed_topics = config.subbed_topics.clone();
+        self.client_id = config.client_id.clone();
+        self.keep_alive_secs = config.keep_alive_secs;
+        self.protocol_version = config.protocol_version;
+        self.poll_frequency = config.poll_frequency;
+        self.publish_target = config.publish_target;
+        self.publish_topic = config.publish_topic.clone();
+        self.synced_config = config;
+        self.message_history = saved.msg;
+        self.templates = saved.templates;
+        self.template_vars = saved.variables;
+        self.timestamps = Self::load_timestamps(&self.config_portal);
+
+        // Restore the live log when a different session has been loaded
+        let session = Self::session_name(&self.config_portal);
+        if session != self.log_session {
+            self.received_messages = Self::load_received_log(&self.config_portal);
+            self.log_session = session;
+        }
+    }
+
+    /// Returns the name of the session currently held by the ConfigPortal.
+    fn session_name(config_portal: &ConfigPortal) -> String {
+        match config_portal.execute_potal_action(PortalAction::GetSessionName) {
+            ConfigResult::String(name) => name,
+            _ => String::new(),
+        }
+    }
+
+    /// Reads the timestamp settings of the current session.
+    fn load_timestamps(config_portal: &ConfigPortal) -> TimestampSettings {
+        match config_portal.execute_potal_action(PortalAction::GetTimestamps) {
+            ConfigResult::Timestamps(timestamps) => timestamps,
+            _ => TimestampSettings::default(),
+        }
+    }
+
+    /// Reads the persisted live message log of the current session.
+    fn load_received_log(config_portal: &ConfigPortal) -> Vec<MQTTMessage> {
+        match config_portal.execute_potal_action(PortalAction::GetReceivedLogMsg) {
+            ConfigResult::MqttMessages(msg) => msg,
+            _ => {
+                warn!("Could not load received MQTT message log");
+                Vec::new()
+            }
+        }
     }
 
     /// Persists current UI state back to ConfigPortal configuration.
     ///
-    /// Writes current MQTT configuration to the ConfigPortal at the end of
-    /// each frame to ensure user changes are immediately persisted and
-    /// available to other parts of the application.
+    /// Writes the MQTT configuration to the ConfigPortal at the end of a
+    /// frame, but only if the user changed something during the frame, so
+    /// user changes still reach the backend on the next config poll.
     ///
     /// ## Design Rationale
-    /// Called at frame end to capture any user modifications and trigger
-    /// backend reconfiguration through the ConfigPortal update mechanism.
-    fn post_update_config(&self) {
-        let new_config = MqttConfig {
+    /// Comparing against the configuration read in [`Self::pre_update_config`]
+    /// keeps idle frames from taking the write lock and marking the session
+    /// dirty 30 times a second. Fields the menu does not edit are carried over
+    /// from that read.
+    fn post_update_config(&mut self) {
+        let new_config = self.current_config();
+        if new_config == self.synced_config {
+            return;
+        }
+
+        if let ConfigResult::Failed(e) = self
+            .config_portal
+            .execute_potal_action(PortalAction::WriteMqttConfig(new_config.clone()))
+        {
+            warn!("Failed to store MQTT config: {}", e);
+            return;
+        }
+        self.synced_config = new_config;
+    }
+
+    /// Returns the MQTT configuration as currently edited in the menu.
+    fn current_config(&self) -> MqttConfig {
+        MqttConfig {
             available_topics: self.available_topics.clone(),
             subbed_topics: self.subscribed_topics.clone(),
+            pinned_topics: self.pinned_topics.clone(),
             server: self.active_server.clone(),
             available_servers: self.saved_servers.clone(),
-            poll_frequency: 10,
-        };
+            client_id: self.client_id.clone(),
+            keep_alive_secs: self.keep_alive_secs,
+            protocol_version: self.protocol_version,
+            poll_frequency: self.poll_frequency,
+            profiles: self.profiles.clone(),
+            publish_target: self.publish_target,
+            publish_topic: self.publish_topic.clone(),
+        }
+    }
 
-        let _res = self
-            .config_portal
-            .execute_potal_action(PortalAction::WriteMqttConfig(new_config));
+    /// Switches server and subscriptions to those of `profile`.
+    ///
+    /// Only the menu state changes here; [`Self::post_update_config`] writes
+    /// the result as one config change at the end of the frame, so the
+    /// connection handler reconnects and resubscribes in one go.
+    fn apply_profile(&mut self, profile: &MqttProfile) {
+        info!("Switching to MQTT profile {}", profile.name);
+        let mut config = self.current_config();
+        config.apply_profile(profile);
+        self.active_server = config.server;
+        self.saved_servers = config.available_servers;
+        self.available_topics = config.available_topics;
+        self.subscribed_topics = config.subbed_topics;
+    }
+
+    /// Renders the profile quick-switch and the profile management dialog.
+    ///
+    /// Selecting a profile applies it right away. The dialog saves the active
+    /// server with its subscriptions under a new name, overwrites existing
+    /// profiles with them, or deletes profiles.
+    fn profile_selection(&mut self, ui: &mut Ui) {
+        let active = self
+            .profiles
+            .iter()
+            .find(|profile| {
+                profile.server.same_account(&self.active_server)
+                    && profile.topics == self.subscribed_topics
+            })
+            .map(|profile| profile.name.clone());
+
+        let mut selected: Option<MqttProfile> = None;
+        ComboBox::from_id_salt("mqtt_profile")
+            .selected_text(active.as_deref().unwrap_or("Profile"))
+            .show_ui(ui, |ui| {
+                for profile in &self.profiles {
+                    let is_active = active.as_ref() == Some(&profile.name);
+                    if ui
+                        .selectable_label(is_active, profile.name.as_str())
+                        .on_hover_text(format!(
+                            "{}, {} topic(s)",
+                            profile.server,
+                            profile.topics.len()
+                        ))
+                        .clicked()
+                    {
+                        selected = Some(profile.clone());
+                    }
+                }
+                ui.toggle_value(&mut self.managing_profiles, "Manage Profiles");
+            });
+        if let Some(profile) = selected {
+            self.apply_profile(&profile);
+        }
+
+        if !self.managing_profiles {
+            self.profile_status = None;
+            return;
+        }
+
+        let modal = Modal::new(Id::new("Modal Profiles"));
+        modal.show(ui.ctx(), |ui| {
+            ui.set_width(300.0);
+            ui.heading("Profiles");
+
+            let current = MqttProfile {
+                name: String::new(),
+                server: MQTTServer {
+                    connected: false,
+                    ..self.active_server.clone()
+                },
+                topics: self.subscribed_topics.clone(),
+            };
+
+            let mut delete: Option<usize> = None;
+            egui::Grid::new("mqtt_profiles")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (idx, profile) in self.profiles.iter_mut().enumerate() {
+                        ui.label(profile.name.as_str()).on_hover_text(format!(
+                            "{}\n{}",
+                            profile.server,
+                            profile.topics.join("\n")
+                        ));
+                        if ui
+                            .button("Update")
+                            .on_hover_text("Store the active server and subscriptions")
+                            .clicked()
+                        {
+                            profile.server = current.server.clone();
+                            profile.topics = current.topics.clone();
+                            self.profile_status = Some(format!("Updated {}", profile.name));
+                        }
+                        if ui.button("Delete").clicked() {
+                            delete = Some(idx);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(idx) = delete {
+                let removed = self.profiles.remove(idx);
+                self.profile_status = Some(format!("Deleted {}", removed.name));
+            }
+
+            ui.separator();
+            ui.label("Save active server and subscriptions as");
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut self.new_profile_name);
+                if ui.button("Save").clicked() {
+                    let name = self.new_profile_name.trim().to_string();
+                    self.profile_status = Some(if name.is_empty() {
+                        "Profile name empty!".to_string()
+                    } else if self.profiles.iter().any(|profile| profile.name == name) {
+                        format!("{} already exists, use Update", name)
+                    } else if current.server.url.trim().is_empty() {
+                        "No active server".to_string()
+                    } else {
+                        self.profiles.push(MqttProfile { name, ..current });
+                        self.new_profile_name.clear();
+                        "Saved!".to_string()
+                    });
+                }
+            });
+            if let Some(status) = &self.profile_status {
+                ui.label(status);
+            }
+
+            ui.separator();
+            if ui.button("Close").clicked() {
+                self.managing_profiles = false;
+            }
+        });
+    }
+
+    /// Renders the quick-bar of pinned topics above the message log.
+    ///
+    /// A click subscribes to or unsubscribes from a topic, like selecting it
+    /// in the topic list; right-click unpins it. Pinned topics that are no
+    /// longer available are greyed out and only offer unpinning.
+    fn pinned_topic_bar(&mut self, ui: &mut Ui) {
+        if self.pinned_topics.is_empty() {
+            return;
+        }
+
+        let mut unpin: Option<String> = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label("\u{1F4CC}");
+            for topic in &self.pinned_topics {
+                if !self.available_topics.contains(topic) {
+                    ui.add_enabled(false, egui::SelectableLabel::new(false, topic.as_str()))
+                        .on_disabled_hover_text("No longer in the available topics");
+                    if ui.small_button("\u{D7}").on_hover_text("Unpin").clicked() {
+                        unpin = Some(topic.clone());
+                    }
+                    continue;
+                }
+
+                let position = self.subscribed_topics.iter().position(|sub| sub == topic);
+                let response = ui
+                    .selectable_label(position.is_some(), topic.as_str())
+                    .on_hover_text("Click to (un)subscribe, right-click to unpin");
+                if response.clicked() {
+                    match position {
+                        Some(pos) => {
+                            self.subscribed_topics.remove(pos);
+                            debug!("Deactivate pinned topic {}", topic);
+                        }
+                        None => {
+                            self.subscribed_topics.push(topic.clone());
+                            debug!("Activate pinned topic {}", topic);
+                        }
+                    }
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Unpin").clicked() {
+                        unpin = Some(topic.clone());
+                        ui.close_menu();
+                    }
+                });
+            }
+        });
+
+        if let Some(topic) = unpin {
+            toggle_pin(&mut self.pinned_topics, &topic);
+        }
     }
 
     /// Renders the MQTT server selection interface with add-server capability.
     ///
-    /// Provides a ComboBox for selecting from saved servers plus a modal dialog
-    /// for adding new server configurations with validation.
+    /// Provides a [`GamepadSelect`] for picking one of the saved servers plus a
+    /// modal dialog for adding new server configurations with validation.
     ///
     /// ## Modal Integration
     /// Uses egui's modal system for server configuration to maintain focus
     /// within the main application window and support controller navigation.
     /// Includes validation for required fields and user feedback.
     fn server_selection(&mut self, ui: &mut Ui) {
-        ComboBox::from_id_salt("mqtt_server")
+        GamepadSelect::new("mqtt_server")
             .selected_text(self.active_server.to_string())
-            .show_ui(ui, |ui| {
-                for serv in &mut self.saved_servers {
-                    ui.selectable_value(&mut self.active_server, serv.to_owned(), serv.to_string());
-                }
-                ui.toggle_value(self.adding_server.get_mut(), "Add Server");
-            });
+            .show(
+                ui,
+                &mut self.active_server,
+                self.saved_servers
+                    .iter()
+                    .map(|serv| (serv.to_owned(), serv.to_string())),
+            );
+        ui.toggle_value(self.adding_server.get_mut(), "Add Server");
+
+        self.poll_connection_test(ui.ctx());
+        if !self.adding_server.get() {
+            // Results of a closed form would be stale when it reopens
+            self.connection_test = None;
+            self.connection_test_status = None;
+        }
 
         if self.adding_server.get() {
             let modal = Modal::new(Id::new("Modal A"));
@@ -506,46 +1036,124 @@ impl MQTTMenuData {
                 ui.label("Password");
                 ui.text_edit_singleline(new_pw);
 
+                // Connection parameters shared by all servers
+                ui.label("Client ID");
+                ui.text_edit_singleline(&mut self.client_id);
+                ui.label("Keep-alive (s)");
+                ui.add(
+                    DragValue::new(&mut self.keep_alive_secs).range(MIN_KEEP_ALIVE_SECS..=u16::MAX),
+                );
+                if let Err(e) = MqttConfig::validate_keep_alive(self.keep_alive_secs) {
+                    ui.colored_label(UiColors::inactive(), e);
+                }
+                ui.label("Config poll rate (Hz)")
+                    .on_hover_text("Higher rates apply menu changes sooner but cost more CPU");
+                ui.add(Slider::new(&mut self.poll_frequency, POLL_FREQUENCY_RANGE));
+                ui.label("Protocol");
+                GamepadSelect::new("mqtt_protocol_version")
+                    .selected_text(self.protocol_version.to_string())
+                    .show(
+                        ui,
+                        &mut self.protocol_version,
+                        MqttVersion::ALL.map(|version| (version, version.to_string())),
+                    );
+
+                ui.horizontal(|ui| {
+                    let testing = self.connection_test.is_some();
+                    if ui
+                        .add_enabled(!testing, egui::Button::new("Test"))
+                        .clicked()
+                    {
+                        self.connection_test_status =
+                            match Self::validate_server(new_server_url, new_user) {
+                                Ok(()) => {
+                                    let server = MQTTServer {
+                                        url: new_server_url.trim().to_owned(),
+                                        user: new_user.to_owned(),
+                                        pw: new_pw.to_owned(),
+                                        connected: false,
+                                    };
+                                    self.connection_test =
+                                        Some(mqtt_handler::spawn_connection_test(
+                                            server,
+                                            self.client_id.clone(),
+                                            Duration::from_secs(self.keep_alive_secs.into()),
+                                            self.protocol_version,
+                                        ));
+                                    None
+                                }
+                                Err(e) => Some(Err(e)),
+                            };
+                    }
+
+                    match &self.connection_test_status {
+                        _ if testing => {
+                            ui.spinner();
+                            ui.label("Testing...");
+                        }
+                        Some(Ok(())) => {
+                            ui.colored_label(UiColors::active(), "Connection OK");
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(UiColors::inactive(), e);
+                        }
+                        None => {}
+                    }
+                });
+
                 ui.separator();
 
                 egui::Sides::new().show(
                     ui,
                     |left| {
-                        let mut validation = (true, true);
                         if left.button("Save").clicked() {
                             self.response_trigger = true;
-                            validation = (new_server_url.is_empty(), new_user.is_empty());
                         }
 
                         if self.response_trigger {
                             let err_msg = Modal::new(Id::new("ValidationErr"));
-                            let err_response = match validation {
-                                (true, true) => err_msg.show(left.ctx(), |pop| {
-                                    pop.set_width(100.0);
-                                    pop.label("Server and User empty!");
+                            let err_response = match Self::validate_server(new_server_url, new_user)
+                            {
+                                Err(e) => err_msg.show(left.ctx(), |pop| {
+                                    pop.set_width(200.0);
+                                    pop.label(e);
                                 }),
-                                (true, false) => err_msg.show(left.ctx(), |pop| {
-                                    pop.set_width(100.0);
-                                    pop.label("Server empty!");
-                                }),
-                                (false, true) => err_msg.show(left.ctx(), |pop| {
-                                    pop.set_width(100.0);
-                                    pop.label("User empty!");
-                                }),
-                                (false, false) => {
+                                Ok(()) => {
                                     let new_server = MQTTServer {
-                                        url: new_server_url.to_owned(),
+                                        url: new_server_url.trim().to_owned(),
                                         user: new_user.to_owned(),
                                         pw: new_pw.to_owned(),
                                         connected: false,
                                     };
-                                    self.response_trigger = false;
-                                    add_server.set(false);
-
-                                    servers.push(new_server);
-                                    err_msg.show(left.ctx(), |pop| {
-                                        pop.label("Saved!");
-                                    })
+                                    let duplicate =
+                                        servers.iter().position(|s| s.same_account(&new_server));
+
+                                    match duplicate {
+                                        Some(idx) => err_msg.show(left.ctx(), |pop| {
+                                            pop.set_width(200.0);
+                                            pop.label(format!(
+                                                "{} is already saved.",
+                                                servers[idx]
+                                            ));
+                                            if pop.button("Update password").clicked() {
+                                                servers[idx].pw = new_server.pw.clone();
+                                                if self.active_server.same_account(&new_server) {
+                                                    self.active_server.pw = new_server.pw.clone();
+                                                }
+                                                self.response_trigger = false;
+                                                add_server.set(false);
+                                            }
+                                        }),
+                                        None => {
+                                            self.response_trigger = false;
+                                            add_server.set(false);
+
+                                            servers.push(new_server);
+                                            err_msg.show(left.ctx(), |pop| {
+                                                pop.label("Saved!");
+                                            })
+                                        }
+                                    }
                                 }
                             };
                             if err_response.should_close() {
@@ -563,6 +1171,44 @@ impl MQTTMenuData {
         }
     }
 
+    /// Picks up the result of a running connection test.
+    ///
+    /// Keeps repainting while the test runs so the result shows up without
+    /// waiting for other input.
+    fn poll_connection_test(&mut self, ctx: &egui::Context) {
+        let Some(result_rx) = &mut self.connection_test else {
+            return;
+        };
+        let status = match result_rx.try_recv() {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(oneshot::error::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+                return;
+            }
+            Err(oneshot::error::TryRecvError::Closed) => {
+                Err("Connection test was aborted".to_string())
+            }
+        };
+        self.connection_test_status = Some(status);
+        self.connection_test = None;
+    }
+
+    /// Checks the server-add form before a new server is stored.
+    ///
+    /// Uses [`MqttConfig::parse_endpoint`] so the UI rejects exactly the URLs
+    /// the connection handler cannot use.
+    fn validate_server(url: &str, user: &str) -> Result<(), String> {
+        match (url.trim().is_empty(), user.is_empty()) {
+            (true, true) => return Err("Server and User empty!".to_string()),
+            (true, false) => return Err("Server empty!".to_string()),
+            (false, true) => return Err("User empty!".to_string()),
+            (false, false) => {}
+        }
+        MqttConfig::parse_endpoint(url)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
     /// Renders the MQTT topic selection and subscription management interface.
     ///
     /// Provides dynamic topic subscription/unsubscription with visual indication
@@ -572,6 +1218,16 @@ impl MQTTMenuData {
     /// Implements toggle-based subscription: clicking a subscribed topic
     /// unsubscribes it, clicking an unsubscribed topic subscribes it.
     /// Visual highlighting indicates current subscription status.
+    ///
+    /// ## Failed Subscriptions
+    /// Topics the broker rejected (e.g. by ACL) get a red marker with the
+    /// reason on hover and a Retry button. A summary next to the selector
+    /// keeps failures visible while the list is closed.
+    ///
+    /// ## Message Counts
+    /// Subscribed topics show the messages received through them, e.g.
+    /// `sensors/temp (42)`. The handler counts them; "Reset Counts" asks it to
+    /// start over.
     fn topic_selection(&mut self, ui: &mut Ui) {
         let none_topic = String::new();
         let selected_topic = &mut self.selected_topic;
@@ -580,30 +1236,90 @@ impl MQTTMenuData {
 
         let available_topics = &mut self.available_topics;
         let subscribed_topics = &mut self.subscribed_topics;
+        let pinned_topics = &mut self.pinned_topics;
+        let browsing_topic_tree = &mut self.browsing_topic_tree;
+        let subscription_states = self.subscription_rx.borrow().clone();
+        let mut requests: Vec<SubscriptionRequest> = Vec::new();
 
         ComboBox::from_id_salt("topic_selector")
             .selected_text("Select Topics".to_string())
             .show_ui(ui, |ui| {
                 for availabel in available_topics.clone() {
-                    if subscribed_topics.iter().any(|sub| *sub == availabel) {
-                        ui.selectable_value(
-                            selected_topic,
-                            availabel.clone(),
-                            availabel.to_string(),
-                        )
-                        .highlight();
-                    } else {
-                        ui.selectable_value(
-                            selected_topic,
-                            availabel.clone(),
-                            availabel.to_string(),
-                        );
-                    }
+                    ui.horizontal(|ui| {
+                        let mut pinned = pinned_topics.contains(&availabel);
+                        if ui
+                            .toggle_value(&mut pinned, "\u{1F4CC}")
+                            .on_hover_text("Pin to the quick-bar")
+                            .changed()
+                        {
+                            toggle_pin(pinned_topics, &availabel);
+                        }
+
+                        if subscribed_topics.contains(&availabel) {
+                            let subscription = subscription_states.get(&availabel);
+                            let label = match subscription {
+                                Some(subscription) => {
+                                    format!("{} ({})", availabel, subscription.received)
+                                }
+                                None => availabel.to_string(),
+                            };
+                            ui.selectable_value(selected_topic, availabel.clone(), label)
+                                .highlight();
+
+                            if let Some(SubscriptionState::Failed(reason)) =
+                                subscription.map(|subscription| &subscription.state)
+                            {
+                                ui.colored_label(UiColors::inactive(), "\u{2B24}")
+                                    .on_hover_text(reason);
+                                if ui.small_button("Retry").clicked() {
+                                    requests.push(SubscriptionRequest::Retry(availabel.clone()));
+                                }
+                            }
+                        } else {
+                            ui.selectable_value(
+                                selected_topic,
+                                availabel.clone(),
+                                availabel.to_string(),
+                            );
+                        }
+                    });
                 }
 
-                ui.toggle_value(add_topic.get_mut(), "Add Topic");
+                ui.horizontal(|ui| {
+                    ui.toggle_value(add_topic.get_mut(), "Add Topic");
+                    ui.toggle_value(browsing_topic_tree, "Topic Tree");
+                    if ui.button("Reset Counts").clicked() {
+                        requests.push(SubscriptionRequest::ResetCounts);
+                    }
+                });
             });
 
+        let failed: Vec<String> = subscribed_topics
+            .iter()
+            .filter_map(
+                |topic| match subscription_states.get(topic).map(|sub| &sub.state) {
+                    Some(SubscriptionState::Failed(reason)) => {
+                        Some(format!("{}: {}", topic, reason))
+                    }
+                    _ => None,
+                },
+            )
+            .collect();
+        if !failed.is_empty() {
+            ui.colored_label(
+                UiColors::inactive(),
+                format!("{} subscription(s) failed", failed.len()),
+            )
+            .on_hover_text(failed.join("\n"));
+        }
+
+        for request in requests {
+            info!("Subscription request: {:?}", request);
+            if let Err(e) = self.subscription_request_tx.try_send(request) {
+                warn!("Failed to send subscription request: {}", e);
+            }
+        }
+
         let validate = (
             !selected_topic.is_empty(),
             subscribed_topics.iter().any(|sub| *sub == *selected_topic),
@@ -687,38 +1403,162 @@ impl MQTTMenuData {
     ///
     /// Provides quick access to previously saved messages for debugging workflows,
     /// loading selected messages into the editor for modification and resending.
+    ///
+    /// "Resend" republishes the selected message to its stored topic. If that
+    /// topic is no longer subscribed, the user is asked to re-add it first.
     fn message_history(&mut self, ui: &mut Ui) {
-        ComboBox::from_id_salt("message history")
-            .selected_text("Message History")
-            .show_ui(ui, |ui| {
-                for message in &mut self.message_history {
-                    if ui
-                        .selectable_value(
-                            &mut self.active_message,
-                            message.clone(),
-                            message.to_string(),
-                        )
-                        .clicked()
-                    {
-                        self.current_message = self.active_message.content.clone();
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("message history")
+                .selected_text("Message History")
+                .show_ui(ui, |ui| {
+                    for message in &mut self.message_history {
+                        if ui
+                            .selectable_value(
+                                &mut self.active_message,
+                                message.clone(),
+                                message.preview(HISTORY_PREVIEW_CHARS, &self.timestamps),
+                            )
+                            .on_hover_text(message.render(self.payload_format, &self.timestamps))
+                            .clicked()
+                        {
+                            self.current_message = self.active_message.content.clone();
+                            self.hex_mode = self.active_message.binary;
+                        }
                     }
+                });
+
+            let has_selection = !self.active_message.topic.is_empty();
+            if ui
+                .add_enabled(has_selection, egui::Button::new("Resend"))
+                .clicked()
+            {
+                let msg = self.active_message.clone();
+                if self.subscribed_topics.contains(&msg.topic) {
+                    self.republish(&msg);
+                } else {
+                    self.pending_resend = Some(msg);
                 }
-            });
+            }
+        });
+
+        self.resend_prompt(ui);
+    }
+
+    /// Asks whether to re-subscribe to a resent message's topic.
+    ///
+    /// Confirming adds the topic to the available and subscribed lists
+    /// before republishing, cancelling drops the resend.
+    fn resend_prompt(&mut self, ui: &mut Ui) {
+        let Some(msg) = self.pending_resend.clone() else {
+            return;
+        };
+
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let modal = Modal::new(Id::new("ResendTopicPrompt")).show(ui.ctx(), |ui| {
+            ui.set_width(250.0);
+            ui.heading("Topic not subscribed");
+            ui.label(format!(
+                "'{}' is no longer subscribed. Add it and resend?",
+                msg.topic
+            ));
+            ui.separator();
+
+            egui::Sides::new().show(
+                ui,
+                |left| confirmed = left.button("Add & Resend").clicked(),
+                |right| cancelled = right.button("Cancel").clicked(),
+            );
+        });
+
+        if confirmed {
+            if !self.available_topics.contains(&msg.topic) {
+                self.available_topics.push(msg.topic.clone());
+            }
+            self.subscribed_topics.push(msg.topic.clone());
+            self.republish(&msg);
+        }
+        if confirmed || cancelled || modal.should_close() {
+            self.pending_resend = None;
+        }
+    }
+
+    /// Whether the MQTT handler is switched on.
+    fn is_connection_active(&self) -> bool {
+        *self.mqtt_active_tx.borrow()
+    }
+
+    /// Renders the connection state and the button switching it.
+    ///
+    /// Disconnecting sends a clean DISCONNECT, so the broker does not publish
+    /// the last will. Connecting builds a new client and resubscribes.
+    fn connection_toggle(&mut self, ui: &mut Ui) {
+        let active = self.is_connection_active();
+        let (status_color, status, toggle) = if active {
+            (UiColors::active(), "Connection enabled", "Disconnect")
+        } else {
+            (UiColors::inactive(), "Disconnected", "Connect")
+        };
+        ui.colored_label(status_color, "\u{2B24}")
+            .on_hover_text(status);
+        if ui.button(toggle).clicked() {
+            info!(
+                "MQTT connection {} by user",
+                if active { "disabled" } else { "enabled" }
+            );
+            self.mqtt_active_tx.send_replace(!active);
+        }
+    }
+
+    /// Publishes a copy of `msg` to its original topic with a fresh timestamp.
+    fn republish(&self, msg: &MQTTMessage) {
+        let resend = MQTTMessage::from_topic(msg.topic.clone(), msg.content.clone())
+            .with_retain(msg.retain)
+            .with_binary(msg.binary);
+        match self.msg_sender.try_send(resend) {
+            Ok(()) => debug!("Resent message to {}", msg.topic),
+            Err(e) => warn!("Failed to resend message to {}: {}", msg.topic, e),
+        }
+    }
+
+    /// Renders the live message rate for each subscribed topic.
+    ///
+    /// Rates cover the last [`RATE_WINDOW`](crate::mqtt::message_manager::RATE_WINDOW),
+    /// so a topic that stops publishing falls back to 0/s within a second.
+    fn topic_rate_list(&mut self, ui: &mut Ui) {
+        self.topic_rates.decay();
+        for topic in &self.subscribed_topics {
+            let rate = self.topic_rates.rate(topic);
+            let color = if rate > 0 {
+                UiColors::active()
+            } else {
+                UiColors::inactive()
+            };
+            ui.small(egui::RichText::new(format!("{}: {}/s", topic, rate)).color(color));
+        }
     }
 
     /// Renders the real-time MQTT message log with live message reception.
     ///
     /// Displays incoming MQTT messages in real-time with click-to-copy functionality
     /// for debugging workflows. Uses ScrollArea for efficient rendering of large
-    /// message volumes.
+    /// message volumes. Each entry is a one-line preview, hovering it shows the
+    /// full message in the selected payload format.
     ///
     /// ## Performance Considerations
     /// Processes incoming messages without blocking UI thread, maintaining
     /// responsiveness during high message frequency scenarios.
     fn message_log(&mut self, ui: &mut Ui, size: Vec2, border_color: Color32) {
-        let new_incoming_msg = self.received_msg.try_recv();
-        if let Ok(msg) = new_incoming_msg {
+        while let Ok(msg) = self.received_msg.try_recv() {
+            self.topic_rates.record(&msg.topic);
+            // Mirror into the session so the live log survives restarts
+            let _res = self
+                .config_portal
+                .execute_potal_action(PortalAction::AppendReceivedLogMsg(msg.clone()));
             self.received_messages.push(msg);
+            if self.received_messages.len() > RECEIVED_LOG_LIMIT {
+                self.received_messages.remove(0);
+            }
         }
 
         Frame::new()
@@ -734,18 +1574,36 @@ impl MQTTMenuData {
                             Frame::new()
                                 .stroke(Stroke::new(1.0, border_color))
                                 .inner_margin(2)
-                                .fill(UiColors::EXTREME_BG)
+                                .fill(UiColors::extreme_bg())
                                 .show(ui, |ui| {
+                                    ui.horizontal(|ui| {
+                                        for badge in msg.badges() {
+                                            ui.label(
+                                                egui::RichText::new(badge)
+                                                    .monospace()
+                                                    .small()
+                                                    .background_color(UiColors::inner_bg()),
+                                            );
+                                        }
+                                    });
                                     if ui
                                         .add_sized(
                                             vec2(size.x, size.y / 6.0),
-                                            Label::new(msg.render())
-                                                .selectable(true)
-                                                .sense(egui::Sense::click()),
+                                            Label::new(
+                                                msg.preview(LOG_PREVIEW_CHARS, &self.timestamps),
+                                            )
+                                            .selectable(true)
+                                            .sense(egui::Sense::click()),
+                                        )
+                                        .on_hover_text(
+                                            msg.render(self.payload_format, &self.timestamps),
                                         )
                                         .clicked()
                                     {
-                                        info!("MSG: {} \n COPIED!", msg.render());
+                                        info!(
+                                            "MSG: {} \n COPIED!",
+                                            msg.render(PayloadFormat::Raw, &self.timestamps)
+                                        );
                                         // TODO: Implement clipboard copy functionality
                                     }
                                 });
@@ -759,18 +1617,466 @@ impl MQTTMenuData {
     /// Renders the message composition editor for creating MQTT messages.
     ///
     /// Provides a multi-line text editor with syntax highlighting for composing
-    /// MQTT message payloads with debugging-friendly features.
+    /// MQTT message payloads with debugging-friendly features. In hex mode the
+    /// content is read as bytes, e.g. `DE AD BE EF`.
     fn msg_editor(&mut self, ui: &mut Ui, size: Vec2) {
         let textbuffer = &mut self.current_message;
+        let hint = if self.hex_mode {
+            "DE AD BE EF"
+        } else {
+            "Nachricht eingeben..."
+        };
         ScrollArea::vertical().id_salt("msg_editor").show(ui, |ui| {
             TextEdit::multiline(textbuffer)
                 .min_size(size)
-                .hint_text("Nachricht eingeben...")
+                .hint_text(hint)
                 .code_editor()
                 .show(ui);
         });
     }
 
+    /// Renders the publish target mode and, for the selected topic mode, the
+    /// topic to publish to.
+    ///
+    /// - **All subscribed**: a copy goes to every subscribed topic
+    /// - **Selected topic**: only the topic picked here, nothing without one
+    /// - **Message topic**: the message's own topic, e.g. a history entry's
+    fn publish_target_selection(&mut self, ui: &mut Ui) {
+        if self.publish_target == PublishTarget::SelectedTopic {
+            let selected_text = if self.publish_topic.is_empty() {
+                "No topic"
+            } else {
+                self.publish_topic.as_str()
+            };
+            GamepadSelect::new("mqtt_publish_topic")
+                .selected_text(selected_text)
+                .show(
+                    ui,
+                    &mut self.publish_topic,
+                    self.available_topics
+                        .iter()
+                        .map(|topic| (topic.clone(), topic.clone())),
+                );
+        }
+        GamepadSelect::new("mqtt_publish_target")
+            .selected_text(self.publish_target.to_string())
+            .show(
+                ui,
+                &mut self.publish_target,
+                PublishTarget::ALL.map(|target| (target, target.to_string())),
+            );
+    }
+
+    /// Builds a message from the editor content in the current mode.
+    ///
+    /// In [`PublishTarget::SelectedTopic`] mode the message carries the
+    /// selected topic, so saved templates and copied commands show it.
+    fn composed_message(&self, content: String) -> MQTTMessage {
+        let topic = match self.publish_target {
+            PublishTarget::SelectedTopic if !self.publish_topic.is_empty() => {
+                self.publish_topic.clone()
+            }
+            _ => "OpenController".to_string(),
+        };
+        MQTTMessage::from_topic(topic, content).with_binary(self.hex_mode)
+    }
+
+    /// Copies the composed message as a `mosquitto_pub` command line.
+    ///
+    /// Uses the active server and protocol version. The password is only
+    /// included if "With password" is checked.
+    fn copy_publish_command(&self, ctx: &egui::Context) {
+        let content = match self.outgoing_payload() {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Cannot build mosquitto_pub command: {}", e);
+                return;
+            }
+        };
+        let msg = self
+            .composed_message(content)
+            .with_retain(self.retain_message);
+        match msg.mosquitto_pub_command(
+            &self.active_server,
+            self.protocol_version,
+            self.export_password,
+        ) {
+            Ok(command) => {
+                ctx.copy_text(command);
+                info!("Copied mosquitto_pub command for {}", msg.topic);
+            }
+            Err(e) => warn!("Cannot build mosquitto_pub command: {}", e),
+        }
+    }
+
+    /// Returns the payload that would be sent right now.
+    ///
+    /// In template mode the placeholders are filled in with the current
+    /// counter, which only advances once the message was actually sent.
+    ///
+    /// # Errors
+    /// Returns why the editor content cannot be sent: an unknown or unclosed
+    /// placeholder, or invalid hex bytes in hex mode.
+    fn outgoing_payload(&self) -> Result<String, String> {
+        let content = if self.template_mode {
+            self.template_vars
+                .render(&self.current_message)
+                .map_err(|e| e.to_string())?
+        } else {
+            self.current_message.clone()
+        };
+        if self.hex_mode {
+            parse_hex(&content).map_err(|e| e.to_string())?;
+        }
+        Ok(content)
+    }
+
+    /// Stores the editor content as a template, replacing one with the same
+    /// topic and content.
+    fn save_template(&mut self, template: MQTTMessage) {
+        self.templates
+            .retain(|saved| saved.topic != template.topic || saved.content != template.content);
+        self.templates.push(template);
+        self.store_templates();
+    }
+
+    /// Persists templates and variables through the ConfigPortal.
+    ///
+    /// The history is re-read from the ConfigPortal so a concurrent history
+    /// write is not lost. Called on every variable edit, since
+    /// [`Self::pre_update_config`] reloads the variables each frame.
+    fn store_templates(&self) {
+        let mut saved = match self
+            .config_portal
+            .execute_potal_action(PortalAction::GetSavedMessages)
+        {
+            ConfigResult::MqttHistory(saved) => saved,
+            _ => SavedMessages {
+                msg: self.message_history.clone(),
+                ..Default::default()
+            },
+        };
+        saved.templates = self.templates.clone();
+        saved.variables = self.template_vars.clone();
+
+        if let ConfigResult::Failed(e) = self
+            .config_portal
+            .execute_potal_action(PortalAction::WriteSavedMessages(saved))
+        {
+            warn!("Failed to store MQTT templates: {}", e);
+        }
+    }
+
+    /// Renders the dialog listing templates and template variables.
+    ///
+    /// Loading a template puts it into the editor and enables template mode.
+    /// Variables are stored as soon as they are added, edited or deleted.
+    fn template_dialog(&mut self, ui: &mut Ui) {
+        if !self.editing_templates {
+            self.template_status = None;
+            return;
+        }
+
+        let mut changed = false;
+        Modal::new(Id::new("Modal Templates")).show(ui.ctx(), |ui| {
+            ui.set_width(320.0);
+            ui.heading("Templates");
+
+            let mut load: Option<MQTTMessage> = None;
+            let mut delete: Option<usize> = None;
+            egui::Grid::new("mqtt_templates")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (idx, template) in self.templates.iter().enumerate() {
+                        ui.label(template.preview(HISTORY_PREVIEW_CHARS, &self.timestamps))
+                            .on_hover_text(template.content.as_str());
+                        if ui.button("Load").clicked() {
+                            load = Some(template.clone());
+                        }
+                        if ui.button("Delete").clicked() {
+                            delete = Some(idx);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(template) = load {
+                self.current_message = template.content;
+                self.hex_mode = template.binary;
+                self.template_mode = true;
+            }
+            if let Some(idx) = delete {
+                self.templates.remove(idx);
+                changed = true;
+            }
+
+            ui.separator();
+            ui.heading("Variables");
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{{{{{}}}}}: {}",
+                    COUNTER_VAR, self.template_vars.counter
+                ));
+                if ui.small_button("Reset").clicked() {
+                    self.template_vars.counter = 0;
+                    changed = true;
+                }
+            });
+
+            let mut remove: Option<String> = None;
+            egui::Grid::new("mqtt_template_vars")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    for (name, value) in self.template_vars.vars.iter_mut() {
+                        ui.label(format!("{{{{{}}}}}", name));
+                        changed |= ui.text_edit_singleline(value).changed();
+                        if ui.small_button("\u{D7}").on_hover_text("Delete").clicked() {
+                            remove = Some(name.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(name) = remove {
+                self.template_vars.vars.remove(&name);
+                changed = true;
+            }
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.new_var_name)
+                        .hint_text("name")
+                        .desired_width(90.0),
+                );
+                ui.add(
+                    TextEdit::singleline(&mut self.new_var_value)
+                        .hint_text("value")
+                        .desired_width(120.0),
+                );
+                if ui.button("Add").clicked() {
+                    let name = self.new_var_name.trim().to_string();
+                    match TemplateVariables::validate_name(&name) {
+                        Ok(()) => {
+                            self.template_vars
+                                .vars
+                                .insert(name, std::mem::take(&mut self.new_var_value));
+                            self.new_var_name.clear();
+                            self.template_status = None;
+                            changed = true;
+                        }
+                        Err(e) => self.template_status = Some(e),
+                    }
+                }
+            });
+            if let Some(status) = &self.template_status {
+                ui.colored_label(UiColors::inactive(), status);
+            }
+
+            ui.separator();
+            if ui.button("Close").clicked() {
+                self.editing_templates = false;
+            }
+        });
+
+        if changed {
+            self.store_templates();
+        }
+    }
+
+    /// Renders the available topics as a collapsible tree.
+    ///
+    /// Every topic and every branch can be (un)subscribed. Subscribing a
+    /// branch subscribes its `#` filter, which is added to the available
+    /// topics so it is listed in the topic selector as well.
+    fn topic_tree_dialog(&mut self, ui: &mut Ui) {
+        if !self.browsing_topic_tree {
+            return;
+        }
+
+        let tree = TopicNode::build(&self.available_topics);
+        let mut toggled: Option<String> = None;
+        let mut closed = false;
+        let modal = Modal::new(Id::new("Modal Topic Tree")).show(ui.ctx(), |ui| {
+            ui.set_width(320.0);
+            ui.heading("Topic Tree");
+
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                if tree.children.is_empty() {
+                    ui.label("No topics yet, add them in the topic selector");
+                }
+                for node in tree.children.values() {
+                    self.topic_tree_node(ui, node, &mut toggled);
+                }
+            });
+
+            ui.separator();
+            closed = ui.button("Close").clicked();
+        });
+
+        if let Some(topic) = toggled {
+            self.toggle_subscription(topic);
+        }
+        if closed || modal.should_close() {
+            self.browsing_topic_tree = false;
+        }
+    }
+
+    /// Renders the dialog exporting the live log to a file.
+    ///
+    /// The path may name a file, a directory or nothing; see
+    /// [`Self::export_log`].
+    fn export_dialog(&mut self, ui: &mut Ui) {
+        if !self.exporting_log {
+            return;
+        }
+
+        let mut export = false;
+        let mut closed = false;
+        let modal = Modal::new(Id::new("Modal Log Export")).show(ui.ctx(), |ui| {
+            ui.set_width(320.0);
+            ui.heading("Export Log");
+            ui.label(format!("{} messages", self.received_messages.len()));
+
+            ui.horizontal(|ui| {
+                ui.label("Format:");
+                GamepadSelect::new("log_export_format")
+                    .selected_text(self.export_format.to_string())
+                    .show(
+                        ui,
+                        &mut self.export_format,
+                        LogFormat::ALL.map(|format| (format, format.to_string())),
+                    );
+            });
+            ui.add(
+                TextEdit::singleline(&mut self.export_path)
+                    .hint_text("File or directory, home if empty"),
+            );
+
+            if let Some(status) = &self.export_status {
+                ui.label(status);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                export = ui
+                    .add_enabled(
+                        !self.received_messages.is_empty(),
+                        egui::Button::new("Export"),
+                    )
+                    .clicked();
+                closed = ui.button("Close").clicked();
+            });
+        });
+
+        if export {
+            self.export_log();
+        }
+        if closed || modal.should_close() {
+            self.exporting_log = false;
+        }
+    }
+
+    /// Writes the live log to the export path in the selected format.
+    ///
+    /// An empty path exports to the home directory. A directory gets a file
+    /// named after the session and the current time.
+    fn export_log(&mut self) {
+        let mut dest = match self.export_path.trim() {
+            "" => dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")),
+            path => PathBuf::from(path),
+        };
+        if dest.is_dir() {
+            dest.push(format!(
+                "mqtt-log-{}-{}",
+                self.log_session,
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            ));
+            dest.set_extension(self.export_format.extension());
+        }
+
+        self.export_status = Some(
+            match message_export::export_messages(
+                &self.received_messages,
+                &dest,
+                self.export_format,
+            ) {
+                Ok(()) => {
+                    info!(
+                        "Exported {} messages to {}",
+                        self.received_messages.len(),
+                        dest.display()
+                    );
+                    format!("Exported to {}", dest.display())
+                }
+                Err(e) => {
+                    error!("Log export failed: {}", e);
+                    e.to_string()
+                }
+            },
+        );
+    }
+
+    /// Renders one level of the topic tree and everything below it.
+    ///
+    /// Branches start with a checkbox for their `#` filter. Their `#` child,
+    /// the same filter, is not repeated.
+    fn topic_tree_node(&self, ui: &mut Ui, node: &TopicNode, toggled: &mut Option<String>) {
+        if !node.is_branch() {
+            self.topic_checkbox(ui, &node.path, level_label(&node.name), toggled);
+            return;
+        }
+
+        CollapsingHeader::new(level_label(&node.name))
+            .id_salt(("topic_tree", &node.path))
+            .show(ui, |ui| {
+                let filter = node.branch_filter();
+                self.topic_checkbox(ui, &filter, format!("All ({})", filter), toggled);
+                if node.is_topic {
+                    self.topic_checkbox(ui, &node.path, node.path.clone(), toggled);
+                }
+                for child in node.children.values() {
+                    if child.name == MULTI_LEVEL_WILDCARD && !child.is_branch() {
+                        continue;
+                    }
+                    self.topic_tree_node(ui, child, toggled);
+                }
+            });
+    }
+
+    /// Renders a checkbox showing whether `topic` is subscribed.
+    ///
+    /// A click is reported through `toggled`.
+    fn topic_checkbox(
+        &self,
+        ui: &mut Ui,
+        topic: &str,
+        label: String,
+        toggled: &mut Option<String>,
+    ) {
+        let mut subscribed = self.subscribed_topics.iter().any(|sub| sub == topic);
+        if ui.checkbox(&mut subscribed, label).changed() {
+            *toggled = Some(topic.to_string());
+        }
+    }
+
+    /// Subscribes `topic`, or unsubscribes it if it already is.
+    ///
+    /// Takes effect through [`Self::post_update_config`] like the selector.
+    fn toggle_subscription(&mut self, topic: String) {
+        match self.subscribed_topics.iter().position(|sub| *sub == topic) {
+            Some(pos) => {
+                self.subscribed_topics.remove(pos);
+                debug!("Deactivate topic {}", topic);
+            }
+            None => {
+                if !self.available_topics.contains(&topic) {
+                    self.available_topics.push(topic.clone());
+                }
+                debug!("Activate topic {}", topic);
+                self.subscribed_topics.push(topic);
+            }
+        }
+    }
+
     /// Saves a message to the persistent message history and triggers session backup.
     ///
     /// Adds the message to the local history, persists it through ConfigPortal,
@@ -791,3 +2097,22 @@ impl MQTTMenuData {
         let _ = session_action!(@save, self.session_sender);
     }
 }
+
+/// Display name of a topic level, empty levels are valid in MQTT.
+fn level_label(name: &str) -> String {
+    if name.is_empty() {
+        "(empty)".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Pins `topic` to the quick-bar, or unpins it if it already is.
+fn toggle_pin(pinned: &mut Vec<String>, topic: &str) {
+    match pinned.iter().position(|t| t == topic) {
+        Some(pos) => {
+            pinned.remove(pos);
+        }
+        None => pinned.push(topic.to_string()),
+    }
+}
@@ -0,0 +1,132 @@
+//! # Config Recovery Prompt
+//!
+//! Modal shown at startup when config files failed the integrity check.
+//!
+//! ## Why This Module Exists
+//!
+//! The integrity check in [`crate::persistence::integrity`] holds all saves
+//! while corrupt files are unresolved, so the user's broken settings are not
+//! overwritten behind their back. This prompt lists the broken files and lets
+//! the user decide between the defaults, the backups, or quitting to fix the
+//! files by hand.
+//!
+//! ## Design Rationale
+//!
+//! The prompt lives inside the main window: the event loop can only be
+//! created once per process, so a separate dialog before the UI is not
+//! possible. It blocks the rest of the UI like every other confirmation and
+//! uses [`choice_modal`], so it works with the gamepad alone.
+
+use eframe::egui::{self, Id};
+use tokio::sync::mpsc;
+use tracing::{error, info};
+
+use crate::persistence::integrity::{self, CorruptFile};
+use crate::persistence::persistence_worker::SessionAction;
+use crate::session_action;
+
+use super::common::choice_modal;
+
+/// Unresolved corrupt config files and the outcome of the user's decision.
+pub struct RecoveryPrompt {
+    /// Files still waiting for a decision, empty once resolved
+    files: Vec<CorruptFile>,
+
+    /// Files that could not be recovered, shown until acknowledged
+    failed: Option<String>,
+
+    /// Channel for reloading the session after a restore
+    session_sender: mpsc::Sender<SessionAction>,
+}
+
+impl RecoveryPrompt {
+    /// Creates the prompt for the files reported by
+    /// [`integrity::check_config_files`]. Nothing is shown if `files` is empty.
+    pub fn new(files: Vec<CorruptFile>, session_sender: mpsc::Sender<SessionAction>) -> Self {
+        Self {
+            files,
+            failed: None,
+            session_sender,
+        }
+    }
+
+    /// Shows the prompt while files are unresolved or failures unacknowledged.
+    pub fn render(&mut self, ctx: &egui::Context) {
+        if let Some(failed) = &self.failed {
+            let message = format!("Some files could not be recovered:\n{}", failed);
+            if choice_modal(ctx, Id::new("RecoveryFailed"), &message, &["OK"]).is_some() {
+                self.failed = None;
+            }
+            return;
+        }
+        if self.files.is_empty() {
+            return;
+        }
+
+        let has_backup = self.files.iter().any(|file| file.backup.is_some());
+        let mut choices = vec!["Use Defaults"];
+        if has_backup {
+            choices.push("Restore Backup");
+        }
+        choices.push("Quit");
+
+        let Some(choice) = choice_modal(
+            ctx,
+            Id::new("ConfigRecoveryPrompt"),
+            &self.message(),
+            &choices,
+        ) else {
+            return;
+        };
+
+        match choices[choice] {
+            "Use Defaults" => {
+                info!("Replacing corrupt config files by defaults");
+                self.failed = integrity::use_defaults(&self.files).err();
+            }
+            "Restore Backup" => {
+                info!("Restoring corrupt config files from backups");
+                self.failed = integrity::restore_backups(&self.files).err();
+                self.reload_session();
+            }
+            _ => {
+                info!("Quitting to leave corrupt config files untouched");
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                return;
+            }
+        }
+        self.files.clear();
+    }
+
+    /// Lists the broken files with their errors and backup availability.
+    fn message(&self) -> String {
+        let mut message = String::from("These config files could not be read:\n");
+        for file in &self.files {
+            let backup = if file.backup.is_some() {
+                "backup available"
+            } else {
+                "no backup"
+            };
+            message.push_str(&format!(
+                "\n{} ({})\n{}\n",
+                file.path.display(),
+                backup,
+                file.error
+            ));
+        }
+        message.push_str("\nDefaults are used until you decide.");
+        message
+    }
+
+    /// Loads the stored session again so restored files take effect.
+    ///
+    /// Discards the in-memory state, which holds defaults for the broken files.
+    fn reload_session(&self) {
+        let Some(name) = integrity::stored_session_name() else {
+            return;
+        };
+        if let Err(e) = session_action!(@load_discard, self.session_sender, name) {
+            error!("Failed to reload session after restore: {}", e);
+        }
+    }
+}
@@ -25,7 +25,43 @@
 //! - Gamepad-based configuration provides input method independence
 //! - Visual network status supports troubleshooting in workshop environments
 //!
+//! ### Live Controller Tuning
+//! Deadzone, processing interval and press threshold are published to the running
+//! controller threads as soon as a slider moves, so drift or sluggish input can be
+//! fixed without restarting. Values are persisted into the session's
+//! `ControllerConfig` once a slider is released.
+//!
+//! ### Keyboard Layout
+//! The alphabet layout used for joystick typing can be switched here. The new
+//! layout is stored in the session's keyboard config and the keyboard engine is
+//! rebuilt from it, so the change applies immediately. Region hysteresis and the
+//! center radius are tuned the same way, as is the minimum interval between
+//! mapped inputs. An on-screen overlay showing the current joystick regions
+//! can be toggled for learning the layout.
+//!
+//! ### Button Mapping Editor
+//! Each gamepad button can be assigned a key or a modifier. Assigning something
+//! that another button already uses asks for confirmation instead of silently
+//! creating a duplicate, and the default button layout can be restored at any time.
+//!
+//! ### Theme Editor
+//! The nine session theme colors can be edited with a color picker each. Changes
+//! are previewed live across the whole UI while picking and only stored on save,
+//! which writes the theme into the current session and persists it, so every
+//! session keeps its own look. Unsaved edits can be discarded, and the built-in
+//! default theme can be restored.
+//!
+//! ### Autosave
+//! The session autosave can be paused, e.g. during a long MQTT capture, and its
+//! interval changed. Both apply to the running autosave task immediately; with
+//! autosave off only explicit saves persist. Reloading session files edited
+//! outside the app is switched on in the same section, as is the number of
+//! session backups kept.
+//!
 //! ### Display Configuration for Workshop Use
+//! The UI frame rate is stored in the session and applies on the next frame.
+//! Brightness drives the sysfs backlight (e.g. the Raspberry Pi touchscreen)
+//! and is restored from the session at startup.
 //! Display settings address the specific usage patterns of maker/workshop environments:
 //! - Variable lighting conditions requiring brightness adjustment
 //! - Power management for battery-powered deployments
@@ -36,7 +72,13 @@
 //! ### Two-Section Layout Architecture
 //! The settings interface is organized into distinct functional areas:
 //! - **WLAN Section**: Network connectivity management with immediate status feedback
+//! - **Controller Section**: Live input tuning for the gamepad
+//! - **Keyboard Section**: Alphabet layout for joystick typing and button mapping
 //! - **Display Section**: Visual and power management settings
+//! - **Theme Section**: Session colors with live preview
+//! - **Autosave Section**: Periodic session saving, can be paused
+//! - **Diagnostics Section**: Lock contention counters of the ConfigPortal
+//!   and an optional input latency histogram
 //!
 //! This separation reflects the different update frequencies and criticality of these
 //! configuration areas - network settings change infrequently but are critical for
@@ -52,9 +94,10 @@
 //! ## Integration with Backend Systems
 //!
 //! ### Current Implementation Status
-//! **⚠️ IMPORTANT**: This module currently uses mock data and simulated functionality.
-//! The UI layout and interaction patterns are finalized, but backend integration
-//! is pending implementation.
+//! **⚠️ IMPORTANT**: The network and display sections currently use mock data and
+//! simulated functionality. The UI layout and interaction patterns are finalized,
+//! but backend integration is pending implementation. The controller section is
+//! fully wired to the controller subsystem and the ConfigPortal.
 //!
 //! ### Planned Backend Integration
 //! When fully implemented, this module will integrate with:
@@ -72,9 +115,40 @@
 //! - Advanced network settings (static IP, proxy configuration)
 //! - Hardware-specific settings (GPIO configuration, sensor calibration)
 
-use eframe::egui::{self, Color32, DragValue, Frame, Slider, Stroke, TextEdit, Ui};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use eframe::egui::{self, DragValue, Frame, ProgressBar, Response, Slider, Stroke, TextEdit, Ui};
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, error, info, warn};
 
-use super::common::{UiColors, WiFiNetwork};
+use super::common::{GamepadSelect, UiColors, WiFiNetwork};
+use super::controller_test::ControllerTest;
+use super::event_monitor::EventMonitor;
+use super::log_viewer::LogViewer;
+use crate::controller::controller_handle::{
+    ButtonType, ControllerOutput, ControllerSettings, ControllerStatus, DeadzoneMode,
+    ResponseCurve, StickCurve,
+};
+use crate::controller::gamepad_profile::{GamepadProfile, PadButton, ProfileSelection};
+use crate::latency;
+use crate::mapping::keyboard::{
+    ButtonAssignment, InputMode, KeyboardConfig, LayoutPreset, RegionSet, Section,
+    CENTER_RADIUS_RANGE, CURSOR_SPEED_RANGE, HYSTERESIS_RANGE, LAYOUT_FILE_EXTENSION,
+    MODIFIER_CHOICES, MOUSE_CLICK_BUTTON,
+};
+use crate::mapping::navigation::NavigationAction;
+use crate::mapping::{DroppedEvents, MappingConfig, MappingType, MIN_RATE_LIMIT_MS};
+use crate::persistence::config_portal::{ConfigPortal, ConfigResult, PortalAction};
+use crate::persistence::persistence_worker::SessionAction;
+use crate::persistence::{
+    effective_fps, AutosaveSettings, FocusHighlight, Theme, TimestampSettings,
+    AUTOSAVE_INTERVAL_RANGE, BACKUP_COUNT_RANGE, DEFAULT_FPS, DEFAULT_TIMESTAMP_FORMAT,
+    FOCUS_WIDTH_RANGE, FPS_RANGE,
+};
+use crate::platform::backlight::Backlight;
+use crate::session_action;
+use crate::supervisor::TaskHealth;
 
 /// Main data structure for system settings and configuration management.
 ///
@@ -103,7 +177,6 @@ use super::common::{UiColors, WiFiNetwork};
 /// Current implementation uses mock data to establish UI patterns. Production
 /// implementation will replace mock data with actual system integration while
 /// maintaining the same user interaction model.
-#[derive(Default)]
 pub struct SettingsMenuData {
     /// Currently connected WiFi network
     current_network: WiFiNetwork,
@@ -123,23 +196,154 @@ pub struct SettingsMenuData {
     /// Display brightness level (0.0 to 1.0)
     display_brightness: f32,
 
+    /// Display backlight, `None` if the platform has none
+    backlight: Option<Backlight>,
+
     /// Screensaver timeout in seconds
     screensave: usize,
+
+    /// Controller settings as shown in the sliders
+    controller_settings: ControllerSettings,
+
+    /// Publishes changed settings to the running controller threads
+    controller_settings_tx: watch::Sender<ControllerSettings>,
+
+    /// Persists controller settings into the session
+    config_portal: Arc<ConfigPortal>,
+
+    /// Alphabet layout of the keyboard mapping
+    keyboard_layout: LayoutPreset,
+
+    /// Region hysteresis of the keyboard mapping
+    keyboard_hysteresis: f32,
+
+    /// Center radius of the keyboard mapping
+    keyboard_center_radius: f32,
+
+    /// Minimum milliseconds between keyboard mappings
+    keyboard_rate_limit_ms: u64,
+
+    /// Hold time before a held button repeats its key
+    keyboard_repeat_delay_ms: u64,
+
+    /// Time between key repeats, 0 disables repeat
+    keyboard_repeat_interval_ms: u64,
+
+    /// Dwell time before a stick combo types, 0 disables dwell mode
+    keyboard_dwell_ms: u64,
+
+    /// Whether the sticks type or drive the virtual mouse
+    keyboard_input_mode: InputMode,
+
+    /// Virtual mouse speed at full stick deflection
+    keyboard_cursor_speed: f32,
+
+    /// Requests a keyboard engine rebuild after a layout switch
+    mapping_reload_tx: mpsc::Sender<MappingType>,
+
+    /// Whether the on-screen keyboard overlay is shown
+    show_keyboard_overlay: bool,
+
+    /// Current key or modifier of every gamepad button
+    button_assignments: Vec<(ButtonType, ButtonAssignment)>,
+
+    /// Assignment waiting for confirmation because another button uses it
+    pending_assignment: Option<PendingAssignment>,
+
+    /// File or directory used for keyboard layout import/export
+    layout_path: String,
+
+    /// Result of the last layout import or export
+    layout_status: Option<String>,
+
+    /// Whether the mapped event monitor is open
+    show_event_monitor: bool,
+
+    /// Recent events injected by the mapping engines
+    event_monitor: EventMonitor,
+
+    /// Recent log records, for devices without a terminal
+    log_viewer: LogViewer,
+
+    /// Mapped events lost to full output channels
+    dropped_events: Arc<DroppedEvents>,
+
+    /// Health of the supervised background tasks
+    task_health_rx: watch::Receiver<Vec<TaskHealth>>,
+
+    /// Live gamepad state for checking buttons, sticks and triggers
+    controller_test: ControllerTest,
+
+    /// Edited theme being previewed, `None` while the stored theme is shown
+    theme_draft: Option<Theme>,
+
+    /// Persists the current session after a theme is saved
+    session_sender: mpsc::Sender<SessionAction>,
+
+    /// Autosave settings as shown in the controls
+    autosave: AutosaveSettings,
+
+    /// Publishes changed autosave settings to the running autosave task
+    autosave_tx: watch::Sender<AutosaveSettings>,
+
+    /// Notices autosave settings replaced by a session load
+    autosave_rx: watch::Receiver<AutosaveSettings>,
+}
+
+/// Backend handles the settings menu reads from and writes to
+pub struct SettingsChannels {
+    /// Live controller settings, published to the controller threads
+    pub controller_settings_tx: watch::Sender<ControllerSettings>,
+    pub config_portal: Arc<ConfigPortal>,
+    /// Requests mapping engine rebuilds after config changes
+    pub mapping_reload_tx: mpsc::Sender<MappingType>,
+    pub session_sender: mpsc::Sender<SessionAction>,
+    /// Live autosave settings of the persistence system
+    pub autosave_tx: watch::Sender<AutosaveSettings>,
+    pub dropped_events: Arc<DroppedEvents>,
+    pub task_health_rx: watch::Receiver<Vec<TaskHealth>>,
+    /// Latest controller input, for the controller test
+    pub controller_state_rx: watch::Receiver<Option<ControllerOutput>>,
+    pub controller_status_rx: watch::Receiver<ControllerStatus>,
+}
+
+/// A button assignment that would duplicate another button's mapping.
+struct PendingAssignment {
+    button: ButtonType,
+    assignment: ButtonAssignment,
+    conflict: ButtonType,
 }
 
 impl SettingsMenuData {
-    /// Creates mock data for UI development and testing.
+    /// Creates the settings menu with live controller settings and mock system data.
     ///
-    /// Provides realistic test data that demonstrates the full functionality
-    /// of the settings interface without requiring actual system integration.
-    /// This allows UI development and gamepad interaction testing on any platform.
+    /// The controller section starts from the settings currently active in the
+    /// controller subsystem. Network and display sections still use mock data
+    /// that demonstrates the full functionality of the interface without
+    /// requiring actual system integration.
     ///
     /// ## Mock Data Strategy
     /// Creates a scenario with:
     /// - Active network connection for status display testing
     /// - Multiple available networks for selection workflow testing
     /// - Realistic display settings for control testing
-    pub fn mock_data() -> Self {
+    pub fn new(channels: SettingsChannels) -> Self {
+        let SettingsChannels {
+            controller_settings_tx,
+            config_portal,
+            mapping_reload_tx,
+            session_sender,
+            autosave_tx,
+            dropped_events,
+            task_health_rx,
+            controller_state_rx,
+            controller_status_rx,
+        } = channels;
+        let controller_settings = controller_settings_tx.borrow().clone();
+        let keyboard_config = load_keyboard_config(&config_portal);
+        let backlight = Backlight::detect();
+        let autosave = autosave_tx.borrow().clone();
+        let display_brightness = restore_brightness(&config_portal, backlight.as_ref());
         let active_net = WiFiNetwork::new("NetActive".to_string(), "ddd".to_string());
         let networks = vec![
             WiFiNetwork::new("Test1".to_string(), "123".to_string()),
@@ -151,8 +355,38 @@ impl SettingsMenuData {
             available_networks: networks,
             network_pw: String::new(),
             connected: false,
-            display_brightness: 0.7,
+            display_brightness,
+            backlight,
             screensave: 300,
+            controller_settings,
+            controller_settings_tx,
+            config_portal,
+            keyboard_layout: keyboard_config.layout(),
+            keyboard_hysteresis: keyboard_config.hysteresis,
+            keyboard_center_radius: keyboard_config.center_radius,
+            keyboard_rate_limit_ms: keyboard_config.rate_limit_ms,
+            keyboard_repeat_delay_ms: keyboard_config.repeat_delay_ms,
+            keyboard_repeat_interval_ms: keyboard_config.repeat_interval_ms,
+            keyboard_dwell_ms: keyboard_config.dwell_ms.unwrap_or(0),
+            keyboard_input_mode: keyboard_config.input_mode,
+            keyboard_cursor_speed: keyboard_config.cursor_speed,
+            mapping_reload_tx,
+            show_keyboard_overlay: false,
+            button_assignments: button_assignments(&keyboard_config),
+            pending_assignment: None,
+            layout_path: String::new(),
+            layout_status: None,
+            show_event_monitor: false,
+            event_monitor: EventMonitor::default(),
+            log_viewer: LogViewer::default(),
+            dropped_events,
+            task_health_rx,
+            controller_test: ControllerTest::new(controller_state_rx, controller_status_rx),
+            theme_draft: None,
+            session_sender,
+            autosave,
+            autosave_rx: autosave_tx.subscribe(),
+            autosave_tx,
         }
     }
 
@@ -172,6 +406,21 @@ impl SettingsMenuData {
         self.current_network.ssid.clone()
     }
 
+    /// Returns whether the on-screen keyboard overlay is enabled.
+    pub fn show_keyboard_overlay(&self) -> bool {
+        self.show_keyboard_overlay
+    }
+
+    /// Returns whether the event monitor is open and wants mapped events.
+    pub fn is_monitoring_events(&self) -> bool {
+        self.show_event_monitor
+    }
+
+    /// Adds mapped events to the event monitor.
+    pub fn record_events(&mut self, events: &[egui::Event], sections: (Section, Section)) {
+        self.event_monitor.record(events, sections);
+    }
+
     /// Renders the complete settings interface with network and display sections.
     ///
     /// Creates a vertically organized settings interface with clearly separated
@@ -207,11 +456,130 @@ impl SettingsMenuData {
 
             ui.add_space(section_spacing);
 
+            // Live gamepad input tuning
+            self.render_controller_section(ui);
+
+            ui.add_space(section_spacing);
+
+            // Alphabet layout for joystick typing
+            self.render_keyboard_section(ui);
+
+            ui.add_space(section_spacing);
+
             // Display and power management configuration
             self.render_display_section(ui);
+
+            ui.add_space(section_spacing);
+
+            // Session colors
+            self.render_theme_section(ui);
+
+            ui.add_space(section_spacing);
+
+            // Periodic session saving
+            self.render_autosave_section(ui);
+
+            ui.add_space(section_spacing);
+
+            // Internal counters for troubleshooting
+            self.render_diagnostics_section(ui);
         });
     }
 
+    /// Renders lock contention counters of the ConfigPortal sections, dropped
+    /// mapping events, input latency, background task restarts and the recent
+    /// log.
+    ///
+    /// Shows which section the "lock blocked" warnings in the log come from.
+    /// The counters run since startup; all views are collapsed by default.
+    fn render_diagnostics_section(&mut self, ui: &mut Ui) {
+        Frame::new()
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
+            .inner_margin(8.0)
+            .outer_margin(2.0)
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    let total_width = ui.available_width() - 15.0;
+                    ui.set_min_width(total_width);
+                    ui.heading("Diagnostics");
+
+                    egui::CollapsingHeader::new("Config lock contention")
+                        .id_salt("lock_metrics")
+                        .show(ui, |ui| {
+                            egui::Grid::new("lock_metrics_grid")
+                                .num_columns(4)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.strong("Section");
+                                    ui.strong("Read waits");
+                                    ui.strong("Write waits");
+                                    ui.strong("Timeouts");
+                                    ui.end_row();
+                                    for (section, metrics) in self.config_portal.metrics() {
+                                        ui.label(section);
+                                        ui.label(metrics.read_waits.to_string());
+                                        ui.label(metrics.write_waits.to_string());
+                                        ui.label(metrics.timeouts.to_string());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+
+                    egui::CollapsingHeader::new("Dropped mapping events")
+                        .id_salt("dropped_events")
+                        .show(ui, |ui| {
+                            egui::Grid::new("dropped_events_grid")
+                                .num_columns(2)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.strong("Output");
+                                    ui.strong("Dropped");
+                                    ui.end_row();
+                                    for output in [
+                                        MappingType::Keyboard,
+                                        MappingType::ELRS,
+                                        MappingType::Custom,
+                                    ] {
+                                        ui.label(output.to_string());
+                                        ui.label(self.dropped_events.get(output).to_string());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+
+                    egui::CollapsingHeader::new("Input latency")
+                        .id_salt("input_latency")
+                        .show(ui, render_input_latency);
+
+                    egui::CollapsingHeader::new("Background tasks")
+                        .id_salt("task_health")
+                        .show(ui, |ui| {
+                            egui::Grid::new("task_health_grid")
+                                .num_columns(4)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for header in ["Task", "State", "Restarts", "Last error"] {
+                                        ui.strong(header);
+                                    }
+                                    ui.end_row();
+                                    for task in self.task_health_rx.borrow().iter() {
+                                        ui.label(task.name);
+                                        ui.label(task.state.to_string());
+                                        ui.label(task.restarts.to_string());
+                                        ui.label(task.last_error.as_deref().unwrap_or("-"));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+
+                    egui::CollapsingHeader::new("Log")
+                        .id_salt("log_viewer")
+                        .show(ui, |ui| self.log_viewer.render(ui));
+                });
+            });
+    }
+
     /// Renders the WiFi network configuration section.
     ///
     /// Provides comprehensive network management functionality including current
@@ -222,7 +590,7 @@ impl SettingsMenuData {
     ///
     /// ### Three-Column Layout
     /// Uses calculated width distribution to balance functionality:
-    /// - **Network Selection (45%)**: ComboBox for available networks
+    /// - **Network Selection (45%)**: Gamepad-operable dropdown of available networks
     /// - **Password Entry (45%)**: Secure text input for authentication  
     /// - **Connection Control (10%)**: Connect button for action triggering
     ///
@@ -250,8 +618,8 @@ impl SettingsMenuData {
     /// - Error handling and user feedback
     fn render_wlan_section(&mut self, ui: &mut Ui) {
         Frame::new()
-            .stroke(Stroke::new(1.0, UiColors::BORDER))
-            .fill(UiColors::MAIN_BG)
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
             .inner_margin(8.0)
             .outer_margin(2.0)
             .show(ui, |ui| {
@@ -275,22 +643,20 @@ impl SettingsMenuData {
                         ui.vertical(|ui| {
                             let networkname = self.selected_network.to_string();
                             ui.set_min_width(network_width);
-                            egui::ComboBox::from_id_salt("available_networks")
+                            GamepadSelect::new("available_networks")
                                 .selected_text(if networkname.is_empty() {
                                     "Available Networks"
                                 } else {
                                     networkname.as_str()
                                 })
                                 .width(network_width - 10.0)
-                                .show_ui(ui, |ui| {
-                                    for network in &self.available_networks {
-                                        ui.selectable_value(
-                                            &mut self.selected_network,
-                                            network.clone(),
-                                            network.to_string(),
-                                        );
-                                    }
-                                });
+                                .show(
+                                    ui,
+                                    &mut self.selected_network,
+                                    self.available_networks
+                                        .iter()
+                                        .map(|network| (network.clone(), network.to_string())),
+                                );
                         });
 
                         // Password entry field
@@ -320,6 +686,707 @@ impl SettingsMenuData {
             });
     }
 
+    /// Renders the controller tuning section.
+    ///
+    /// Every slider change is published to the controller threads immediately so
+    /// the effect can be felt while adjusting. Persisting waits until the slider
+    /// is released to avoid rewriting the session config on every drag step.
+    ///
+    /// ## Ranges
+    /// - **Deadzone**: 0-30%, beyond that sticks lose most of their travel. The
+    ///   mode picks radial, per-axis or scaled radial filtering
+    /// - **Interval**: 20-500ms, from very responsive to battery saving
+    /// - **Press threshold**: 0-200ms, longer values start swallowing taps
+    /// - **Chord window**: 0-200ms, longer values delay single presses of chord buttons
+    /// - **Curve factor**: 0.0 (linear) to 1.0 (full curve), per stick
+    ///
+    /// The invert checkboxes flip single stick axes, the Section column of the
+    /// controller test follows them.
+    ///
+    /// The button layout picks the gamepad profile, see
+    /// [`Self::render_gamepad_profile`].
+    ///
+    /// The collapsible controller test below the sliders shows the live pad
+    /// state, so the effect of each setting can be checked right away.
+    fn render_controller_section(&mut self, ui: &mut Ui) {
+        Frame::new()
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
+            .inner_margin(8.0)
+            .outer_margin(2.0)
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    let total_width = ui.available_width() - 15.0;
+                    ui.set_min_width(total_width);
+                    ui.heading("Controller");
+
+                    let mut deadzone_mode_changed = false;
+                    let deadzone = ui
+                        .horizontal(|ui| {
+                            ui.label("Stick deadzone:");
+                            let slider = ui.add(
+                                Slider::new(
+                                    &mut self.controller_settings.joystick_deadzone,
+                                    0.0..=0.3,
+                                )
+                                .fixed_decimals(2),
+                            );
+                            let mode = &mut self.controller_settings.deadzone_mode;
+                            deadzone_mode_changed = GamepadSelect::new("deadzone_mode")
+                                .selected_text(mode.to_string())
+                                .show(
+                                    ui,
+                                    mode,
+                                    DeadzoneMode::ALL.map(|option| (option, option.to_string())),
+                                )
+                                .changed();
+                            slider
+                        })
+                        .inner;
+
+                    let interval = ui
+                        .horizontal(|ui| {
+                            ui.label("Processing interval (ms):");
+                            ui.add(Slider::new(
+                                &mut self.controller_settings.collection_interval_ms,
+                                20..=500,
+                            ))
+                        })
+                        .inner;
+
+                    let threshold = ui
+                        .horizontal(|ui| {
+                            ui.label("Press threshold (ms):");
+                            ui.add(Slider::new(
+                                &mut self.controller_settings.button_press_threshold_ms,
+                                0..=200,
+                            ))
+                        })
+                        .inner;
+
+                    let chord_window = ui
+                        .horizontal(|ui| {
+                            ui.label("Chord window (ms):");
+                            ui.add(Slider::new(
+                                &mut self.controller_settings.chord_window_ms,
+                                0..=200,
+                            ))
+                        })
+                        .inner;
+
+                    let (left_curve_changed, left_factor) = render_curve_row(
+                        ui,
+                        "Left stick curve:",
+                        "left_stick_curve",
+                        &mut self.controller_settings.left_stick_curve,
+                    );
+                    let (right_curve_changed, right_factor) = render_curve_row(
+                        ui,
+                        "Right stick curve:",
+                        "right_stick_curve",
+                        &mut self.controller_settings.right_stick_curve,
+                    );
+
+                    let mut inversion_changed = false;
+                    let settings = &mut self.controller_settings;
+                    for (label, invert_x, invert_y) in [
+                        (
+                            "Invert left stick:",
+                            &mut settings.invert_left_x,
+                            &mut settings.invert_left_y,
+                        ),
+                        (
+                            "Invert right stick:",
+                            &mut settings.invert_right_x,
+                            &mut settings.invert_right_y,
+                        ),
+                    ] {
+                        ui.horizontal(|ui| {
+                            ui.label(label);
+                            inversion_changed |= ui.checkbox(invert_x, "X").changed();
+                            inversion_changed |= ui.checkbox(invert_y, "Y").changed();
+                        });
+                    }
+
+                    let profile_changed = self.render_gamepad_profile(ui);
+
+                    self.apply_controller_settings(
+                        &[
+                            deadzone,
+                            interval,
+                            threshold,
+                            chord_window,
+                            left_factor,
+                            right_factor,
+                        ],
+                        deadzone_mode_changed
+                            || left_curve_changed
+                            || right_curve_changed
+                            || inversion_changed
+                            || profile_changed,
+                    );
+
+                    let regions =
+                        RegionSet::new(self.keyboard_hysteresis, self.keyboard_center_radius);
+                    egui::CollapsingHeader::new("Controller test")
+                        .id_salt("controller_test")
+                        .show(ui, |ui| {
+                            self.controller_test
+                                .render(ui, &regions, &self.button_assignments)
+                        });
+                });
+            });
+    }
+
+    /// Renders the button layout selection and the editor for custom profiles.
+    ///
+    /// "New custom" copies the layout of the selected profile, or of the
+    /// generic profile while auto-detecting, into a new custom profile and
+    /// selects it. Only the selected custom profile can be edited; built-in
+    /// profiles are fixed. Returns whether anything changed.
+    fn render_gamepad_profile(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        let settings = &mut self.controller_settings;
+
+        ui.horizontal(|ui| {
+            ui.label("Button layout:");
+            let selection = &mut settings.gamepad_profile;
+            let fixed = settings
+                .custom_profiles
+                .iter()
+                .chain(GamepadProfile::builtin().iter())
+                .map(|profile| {
+                    let name = profile.name.clone();
+                    (ProfileSelection::Fixed(name.clone()), name)
+                })
+                .collect::<Vec<_>>();
+            let options =
+                std::iter::once((ProfileSelection::Auto, ProfileSelection::Auto.to_string()))
+                    .chain(fixed);
+            changed |= GamepadSelect::new("gamepad_profile")
+                .selected_text(selection.to_string())
+                .show(ui, selection, options)
+                .changed();
+
+            if ui.button("New custom").clicked() {
+                let mut profile = settings
+                    .gamepad_profile
+                    .resolve(&settings.custom_profiles, "");
+                let mut number = settings.custom_profiles.len() + 1;
+                while settings
+                    .custom_profiles
+                    .iter()
+                    .any(|p| p.name == format!("Custom {}", number))
+                {
+                    number += 1;
+                }
+                profile.name = format!("Custom {}", number);
+                profile.name_patterns.clear();
+                info!("Created gamepad profile {}", profile.name);
+                settings.gamepad_profile = ProfileSelection::Fixed(profile.name.clone());
+                settings.custom_profiles.push(profile);
+                changed = true;
+            }
+        });
+
+        let ProfileSelection::Fixed(selected) = settings.gamepad_profile.clone() else {
+            return changed;
+        };
+        let Some(index) = settings
+            .custom_profiles
+            .iter()
+            .position(|profile| profile.name == selected)
+        else {
+            return changed;
+        };
+
+        let mut delete = false;
+        egui::CollapsingHeader::new(format!("Edit {}", selected))
+            .id_salt("gamepad_profile_editor")
+            .show(ui, |ui| {
+                let profile = &mut settings.custom_profiles[index];
+
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    let mut name = profile.name.clone();
+                    if ui.text_edit_singleline(&mut name).changed() && !name.trim().is_empty() {
+                        profile.name = name.clone();
+                        settings.gamepad_profile = ProfileSelection::Fixed(name);
+                        changed = true;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Detect from names containing:");
+                    let mut patterns = profile.name_patterns.join(",");
+                    if ui
+                        .add(TextEdit::singleline(&mut patterns).hint_text("comma separated"))
+                        .changed()
+                    {
+                        profile.name_patterns = if patterns.is_empty() {
+                            Vec::new()
+                        } else {
+                            patterns.split(',').map(str::to_string).collect()
+                        };
+                        changed = true;
+                    }
+                });
+
+                egui::Grid::new("gamepad_profile_buttons")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for button in PadButton::ALL {
+                            ui.label(button.to_string());
+                            let mut target = profile.map(button);
+                            let text = match &target {
+                                Some(button_type) => format!("{:?}", button_type),
+                                None => "None".to_string(),
+                            };
+                            let options = std::iter::once((None, "None".to_string())).chain(
+                                ButtonType::ALL.map(|button_type| {
+                                    let label = format!("{:?}", button_type);
+                                    (Some(button_type), label)
+                                }),
+                            );
+                            let target_changed =
+                                GamepadSelect::new(("gamepad_profile_button", button))
+                                    .selected_text(text)
+                                    .show(ui, &mut target, options)
+                                    .changed();
+                            if target_changed {
+                                profile.set(button, target);
+                                changed = true;
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                delete = ui.button("Delete profile").clicked();
+            });
+
+        if delete {
+            let removed = settings.custom_profiles.remove(index);
+            info!("Deleted gamepad profile {}", removed.name);
+            settings.gamepad_profile = ProfileSelection::Auto;
+            changed = true;
+        }
+        changed
+    }
+
+    /// Publishes and persists controller settings after slider interaction.
+    ///
+    /// `selection_changed` covers dropdowns, which settle immediately.
+    fn apply_controller_settings(&self, responses: &[Response], selection_changed: bool) {
+        if selection_changed || responses.iter().any(|r| r.changed()) {
+            self.controller_settings_tx
+                .send_replace(self.controller_settings.clone());
+        }
+
+        // Persist once the value settled: drag released, or changed via keys/gamepad
+        let settled = selection_changed
+            || responses
+                .iter()
+                .any(|r| r.drag_stopped() || (r.changed() && !r.dragged()));
+        if settled {
+            if let ConfigResult::Failed(e) =
+                self.config_portal
+                    .execute_potal_action(PortalAction::WriteControllerSettings(
+                        self.controller_settings.clone(),
+                    ))
+            {
+                error!("Failed to persist controller settings: {}", e);
+            }
+        }
+    }
+
+    /// Renders the keyboard layout selection and region tuning.
+    ///
+    /// Selecting a preset rewrites the letter mapping of the stored keyboard
+    /// config and asks the mapping manager to rebuild the keyboard engine.
+    /// Slider values are stored the same way once released; the slider ranges
+    /// stay just inside the values accepted by validation.
+    fn render_keyboard_section(&mut self, ui: &mut Ui) {
+        Frame::new()
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
+            .inner_margin(8.0)
+            .outer_margin(2.0)
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    let total_width = ui.available_width() - 15.0;
+                    ui.set_min_width(total_width);
+                    ui.heading("Keyboard");
+
+                    let mut selected = self.keyboard_layout;
+                    ui.horizontal(|ui| {
+                        ui.label("Alphabet layout:");
+                        GamepadSelect::new("keyboard_layout")
+                            .selected_text(selected.to_string())
+                            .show(
+                                ui,
+                                &mut selected,
+                                LayoutPreset::ALL.map(|preset| (preset, preset.to_string())),
+                            );
+                    });
+
+                    if selected != self.keyboard_layout {
+                        self.switch_keyboard_layout(selected);
+                    }
+
+                    let mut input_mode = self.keyboard_input_mode;
+                    ui.horizontal(|ui| {
+                        ui.label("Input mode:");
+                        for mode in InputMode::ALL {
+                            ui.selectable_value(&mut input_mode, mode, mode.to_string());
+                        }
+                    })
+                    .response
+                    .on_hover_text(format!(
+                        "Virtual mouse: right stick moves the pointer, {:?} clicks",
+                        MOUSE_CLICK_BUTTON
+                    ));
+                    if input_mode != self.keyboard_input_mode
+                        && self.update_keyboard_config(|config| config.input_mode = input_mode)
+                    {
+                        info!("Switched input mode to {}", input_mode);
+                        self.keyboard_input_mode = input_mode;
+                    }
+
+                    let hysteresis = ui
+                        .horizontal(|ui| {
+                            ui.label("Region hysteresis:");
+                            ui.add(
+                                Slider::new(
+                                    &mut self.keyboard_hysteresis,
+                                    HYSTERESIS_RANGE.start..=HYSTERESIS_RANGE.end - 0.01,
+                                )
+                                .fixed_decimals(2),
+                            )
+                        })
+                        .inner;
+                    let center_radius = ui
+                        .horizontal(|ui| {
+                            ui.label("Center radius:");
+                            ui.add(
+                                Slider::new(
+                                    &mut self.keyboard_center_radius,
+                                    CENTER_RADIUS_RANGE.start + 0.05
+                                        ..=CENTER_RADIUS_RANGE.end - 0.01,
+                                )
+                                .fixed_decimals(2),
+                            )
+                        })
+                        .inner;
+
+                    let rate_limit = ui
+                        .horizontal(|ui| {
+                            ui.label("Input rate limit (ms):");
+                            ui.add(Slider::new(
+                                &mut self.keyboard_rate_limit_ms,
+                                MIN_RATE_LIMIT_MS..=200,
+                            ))
+                        })
+                        .inner;
+
+                    let repeat_delay = ui
+                        .horizontal(|ui| {
+                            ui.label("Key repeat delay (ms):");
+                            ui.add(Slider::new(&mut self.keyboard_repeat_delay_ms, 100..=1500))
+                        })
+                        .inner;
+                    let repeat_interval = ui
+                        .horizontal(|ui| {
+                            ui.label("Key repeat interval (ms):");
+                            ui.add(Slider::new(&mut self.keyboard_repeat_interval_ms, 0..=500))
+                                .on_hover_text("0 disables key repeat")
+                        })
+                        .inner;
+                    let dwell = ui
+                        .horizontal(|ui| {
+                            ui.label("Dwell to type (ms):");
+                            ui.add(Slider::new(&mut self.keyboard_dwell_ms, 0..=2000))
+                                .on_hover_text(
+                                    "Letters type once the sticks rest in a combo this long, \
+                                     0 types on every movement",
+                                )
+                        })
+                        .inner;
+                    let cursor_speed = ui
+                        .horizontal(|ui| {
+                            ui.label("Cursor speed (pt/s):");
+                            ui.add(
+                                Slider::new(&mut self.keyboard_cursor_speed, CURSOR_SPEED_RANGE)
+                                    .fixed_decimals(0),
+                            )
+                        })
+                        .inner;
+
+                    let settled = [
+                        hysteresis,
+                        center_radius,
+                        rate_limit,
+                        repeat_delay,
+                        repeat_interval,
+                        dwell,
+                        cursor_speed,
+                    ]
+                    .iter()
+                    .any(|r| r.drag_stopped() || (r.changed() && !r.dragged()));
+                    if settled {
+                        let (hysteresis, center_radius, rate_limit_ms) = (
+                            self.keyboard_hysteresis,
+                            self.keyboard_center_radius,
+                            self.keyboard_rate_limit_ms,
+                        );
+                        let (repeat_delay_ms, repeat_interval_ms) = (
+                            self.keyboard_repeat_delay_ms,
+                            self.keyboard_repeat_interval_ms,
+                        );
+                        let dwell_ms =
+                            (self.keyboard_dwell_ms > 0).then_some(self.keyboard_dwell_ms);
+                        let cursor_speed = self.keyboard_cursor_speed;
+                        self.update_keyboard_config(|config| {
+                            config.hysteresis = hysteresis;
+                            config.center_radius = center_radius;
+                            config.rate_limit_ms = rate_limit_ms;
+                            config.repeat_delay_ms = repeat_delay_ms;
+                            config.repeat_interval_ms = repeat_interval_ms;
+                            config.dwell_ms = dwell_ms;
+                            config.cursor_speed = cursor_speed;
+                        });
+                    }
+
+                    ui.checkbox(&mut self.show_keyboard_overlay, "Show keyboard overlay");
+
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut self.layout_path)
+                                .hint_text("Layout file (export defaults to home)"),
+                        );
+                        if ui.button("Export Layout").clicked() {
+                            self.export_layout();
+                        }
+                        if ui.button("Import Layout").clicked() {
+                            self.import_layout();
+                        }
+                    });
+                    if let Some(status) = &self.layout_status {
+                        ui.label(status);
+                    }
+
+                    egui::CollapsingHeader::new("Button mapping")
+                        .id_salt("keyboard_button_mapping")
+                        .show(ui, |ui| self.render_button_mapping(ui));
+
+                    let monitor = egui::CollapsingHeader::new("Event monitor")
+                        .id_salt("keyboard_event_monitor")
+                        .show(ui, |ui| self.event_monitor.render(ui));
+                    self.show_event_monitor = monitor.body_returned.is_some();
+                });
+            });
+    }
+
+    /// Renders one assignment dropdown per button plus conflict and reset controls.
+    fn render_button_mapping(&mut self, ui: &mut Ui) {
+        let mut requested = None;
+        egui::Grid::new("button_mapping_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (button, current) in &self.button_assignments {
+                    ui.label(format!("{:?}", button));
+                    let mut selected = *current;
+                    // Unmapped first, then modifiers, navigation actions and keys
+                    let options =
+                        std::iter::once((
+                            ButtonAssignment::Unmapped,
+                            ButtonAssignment::Unmapped.to_string(),
+                        ))
+                        .chain(MODIFIER_CHOICES.into_iter().map(|(modifiers, name)| {
+                            (ButtonAssignment::Modifier(modifiers), name.to_string())
+                        }))
+                        .chain(NavigationAction::ALL.into_iter().map(|action| {
+                            (ButtonAssignment::Navigation(action), action.to_string())
+                        }))
+                        .chain(
+                            egui::Key::ALL
+                                .iter()
+                                .map(|key| (ButtonAssignment::Key(*key), key.name().to_string())),
+                        );
+                    GamepadSelect::new(("button_mapping", button))
+                        .selected_text(selected.to_string())
+                        .max_height(300.0)
+                        .show(ui, &mut selected, options);
+                    if selected != *current {
+                        requested = Some((button.clone(), selected));
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some((button, assignment)) = requested {
+            self.request_button_assignment(button, assignment);
+        }
+
+        if let Some(pending) = &self.pending_assignment {
+            ui.colored_label(
+                UiColors::inactive(),
+                format!(
+                    "{} is already mapped to {:?}. Replace it?",
+                    pending.assignment, pending.conflict
+                ),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("Replace").clicked() {
+                    if let Some(pending) = self.pending_assignment.take() {
+                        self.store_button_mapping(|config| {
+                            config.assign(pending.conflict, ButtonAssignment::Unmapped);
+                            config.assign(pending.button, pending.assignment);
+                        });
+                    }
+                }
+                if ui.button("Cancel").clicked() {
+                    self.pending_assignment = None;
+                }
+            });
+        }
+
+        if ui.button("Reset to default").clicked() {
+            info!("Resetting keyboard button mapping to default");
+            self.pending_assignment = None;
+            self.store_button_mapping(KeyboardConfig::reset_button_mapping);
+        }
+    }
+
+    /// Stores a new button assignment unless another button already uses it.
+    ///
+    /// Conflicts are held back as a pending assignment for the user to confirm.
+    fn request_button_assignment(&mut self, button: ButtonType, assignment: ButtonAssignment) {
+        let keyboard_config = load_keyboard_config(&self.config_portal);
+        if let Some(conflict) = keyboard_config.conflicting_button(&button, assignment) {
+            self.pending_assignment = Some(PendingAssignment {
+                button,
+                assignment,
+                conflict,
+            });
+            return;
+        }
+
+        self.pending_assignment = None;
+        self.store_button_mapping(|config| config.assign(button, assignment));
+    }
+
+    /// Applies a button mapping change and refreshes the shown assignments.
+    fn store_button_mapping(&mut self, apply: impl FnOnce(&mut KeyboardConfig)) {
+        if self.update_keyboard_config(apply) {
+            self.button_assignments =
+                button_assignments(&load_keyboard_config(&self.config_portal));
+        }
+    }
+
+    /// Writes the stored keyboard config to a standalone layout file.
+    ///
+    /// Uses the layout path, or the home directory if it is empty. A
+    /// directory gets a file named after the layout preset.
+    fn export_layout(&mut self) {
+        let mut dest = match self.layout_path.trim() {
+            "" => dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")),
+            path => PathBuf::from(path),
+        };
+        if dest.is_dir() {
+            dest.push(format!("keyboard-{:?}", self.keyboard_layout).to_lowercase());
+            dest.set_extension(LAYOUT_FILE_EXTENSION);
+        }
+
+        let keyboard_config = load_keyboard_config(&self.config_portal);
+        self.layout_status = Some(match keyboard_config.export_layout(&dest) {
+            Ok(()) => {
+                info!("Exported keyboard layout to {}", dest.display());
+                format!("Exported to {}", dest.display())
+            }
+            Err(e) => {
+                error!("Keyboard layout export failed: {}", e);
+                e.to_string()
+            }
+        });
+    }
+
+    /// Loads a layout file into the session and rebuilds the keyboard engine.
+    fn import_layout(&mut self) {
+        let src = PathBuf::from(self.layout_path.trim());
+        if src.as_os_str().is_empty() {
+            self.layout_status = Some("Enter the path of a layout file".to_string());
+            return;
+        }
+
+        let imported = match KeyboardConfig::import_layout(&src) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Rejected keyboard layout {}: {}", src.display(), e);
+                self.layout_status = Some(e.to_string());
+                return;
+            }
+        };
+
+        if self.update_keyboard_config(|config| *config = imported) {
+            info!("Imported keyboard layout from {}", src.display());
+            self.sync_keyboard_fields();
+            self.pending_assignment = None;
+            self.layout_status = Some(format!("Imported {}", src.display()));
+        } else {
+            self.layout_status = Some("Failed to store the imported layout".to_string());
+        }
+    }
+
+    /// Reloads the keyboard controls from the stored keyboard config.
+    fn sync_keyboard_fields(&mut self) {
+        let keyboard_config = load_keyboard_config(&self.config_portal);
+        self.keyboard_layout = keyboard_config.layout();
+        self.keyboard_hysteresis = keyboard_config.hysteresis;
+        self.keyboard_center_radius = keyboard_config.center_radius;
+        self.keyboard_rate_limit_ms = keyboard_config.rate_limit_ms;
+        self.keyboard_repeat_delay_ms = keyboard_config.repeat_delay_ms;
+        self.keyboard_repeat_interval_ms = keyboard_config.repeat_interval_ms;
+        self.keyboard_dwell_ms = keyboard_config.dwell_ms.unwrap_or(0);
+        self.keyboard_input_mode = keyboard_config.input_mode;
+        self.keyboard_cursor_speed = keyboard_config.cursor_speed;
+        self.button_assignments = button_assignments(&keyboard_config);
+    }
+
+    /// Stores the new layout and triggers a keyboard engine rebuild.
+    fn switch_keyboard_layout(&mut self, preset: LayoutPreset) {
+        info!("Switching keyboard layout to {}", preset);
+        if self.update_keyboard_config(|config| config.set_layout(preset)) {
+            self.keyboard_layout = preset;
+        }
+    }
+
+    /// Applies a change to the stored keyboard config and rebuilds the engine.
+    ///
+    /// Invalid results are rejected before writing. Returns whether the change
+    /// was stored.
+    fn update_keyboard_config(&self, apply: impl FnOnce(&mut KeyboardConfig)) -> bool {
+        let mut keyboard_config = load_keyboard_config(&self.config_portal);
+        apply(&mut keyboard_config);
+
+        if let Err(e) = keyboard_config.validate() {
+            error!("Rejected keyboard config change: {}", e);
+            return false;
+        }
+        if let ConfigResult::Failed(e) = self
+            .config_portal
+            .execute_potal_action(PortalAction::WriteKeyboardConfig(keyboard_config))
+        {
+            error!("Failed to store keyboard config: {}", e);
+            return false;
+        }
+
+        if let Err(e) = self.mapping_reload_tx.try_send(MappingType::Keyboard) {
+            error!("Failed to request keyboard mapping reload: {}", e);
+        }
+        true
+    }
+
     /// Renders the display and power management configuration section.
     ///
     /// Provides controls for display brightness and screensaver timeout settings,
@@ -329,15 +1396,29 @@ impl SettingsMenuData {
     /// ## Design Rationale
     ///
     /// ### Brightness Control
-    /// Uses a slider interface (0.0 to 1.0 range) that maps intuitively to
-    /// both user expectations and underlying system brightness controls.
-    /// This provides immediate visual feedback during adjustment.
+    /// Uses a slider interface (0.0 to 1.0 range) that is scaled onto the
+    /// detected backlight device while dragging, for immediate visual feedback.
+    /// The level is stored in the session once released. Without a backlight
+    /// the slider is disabled and marked as not supported.
     ///
     /// ### Screensaver Management
     /// Uses a drag-value control for timeout configuration (0 to 3600 seconds)
     /// allowing both quick adjustment and precise timing configuration.
     /// Range supports both immediate screensaver (0) and extended operation (1 hour).
     ///
+    /// ### Frame Rate
+    /// Written to the session on every change, so the UI repaints at the new
+    /// rate from the next frame on. The slider shows the stored rate otherwise,
+    /// following session switches.
+    ///
+    /// ### Focus Highlight
+    /// Toggle and outline width of the focused widget's highlight, see
+    /// [`Self::render_focus_settings`].
+    ///
+    /// ### Message Timestamps
+    /// Format and zone of MQTT message timestamps are handled the same way,
+    /// see [`Self::render_timestamp_settings`].
+    ///
     /// ## Workshop Environment Considerations
     /// These settings address common workshop and embedded usage patterns:
     /// - Brightness adjustment for varying ambient lighting
@@ -345,15 +1426,14 @@ impl SettingsMenuData {
     /// - Automatic display management for unattended operation
     ///
     /// ## Future Implementation Notes
-    /// Current implementation provides UI controls only. Production implementation
-    /// will integrate with:
-    /// - System brightness control interfaces
+    /// Apart from frame rate and brightness, the current implementation provides
+    /// UI controls only. Production implementation will integrate with:
     /// - Power management subsystems
     /// - Hardware-specific display controllers
     fn render_display_section(&mut self, ui: &mut Ui) {
         Frame::new()
-            .stroke(Stroke::new(1.0, UiColors::BORDER))
-            .fill(UiColors::MAIN_BG)
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
             .inner_margin(8.0)
             .outer_margin(2.0)
             .show(ui, |ui| {
@@ -362,11 +1442,34 @@ impl SettingsMenuData {
                     ui.set_min_width(total_width);
                     ui.heading("Display");
 
-                    // Brightness control slider
-                    ui.horizontal(|ui| {
-                        ui.label("Brightness:");
-                        ui.add(Slider::new(&mut self.display_brightness, 0.0..=1.0));
-                    });
+                    // Backlight brightness, only where the platform has one
+                    let brightness = ui
+                        .horizontal(|ui| {
+                            ui.label("Brightness:");
+                            let response = ui.add_enabled(
+                                self.backlight.is_some(),
+                                Slider::new(&mut self.display_brightness, 0.0..=1.0),
+                            );
+                            if self.backlight.is_none() {
+                                ui.label("not supported");
+                            }
+                            response
+                        })
+                        .inner;
+                    if brightness.changed() {
+                        self.apply_brightness();
+                    }
+                    if brightness.drag_stopped() || (brightness.changed() && !brightness.dragged())
+                    {
+                        if let ConfigResult::Failed(e) =
+                            self.config_portal
+                                .execute_potal_action(PortalAction::WriteBrightness(
+                                    self.display_brightness,
+                                ))
+                        {
+                            error!("Failed to store brightness: {}", e);
+                        }
+                    }
 
                     // Screensaver timeout configuration
                     ui.horizontal(|ui| {
@@ -377,7 +1480,455 @@ impl SettingsMenuData {
                                 .range(0..=3600),
                         );
                     });
+
+                    // UI repaint rate, lower saves CPU on embedded hardware
+                    let mut fps = match self
+                        .config_portal
+                        .execute_potal_action(PortalAction::GetFps)
+                    {
+                        ConfigResult::Fps(fps) => effective_fps(fps),
+                        _ => DEFAULT_FPS,
+                    };
+                    let fps_slider = ui
+                        .horizontal(|ui| {
+                            ui.label("Frame rate (fps):");
+                            ui.add(Slider::new(&mut fps, FPS_RANGE))
+                        })
+                        .inner;
+                    if fps_slider.changed() {
+                        if let ConfigResult::Failed(e) = self
+                            .config_portal
+                            .execute_potal_action(PortalAction::WriteFps(fps))
+                        {
+                            error!("Failed to store frame rate: {}", e);
+                        }
+                    }
+
+                    self.render_focus_settings(ui);
+                    self.render_timestamp_settings(ui);
+                });
+            });
+    }
+
+    /// Renders the focus highlight toggle and outline width.
+    ///
+    /// Changes are stored in the session and applied right away instead of
+    /// waiting for the UI's next theme check.
+    fn render_focus_settings(&mut self, ui: &mut Ui) {
+        let mut focus = load_focus_highlight(&self.config_portal);
+
+        let toggled = ui
+            .checkbox(&mut focus.enabled, "Highlight focused widget")
+            .on_hover_text("Outlines the widget gamepad input goes to")
+            .changed();
+        let width = ui
+            .horizontal(|ui| {
+                ui.label("Focus outline width:");
+                ui.add_enabled(
+                    focus.enabled,
+                    Slider::new(&mut focus.width, FOCUS_WIDTH_RANGE),
+                )
+            })
+            .inner;
+
+        if toggled || width.changed() {
+            let theme = match &self.theme_draft {
+                Some(draft) => draft.clone(),
+                None => load_theme(&self.config_portal),
+            };
+            theme.apply(ui.ctx(), &focus);
+            if let ConfigResult::Failed(e) = self
+                .config_portal
+                .execute_potal_action(PortalAction::WriteFocusHighlight(focus))
+            {
+                error!("Failed to store focus highlight: {}", e);
+            }
+        }
+    }
+
+    /// Renders the format and zone of MQTT message timestamps.
+    ///
+    /// Every change is written to the session, like the frame rate. An
+    /// invalid format is stored as typed but flagged; messages fall back to
+    /// the default format until it is fixed.
+    fn render_timestamp_settings(&mut self, ui: &mut Ui) {
+        let mut timestamps = match self
+            .config_portal
+            .execute_potal_action(PortalAction::GetTimestamps)
+        {
+            ConfigResult::Timestamps(timestamps) => timestamps,
+            _ => TimestampSettings::default(),
+        };
+
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Message timestamps:");
+            changed |= ui
+                .add(TextEdit::singleline(&mut timestamps.format).desired_width(160.0))
+                .on_hover_text("chrono strftime format, e.g. %H:%M:%S%.3f")
+                .changed();
+            changed |= ui.checkbox(&mut timestamps.utc, "UTC").changed();
+            if ui.button("Default").clicked() {
+                timestamps.format = DEFAULT_TIMESTAMP_FORMAT.to_string();
+                changed = true;
+            }
+        });
+        if timestamps.is_valid_format() {
+            ui.label(format!(
+                "Preview: {}",
+                timestamps.format(chrono::Local::now().naive_local())
+            ));
+        } else {
+            ui.colored_label(UiColors::inactive(), "Invalid format, the default is used");
+        }
+
+        if changed {
+            if let ConfigResult::Failed(e) = self
+                .config_portal
+                .execute_potal_action(PortalAction::WriteTimestamps(timestamps))
+            {
+                error!("Failed to store timestamp settings: {}", e);
+            }
+        }
+    }
+
+    /// Sets the backlight to the slider level.
+    fn apply_brightness(&self) {
+        if let Some(backlight) = &self.backlight {
+            if let Err(e) = backlight.set_level(self.display_brightness) {
+                warn!("Failed to set display brightness: {}", e);
+            }
+        }
+    }
+
+    /// Renders the autosave toggle and interval and the file reload toggle.
+    ///
+    /// Changes go to the autosave and file watch tasks right away and are stored in the
+    /// session's UI config. A session load brings its own settings, which the
+    /// controls follow.
+    fn render_autosave_section(&mut self, ui: &mut Ui) {
+        if self.autosave_rx.has_changed().unwrap_or(false) {
+            self.autosave = self.autosave_rx.borrow_and_update().clone();
+        }
+
+        Frame::new()
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
+            .inner_margin(8.0)
+            .outer_margin(2.0)
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    let total_width = ui.available_width() - 15.0;
+                    ui.set_min_width(total_width);
+                    ui.heading("Autosave");
+
+                    let toggled = ui
+                        .checkbox(&mut self.autosave.enabled, "Save session periodically")
+                        .changed();
+                    let interval = ui
+                        .horizontal(|ui| {
+                            ui.label("Interval (seconds):");
+                            ui.add_enabled(
+                                self.autosave.enabled,
+                                Slider::new(
+                                    &mut self.autosave.interval_secs,
+                                    AUTOSAVE_INTERVAL_RANGE,
+                                ),
+                            )
+                        })
+                        .inner;
+
+                    let reload = ui
+                        .checkbox(
+                            &mut self.autosave.reload_on_change,
+                            "Reload session files changed outside the app",
+                        )
+                        .on_hover_text("Picks up hand edits and synced files without a restart")
+                        .changed();
+
+                    let backups = ui
+                        .horizontal(|ui| {
+                            ui.label("Session backups kept:");
+                            ui.add(Slider::new(
+                                &mut self.autosave.backup_count,
+                                BACKUP_COUNT_RANGE,
+                            ))
+                            .on_hover_text("Previous versions kept before each save, 0 keeps none")
+                        })
+                        .inner;
+
+                    let settled = |slider: &egui::Response| {
+                        slider.drag_stopped() || (slider.changed() && !slider.dragged())
+                    };
+                    if toggled || settled(&interval) || settled(&backups) || reload {
+                        self.store_autosave();
+                    }
+                });
+            });
+    }
+
+    /// Publishes and stores the shown autosave settings.
+    fn store_autosave(&self) {
+        info!(
+            "Autosave {} with interval {}s, file reload {}, {} backups",
+            if self.autosave.enabled { "on" } else { "off" },
+            self.autosave.interval_secs,
+            if self.autosave.reload_on_change {
+                "on"
+            } else {
+                "off"
+            },
+            self.autosave.backup_count
+        );
+        self.autosave_tx.send_replace(self.autosave.clone());
+        if let ConfigResult::Failed(e) = self
+            .config_portal
+            .execute_potal_action(PortalAction::WriteAutosave(self.autosave.clone()))
+        {
+            error!("Failed to store autosave settings: {}", e);
+        }
+    }
+
+    /// Renders the theme editor section.
+    fn render_theme_section(&mut self, ui: &mut Ui) {
+        Frame::new()
+            .stroke(Stroke::new(1.0, UiColors::border()))
+            .fill(UiColors::main_bg())
+            .inner_margin(8.0)
+            .outer_margin(2.0)
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    let total_width = ui.available_width() - 15.0;
+                    ui.set_min_width(total_width);
+                    ui.heading("Theme");
+
+                    egui::CollapsingHeader::new("Colors")
+                        .id_salt("theme_colors")
+                        .show(ui, |ui| self.render_theme_editor(ui));
                 });
             });
     }
+
+    /// Renders one color picker per theme color plus save, discard and reset.
+    ///
+    /// Every change is applied to the context right away as preview. Without
+    /// a draft the stored theme is shown, so a session switch is picked up.
+    fn render_theme_editor(&mut self, ui: &mut Ui) {
+        let mut theme = match &self.theme_draft {
+            Some(draft) => draft.clone(),
+            None => load_theme(&self.config_portal),
+        };
+        let mut changed = false;
+
+        egui::Grid::new("theme_color_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, color) in theme.colors_mut() {
+                    ui.label(label);
+                    let mut rgb = [color.0, color.1, color.2];
+                    if ui.color_edit_button_srgb(&mut rgb).changed() {
+                        *color = (rgb[0], rgb[1], rgb[2]);
+                        changed = true;
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal(|ui| {
+            let has_draft = self.theme_draft.is_some();
+            if ui
+                .add_enabled(has_draft, egui::Button::new("Save to session"))
+                .clicked()
+            {
+                self.save_theme(theme.clone());
+            }
+            if ui
+                .add_enabled(has_draft, egui::Button::new("Discard"))
+                .clicked()
+            {
+                self.theme_draft = None;
+                load_theme(&self.config_portal)
+                    .apply(ui.ctx(), &load_focus_highlight(&self.config_portal));
+            }
+            if ui.button("Reset to default").clicked() {
+                theme = Theme::default();
+                changed = true;
+            }
+        });
+
+        if changed {
+            theme.apply(ui.ctx(), &load_focus_highlight(&self.config_portal));
+            self.theme_draft = Some(theme);
+        }
+    }
+
+    /// Stores the theme in the current session and persists the session.
+    fn save_theme(&mut self, theme: Theme) {
+        if let ConfigResult::Failed(e) = self
+            .config_portal
+            .execute_potal_action(PortalAction::WriteTheme(theme))
+        {
+            error!("Failed to store theme: {}", e);
+            return;
+        }
+        info!("Saved theme to current session");
+        self.theme_draft = None;
+
+        // The worker saves asynchronously, a missing confirmation is expected
+        if let Err(e) = session_action!(@save, self.session_sender) {
+            debug!("Session save not confirmed yet: {}", e);
+        }
+    }
+}
+
+/// Loads the stored keyboard config, falling back to the default layout.
+///
+/// Mirrors the mapping manager, which treats an empty button mapping as unset.
+fn load_keyboard_config(config_portal: &ConfigPortal) -> KeyboardConfig {
+    match config_portal.execute_potal_action(PortalAction::GetKeyboardConfig) {
+        ConfigResult::KeyboardConfig(config) if !config.button_mapping.is_empty() => config,
+        _ => KeyboardConfig::default_config(),
+    }
+}
+
+/// Loads the stored theme, showing the default for sessions without one.
+fn load_theme(config_portal: &ConfigPortal) -> Theme {
+    match config_portal.execute_potal_action(PortalAction::GetTheme) {
+        ConfigResult::Theme(theme) if !theme.is_unset() => theme,
+        _ => Theme::default(),
+    }
+}
+
+/// Loads the stored focus highlight settings.
+fn load_focus_highlight(config_portal: &ConfigPortal) -> FocusHighlight {
+    match config_portal.execute_potal_action(PortalAction::GetFocusHighlight) {
+        ConfigResult::FocusHighlight(focus) => focus,
+        _ => FocusHighlight::default(),
+    }
+}
+
+/// Applies the session's stored brightness and returns the level to show.
+///
+/// Without a stored level the current backlight level is kept.
+fn restore_brightness(config_portal: &ConfigPortal, backlight: Option<&Backlight>) -> f32 {
+    let stored = match config_portal.execute_potal_action(PortalAction::GetBrightness) {
+        ConfigResult::Brightness(level) => level,
+        _ => None,
+    };
+    let Some(backlight) = backlight else {
+        return stored.unwrap_or(1.0);
+    };
+
+    match stored {
+        Some(level) => {
+            if let Err(e) = backlight.set_level(level) {
+                warn!("Failed to restore display brightness: {}", e);
+            }
+            level
+        }
+        None => backlight.level().unwrap_or(1.0),
+    }
+}
+
+/// Lists every gamepad button with its current assignment.
+fn button_assignments(keyboard_config: &KeyboardConfig) -> Vec<(ButtonType, ButtonAssignment)> {
+    ButtonType::ALL
+        .into_iter()
+        .map(|button| {
+            let assignment = keyboard_config.assignment(&button);
+            (button, assignment)
+        })
+        .collect()
+}
+
+/// Renders the latency measurement toggle and the histogram of the samples.
+///
+/// Only keyboard events caused by new input are measured, see
+/// [`crate::latency`].
+fn render_input_latency(ui: &mut Ui) {
+    let mut enabled = latency::is_enabled();
+    if ui
+        .checkbox(&mut enabled, "Measure input latency")
+        .on_hover_text(
+            "Time from a gamepad event to its keyboard events reaching the UI. \
+             Use it to tune the processing interval and the keyboard rate limit.",
+        )
+        .changed()
+    {
+        latency::set_enabled(enabled);
+    }
+
+    let stats = latency::stats();
+    if stats.samples == 0 {
+        ui.label(if enabled {
+            "No samples yet, type with the sticks or press mapped buttons"
+        } else {
+            "Off"
+        });
+        return;
+    }
+
+    let ms = |duration: std::time::Duration| format!("{:.1} ms", duration.as_secs_f64() * 1000.0);
+    ui.label(format!(
+        "{} samples, median {}, 95% {}, max {}",
+        stats.samples,
+        ms(stats.median),
+        ms(stats.p95),
+        ms(stats.max)
+    ));
+
+    let largest = stats
+        .buckets
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    egui::Grid::new("input_latency_grid")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (label, count) in &stats.buckets {
+                ui.label(label);
+                ui.add(
+                    ProgressBar::new(*count as f32 / largest as f32)
+                        .desired_width(200.0)
+                        .text(count.to_string()),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+/// Renders curve selection and factor slider for one stick.
+///
+/// Returns whether the curve type changed and the factor slider's response.
+/// The slider is disabled for linear curves, where the factor has no effect.
+fn render_curve_row(
+    ui: &mut Ui,
+    label: &str,
+    id_salt: &str,
+    stick_curve: &mut StickCurve,
+) -> (bool, Response) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+
+        let curve_changed = GamepadSelect::new(id_salt)
+            .selected_text(stick_curve.curve.to_string())
+            .show(
+                ui,
+                &mut stick_curve.curve,
+                ResponseCurve::ALL.map(|curve| (curve, curve.to_string())),
+            )
+            .changed();
+
+        let factor = ui.add_enabled(
+            stick_curve.curve != ResponseCurve::Linear,
+            Slider::new(&mut stick_curve.factor, 0.0..=1.0)
+                .text("Factor")
+                .fixed_decimals(2),
+        );
+
+        (curve_changed, factor)
+    })
+    .inner
 }
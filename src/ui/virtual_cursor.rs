@@ -0,0 +1,121 @@
+//! # Virtual Mouse Cursor
+//!
+//! Pointer driven by the gamepad in the mouse mode of the keyboard mapping.
+//!
+//! ## Why This Module Exists
+//!
+//! Some widgets, like sliders and color pickers, are much easier to use with
+//! a pointer than with key navigation. In
+//! [`InputMode::Mouse`](crate::mapping::keyboard::InputMode::Mouse) the
+//! keyboard strategy emits relative cursor steps and clicks, and this module
+//! turns them into the pointer events egui expects.
+//!
+//! ## Design Rationale
+//!
+//! ### The UI Owns the Position
+//! The mapping engine runs in its own thread and knows nothing about the
+//! window. It sends [`Event::MouseMoved`] steps, which the cursor adds to its
+//! position, clamped to the screen, before passing a [`Event::PointerMoved`]
+//! on. Clicks arrive without a position and get the cursor's.
+//!
+//! ### Drawn Cursor
+//! A kiosk setup usually hides the system pointer, so the cursor paints
+//! itself above all other layers. It disappears after [`CURSOR_HIDE_AFTER`]
+//! without pointer input, e.g. after switching back to keyboard navigation.
+
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, Context, Event, Id, LayerId, Order, Pos2, Rect, Stroke};
+
+use super::common::UiColors;
+
+/// Time without pointer input after which the cursor is no longer drawn.
+pub const CURSOR_HIDE_AFTER: Duration = Duration::from_secs(5);
+
+/// Radius of the drawn cursor in points.
+const CURSOR_RADIUS: f32 = 6.0;
+
+/// Gamepad pointer position and visibility.
+#[derive(Debug, Default)]
+pub struct VirtualCursor {
+    /// Current position, `None` until the first pointer event
+    pos: Option<Pos2>,
+    /// When the last pointer event arrived
+    last_input: Option<Instant>,
+}
+
+impl VirtualCursor {
+    /// Converts a mapped event for injection into egui.
+    ///
+    /// Cursor steps become absolute moves within `screen`, clicks get the
+    /// cursor position. A click before any step first moves the cursor to the
+    /// screen center. Other events are returned unchanged.
+    pub fn translate(&mut self, event: Event, screen: Rect) -> Vec<Event> {
+        match event {
+            Event::MouseMoved(step) => {
+                let pos = self.position(screen) + step;
+                vec![self.move_to(pos, screen)]
+            }
+            Event::PointerButton {
+                button,
+                pressed,
+                modifiers,
+                ..
+            } => {
+                let mut events = Vec::new();
+                if self.pos.is_none() {
+                    events.push(self.move_to(screen.center(), screen));
+                }
+                self.last_input = Some(Instant::now());
+                events.push(Event::PointerButton {
+                    pos: self.position(screen),
+                    button,
+                    pressed,
+                    modifiers,
+                });
+                events
+            }
+            other => vec![other],
+        }
+    }
+
+    /// Paints the cursor on top of everything, while it is in use.
+    pub fn paint(&self, ctx: &Context) {
+        let (Some(pos), Some(last_input)) = (self.pos, self.last_input) else {
+            return;
+        };
+        if last_input.elapsed() > CURSOR_HIDE_AFTER {
+            return;
+        }
+
+        let painter = ctx.layer_painter(LayerId::new(Order::Tooltip, Id::new("virtual_cursor")));
+        painter.circle(
+            pos,
+            CURSOR_RADIUS,
+            UiColors::active().gamma_multiply(0.6),
+            Stroke::new(2.0, UiColors::main_bg()),
+        );
+    }
+
+    /// Current position clamped to `screen`, the screen center before any
+    /// pointer event.
+    fn position(&self, screen: Rect) -> Pos2 {
+        self.pos.map_or(screen.center(), |pos| clamp(pos, screen))
+    }
+
+    /// Moves the cursor to `pos`, kept inside `screen`.
+    fn move_to(&mut self, pos: Pos2, screen: Rect) -> Event {
+        let pos = clamp(pos, screen);
+        self.pos = Some(pos);
+        self.last_input = Some(Instant::now());
+        Event::PointerMoved(pos)
+    }
+}
+
+/// Keeps `pos` inside `screen`, whose max edge lies just outside of it.
+fn clamp(pos: Pos2, screen: Rect) -> Pos2 {
+    pos.clamp(
+        screen.min,
+        (screen.max - egui::vec2(1.0, 1.0)).max(screen.min),
+    )
+}